@@ -0,0 +1,764 @@
+//! Minimal reader / writer for Avro object container files, as used for Iceberg manifests and
+//! manifest lists.
+//!
+//! This does not build on the arrow Avro reader / writer (`io_avro`), because:
+//! * Iceberg identifies manifest columns by `field-id` annotations in the Avro schema, which
+//!   cannot be expressed through the arrow Avro writer.
+//! * Manifests are consumed record by record, with field names that differ between Iceberg
+//!   format versions and partition records whose type depends on the partition spec. The arrow
+//!   reader decodes into arrays of a single fixed schema, and does not support Avro maps.
+//!
+//! Records are instead (de)serialized against the parsed schema as [`AvroValue`]s.
+
+use std::io::{Read, Write};
+
+use polars_core::prelude::PlHashMap;
+use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err, to_compute_err};
+
+use super::random_u64;
+
+const MAGIC: &[u8; 4] = b"Obj\x01";
+const SYNC_MARKER_LEN: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AvroValue {
+    Null,
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Bytes(Vec<u8>),
+    String(String),
+    Fixed(Vec<u8>),
+    Enum(String),
+    Array(Vec<AvroValue>),
+    Map(Vec<(String, AvroValue)>),
+    Record(Vec<(String, AvroValue)>),
+}
+
+impl AvroValue {
+    /// Returns the value of a record field, or `None` if the field is missing or null.
+    pub fn field(&self, name: &str) -> Option<&AvroValue> {
+        match self {
+            Self::Record(fields) => fields
+                .iter()
+                .find(|(field_name, _)| field_name == name)
+                .map(|(_, v)| v)
+                .filter(|v| !matches!(v, Self::Null)),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int(v) => Some(*v as i64),
+            Self::Long(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(v) | Self::Fixed(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[AvroValue]> {
+        match self {
+            Self::Array(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed Avro schema. Logical types are resolved to their underlying physical type.
+#[derive(Clone, Debug)]
+enum AvroSchema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Fixed(usize),
+    Enum(Vec<String>),
+    Array(Box<AvroSchema>),
+    Map(Box<AvroSchema>),
+    Union(Vec<AvroSchema>),
+    Record(Vec<(String, AvroSchema)>),
+}
+
+impl AvroSchema {
+    fn try_from_json(json: &[u8]) -> PolarsResult<Self> {
+        let json: serde_json::Value = serde_json::from_slice(json).map_err(to_compute_err)?;
+        Self::parse(&json, &mut PlHashMap::new())
+    }
+
+    fn parse(
+        json: &serde_json::Value,
+        named: &mut PlHashMap<String, AvroSchema>,
+    ) -> PolarsResult<Self> {
+        use serde_json::Value;
+
+        let get_str = |obj: &serde_json::Map<String, Value>, key: &str| {
+            obj.get(key).and_then(Value::as_str).ok_or_else(
+                || polars_err!(ComputeError: "avro schema: expected string for '{}'", key),
+            )
+        };
+
+        Ok(match json {
+            Value::String(name) => match name.as_str() {
+                "null" => Self::Null,
+                "boolean" => Self::Boolean,
+                "int" => Self::Int,
+                "long" => Self::Long,
+                "float" => Self::Float,
+                "double" => Self::Double,
+                "bytes" => Self::Bytes,
+                "string" => Self::String,
+                name => named.get(name).cloned().ok_or_else(
+                    || polars_err!(ComputeError: "avro schema: unknown type '{}'", name),
+                )?,
+            },
+            Value::Array(variants) => Self::Union(
+                variants
+                    .iter()
+                    .map(|v| Self::parse(v, named))
+                    .collect::<PolarsResult<_>>()?,
+            ),
+            Value::Object(obj) => match obj.get("type") {
+                Some(Value::String(type_name)) => match type_name.as_str() {
+                    "record" => {
+                        let Some(Value::Array(fields)) = obj.get("fields") else {
+                            polars_bail!(ComputeError: "avro schema: record is missing 'fields'")
+                        };
+                        let fields = fields
+                            .iter()
+                            .map(|field| {
+                                let Value::Object(field) = field else {
+                                    polars_bail!(ComputeError: "avro schema: invalid record field")
+                                };
+                                let name = get_str(field, "name")?.to_string();
+                                let schema =
+                                    Self::parse(field.get("type").unwrap_or(&Value::Null), named)?;
+                                Ok((name, schema))
+                            })
+                            .collect::<PolarsResult<Vec<_>>>()?;
+                        let out = Self::Record(fields);
+                        named.insert(get_str(obj, "name")?.to_string(), out.clone());
+                        out
+                    },
+                    "enum" => {
+                        let Some(Value::Array(symbols)) = obj.get("symbols") else {
+                            polars_bail!(ComputeError: "avro schema: enum is missing 'symbols'")
+                        };
+                        let out = Self::Enum(
+                            symbols
+                                .iter()
+                                .filter_map(|s| s.as_str().map(str::to_string))
+                                .collect(),
+                        );
+                        named.insert(get_str(obj, "name")?.to_string(), out.clone());
+                        out
+                    },
+                    "fixed" => {
+                        let size = obj.get("size").and_then(Value::as_u64).ok_or_else(
+                            || polars_err!(ComputeError: "avro schema: fixed is missing 'size'"),
+                        )?;
+                        let out = Self::Fixed(size as usize);
+                        named.insert(get_str(obj, "name")?.to_string(), out.clone());
+                        out
+                    },
+                    "array" => Self::Array(Box::new(Self::parse(
+                        obj.get("items").unwrap_or(&Value::Null),
+                        named,
+                    )?)),
+                    "map" => Self::Map(Box::new(Self::parse(
+                        obj.get("values").unwrap_or(&Value::Null),
+                        named,
+                    )?)),
+                    // Primitive annotated with a logical type.
+                    _ => Self::parse(&Value::String(type_name.clone()), named)?,
+                },
+                Some(inner) => Self::parse(inner, named)?,
+                None => polars_bail!(ComputeError: "avro schema: object is missing 'type'"),
+            },
+            v => polars_bail!(ComputeError: "avro schema: unexpected value: {}", v),
+        })
+    }
+
+    /// Whether a value can be encoded with this (non-union) schema.
+    fn accepts(&self, value: &AvroValue) -> bool {
+        use AvroValue as V;
+
+        matches!(
+            (self, value),
+            (Self::Null, V::Null)
+                | (Self::Boolean, V::Boolean(_))
+                | (Self::Int, V::Int(_))
+                | (Self::Long, V::Long(_) | V::Int(_))
+                | (Self::Float, V::Float(_))
+                | (Self::Double, V::Double(_))
+                | (Self::Bytes, V::Bytes(_))
+                | (Self::String, V::String(_))
+                | (Self::Fixed(_), V::Fixed(_))
+                | (Self::Enum(_), V::Enum(_))
+                | (Self::Array(_), V::Array(_))
+                | (Self::Map(_), V::Map(_))
+                | (Self::Record(_), V::Record(_))
+        )
+    }
+}
+
+/// Contents of an Avro object container file.
+pub struct AvroFile {
+    /// File-level metadata (e.g. Iceberg stores the table schema and partition spec here).
+    pub metadata: PlHashMap<String, Vec<u8>>,
+    pub records: Vec<AvroValue>,
+}
+
+impl AvroFile {
+    pub fn metadata_str(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .get(key)
+            .and_then(|v| std::str::from_utf8(v).ok())
+    }
+}
+
+pub fn read_avro_file(bytes: &[u8]) -> PolarsResult<AvroFile> {
+    let mut buf = bytes;
+
+    polars_ensure!(
+        buf.starts_with(MAGIC),
+        ComputeError: "invalid avro file: header magic not found"
+    );
+    buf = &buf[MAGIC.len()..];
+
+    let mut metadata = PlHashMap::new();
+    read_blocks(&mut buf, |buf| {
+        let key = read_string(buf)?;
+        let len = read_len(buf)?;
+        metadata.insert(key, read_slice(buf, len)?.to_vec());
+        Ok(())
+    })?;
+    let sync_marker = read_slice(&mut buf, SYNC_MARKER_LEN)?;
+
+    let Some(schema) = metadata.get("avro.schema") else {
+        polars_bail!(ComputeError: "invalid avro file: missing 'avro.schema' metadata")
+    };
+    let schema = AvroSchema::try_from_json(schema)?;
+    let codec = metadata
+        .get("avro.codec")
+        .map_or(&b"null"[..], |v| v.as_slice());
+
+    let mut records = vec![];
+
+    while !buf.is_empty() {
+        let count = read_len(&mut buf)?;
+        let size = read_len(&mut buf)?;
+        let block = read_slice(&mut buf, size)?;
+
+        let decompressed;
+        let mut block = match codec {
+            b"null" => block,
+            b"deflate" => {
+                let mut out = vec![];
+                flate2::read::DeflateDecoder::new(block).read_to_end(&mut out)?;
+                decompressed = out;
+                decompressed.as_slice()
+            },
+            codec => polars_bail!(
+                ComputeError:
+                "unsupported avro codec: '{}'",
+                String::from_utf8_lossy(codec)
+            ),
+        };
+
+        // Every record takes at least one byte (except for schemas without data), so don't trust
+        // the count beyond the size of the block.
+        records.reserve(count.min(block.len()));
+        for _ in 0..count {
+            records.push(decode(&schema, &mut block)?);
+        }
+
+        polars_ensure!(
+            read_slice(&mut buf, SYNC_MARKER_LEN)? == sync_marker,
+            ComputeError: "invalid avro file: sync marker mismatch"
+        );
+    }
+
+    Ok(AvroFile { metadata, records })
+}
+
+/// Serializes the records into a deflate-compressed Avro object container file.
+pub fn write_avro_file(
+    schema_json: &str,
+    metadata: &[(&str, &str)],
+    records: &[AvroValue],
+) -> PolarsResult<Vec<u8>> {
+    let schema = AvroSchema::try_from_json(schema_json.as_bytes())?;
+
+    let mut out = MAGIC.to_vec();
+
+    let metadata = [("avro.schema", schema_json), ("avro.codec", "deflate")]
+        .into_iter()
+        .chain(metadata.iter().copied())
+        .collect::<Vec<_>>();
+    write_long(&mut out, metadata.len() as i64);
+    for (key, value) in metadata {
+        write_bytes(&mut out, key.as_bytes());
+        write_bytes(&mut out, value.as_bytes());
+    }
+    write_long(&mut out, 0);
+
+    let sync_marker = [random_u64().to_le_bytes(), random_u64().to_le_bytes()].concat();
+    out.extend_from_slice(&sync_marker);
+
+    if !records.is_empty() {
+        let mut block = vec![];
+        for record in records {
+            encode(&schema, record, &mut block)?;
+        }
+
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&block)?;
+        let block = encoder.finish()?;
+
+        write_long(&mut out, records.len() as i64);
+        write_bytes(&mut out, &block);
+        out.extend_from_slice(&sync_marker);
+    }
+
+    Ok(out)
+}
+
+fn read_slice<'a>(buf: &mut &'a [u8], len: usize) -> PolarsResult<&'a [u8]> {
+    let Some((out, rest)) = buf.split_at_checked(len) else {
+        polars_bail!(ComputeError: "invalid avro data: unexpected end of input")
+    };
+    *buf = rest;
+    Ok(out)
+}
+
+/// Reads a zig-zag encoded variable-length integer.
+fn read_long(buf: &mut &[u8]) -> PolarsResult<i64> {
+    let mut value: u64 = 0;
+    let mut shift = 0;
+
+    loop {
+        let byte = read_slice(buf, 1)?[0];
+        value |= ((byte & 0x7f) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+
+        shift += 7;
+        polars_ensure!(shift < 64, ComputeError: "invalid avro data: varint overflow");
+    }
+
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+}
+
+fn read_len(buf: &mut &[u8]) -> PolarsResult<usize> {
+    usize::try_from(read_long(buf)?)
+        .map_err(|_| polars_err!(ComputeError: "invalid avro data: negative length"))
+}
+
+fn read_string(buf: &mut &[u8]) -> PolarsResult<String> {
+    let len = read_len(buf)?;
+    String::from_utf8(read_slice(buf, len)?.to_vec()).map_err(to_compute_err)
+}
+
+/// Reads the blocks of an array or map, calling `f` for every item.
+fn read_blocks(
+    buf: &mut &[u8],
+    mut f: impl FnMut(&mut &[u8]) -> PolarsResult<()>,
+) -> PolarsResult<()> {
+    loop {
+        let mut count = read_long(buf)?;
+
+        if count == 0 {
+            return Ok(());
+        }
+
+        if count < 0 {
+            // A negative count is followed by the block size in bytes.
+            count = count.checked_neg().ok_or_else(
+                || polars_err!(ComputeError: "invalid avro data: block count out of range"),
+            )?;
+            read_long(buf)?;
+        }
+
+        for _ in 0..count {
+            f(buf)?;
+        }
+    }
+}
+
+fn decode(schema: &AvroSchema, buf: &mut &[u8]) -> PolarsResult<AvroValue> {
+    use AvroSchema as S;
+
+    Ok(match schema {
+        S::Null => AvroValue::Null,
+        S::Boolean => AvroValue::Boolean(read_slice(buf, 1)?[0] != 0),
+        S::Int => AvroValue::Int(read_long(buf)? as i32),
+        S::Long => AvroValue::Long(read_long(buf)?),
+        S::Float => AvroValue::Float(f32::from_le_bytes(read_slice(buf, 4)?.try_into().unwrap())),
+        S::Double => AvroValue::Double(f64::from_le_bytes(read_slice(buf, 8)?.try_into().unwrap())),
+        S::Bytes => {
+            let len = read_len(buf)?;
+            AvroValue::Bytes(read_slice(buf, len)?.to_vec())
+        },
+        S::String => AvroValue::String(read_string(buf)?),
+        S::Fixed(len) => AvroValue::Fixed(read_slice(buf, *len)?.to_vec()),
+        S::Enum(symbols) => {
+            let idx = read_len(buf)?;
+            AvroValue::Enum(symbols.get(idx).cloned().ok_or_else(
+                || polars_err!(ComputeError: "invalid avro data: enum index {} out of bounds", idx),
+            )?)
+        },
+        S::Array(items) => {
+            let mut out = vec![];
+            read_blocks(buf, |buf| {
+                out.push(decode(items, buf)?);
+                Ok(())
+            })?;
+            AvroValue::Array(out)
+        },
+        S::Map(values) => {
+            let mut out = vec![];
+            read_blocks(buf, |buf| {
+                let key = read_string(buf)?;
+                out.push((key, decode(values, buf)?));
+                Ok(())
+            })?;
+            AvroValue::Map(out)
+        },
+        S::Union(variants) => {
+            let idx = read_len(buf)?;
+            let Some(variant) = variants.get(idx) else {
+                polars_bail!(ComputeError: "invalid avro data: union index {} out of bounds", idx)
+            };
+            decode(variant, buf)?
+        },
+        S::Record(fields) => AvroValue::Record(
+            fields
+                .iter()
+                .map(|(name, schema)| Ok((name.clone(), decode(schema, buf)?)))
+                .collect::<PolarsResult<_>>()?,
+        ),
+    })
+}
+
+fn write_long(out: &mut Vec<u8>, value: i64) {
+    let mut n = ((value << 1) ^ (value >> 63)) as u64;
+
+    while n >= 0x80 {
+        out.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+
+    out.push(n as u8);
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_long(out, bytes.len() as i64);
+    out.extend_from_slice(bytes);
+}
+
+fn encode(schema: &AvroSchema, value: &AvroValue, out: &mut Vec<u8>) -> PolarsResult<()> {
+    use AvroSchema as S;
+    use AvroValue as V;
+
+    match (schema, value) {
+        (S::Null, V::Null) => {},
+        (S::Boolean, V::Boolean(v)) => out.push(*v as u8),
+        (S::Int, V::Int(v)) => write_long(out, *v as i64),
+        (S::Long, V::Long(v)) => write_long(out, *v),
+        (S::Long, V::Int(v)) => write_long(out, *v as i64),
+        (S::Float, V::Float(v)) => out.extend_from_slice(&v.to_le_bytes()),
+        (S::Double, V::Double(v)) => out.extend_from_slice(&v.to_le_bytes()),
+        (S::Bytes, V::Bytes(v)) => write_bytes(out, v),
+        (S::String, V::String(v)) => write_bytes(out, v.as_bytes()),
+        (S::Fixed(len), V::Fixed(v)) if v.len() == *len => out.extend_from_slice(v),
+        (S::Enum(symbols), V::Enum(v)) => {
+            let Some(idx) = symbols.iter().position(|s| s == v) else {
+                polars_bail!(ComputeError: "avro: unknown enum symbol '{}'", v)
+            };
+            write_long(out, idx as i64)
+        },
+        (S::Array(items), V::Array(values)) => {
+            if !values.is_empty() {
+                write_long(out, values.len() as i64);
+                for v in values {
+                    encode(items, v, out)?;
+                }
+            }
+            write_long(out, 0);
+        },
+        (S::Map(schema), V::Map(entries)) => {
+            if !entries.is_empty() {
+                write_long(out, entries.len() as i64);
+                for (key, v) in entries {
+                    write_bytes(out, key.as_bytes());
+                    encode(schema, v, out)?;
+                }
+            }
+            write_long(out, 0);
+        },
+        (S::Union(variants), value) => {
+            let Some(idx) = variants.iter().position(|s| s.accepts(value)) else {
+                polars_bail!(ComputeError: "avro: value {:?} does not match union {:?}", value, variants)
+            };
+            write_long(out, idx as i64);
+            encode(&variants[idx], value, out)?;
+        },
+        (S::Record(fields), V::Record(values)) => {
+            for (name, schema) in fields {
+                let value = values
+                    .iter()
+                    .find(|(field_name, _)| field_name == name)
+                    .map_or(&V::Null, |(_, v)| v);
+
+                encode(schema, value, out)
+                    .map_err(|e| e.wrap_msg(|msg| format!("{msg} (field '{name}')")))?;
+            }
+        },
+        (schema, value) => {
+            polars_bail!(ComputeError: "avro: value {:?} does not match schema {:?}", value, schema)
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avro_roundtrip() {
+        let schema = r#"{
+            "type": "record",
+            "name": "entry",
+            "fields": [
+                {"name": "id", "type": "long", "field-id": 1},
+                {"name": "path", "type": "string", "field-id": 2},
+                {"name": "note", "type": ["null", "string"], "default": null, "field-id": 3},
+                {
+                    "name": "bounds",
+                    "type": ["null", {
+                        "type": "array",
+                        "logicalType": "map",
+                        "items": {
+                            "type": "record",
+                            "name": "k1_v2",
+                            "fields": [
+                                {"name": "key", "type": "int", "field-id": 5},
+                                {"name": "value", "type": "bytes", "field-id": 6}
+                            ]
+                        }
+                    }],
+                    "default": null,
+                    "field-id": 4
+                },
+                {"name": "props", "type": {"type": "map", "values": "double"}}
+            ]
+        }"#;
+
+        let records = (0..3)
+            .map(|i| {
+                AvroValue::Record(vec![
+                    ("id".into(), AvroValue::Long(-i * 1000)),
+                    (
+                        "path".into(),
+                        AvroValue::String(format!("s3://bucket/{i}.parquet")),
+                    ),
+                    (
+                        "note".into(),
+                        if i == 1 {
+                            AvroValue::Null
+                        } else {
+                            AvroValue::String("x".repeat(i as usize))
+                        },
+                    ),
+                    (
+                        "bounds".into(),
+                        AvroValue::Array(vec![AvroValue::Record(vec![
+                            ("key".into(), AvroValue::Int(i as i32)),
+                            ("value".into(), AvroValue::Bytes(vec![i as u8; 3])),
+                        ])]),
+                    ),
+                    (
+                        "props".into(),
+                        AvroValue::Map(vec![("a".into(), AvroValue::Double(i as f64 / 2.0))]),
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let bytes = write_avro_file(schema, &[("format-version", "2")], &records).unwrap();
+        let file = read_avro_file(&bytes).unwrap();
+
+        assert_eq!(file.metadata_str("format-version"), Some("2"));
+        assert_eq!(file.records, records);
+        assert_eq!(file.records[1].field("note"), None);
+        assert_eq!(
+            file.records[2].field("path").and_then(AvroValue::as_str),
+            Some("s3://bucket/2.parquet")
+        );
+    }
+
+    #[test]
+    fn test_avro_unions_and_nulls() {
+        let schema = r#"{
+            "type": "record",
+            "name": "r",
+            "fields": [
+                {"name": "n", "type": "null"},
+                {"name": "u", "type": ["null", "int", "long", "string", {
+                    "type": "record",
+                    "name": "inner",
+                    "fields": [{"name": "x", "type": ["null", "double"]}]
+                }]},
+                {"name": "l", "type": ["long", "null"]}
+            ]
+        }"#;
+
+        let record = |u: AvroValue, l: AvroValue| {
+            AvroValue::Record(vec![
+                ("n".into(), AvroValue::Null),
+                ("u".into(), u),
+                ("l".into(), l),
+            ])
+        };
+        let inner = |x: AvroValue| AvroValue::Record(vec![("x".into(), x)]);
+
+        let records = vec![
+            record(AvroValue::Null, AvroValue::Long(1)),
+            record(AvroValue::Int(-2), AvroValue::Null),
+            record(AvroValue::Long(i64::MAX), AvroValue::Long(i64::MIN)),
+            record(AvroValue::String("".into()), AvroValue::Null),
+            record(inner(AvroValue::Double(0.5)), AvroValue::Long(0)),
+            record(inner(AvroValue::Null), AvroValue::Null),
+        ];
+
+        let bytes = write_avro_file(schema, &[], &records).unwrap();
+        assert_eq!(read_avro_file(&bytes).unwrap().records, records);
+
+        // Missing record fields are written as null.
+        let bytes = write_avro_file(schema, &[], &[AvroValue::Record(vec![])]).unwrap();
+        let file = read_avro_file(&bytes).unwrap();
+        assert_eq!(file.records, [record(AvroValue::Null, AvroValue::Null)]);
+        assert_eq!(file.records[0].field("u"), None);
+
+        // Null is rejected by non-nullable types.
+        let schema = r#"{
+            "type": "record",
+            "name": "r",
+            "fields": [{"name": "id", "type": "long"}]
+        }"#;
+        let err = write_avro_file(schema, &[], &[AvroValue::Record(vec![])]).unwrap_err();
+        assert!(err.to_string().contains("(field 'id')"));
+
+        // Union branch index out of bounds.
+        let schema = AvroSchema::try_from_json(br#"["null", "long"]"#).unwrap();
+        let mut buf = vec![];
+        write_long(&mut buf, 2);
+        let err = decode(&schema, &mut buf.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("union index 2 out of bounds"));
+    }
+
+    #[test]
+    fn test_avro_multiple_blocks() {
+        let schema_json = r#"{
+            "type": "record",
+            "name": "r",
+            "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "tags", "type": {"type": "array", "items": "string"}}
+            ]
+        }"#;
+        let schema = AvroSchema::try_from_json(schema_json.as_bytes()).unwrap();
+
+        let record = |id: i64, tags: &[&str]| {
+            AvroValue::Record(vec![
+                ("id".into(), AvroValue::Long(id)),
+                (
+                    "tags".into(),
+                    AvroValue::Array(
+                        tags.iter()
+                            .map(|t| AvroValue::String(t.to_string()))
+                            .collect(),
+                    ),
+                ),
+            ])
+        };
+
+        let first = [record(0, &["a"]), record(1, &[])];
+        let mut bytes = write_avro_file(schema_json, &[], &first).unwrap();
+        let sync_marker = bytes[bytes.len() - SYNC_MARKER_LEN..].to_vec();
+
+        // Append a second block, in which the array of the second record is split into a block
+        // with a negative count (followed by its size in bytes) and a regular block.
+        let mut block = vec![];
+        encode(&schema, &record(2, &["b", "c"]), &mut block).unwrap();
+        write_long(&mut block, 3);
+        let mut items = vec![];
+        write_bytes(&mut items, b"d");
+        write_bytes(&mut items, b"e");
+        write_long(&mut block, -2);
+        write_long(&mut block, items.len() as i64);
+        block.extend_from_slice(&items);
+        write_long(&mut block, 1);
+        write_bytes(&mut block, b"f");
+        write_long(&mut block, 0);
+
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&block).unwrap();
+        write_long(&mut bytes, 2);
+        write_bytes(&mut bytes, &encoder.finish().unwrap());
+        bytes.extend_from_slice(&sync_marker);
+
+        let file = read_avro_file(&bytes).unwrap();
+        assert_eq!(
+            file.records,
+            [
+                record(0, &["a"]),
+                record(1, &[]),
+                record(2, &["b", "c"]),
+                record(3, &["d", "e", "f"]),
+            ]
+        );
+
+        // The sync marker after each block must match the one in the header.
+        let n = bytes.len();
+        bytes[n - 1] ^= 1;
+        let err = read_avro_file(&bytes).unwrap_err();
+        assert!(err.to_string().contains("sync marker mismatch"));
+    }
+
+    #[test]
+    fn test_avro_invalid_block_count() {
+        let mut buf = vec![];
+        write_long(&mut buf, i64::MIN);
+        write_long(&mut buf, 0);
+
+        let err = read_blocks(&mut buf.as_slice(), |_| Ok(())).unwrap_err();
+        assert!(err.to_string().contains("block count out of range"));
+    }
+}
@@ -0,0 +1,465 @@
+use async_trait::async_trait;
+use polars_error::{PolarsResult, polars_bail, to_compute_err};
+use reqwest::RequestBuilder;
+
+use super::commit::IcebergCatalog;
+use super::models::{
+    CatalogConfig, CommitTableResponse, LoadTableResult, TableIdentifier, TableRequirement,
+    TableUpdate,
+};
+use crate::catalog::utils::{do_request, read_response};
+use crate::cloud::USER_AGENT;
+use crate::utils::decode_json_response;
+
+/// Iceberg REST catalog client, see <https://iceberg.apache.org/rest-catalog-spec/>.
+pub struct IcebergCatalogClient {
+    /// Base URL of the catalog API, including the `v1/{prefix}/` path.
+    base_url: String,
+    http_client: reqwest::Client,
+}
+
+impl IcebergCatalogClient {
+    pub async fn list_namespaces(&self, parent: Option<&[&str]>) -> PolarsResult<Vec<Vec<String>>> {
+        let request = self
+            .http_client
+            .get(format!("{}namespaces", &self.base_url));
+
+        let request = if let Some(parent) = parent {
+            request.query(&[("parent", parent.join(NAMESPACE_SEPARATOR))])
+        } else {
+            request
+        };
+
+        read_all_pages(request).await
+    }
+
+    pub async fn list_tables(&self, namespace: &[&str]) -> PolarsResult<Vec<TableIdentifier>> {
+        read_all_pages(self.http_client.get(format!(
+            "{}namespaces/{}/tables",
+            &self.base_url,
+            encode_namespace(namespace)
+        )))
+        .await
+    }
+
+    /// Loads the table metadata, requesting vended storage credentials from the catalog.
+    pub async fn load_table(
+        &self,
+        namespace: &[&str],
+        table_name: &str,
+    ) -> PolarsResult<LoadTableResult> {
+        let bytes = do_request(
+            self.http_client
+                .get(self.table_url(namespace, table_name))
+                .header("X-Iceberg-Access-Delegation", "vended-credentials"),
+        )
+        .await?;
+
+        decode_json_response(&bytes)
+    }
+
+    /// Commits updates to the table. Returns `None` if the catalog rejected the commit because
+    /// the requirements were not met, i.e. the table was concurrently modified.
+    pub async fn commit_table(
+        &self,
+        namespace: &[&str],
+        table_name: &str,
+        requirements: &[TableRequirement],
+        updates: &[TableUpdate],
+    ) -> PolarsResult<Option<CommitTableResponse>> {
+        let resp = self
+            .http_client
+            .post(self.table_url(namespace, table_name))
+            .json(&Body {
+                identifier: Identifier {
+                    namespace,
+                    name: table_name,
+                },
+                requirements,
+                updates,
+            })
+            .send()
+            .await
+            .map_err(to_compute_err)?;
+
+        if resp.status() == reqwest::StatusCode::CONFLICT {
+            return Ok(None);
+        }
+
+        return decode_json_response(&read_response(resp).await?).map(Some);
+
+        #[derive(serde::Serialize)]
+        struct Body<'a> {
+            identifier: Identifier<'a>,
+            requirements: &'a [TableRequirement],
+            updates: &'a [TableUpdate],
+        }
+
+        #[derive(serde::Serialize)]
+        struct Identifier<'a> {
+            namespace: &'a [&'a str],
+            name: &'a str,
+        }
+    }
+
+    fn table_url(&self, namespace: &[&str], table_name: &str) -> String {
+        format!(
+            "{}namespaces/{}/tables/{}",
+            &self.base_url,
+            encode_namespace(namespace),
+            percent_encoding::utf8_percent_encode(table_name, percent_encoding::NON_ALPHANUMERIC),
+        )
+    }
+}
+
+#[async_trait]
+impl IcebergCatalog for IcebergCatalogClient {
+    async fn load_table(
+        &self,
+        namespace: &[&str],
+        table_name: &str,
+    ) -> PolarsResult<LoadTableResult> {
+        IcebergCatalogClient::load_table(self, namespace, table_name).await
+    }
+
+    async fn commit_table(
+        &self,
+        namespace: &[&str],
+        table_name: &str,
+        requirements: &[TableRequirement],
+        updates: &[TableUpdate],
+    ) -> PolarsResult<Option<CommitTableResponse>> {
+        IcebergCatalogClient::commit_table(self, namespace, table_name, requirements, updates).await
+    }
+}
+
+/// Separator between the levels of a multi-level namespace.
+const NAMESPACE_SEPARATOR: &str = "\u{1f}";
+
+fn encode_namespace(namespace: &[&str]) -> String {
+    percent_encoding::utf8_percent_encode(
+        &namespace.join(NAMESPACE_SEPARATOR),
+        percent_encoding::NON_ALPHANUMERIC,
+    )
+    .to_string()
+}
+
+/// Traverses paginated list responses that look like:
+/// ```text
+/// {
+///     "namespaces" | "identifiers": [$T, $T, ...],
+///     "next-page-token": "token" or null,
+/// }
+/// ```
+async fn read_all_pages<T>(request: RequestBuilder) -> PolarsResult<Vec<T>>
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    let mut out = vec![];
+    let mut page_token: Option<String> = None;
+
+    loop {
+        let request = request.try_clone().unwrap();
+
+        let request = if let Some(page_token) = page_token.take() {
+            request.query(&[("pageToken", page_token)])
+        } else {
+            request
+        };
+
+        let Page {
+            items,
+            next_page_token,
+        } = decode_json_response(&do_request(request).await?)?;
+
+        out.extend(items);
+
+        match next_page_token.filter(|v| !v.is_empty()) {
+            Some(v) => page_token = Some(v),
+            None => return Ok(out),
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Page<T> {
+        #[serde(alias = "namespaces", alias = "identifiers", default = "Vec::new")]
+        items: Vec<T>,
+        #[serde(rename = "next-page-token", default)]
+        next_page_token: Option<String>,
+    }
+}
+
+pub struct IcebergCatalogClientBuilder {
+    uri: Option<String>,
+    bearer_token: Option<String>,
+    warehouse: Option<String>,
+    prefix: Option<String>,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for IcebergCatalogClientBuilder {
+    fn default() -> Self {
+        Self {
+            uri: None,
+            bearer_token: None,
+            warehouse: None,
+            prefix: None,
+        }
+    }
+}
+
+impl IcebergCatalogClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_uri(mut self, uri: impl Into<String>) -> Self {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    pub fn with_bearer_token(mut self, bearer_token: impl Into<String>) -> Self {
+        self.bearer_token = Some(bearer_token.into());
+        self
+    }
+
+    pub fn with_warehouse(mut self, warehouse: impl Into<String>) -> Self {
+        self.warehouse = Some(warehouse.into());
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Builds the client, fetching the catalog configuration (which may override the URI and
+    /// path prefix) from the `v1/config` endpoint.
+    pub async fn build(self) -> PolarsResult<IcebergCatalogClient> {
+        let Some(uri) = self.uri else {
+            polars_bail!(ComputeError: "expected Some(_) for uri")
+        };
+
+        let http_client = {
+            let builder = reqwest::ClientBuilder::new().user_agent(USER_AGENT);
+
+            let builder = if let Some(bearer_token) = self.bearer_token {
+                use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+
+                let mut headers = HeaderMap::new();
+
+                let mut auth_value =
+                    HeaderValue::from_str(format!("Bearer {bearer_token}").as_str()).unwrap();
+                auth_value.set_sensitive(true);
+
+                headers.insert(AUTHORIZATION, auth_value);
+
+                builder.default_headers(headers)
+            } else {
+                builder
+            };
+
+            builder.build().map_err(to_compute_err)?
+        };
+
+        let uri = uri.trim_end_matches('/');
+
+        let request = http_client.get(format!("{uri}/v1/config"));
+        let request = if let Some(warehouse) = &self.warehouse {
+            request.query(&[("warehouse", warehouse)])
+        } else {
+            request
+        };
+
+        let CatalogConfig {
+            mut defaults,
+            mut overrides,
+        } = decode_json_response(&do_request(request).await?)?;
+
+        let uri = overrides
+            .remove("uri")
+            .map_or(uri.to_string(), |v| v.trim_end_matches('/').to_string());
+        let prefix = overrides
+            .remove("prefix")
+            .or(self.prefix)
+            .or_else(|| defaults.remove("prefix"));
+
+        let base_url = match prefix.as_deref().map(|p| p.trim_matches('/')) {
+            Some(prefix) if !prefix.is_empty() => format!("{uri}/v1/{prefix}/"),
+            _ => format!("{uri}/v1/"),
+        };
+
+        Ok(IcebergCatalogClient {
+            base_url,
+            http_client,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::catalog::iceberg::commit::{SnapshotOperation, commit_data_files};
+    use crate::catalog::iceberg::manifest::DataFile;
+    use crate::pl_async::get_runtime;
+
+    /// Minimal stand-in for an Iceberg REST catalog serving a single table `db.t`, with the
+    /// table metadata kept as JSON and updated on commit.
+    fn serve_catalog(metadata: Value) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let metadata = Arc::new(Mutex::new(metadata));
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap().to_string();
+                let target = parts.next().unwrap().to_string();
+
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((k, v)) = line.split_once(':') {
+                        if k.eq_ignore_ascii_case("content-length") {
+                            content_length = v.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let mut metadata = metadata.lock().unwrap();
+
+                let response = match (method.as_str(), target.as_str()) {
+                    ("GET", "/v1/config") => json!({"overrides": {"prefix": "cat"}}),
+                    ("GET", "/v1/cat/namespaces") => {
+                        json!({"namespaces": [["a"]], "next-page-token": "1"})
+                    },
+                    ("GET", "/v1/cat/namespaces?pageToken=1") => json!({"namespaces": [["b"]]}),
+                    ("GET", "/v1/cat/namespaces/db/tables/t") => json!({"metadata": *metadata}),
+                    ("POST", "/v1/cat/namespaces/db/tables/t") => {
+                        let commit: Value = serde_json::from_slice(&body).unwrap();
+                        let main_snapshot_id = metadata["refs"]["main"]["snapshot-id"].clone();
+                        assert_eq!(commit["requirements"][0]["snapshot-id"], main_snapshot_id);
+
+                        for update in commit["updates"].as_array().unwrap() {
+                            match update["action"].as_str().unwrap() {
+                                "add-snapshot" => {
+                                    let snapshot = update["snapshot"].clone();
+                                    metadata["last-sequence-number"] =
+                                        snapshot["sequence-number"].clone();
+                                    metadata["snapshots"].as_array_mut().unwrap().push(snapshot);
+                                },
+                                "set-snapshot-ref" => {
+                                    metadata["refs"]["main"] = json!({
+                                        "snapshot-id": update["snapshot-id"],
+                                        "type": update["type"],
+                                    })
+                                },
+                                v => panic!("unexpected update: {v}"),
+                            }
+                        }
+
+                        json!({"metadata-location": "", "metadata": *metadata})
+                    },
+                    v => panic!("unexpected request: {v:?}"),
+                };
+
+                let response = response.to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+
+        format!("http://{addr}")
+    }
+
+    #[test]
+    fn test_iceberg_catalog_append_and_scan() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let uri = serve_catalog(json!({
+            "format-version": 2,
+            "table-uuid": "00000000-0000-4000-8000-000000000000",
+            "location": dir.path().to_str().unwrap(),
+            "last-sequence-number": 0,
+            "current-schema-id": 0,
+            "schemas": [{
+                "type": "struct",
+                "schema-id": 0,
+                "fields": [{"id": 1, "name": "a", "required": false, "type": "long"}],
+            }],
+            "default-spec-id": 0,
+            "partition-specs": [{"spec-id": 0, "fields": []}],
+            "snapshots": [],
+            "refs": {},
+        }));
+
+        get_runtime().block_on(async {
+            let client = IcebergCatalogClientBuilder::new()
+                .with_uri(uri)
+                .build()
+                .await
+                .unwrap();
+
+            assert_eq!(
+                client.list_namespaces(None).await.unwrap(),
+                [vec!["a".to_string()], vec!["b".to_string()]]
+            );
+
+            let table = client.load_table(&["db"], "t").await.unwrap();
+            let files = table.metadata.resolve_scan_files(None, None).await.unwrap();
+            assert!(files.data_files.is_empty());
+
+            for (path, record_count) in [("0.parquet", 3), ("1.parquet", 4)] {
+                commit_data_files(
+                    &client,
+                    &["db"],
+                    "t",
+                    &[DataFile::new_parquet(path.to_string(), record_count, 100)],
+                    SnapshotOperation::Append,
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+
+            let table = client.load_table(&["db"], "t").await.unwrap();
+            let metadata = &table.metadata;
+            let snapshot = metadata.current_snapshot().unwrap();
+            assert_eq!(snapshot.sequence_number, 2);
+            assert_eq!(snapshot.summary["total-records"], "7");
+
+            let files = metadata.resolve_scan_files(None, None).await.unwrap();
+            assert_eq!(files.data_files, ["1.parquet", "0.parquet"]);
+            assert_eq!(files.physical_row_count, 7);
+            assert!(files.position_deletes.is_empty());
+
+            let files = metadata
+                .resolve_scan_files(snapshot.parent_snapshot_id, None)
+                .await
+                .unwrap();
+            assert_eq!(files.data_files, ["0.parquet"]);
+        });
+    }
+}
@@ -0,0 +1,507 @@
+//! Committing new snapshots to Iceberg tables.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use polars_core::prelude::PlHashMap;
+use polars_error::{PolarsResult, polars_bail, polars_ensure};
+use polars_utils::pl_path::PlRefPath;
+use tokio::io::AsyncWriteExt;
+
+use super::avro::AvroValue;
+use super::manifest::{
+    DataFile, ManifestContent, ManifestFile, read_manifest_list, write_data_manifest,
+    write_manifest_list,
+};
+use super::models::{
+    CommitTableResponse, LoadTableResult, MAIN_BRANCH, Snapshot, TableMetadata, TableRequirement,
+    TableUpdate,
+};
+use super::scan::read_file;
+use super::{random_u64, random_uuid};
+use crate::cloud::CloudOptions;
+use crate::parquet::read::ParquetObjectStore;
+use crate::utils::file::AsyncWriteable;
+use crate::utils::sync_on_close::SyncOnCloseType;
+
+/// Catalog that tables are loaded from and snapshots are committed to.
+#[async_trait]
+pub trait IcebergCatalog: Send + Sync {
+    async fn load_table(
+        &self,
+        namespace: &[&str],
+        table_name: &str,
+    ) -> PolarsResult<LoadTableResult>;
+
+    /// Commits updates to the table. Returns `None` if the catalog rejected the commit because
+    /// the requirements were not met, i.e. the table was concurrently modified.
+    async fn commit_table(
+        &self,
+        namespace: &[&str],
+        table_name: &str,
+        requirements: &[TableRequirement],
+        updates: &[TableUpdate],
+    ) -> PolarsResult<Option<CommitTableResponse>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotOperation {
+    /// Adds data files to the table.
+    Append,
+    /// Replaces all data in the table with the added data files.
+    Overwrite,
+}
+
+impl SnapshotOperation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Append => "append",
+            Self::Overwrite => "overwrite",
+        }
+    }
+}
+
+impl TableMetadata {
+    /// Returns a new, unique directory under the table's data location for writing data files.
+    pub fn new_write_data_path(&self) -> String {
+        let data_path = match self.properties.get("write.data.path") {
+            Some(path) => path.trim_end_matches('/').to_string(),
+            None => format!("{}/data", self.location.trim_end_matches('/')),
+        };
+
+        format!("{data_path}/{}/", random_uuid())
+    }
+}
+
+/// Builds the manifest entry for an already-written parquet file, reading the row count from its
+/// footer.
+pub async fn parquet_data_file(
+    path: &str,
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<DataFile> {
+    let mut reader =
+        ParquetObjectStore::from_uri(PlRefPath::new(path), cloud_options, None).await?;
+    let file_size_in_bytes = reader.length().await? as i64;
+    let record_count = reader.num_rows().await? as i64;
+
+    Ok(DataFile::new_parquet(
+        path.to_string(),
+        record_count,
+        file_size_in_bytes,
+    ))
+}
+
+/// Commits the data files to the table as a new snapshot on the `main` branch.
+///
+/// If the table was concurrently modified, the commit is retried on top of the new table state,
+/// up to the number of times configured by the `commit.retry.num-retries` table property. If
+/// `cloud_options` is `None`, the storage configuration returned by the catalog is used.
+pub async fn commit_data_files(
+    catalog: &dyn IcebergCatalog,
+    namespace: &[&str],
+    table_name: &str,
+    data_files: &[DataFile],
+    operation: SnapshotOperation,
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<CommitTableResponse> {
+    let mut table = catalog.load_table(namespace, table_name).await?;
+
+    let catalog_cloud_options;
+    let cloud_options = match cloud_options {
+        Some(v) => Some(v),
+        None => {
+            catalog_cloud_options = table.cloud_options()?;
+            catalog_cloud_options.as_ref()
+        },
+    };
+
+    let snapshot_id = (random_u64() >> 1) as i64;
+    let commit_uuid = random_uuid();
+
+    // The manifest does not depend on the table state (snapshot IDs and sequence numbers are
+    // inherited from the manifest list), so it is written only once.
+    let manifest = {
+        let metadata = &table.metadata;
+        check_supported(metadata)?;
+
+        let schema = metadata.current_schema()?;
+        let partition_spec_id = metadata.default_partition_spec().map_or(0, |s| s.spec_id);
+
+        let manifest_path = format!("{}/{commit_uuid}-m0.avro", metadata_dir(metadata));
+        let bytes = write_data_manifest(
+            data_files,
+            &serde_json::json!({
+                "type": "struct",
+                "schema-id": schema.schema_id,
+                "fields": &schema.fields,
+            })
+            .to_string(),
+            partition_spec_id,
+        )?;
+        let manifest_length = bytes.len() as i64;
+        write_file(&manifest_path, bytes, cloud_options).await?;
+
+        ManifestFile {
+            manifest_path,
+            manifest_length,
+            partition_spec_id,
+            content: ManifestContent::Data,
+            sequence_number: 0,
+            min_sequence_number: 0,
+            added_snapshot_id: snapshot_id,
+            added_files_count: data_files.len() as i32,
+            existing_files_count: 0,
+            deleted_files_count: 0,
+            added_rows_count: data_files.iter().map(|f| f.record_count).sum(),
+            existing_rows_count: 0,
+            deleted_rows_count: 0,
+            partitions: AvroValue::Array(vec![]),
+        }
+    };
+
+    let num_retries = table_property(&table.metadata, "commit.retry.num-retries").unwrap_or(4);
+    let mut retry_wait_ms = table_property(&table.metadata, "commit.retry.min-wait-ms")
+        .unwrap_or(100)
+        .max(1);
+    let max_retry_wait_ms =
+        table_property(&table.metadata, "commit.retry.max-wait-ms").unwrap_or(60_000);
+
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+
+        let metadata = &table.metadata;
+        check_supported(metadata)?;
+
+        let parent_snapshot = metadata.current_snapshot();
+        let sequence_number = metadata.last_sequence_number + 1;
+
+        let mut manifests = vec![ManifestFile {
+            sequence_number,
+            min_sequence_number: sequence_number,
+            ..manifest.clone()
+        }];
+
+        if operation == SnapshotOperation::Append
+            && let Some(parent) = parent_snapshot
+        {
+            let Some(manifest_list) = parent.manifest_list.as_deref() else {
+                polars_bail!(
+                    ComputeError:
+                    "iceberg snapshot {} has no manifest list",
+                    parent.snapshot_id
+                )
+            };
+            manifests.extend(read_manifest_list(
+                &read_file(manifest_list, cloud_options).await?,
+            )?);
+        }
+
+        let manifest_list_path = format!(
+            "{}/snap-{snapshot_id}-{attempt}-{commit_uuid}.avro",
+            metadata_dir(metadata)
+        );
+        let manifest_list = write_manifest_list(
+            &manifests,
+            snapshot_id,
+            parent_snapshot.map(|s| s.snapshot_id),
+            sequence_number,
+        )?;
+        write_file(&manifest_list_path, manifest_list, cloud_options).await?;
+
+        let snapshot = Snapshot {
+            snapshot_id,
+            parent_snapshot_id: parent_snapshot.map(|s| s.snapshot_id),
+            sequence_number,
+            timestamp_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as i64,
+            manifest_list: Some(manifest_list_path),
+            manifests: None,
+            summary: snapshot_summary(data_files, operation, parent_snapshot),
+            schema_id: Some(metadata.current_schema()?.schema_id),
+        };
+
+        let mut requirements = vec![TableRequirement::AssertRefSnapshotId {
+            ref_name: MAIN_BRANCH.to_string(),
+            snapshot_id: parent_snapshot.map(|s| s.snapshot_id),
+        }];
+
+        if let Some(uuid) = &metadata.table_uuid {
+            requirements.push(TableRequirement::AssertTableUuid { uuid: uuid.clone() })
+        }
+
+        let updates = [
+            TableUpdate::AddSnapshot { snapshot },
+            TableUpdate::SetSnapshotRef {
+                ref_name: MAIN_BRANCH.to_string(),
+                ref_type: "branch".to_string(),
+                snapshot_id,
+            },
+        ];
+
+        if let Some(response) = catalog
+            .commit_table(namespace, table_name, &requirements, &updates)
+            .await?
+        {
+            return Ok(response);
+        }
+
+        polars_ensure!(
+            attempt <= num_retries,
+            ComputeError:
+            "failed to commit to iceberg table '{}' after {} attempts: table was concurrently modified",
+            table_name, attempt
+        );
+
+        if polars_core::config::verbose() {
+            eprintln!(
+                "[iceberg commit]: table '{table_name}' was concurrently modified, \
+                retrying in {retry_wait_ms}ms (attempt {attempt})"
+            );
+        }
+
+        tokio::time::sleep(Duration::from_millis(retry_wait_ms)).await;
+        retry_wait_ms = (retry_wait_ms * 2).min(max_retry_wait_ms);
+
+        table = catalog.load_table(namespace, table_name).await?;
+    }
+}
+
+fn check_supported(metadata: &TableMetadata) -> PolarsResult<()> {
+    polars_ensure!(
+        metadata.format_version == 2,
+        ComputeError:
+        "committing to iceberg tables is only supported for format version 2, found: {}",
+        metadata.format_version
+    );
+
+    if metadata
+        .default_partition_spec()
+        .is_some_and(|s| !s.fields.is_empty())
+    {
+        polars_bail!(ComputeError: "committing to partitioned iceberg tables is not yet supported")
+    }
+
+    Ok(())
+}
+
+fn metadata_dir(metadata: &TableMetadata) -> String {
+    format!("{}/metadata", metadata.location.trim_end_matches('/'))
+}
+
+fn table_property(metadata: &TableMetadata, key: &str) -> Option<u64> {
+    metadata.properties.get(key).and_then(|v| v.parse().ok())
+}
+
+fn snapshot_summary(
+    data_files: &[DataFile],
+    operation: SnapshotOperation,
+    parent_snapshot: Option<&Snapshot>,
+) -> PlHashMap<String, String> {
+    let added_data_files = data_files.len() as i64;
+    let added_records: i64 = data_files.iter().map(|f| f.record_count).sum();
+    let added_files_size: i64 = data_files.iter().map(|f| f.file_size_in_bytes).sum();
+
+    let mut summary = PlHashMap::from_iter([
+        ("operation".to_string(), operation.as_str().to_string()),
+        ("added-data-files".to_string(), added_data_files.to_string()),
+        ("added-records".to_string(), added_records.to_string()),
+        ("added-files-size".to_string(), added_files_size.to_string()),
+    ]);
+
+    for (total_key, deleted_key, added) in [
+        ("total-data-files", "deleted-data-files", added_data_files),
+        ("total-records", "deleted-records", added_records),
+        ("total-files-size", "removed-files-size", added_files_size),
+    ] {
+        // Table totals are only tracked if the parent snapshot tracks them.
+        let parent_total = match parent_snapshot {
+            Some(parent) => parent
+                .summary
+                .get(total_key)
+                .and_then(|v| v.parse::<i64>().ok()),
+            None => Some(0),
+        };
+
+        match operation {
+            SnapshotOperation::Append => {
+                if let Some(total) = parent_total {
+                    summary.insert(total_key.to_string(), (total + added).to_string());
+                }
+            },
+            SnapshotOperation::Overwrite => {
+                if let Some(total) = parent_total {
+                    summary.insert(deleted_key.to_string(), total.to_string());
+                }
+                summary.insert(total_key.to_string(), added.to_string());
+            },
+        }
+    }
+
+    summary
+}
+
+/// Writes a complete (local or cloud) file.
+async fn write_file(
+    path: &str,
+    bytes: Vec<u8>,
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<()> {
+    let path = PlRefPath::new(path);
+    crate::utils::mkdir::tokio_mkdir_recursive(&path).await?;
+
+    let mut writer = AsyncWriteable::try_new(
+        path,
+        cloud_options,
+        crate::configs::upload_chunk_size(),
+        crate::configs::upload_concurrency().get(),
+        None,
+    )
+    .await?;
+
+    writer.write_all(&bytes).await?;
+    writer.close(SyncOnCloseType::None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::catalog::iceberg::models::SnapshotReference;
+    use crate::pl_async::get_runtime;
+
+    /// In-memory catalog holding a single table, that rejects the next `num_conflicts` commits.
+    struct MemoryCatalog {
+        metadata: Mutex<TableMetadata>,
+        num_conflicts: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl IcebergCatalog for MemoryCatalog {
+        async fn load_table(&self, _: &[&str], _: &str) -> PolarsResult<LoadTableResult> {
+            Ok(LoadTableResult {
+                metadata_location: None,
+                metadata: self.metadata.lock().unwrap().clone(),
+                config: Default::default(),
+                storage_credentials: vec![],
+            })
+        }
+
+        async fn commit_table(
+            &self,
+            _: &[&str],
+            _: &str,
+            _: &[TableRequirement],
+            updates: &[TableUpdate],
+        ) -> PolarsResult<Option<CommitTableResponse>> {
+            if self
+                .num_conflicts
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Ok(None);
+            }
+
+            let mut metadata = self.metadata.lock().unwrap();
+
+            for update in updates {
+                match update {
+                    TableUpdate::AddSnapshot { snapshot } => {
+                        metadata.last_sequence_number = snapshot.sequence_number;
+                        metadata.snapshots.push(snapshot.clone());
+                    },
+                    TableUpdate::SetSnapshotRef {
+                        ref_name,
+                        ref_type,
+                        snapshot_id,
+                    } => {
+                        metadata.refs.insert(
+                            ref_name.clone(),
+                            SnapshotReference {
+                                snapshot_id: *snapshot_id,
+                                ref_type: ref_type.clone(),
+                            },
+                        );
+                    },
+                }
+            }
+
+            Ok(Some(CommitTableResponse {
+                metadata_location: String::new(),
+                metadata: metadata.clone(),
+            }))
+        }
+    }
+
+    #[test]
+    fn test_commit_retry_and_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let metadata: TableMetadata = serde_json::from_value(serde_json::json!({
+            "format-version": 2,
+            "location": dir.path().to_str().unwrap(),
+            "schemas": [{
+                "schema-id": 0,
+                "fields": [{"id": 1, "name": "a", "required": false, "type": "long"}],
+            }],
+            "current-schema-id": 0,
+            "properties": {"commit.retry.min-wait-ms": "1"},
+        }))
+        .unwrap();
+
+        let catalog = MemoryCatalog {
+            metadata: Mutex::new(metadata),
+            num_conflicts: AtomicUsize::new(2),
+        };
+
+        get_runtime().block_on(async {
+            let commit = |path: &str, record_count, operation| {
+                let data_files = [DataFile::new_parquet(path.to_string(), record_count, 100)];
+                let catalog = &catalog;
+
+                async move {
+                    commit_data_files(catalog, &[], "t", &data_files, operation, None)
+                        .await
+                        .unwrap()
+                        .metadata
+                }
+            };
+
+            let metadata = commit("0.parquet", 3, SnapshotOperation::Append).await;
+            let snapshot = metadata.current_snapshot().unwrap();
+            assert!(snapshot.manifest_list.as_ref().unwrap().contains("-3-"));
+
+            let metadata = commit("1.parquet", 4, SnapshotOperation::Append).await;
+            let files = metadata.resolve_scan_files(None, None).await.unwrap();
+            assert_eq!(files.data_files, ["1.parquet", "0.parquet"]);
+
+            let metadata = commit("2.parquet", 5, SnapshotOperation::Overwrite).await;
+            let snapshot = metadata.current_snapshot().unwrap();
+            assert_eq!(snapshot.summary["operation"], "overwrite");
+            assert_eq!(snapshot.summary["deleted-records"], "7");
+            assert_eq!(snapshot.summary["total-records"], "5");
+
+            let files = metadata.resolve_scan_files(None, None).await.unwrap();
+            assert_eq!(files.data_files, ["2.parquet"]);
+            assert_eq!(files.physical_row_count, 5);
+        });
+
+        // Exhausts the retries.
+        catalog.num_conflicts.store(10, Ordering::Relaxed);
+
+        let result = get_runtime().block_on(commit_data_files(
+            &catalog,
+            &[],
+            "t",
+            &[DataFile::new_parquet("3.parquet".to_string(), 1, 100)],
+            SnapshotOperation::Append,
+            None,
+        ));
+        assert!(result.is_err());
+    }
+}
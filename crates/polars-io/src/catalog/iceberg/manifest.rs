@@ -0,0 +1,473 @@
+//! Iceberg manifest lists and manifests, see <https://iceberg.apache.org/spec/#manifests>.
+
+use polars_error::{PolarsResult, polars_bail, polars_err};
+
+use super::avro::{AvroValue, read_avro_file, write_avro_file};
+
+/// An entry of a snapshot's manifest list.
+#[derive(Debug, Clone)]
+pub struct ManifestFile {
+    pub manifest_path: String,
+    pub manifest_length: i64,
+    pub partition_spec_id: i32,
+    pub content: ManifestContent,
+    pub sequence_number: i64,
+    pub min_sequence_number: i64,
+    pub added_snapshot_id: i64,
+    pub added_files_count: i32,
+    pub existing_files_count: i32,
+    pub deleted_files_count: i32,
+    pub added_rows_count: i64,
+    pub existing_rows_count: i64,
+    pub deleted_rows_count: i64,
+    /// Per partition field summaries, kept as-is.
+    pub partitions: AvroValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestContent {
+    Data,
+    Deletes,
+}
+
+/// An entry of a manifest, tracking a single data or delete file.
+#[derive(Debug, Clone)]
+pub struct ManifestEntry {
+    pub status: ManifestEntryStatus,
+    pub snapshot_id: i64,
+    /// Data sequence number of the file (inherited from the manifest if not explicitly set).
+    pub sequence_number: i64,
+    pub data_file: DataFile,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestEntryStatus {
+    Existing,
+    Added,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFileContent {
+    Data,
+    PositionDeletes,
+    EqualityDeletes,
+}
+
+#[derive(Debug, Clone)]
+pub struct DataFile {
+    pub content: DataFileContent,
+    pub file_path: String,
+    pub file_format: String,
+    pub partition_spec_id: i32,
+    /// Partition tuple, compared for equality to scope delete files.
+    pub partition: AvroValue,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+    pub lower_bounds: Vec<(i32, Vec<u8>)>,
+    pub upper_bounds: Vec<(i32, Vec<u8>)>,
+    pub equality_ids: Option<Vec<i32>>,
+    /// Data file that a position delete file applies to (format version 3).
+    pub referenced_data_file: Option<String>,
+}
+
+/// Field ID of the `file_path` column of position delete files.
+pub(super) const POSITION_DELETE_FILE_PATH_FIELD_ID: i32 = 2147483546;
+
+impl DataFile {
+    /// Unpartitioned parquet data file, to be appended to a table.
+    pub fn new_parquet(file_path: String, record_count: i64, file_size_in_bytes: i64) -> Self {
+        Self {
+            content: DataFileContent::Data,
+            file_path,
+            file_format: "PARQUET".to_string(),
+            partition_spec_id: 0,
+            partition: AvroValue::Record(vec![]),
+            record_count,
+            file_size_in_bytes,
+            lower_bounds: vec![],
+            upper_bounds: vec![],
+            equality_ids: None,
+            referenced_data_file: None,
+        }
+    }
+
+    /// For position delete files, returns the single data file path that the deletes apply to,
+    /// if known.
+    pub fn referenced_data_file(&self) -> Option<&str> {
+        if let Some(path) = self.referenced_data_file.as_deref() {
+            return Some(path);
+        }
+
+        let bound = |bounds: &[(i32, Vec<u8>)]| {
+            bounds
+                .iter()
+                .find(|(id, _)| *id == POSITION_DELETE_FILE_PATH_FIELD_ID)
+                .map(|(_, v)| v.as_slice())
+        };
+
+        match (bound(&self.lower_bounds), bound(&self.upper_bounds)) {
+            (Some(lower), Some(upper)) if lower == upper => std::str::from_utf8(lower).ok(),
+            _ => None,
+        }
+    }
+}
+
+fn required<'a>(record: &'a AvroValue, name: &str) -> PolarsResult<&'a AvroValue> {
+    record
+        .field(name)
+        .ok_or_else(|| polars_err!(ComputeError: "iceberg manifest: missing field '{}'", name))
+}
+
+fn required_i64(record: &AvroValue, name: &str) -> PolarsResult<i64> {
+    required(record, name)?.as_i64().ok_or_else(
+        || polars_err!(ComputeError: "iceberg manifest: expected integer for '{}'", name),
+    )
+}
+
+fn required_str<'a>(record: &'a AvroValue, name: &str) -> PolarsResult<&'a str> {
+    required(record, name)?.as_str().ok_or_else(
+        || polars_err!(ComputeError: "iceberg manifest: expected string for '{}'", name),
+    )
+}
+
+/// Reads the first of the given fields that is present (field names differ between format
+/// versions).
+fn optional_i64(record: &AvroValue, names: &[&str]) -> Option<i64> {
+    names
+        .iter()
+        .find_map(|name| record.field(name).and_then(AvroValue::as_i64))
+}
+
+fn bounds(record: &AvroValue, name: &str) -> Vec<(i32, Vec<u8>)> {
+    record
+        .field(name)
+        .and_then(AvroValue::as_array)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|kv| {
+            Some((
+                kv.field("key")?.as_i64()? as i32,
+                kv.field("value")?.as_bytes()?.to_vec(),
+            ))
+        })
+        .collect()
+}
+
+pub fn read_manifest_list(bytes: &[u8]) -> PolarsResult<Vec<ManifestFile>> {
+    read_avro_file(bytes)?
+        .records
+        .into_iter()
+        .map(|record| {
+            let content = match optional_i64(&record, &["content"]).unwrap_or(0) {
+                0 => ManifestContent::Data,
+                1 => ManifestContent::Deletes,
+                v => polars_bail!(ComputeError: "iceberg manifest list: invalid content: {}", v),
+            };
+            let sequence_number = optional_i64(&record, &["sequence_number"]).unwrap_or(0);
+
+            Ok(ManifestFile {
+                manifest_path: required_str(&record, "manifest_path")?.to_string(),
+                manifest_length: required_i64(&record, "manifest_length")?,
+                partition_spec_id: required_i64(&record, "partition_spec_id")? as i32,
+                content,
+                sequence_number,
+                min_sequence_number: optional_i64(&record, &["min_sequence_number"])
+                    .unwrap_or(sequence_number),
+                added_snapshot_id: required_i64(&record, "added_snapshot_id")?,
+                added_files_count: optional_i64(
+                    &record,
+                    &["added_files_count", "added_data_files_count"],
+                )
+                .unwrap_or(0) as i32,
+                existing_files_count: optional_i64(
+                    &record,
+                    &["existing_files_count", "existing_data_files_count"],
+                )
+                .unwrap_or(0) as i32,
+                deleted_files_count: optional_i64(
+                    &record,
+                    &["deleted_files_count", "deleted_data_files_count"],
+                )
+                .unwrap_or(0) as i32,
+                added_rows_count: optional_i64(&record, &["added_rows_count"]).unwrap_or(0),
+                existing_rows_count: optional_i64(&record, &["existing_rows_count"]).unwrap_or(0),
+                deleted_rows_count: optional_i64(&record, &["deleted_rows_count"]).unwrap_or(0),
+                partitions: record
+                    .field("partitions")
+                    .cloned()
+                    .unwrap_or(AvroValue::Null),
+            })
+        })
+        .collect()
+}
+
+/// Reads the entries of a manifest, inheriting snapshot IDs and sequence numbers from the
+/// manifest list entry where they are not set.
+pub fn read_manifest(bytes: &[u8], manifest: &ManifestFile) -> PolarsResult<Vec<ManifestEntry>> {
+    read_avro_file(bytes)?
+        .records
+        .into_iter()
+        .map(|record| {
+            let status = match required_i64(&record, "status")? {
+                0 => ManifestEntryStatus::Existing,
+                1 => ManifestEntryStatus::Added,
+                2 => ManifestEntryStatus::Deleted,
+                v => polars_bail!(ComputeError: "iceberg manifest: invalid entry status: {}", v),
+            };
+            let data_file = required(&record, "data_file")?;
+
+            let content = match optional_i64(data_file, &["content"]).unwrap_or(0) {
+                0 => DataFileContent::Data,
+                1 => DataFileContent::PositionDeletes,
+                2 => DataFileContent::EqualityDeletes,
+                v => {
+                    polars_bail!(ComputeError: "iceberg manifest: invalid data file content: {}", v)
+                },
+            };
+
+            let equality_ids = data_file
+                .field("equality_ids")
+                .and_then(AvroValue::as_array)
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(|v| v.as_i64().map(|v| v as i32))
+                        .collect()
+                });
+
+            Ok(ManifestEntry {
+                status,
+                snapshot_id: optional_i64(&record, &["snapshot_id"])
+                    .unwrap_or(manifest.added_snapshot_id),
+                sequence_number: optional_i64(&record, &["sequence_number"])
+                    .unwrap_or(manifest.sequence_number),
+                data_file: DataFile {
+                    content,
+                    file_path: required_str(data_file, "file_path")?.to_string(),
+                    file_format: required_str(data_file, "file_format")?.to_string(),
+                    partition_spec_id: manifest.partition_spec_id,
+                    partition: data_file
+                        .field("partition")
+                        .cloned()
+                        .unwrap_or(AvroValue::Null),
+                    record_count: required_i64(data_file, "record_count")?,
+                    file_size_in_bytes: required_i64(data_file, "file_size_in_bytes")?,
+                    lower_bounds: bounds(data_file, "lower_bounds"),
+                    upper_bounds: bounds(data_file, "upper_bounds"),
+                    equality_ids,
+                    referenced_data_file: data_file
+                        .field("referenced_data_file")
+                        .and_then(AvroValue::as_str)
+                        .map(str::to_string),
+                },
+            })
+        })
+        .collect()
+}
+
+/// Writes a format version 2 manifest list.
+pub fn write_manifest_list(
+    manifests: &[ManifestFile],
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+    sequence_number: i64,
+) -> PolarsResult<Vec<u8>> {
+    let records = manifests
+        .iter()
+        .map(|m| {
+            AvroValue::Record(vec![
+                (
+                    "manifest_path".into(),
+                    AvroValue::String(m.manifest_path.clone()),
+                ),
+                ("manifest_length".into(), AvroValue::Long(m.manifest_length)),
+                (
+                    "partition_spec_id".into(),
+                    AvroValue::Int(m.partition_spec_id),
+                ),
+                (
+                    "content".into(),
+                    AvroValue::Int(match m.content {
+                        ManifestContent::Data => 0,
+                        ManifestContent::Deletes => 1,
+                    }),
+                ),
+                ("sequence_number".into(), AvroValue::Long(m.sequence_number)),
+                (
+                    "min_sequence_number".into(),
+                    AvroValue::Long(m.min_sequence_number),
+                ),
+                (
+                    "added_snapshot_id".into(),
+                    AvroValue::Long(m.added_snapshot_id),
+                ),
+                (
+                    "added_files_count".into(),
+                    AvroValue::Int(m.added_files_count),
+                ),
+                (
+                    "existing_files_count".into(),
+                    AvroValue::Int(m.existing_files_count),
+                ),
+                (
+                    "deleted_files_count".into(),
+                    AvroValue::Int(m.deleted_files_count),
+                ),
+                (
+                    "added_rows_count".into(),
+                    AvroValue::Long(m.added_rows_count),
+                ),
+                (
+                    "existing_rows_count".into(),
+                    AvroValue::Long(m.existing_rows_count),
+                ),
+                (
+                    "deleted_rows_count".into(),
+                    AvroValue::Long(m.deleted_rows_count),
+                ),
+                ("partitions".into(), m.partitions.clone()),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let snapshot_id = snapshot_id.to_string();
+    let parent_snapshot_id = parent_snapshot_id.map_or("null".to_string(), |v| v.to_string());
+    let sequence_number = sequence_number.to_string();
+
+    write_avro_file(
+        MANIFEST_LIST_SCHEMA_V2,
+        &[
+            ("format-version", "2"),
+            ("snapshot-id", &snapshot_id),
+            ("parent-snapshot-id", &parent_snapshot_id),
+            ("sequence-number", &sequence_number),
+        ],
+        &records,
+    )
+}
+
+/// Writes a format version 2 manifest of added data files for an unpartitioned table.
+///
+/// Snapshot IDs and sequence numbers are left unset, to be inherited from the manifest list.
+pub fn write_data_manifest(
+    data_files: &[DataFile],
+    schema_json: &str,
+    partition_spec_id: i32,
+) -> PolarsResult<Vec<u8>> {
+    let records = data_files
+        .iter()
+        .map(|f| {
+            AvroValue::Record(vec![
+                ("status".into(), AvroValue::Int(1)),
+                (
+                    "data_file".into(),
+                    AvroValue::Record(vec![
+                        ("content".into(), AvroValue::Int(0)),
+                        ("file_path".into(), AvroValue::String(f.file_path.clone())),
+                        (
+                            "file_format".into(),
+                            AvroValue::String(f.file_format.clone()),
+                        ),
+                        ("partition".into(), AvroValue::Record(vec![])),
+                        ("record_count".into(), AvroValue::Long(f.record_count)),
+                        (
+                            "file_size_in_bytes".into(),
+                            AvroValue::Long(f.file_size_in_bytes),
+                        ),
+                    ]),
+                ),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let partition_spec_id = partition_spec_id.to_string();
+
+    write_avro_file(
+        MANIFEST_ENTRY_SCHEMA_V2_UNPARTITIONED,
+        &[
+            ("schema", schema_json),
+            ("partition-spec", "[]"),
+            ("partition-spec-id", &partition_spec_id),
+            ("format-version", "2"),
+            ("content", "data"),
+        ],
+        &records,
+    )
+}
+
+const MANIFEST_LIST_SCHEMA_V2: &str = r#"{
+  "type": "record",
+  "name": "manifest_file",
+  "fields": [
+    {"name": "manifest_path", "type": "string", "field-id": 500},
+    {"name": "manifest_length", "type": "long", "field-id": 501},
+    {"name": "partition_spec_id", "type": "int", "field-id": 502},
+    {"name": "content", "type": "int", "field-id": 517},
+    {"name": "sequence_number", "type": "long", "field-id": 515},
+    {"name": "min_sequence_number", "type": "long", "field-id": 516},
+    {"name": "added_snapshot_id", "type": "long", "field-id": 503},
+    {"name": "added_files_count", "type": "int", "field-id": 504},
+    {"name": "existing_files_count", "type": "int", "field-id": 505},
+    {"name": "deleted_files_count", "type": "int", "field-id": 506},
+    {"name": "added_rows_count", "type": "long", "field-id": 512},
+    {"name": "existing_rows_count", "type": "long", "field-id": 513},
+    {"name": "deleted_rows_count", "type": "long", "field-id": 514},
+    {
+      "name": "partitions",
+      "type": ["null", {
+        "type": "array",
+        "element-id": 508,
+        "items": {
+          "type": "record",
+          "name": "r508",
+          "fields": [
+            {"name": "contains_null", "type": "boolean", "field-id": 509},
+            {"name": "contains_nan", "type": ["null", "boolean"], "default": null, "field-id": 518},
+            {"name": "lower_bound", "type": ["null", "bytes"], "default": null, "field-id": 510},
+            {"name": "upper_bound", "type": ["null", "bytes"], "default": null, "field-id": 511}
+          ]
+        }
+      }],
+      "default": null,
+      "field-id": 507
+    },
+    {"name": "key_metadata", "type": ["null", "bytes"], "default": null, "field-id": 519}
+  ]
+}"#;
+
+const MANIFEST_ENTRY_SCHEMA_V2_UNPARTITIONED: &str = r#"{
+  "type": "record",
+  "name": "manifest_entry",
+  "fields": [
+    {"name": "status", "type": "int", "field-id": 0},
+    {"name": "snapshot_id", "type": ["null", "long"], "default": null, "field-id": 1},
+    {"name": "sequence_number", "type": ["null", "long"], "default": null, "field-id": 3},
+    {"name": "file_sequence_number", "type": ["null", "long"], "default": null, "field-id": 4},
+    {
+      "name": "data_file",
+      "type": {
+        "type": "record",
+        "name": "r2",
+        "fields": [
+          {"name": "content", "type": "int", "field-id": 134},
+          {"name": "file_path", "type": "string", "field-id": 100},
+          {"name": "file_format", "type": "string", "field-id": 101},
+          {"name": "partition", "type": {"type": "record", "name": "r102", "fields": []}, "field-id": 102},
+          {"name": "record_count", "type": "long", "field-id": 103},
+          {"name": "file_size_in_bytes", "type": "long", "field-id": 104},
+          {"name": "column_sizes", "type": ["null", {"type": "array", "logicalType": "map", "items": {"type": "record", "name": "k117_v118", "fields": [{"name": "key", "type": "int", "field-id": 117}, {"name": "value", "type": "long", "field-id": 118}]}}], "default": null, "field-id": 108},
+          {"name": "value_counts", "type": ["null", {"type": "array", "logicalType": "map", "items": {"type": "record", "name": "k119_v120", "fields": [{"name": "key", "type": "int", "field-id": 119}, {"name": "value", "type": "long", "field-id": 120}]}}], "default": null, "field-id": 109},
+          {"name": "null_value_counts", "type": ["null", {"type": "array", "logicalType": "map", "items": {"type": "record", "name": "k121_v122", "fields": [{"name": "key", "type": "int", "field-id": 121}, {"name": "value", "type": "long", "field-id": 122}]}}], "default": null, "field-id": 110},
+          {"name": "nan_value_counts", "type": ["null", {"type": "array", "logicalType": "map", "items": {"type": "record", "name": "k138_v139", "fields": [{"name": "key", "type": "int", "field-id": 138}, {"name": "value", "type": "long", "field-id": 139}]}}], "default": null, "field-id": 137},
+          {"name": "lower_bounds", "type": ["null", {"type": "array", "logicalType": "map", "items": {"type": "record", "name": "k126_v127", "fields": [{"name": "key", "type": "int", "field-id": 126}, {"name": "value", "type": "bytes", "field-id": 127}]}}], "default": null, "field-id": 125},
+          {"name": "upper_bounds", "type": ["null", {"type": "array", "logicalType": "map", "items": {"type": "record", "name": "k129_v130", "fields": [{"name": "key", "type": "int", "field-id": 129}, {"name": "value", "type": "bytes", "field-id": 130}]}}], "default": null, "field-id": 128},
+          {"name": "key_metadata", "type": ["null", "bytes"], "default": null, "field-id": 131},
+          {"name": "split_offsets", "type": ["null", {"type": "array", "element-id": 133, "items": "long"}], "default": null, "field-id": 132},
+          {"name": "equality_ids", "type": ["null", {"type": "array", "element-id": 136, "items": "int"}], "default": null, "field-id": 135},
+          {"name": "sort_order_id", "type": ["null", "int"], "default": null, "field-id": 140}
+        ]
+      },
+      "field-id": 2
+    }
+  ]
+}"#;
@@ -0,0 +1,11 @@
+//! Native client for Iceberg REST catalogs, and reading / writing of the Iceberg metadata files
+//! (manifest lists and manifests) needed to scan and append to tables.
+pub mod avro;
+pub mod client;
+pub mod commit;
+pub mod manifest;
+pub mod models;
+pub mod scan;
+pub mod schema;
+
+pub(crate) use crate::utils::{random_u64, random_uuid};
@@ -0,0 +1,346 @@
+use polars_core::prelude::PlHashMap;
+use polars_error::{PolarsResult, polars_err};
+use polars_utils::pl_path::CloudScheme;
+
+use crate::cloud::CloudOptions;
+
+/// Iceberg table metadata, see <https://iceberg.apache.org/spec/#table-metadata-fields>.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableMetadata {
+    pub format_version: u8,
+    #[serde(default)]
+    pub table_uuid: Option<String>,
+    pub location: String,
+    #[serde(default)]
+    pub last_sequence_number: i64,
+    #[serde(default)]
+    pub last_updated_ms: i64,
+
+    #[serde(default)]
+    pub current_schema_id: Option<i32>,
+    #[serde(default)]
+    pub schemas: Vec<TableSchema>,
+    /// Only present in format version 1.
+    #[serde(default)]
+    pub schema: Option<TableSchema>,
+
+    #[serde(default)]
+    pub default_spec_id: Option<i32>,
+    #[serde(default)]
+    pub partition_specs: Vec<PartitionSpec>,
+
+    #[serde(default)]
+    pub properties: PlHashMap<String, String>,
+
+    #[serde(default)]
+    pub current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    pub refs: PlHashMap<String, SnapshotReference>,
+}
+
+impl TableMetadata {
+    pub fn current_schema(&self) -> PolarsResult<&TableSchema> {
+        match self.current_schema_id {
+            Some(schema_id) => self.schema_by_id(schema_id),
+            None => {
+                self.schema.as_ref().or(self.schemas.last()).ok_or_else(
+                    || polars_err!(ComputeError: "iceberg table metadata has no schema"),
+                )
+            },
+        }
+    }
+
+    pub fn schema_by_id(&self, schema_id: i32) -> PolarsResult<&TableSchema> {
+        self.schemas
+            .iter()
+            .chain(self.schema.as_ref())
+            .find(|s| s.schema_id == schema_id)
+            .ok_or_else(
+                || polars_err!(ComputeError: "iceberg schema not found: schema_id: {}", schema_id),
+            )
+    }
+
+    /// Returns the schema that was current when the snapshot was written.
+    pub fn schema_for_snapshot(&self, snapshot: &Snapshot) -> PolarsResult<&TableSchema> {
+        match snapshot.schema_id {
+            Some(schema_id) => self.schema_by_id(schema_id),
+            None => self.current_schema(),
+        }
+    }
+
+    pub fn partition_spec_by_id(&self, spec_id: i32) -> Option<&PartitionSpec> {
+        self.partition_specs.iter().find(|s| s.spec_id == spec_id)
+    }
+
+    pub fn default_partition_spec(&self) -> Option<&PartitionSpec> {
+        match self.default_spec_id {
+            Some(spec_id) => self.partition_spec_by_id(spec_id),
+            None => self.partition_specs.last(),
+        }
+    }
+
+    pub fn snapshot_by_id(&self, snapshot_id: i64) -> Option<&Snapshot> {
+        self.snapshots.iter().find(|s| s.snapshot_id == snapshot_id)
+    }
+
+    /// The snapshot at the head of the `main` branch.
+    pub fn current_snapshot(&self) -> Option<&Snapshot> {
+        let snapshot_id = match self.refs.get(MAIN_BRANCH) {
+            Some(r) => Some(r.snapshot_id),
+            // Format version 1 uses -1 to indicate no snapshot.
+            None => self.current_snapshot_id.filter(|id| *id != -1),
+        };
+
+        snapshot_id.and_then(|id| self.snapshot_by_id(id))
+    }
+
+    /// The latest snapshot of the `main` branch that was committed at or before the given time.
+    pub fn snapshot_as_of_timestamp(&self, timestamp_ms: i64) -> Option<&Snapshot> {
+        let mut snapshot = self.current_snapshot();
+
+        while let Some(s) = snapshot {
+            if s.timestamp_ms <= timestamp_ms {
+                return Some(s);
+            }
+            snapshot = s.parent_snapshot_id.and_then(|id| self.snapshot_by_id(id));
+        }
+
+        None
+    }
+}
+
+pub const MAIN_BRANCH: &str = "main";
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableSchema {
+    #[serde(default)]
+    pub schema_id: i32,
+    pub fields: Vec<NestedField>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NestedField {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    #[serde(rename = "type")]
+    pub field_type: IcebergType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+/// Iceberg field type; primitive types are kept as their string representation,
+/// e.g. `"long"` or `"decimal(10, 2)"`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum IcebergType {
+    Primitive(String),
+    Nested(NestedType),
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum NestedType {
+    Struct {
+        fields: Vec<NestedField>,
+    },
+    List {
+        #[serde(rename = "element-id")]
+        element_id: i32,
+        element: Box<IcebergType>,
+        #[serde(rename = "element-required")]
+        element_required: bool,
+    },
+    Map {
+        #[serde(rename = "key-id")]
+        key_id: i32,
+        key: Box<IcebergType>,
+        #[serde(rename = "value-id")]
+        value_id: i32,
+        value: Box<IcebergType>,
+        #[serde(rename = "value-required")]
+        value_required: bool,
+    },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionSpec {
+    pub spec_id: i32,
+    pub fields: Vec<PartitionField>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionField {
+    pub source_id: i32,
+    #[serde(default)]
+    pub field_id: Option<i32>,
+    pub name: String,
+    pub transform: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Snapshot {
+    pub snapshot_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub sequence_number: i64,
+    pub timestamp_ms: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_list: Option<String>,
+    /// Only present in format version 1, in place of `manifest_list`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifests: Option<Vec<String>>,
+    #[serde(default)]
+    pub summary: PlHashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotReference {
+    pub snapshot_id: i64,
+    #[serde(rename = "type")]
+    pub ref_type: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TableIdentifier {
+    pub namespace: Vec<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct CatalogConfig {
+    #[serde(default)]
+    pub defaults: PlHashMap<String, String>,
+    #[serde(default)]
+    pub overrides: PlHashMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LoadTableResult {
+    #[serde(default)]
+    pub metadata_location: Option<String>,
+    pub metadata: TableMetadata,
+    /// Table-specific configuration, e.g. vended storage credentials.
+    #[serde(default)]
+    pub config: PlHashMap<String, String>,
+    #[serde(default)]
+    pub storage_credentials: Vec<StorageCredential>,
+}
+
+impl LoadTableResult {
+    /// Builds cloud options for accessing the table location, from the storage configuration
+    /// returned by the catalog.
+    pub fn cloud_options(&self) -> PolarsResult<Option<CloudOptions>> {
+        let location = self.metadata.location.as_str();
+
+        // Prefer the most specific storage credential matching the table location.
+        let storage_credential = self
+            .storage_credentials
+            .iter()
+            .filter(|c| location.starts_with(c.prefix.as_str()))
+            .max_by_key(|c| c.prefix.len());
+
+        let config = self
+            .config
+            .iter()
+            .chain(storage_credential.into_iter().flat_map(|c| c.config.iter()))
+            .filter_map(|(k, v)| iceberg_to_object_store_config_key(k).map(|k| (k, v.clone())))
+            .collect::<Vec<_>>();
+
+        if config.is_empty() {
+            return Ok(None);
+        }
+
+        CloudOptions::from_untyped_config(CloudScheme::from_path(location), config).map(Some)
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct StorageCredential {
+    pub prefix: String,
+    pub config: PlHashMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitTableResponse {
+    pub metadata_location: String,
+    pub metadata: TableMetadata,
+}
+
+/// Optimistic concurrency assertions checked by the catalog before applying a commit.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TableRequirement {
+    AssertTableUuid {
+        uuid: String,
+    },
+    AssertRefSnapshotId {
+        #[serde(rename = "ref")]
+        ref_name: String,
+        #[serde(rename = "snapshot-id")]
+        snapshot_id: Option<i64>,
+    },
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum TableUpdate {
+    AddSnapshot {
+        snapshot: Snapshot,
+    },
+    SetSnapshotRef {
+        #[serde(rename = "ref-name")]
+        ref_name: String,
+        #[serde(rename = "type")]
+        ref_type: String,
+        #[serde(rename = "snapshot-id")]
+        snapshot_id: i64,
+    },
+}
+
+/// Translates an Iceberg FileIO property into the equivalent object store config key.
+///
+/// Keys without a dot are passed through as they may be native config keys; other unknown keys
+/// are dropped, as they would otherwise be rejected when building the cloud options.
+fn iceberg_to_object_store_config_key(key: &str) -> Option<&str> {
+    Some(match key {
+        // S3
+        "s3.endpoint" => "aws_endpoint_url",
+        "s3.access-key-id" => "aws_access_key_id",
+        "s3.secret-access-key" => "aws_secret_access_key",
+        "s3.session-token" => "aws_session_token",
+        "s3.region" => "aws_region",
+        "s3.proxy-uri" => "proxy_url",
+        "s3.connect-timeout" => "connect_timeout",
+        "s3.request-timeout" => "timeout",
+        "s3.force-virtual-addressing" => "aws_virtual_hosted_style_request",
+        // Azure
+        "adls.account-name" => "azure_storage_account_name",
+        "adls.account-key" => "azure_storage_account_key",
+        "adls.sas-token" => "azure_storage_sas_key",
+        "adls.tenant-id" => "azure_storage_tenant_id",
+        "adls.client-id" => "azure_storage_client_id",
+        "adls.client-secret" => "azure_storage_client_secret",
+        "adls.account-host" => "azure_storage_authority_host",
+        "adls.token" => "azure_storage_token",
+        // Google storage
+        "gcs.oauth2.token" => "bearer_token",
+        // HuggingFace
+        "hf.token" => "token",
+        key if !key.contains('.') => key,
+        _ => return None,
+    })
+}
@@ -0,0 +1,312 @@
+use std::sync::Arc;
+
+use polars_core::prelude::PlIndexMap;
+use polars_error::{PolarsResult, polars_bail, polars_err};
+use polars_utils::pl_path::PlRefPath;
+
+use super::manifest::{
+    DataFile, DataFileContent, ManifestEntryStatus, read_manifest, read_manifest_list,
+};
+use super::models::{Snapshot, TableMetadata};
+use crate::cloud::CloudOptions;
+use crate::utils::byte_source::{ByteSource, DynByteSourceBuilder};
+
+/// Files to scan for a snapshot of an Iceberg table.
+#[derive(Debug, Clone, Default)]
+pub struct IcebergScanFiles {
+    /// Paths of the data files.
+    pub data_files: Vec<String>,
+    /// Position delete files, keyed by the index of the data file they apply to.
+    pub position_deletes: PlIndexMap<usize, Arc<[String]>>,
+    /// Equality delete files, keyed by the index of the data file they apply to.
+    pub equality_deletes: PlIndexMap<usize, Arc<[EqualityDeleteFile]>>,
+    /// Total number of rows in the data files, before applying deletes.
+    pub physical_row_count: u64,
+}
+
+/// An equality delete file that applies to a data file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EqualityDeleteFile {
+    pub path: String,
+    /// Field IDs of the columns that rows are matched on.
+    pub equality_ids: Arc<[u32]>,
+}
+
+/// Reads the full contents of a (local or cloud) file.
+pub(super) async fn read_file(
+    path: &str,
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<polars_buffer::Buffer<u8>> {
+    let path = PlRefPath::new(path);
+
+    let byte_source_builder = if path.has_scheme() {
+        DynByteSourceBuilder::ObjectStore
+    } else {
+        DynByteSourceBuilder::Mmap
+    };
+
+    let source = byte_source_builder
+        .try_build_from_path(path, cloud_options, None)
+        .await?;
+    let size = source.get_size().await?;
+    source.get_range(0..size).await
+}
+
+/// Reads the live data and delete files of the snapshot from its manifests.
+pub(super) async fn read_live_files(
+    snapshot: &Snapshot,
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<Vec<(i64, DataFile)>> {
+    let Some(manifest_list) = snapshot.manifest_list.as_deref() else {
+        polars_bail!(
+            ComputeError:
+            "iceberg snapshot {} has no manifest list (format version 1 manifests are not supported)",
+            snapshot.snapshot_id
+        )
+    };
+
+    let manifests = read_manifest_list(&read_file(manifest_list, cloud_options).await?)?;
+
+    let manifest_entries = futures::future::try_join_all(manifests.iter().map(|manifest| async {
+        let bytes = read_file(&manifest.manifest_path, cloud_options).await?;
+        read_manifest(&bytes, manifest)
+    }))
+    .await?;
+
+    Ok(manifest_entries
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.status != ManifestEntryStatus::Deleted)
+        .map(|entry| (entry.sequence_number, entry.data_file))
+        .collect())
+}
+
+impl TableMetadata {
+    /// Resolves the data files and delete files of the given snapshot (or the current snapshot if
+    /// `None`).
+    pub async fn resolve_scan_files(
+        &self,
+        snapshot_id: Option<i64>,
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<IcebergScanFiles> {
+        let snapshot = match snapshot_id {
+            Some(snapshot_id) => match self.snapshot_by_id(snapshot_id) {
+                Some(v) => v,
+                None => polars_bail!(ComputeError: "iceberg snapshot not found: {}", snapshot_id),
+            },
+            None => match self.current_snapshot() {
+                Some(v) => v,
+                // Table without any commits.
+                None => return Ok(IcebergScanFiles::default()),
+            },
+        };
+
+        let files = read_live_files(snapshot, cloud_options).await?;
+
+        let mut data_files = vec![];
+        let mut position_delete_files = vec![];
+        let mut equality_delete_files = vec![];
+
+        for (sequence_number, file) in files {
+            match file.content {
+                DataFileContent::Data => data_files.push((sequence_number, file)),
+                DataFileContent::PositionDeletes => {
+                    position_delete_files.push((sequence_number, file))
+                },
+                DataFileContent::EqualityDeletes => {
+                    equality_delete_files.push((sequence_number, file))
+                },
+            }
+        }
+
+        for (_, file) in data_files
+            .iter()
+            .chain(position_delete_files.iter())
+            .chain(equality_delete_files.iter())
+        {
+            if !file.file_format.eq_ignore_ascii_case("parquet") {
+                polars_bail!(
+                    ComputeError:
+                    "unsupported iceberg file format '{}': {}",
+                    file.file_format, file.file_path
+                )
+            }
+        }
+
+        let equality_delete_files = equality_delete_files
+            .into_iter()
+            .map(|(sequence_number, file)| {
+                let equality_ids = file
+                    .equality_ids
+                    .as_deref()
+                    .filter(|ids| !ids.is_empty())
+                    .ok_or_else(|| {
+                        polars_err!(
+                            ComputeError:
+                            "iceberg equality delete file has no equality_ids: {}",
+                            file.file_path
+                        )
+                    })?
+                    .iter()
+                    .map(|id| {
+                        u32::try_from(*id).map_err(|_| {
+                            polars_err!(
+                                ComputeError:
+                                "invalid equality field ID {} in iceberg delete file: {}",
+                                id, file.file_path
+                            )
+                        })
+                    })
+                    .collect::<PolarsResult<Arc<[u32]>>>()?;
+
+                // A delete file written with an unpartitioned spec applies to all partitions.
+                let is_global = self
+                    .partition_spec_by_id(file.partition_spec_id)
+                    .is_some_and(|spec| spec.fields.is_empty());
+
+                PolarsResult::Ok((sequence_number, file, is_global, equality_ids))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let mut position_deletes = PlIndexMap::new();
+        let mut equality_deletes = PlIndexMap::new();
+
+        for (i, (data_sequence_number, data_file)) in data_files.iter().enumerate() {
+            let paths = position_delete_files
+                .iter()
+                .filter(|(delete_sequence_number, delete_file)| {
+                    position_delete_applies(
+                        *delete_sequence_number,
+                        delete_file,
+                        *data_sequence_number,
+                        data_file,
+                    )
+                })
+                .map(|(_, delete_file)| delete_file.file_path.clone())
+                .collect::<Arc<[_]>>();
+
+            if !paths.is_empty() {
+                position_deletes.insert(i, paths);
+            }
+
+            let files = equality_delete_files
+                .iter()
+                .filter(|(delete_sequence_number, delete_file, is_global, _)| {
+                    equality_delete_applies(
+                        *delete_sequence_number,
+                        delete_file,
+                        *is_global,
+                        *data_sequence_number,
+                        data_file,
+                    )
+                })
+                .map(|(_, delete_file, _, equality_ids)| EqualityDeleteFile {
+                    path: delete_file.file_path.clone(),
+                    equality_ids: equality_ids.clone(),
+                })
+                .collect::<Arc<[_]>>();
+
+            if !files.is_empty() {
+                equality_deletes.insert(i, files);
+            }
+        }
+
+        Ok(IcebergScanFiles {
+            physical_row_count: data_files.iter().map(|(_, f)| f.record_count as u64).sum(),
+            data_files: data_files.into_iter().map(|(_, f)| f.file_path).collect(),
+            position_deletes,
+            equality_deletes,
+        })
+    }
+}
+
+/// A position delete file applies to data files in the same partition with a data sequence
+/// number less than or equal to its own.
+fn position_delete_applies(
+    delete_sequence_number: i64,
+    delete_file: &DataFile,
+    data_sequence_number: i64,
+    data_file: &DataFile,
+) -> bool {
+    if data_sequence_number > delete_sequence_number {
+        return false;
+    }
+
+    if let Some(path) = delete_file.referenced_data_file() {
+        return path == data_file.file_path;
+    }
+
+    delete_file.partition_spec_id == data_file.partition_spec_id
+        && delete_file.partition == data_file.partition
+}
+
+/// An equality delete file applies to data files in the same partition (or all partitions if
+/// `is_global`) with a data sequence number strictly less than its own.
+fn equality_delete_applies(
+    delete_sequence_number: i64,
+    delete_file: &DataFile,
+    is_global: bool,
+    data_sequence_number: i64,
+    data_file: &DataFile,
+) -> bool {
+    if data_sequence_number >= delete_sequence_number {
+        return false;
+    }
+
+    is_global
+        || (delete_file.partition_spec_id == data_file.partition_spec_id
+            && delete_file.partition == data_file.partition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::iceberg::avro::AvroValue;
+
+    #[test]
+    fn test_equality_delete_applies() {
+        let data_file = DataFile::new_parquet("data.parquet".into(), 1, 1);
+        let mut delete_file = DataFile::new_parquet("delete.parquet".into(), 1, 1);
+        delete_file.content = DataFileContent::EqualityDeletes;
+        delete_file.equality_ids = Some(vec![1]);
+
+        // Only data written before the delete is affected.
+        assert!(equality_delete_applies(
+            2,
+            &delete_file,
+            false,
+            1,
+            &data_file
+        ));
+        assert!(!equality_delete_applies(
+            2,
+            &delete_file,
+            false,
+            2,
+            &data_file
+        ));
+        assert!(!equality_delete_applies(
+            2,
+            &delete_file,
+            false,
+            3,
+            &data_file
+        ));
+
+        delete_file.partition = AvroValue::Record(vec![("x".into(), AvroValue::Int(1))]);
+        assert!(!equality_delete_applies(
+            2,
+            &delete_file,
+            false,
+            1,
+            &data_file
+        ));
+        assert!(equality_delete_applies(
+            2,
+            &delete_file,
+            true,
+            1,
+            &data_file
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+use arrow::datatypes::{ArrowDataType, ArrowSchema, Field as ArrowField, Metadata, TimeUnit};
+use polars_core::schema::iceberg::IcebergSchema;
+use polars_core::schema::{Schema, SchemaExt};
+use polars_error::{PolarsResult, polars_bail};
+use polars_utils::pl_str::PlSmallStr;
+
+use super::models::{IcebergType, NestedField, NestedType, TableSchema};
+
+const PARQUET_FIELD_ID_KEY: &str = "PARQUET:field_id";
+
+impl TableSchema {
+    /// Converts to an arrow schema, with Iceberg field IDs stored in the `PARQUET:field_id`
+    /// metadata of every field.
+    pub fn to_arrow_schema(&self) -> PolarsResult<ArrowSchema> {
+        self.fields
+            .iter()
+            .map(|field| {
+                Ok((
+                    PlSmallStr::from_str(&field.name),
+                    nested_field_to_arrow(field)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub fn to_polars_schema(&self) -> PolarsResult<Schema> {
+        Ok(Schema::from_arrow_schema(&self.to_arrow_schema()?))
+    }
+
+    /// Schema keyed by field ID, used to map the physical columns of data files (which may have
+    /// been written with an older schema) to the table schema.
+    pub fn to_iceberg_schema(&self) -> PolarsResult<IcebergSchema> {
+        IcebergSchema::from_arrow_schema(&self.to_arrow_schema()?)
+    }
+}
+
+fn nested_field_to_arrow(field: &NestedField) -> PolarsResult<ArrowField> {
+    arrow_field(&field.name, field.id, &field.field_type, !field.required)
+}
+
+fn arrow_field(
+    name: &str,
+    field_id: i32,
+    field_type: &IcebergType,
+    nullable: bool,
+) -> PolarsResult<ArrowField> {
+    Ok(ArrowField::new(
+        PlSmallStr::from_str(name),
+        iceberg_type_to_arrow(field_type)?,
+        nullable,
+    )
+    .with_metadata(Metadata::from([(
+        PlSmallStr::from_static(PARQUET_FIELD_ID_KEY),
+        PlSmallStr::from(field_id.to_string()),
+    )])))
+}
+
+fn iceberg_type_to_arrow(field_type: &IcebergType) -> PolarsResult<ArrowDataType> {
+    use ArrowDataType as ADT;
+
+    Ok(match field_type {
+        IcebergType::Primitive(name) => match name.as_str() {
+            "boolean" => ADT::Boolean,
+            "int" => ADT::Int32,
+            "long" => ADT::Int64,
+            "float" => ADT::Float32,
+            "double" => ADT::Float64,
+            "date" => ADT::Date32,
+            "time" => ADT::Time64(TimeUnit::Microsecond),
+            "timestamp" => ADT::Timestamp(TimeUnit::Microsecond, None),
+            "timestamptz" => ADT::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            "timestamp_ns" => ADT::Timestamp(TimeUnit::Nanosecond, None),
+            "timestamptz_ns" => ADT::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+            "string" => ADT::LargeUtf8,
+            "uuid" | "binary" => ADT::LargeBinary,
+            name if name.starts_with("fixed[") => ADT::LargeBinary,
+            name if name.starts_with("decimal(") => {
+                let Some((precision, scale)) = name
+                    .strip_prefix("decimal(")
+                    .and_then(|s| s.strip_suffix(')'))
+                    .and_then(|s| s.split_once(','))
+                    .and_then(|(p, s)| Some((p.trim().parse().ok()?, s.trim().parse().ok()?)))
+                else {
+                    polars_bail!(ComputeError: "invalid iceberg decimal type: '{}'", name)
+                };
+                ADT::Decimal(precision, scale)
+            },
+            name => polars_bail!(ComputeError: "unsupported iceberg type: '{}'", name),
+        },
+        IcebergType::Nested(NestedType::Struct { fields }) => ADT::Struct(
+            fields
+                .iter()
+                .map(nested_field_to_arrow)
+                .collect::<PolarsResult<_>>()?,
+        ),
+        IcebergType::Nested(NestedType::List {
+            element_id,
+            element,
+            element_required,
+        }) => ADT::LargeList(Box::new(arrow_field(
+            "element",
+            *element_id,
+            element,
+            !element_required,
+        )?)),
+        // Maps are read as a list of key / value structs.
+        IcebergType::Nested(NestedType::Map {
+            key_id,
+            key,
+            value_id,
+            value,
+            value_required,
+        }) => ADT::LargeList(Box::new(ArrowField::new(
+            PlSmallStr::from_static("entries"),
+            ADT::Struct(vec![
+                arrow_field("key", *key_id, key, false)?,
+                arrow_field("value", *value_id, value, !value_required)?,
+            ]),
+            false,
+        ))),
+    })
+}
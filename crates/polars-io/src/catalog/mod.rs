@@ -1 +1,4 @@
+#[cfg(feature = "iceberg")]
+pub mod iceberg;
 pub mod unity;
+pub(crate) mod utils;
@@ -4,8 +4,9 @@ use polars_error::{PolarsResult, polars_bail, to_compute_err};
 
 use super::models::{CatalogInfo, NamespaceInfo, TableCredentials, TableInfo};
 use super::schema::schema_to_column_info_list;
-use super::utils::{PageWalker, do_request};
+use super::utils::PageWalker;
 use crate::catalog::unity::models::{ColumnInfo, DataSourceFormat, TableType};
+use crate::catalog::utils::do_request;
 use crate::cloud::USER_AGENT;
 use crate::impl_page_walk;
 use crate::utils::decode_json_response;
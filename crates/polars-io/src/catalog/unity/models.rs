@@ -1,6 +1,11 @@
 use polars_core::prelude::PlHashMap;
+use polars_error::{PolarsResult, polars_bail};
 use polars_utils::pl_str::PlSmallStr;
 
+use crate::cloud::credential_provider::ObjectStoreCredential;
+#[cfg(feature = "azure")]
+use crate::cloud::credential_provider::split_sas;
+
 #[derive(Debug, serde::Deserialize)]
 pub struct CatalogInfo {
     pub name: String,
@@ -277,6 +282,55 @@ impl TableCredentials {
             None
         }
     }
+
+    /// Convert the vended credentials into an [`ObjectStoreCredential`], along with their
+    /// expiry time (as seconds since UNIX_EPOCH).
+    pub fn into_object_store_credential(self) -> PolarsResult<(ObjectStoreCredential, u64)> {
+        // note: the catalog reports expiry in milliseconds
+        let expiry = u64::try_from(self.expiration_time / 1000).unwrap_or(0);
+
+        #[allow(unused_imports)]
+        use TableCredentialsVariants::*;
+        let credential = match self.into_enum() {
+            #[cfg(feature = "aws")]
+            Some(Aws(TableCredentialsAws {
+                access_key_id,
+                secret_access_key,
+                session_token,
+                access_point: _,
+            })) => {
+                let credential = object_store::aws::AwsCredential {
+                    key_id: access_key_id,
+                    secret_key: secret_access_key,
+                    token: session_token,
+                };
+                ObjectStoreCredential::Aws(std::sync::Arc::new(credential))
+            },
+            #[cfg(feature = "azure")]
+            Some(Azure(TableCredentialsAzure { sas_token })) => {
+                let pairs = split_sas(&sas_token).map_err(|err_msg| {
+                    polars_error::polars_err!(ComputeError: "error decoding SAS token: {}", err_msg)
+                })?;
+                let credential = object_store::azure::AzureCredential::SASToken(pairs);
+                ObjectStoreCredential::Azure(std::sync::Arc::new(credential))
+            },
+            #[cfg(feature = "gcp")]
+            Some(Gcp(TableCredentialsGcp { oauth_token })) => {
+                let credential = object_store::gcp::GcpCredential {
+                    bearer: oauth_token,
+                };
+                ObjectStoreCredential::Gcp(std::sync::Arc::new(credential))
+            },
+            #[allow(unreachable_patterns)]
+            Some(_) => polars_bail!(
+                ComputeError: "catalog vended credentials for a cloud provider whose feature is not enabled"
+            ),
+            None => {
+                polars_bail!(ComputeError: "did not receive credentials from temporary credentials API")
+            },
+        };
+        Ok((credential, expiry))
+    }
 }
 
 pub enum TableCredentialsVariants {
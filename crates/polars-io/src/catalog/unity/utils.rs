@@ -1,28 +1,8 @@
 use bytes::Bytes;
-use polars_error::{PolarsResult, to_compute_err};
-use polars_utils::error::TruncateErrorDetail;
+use polars_error::PolarsResult;
 use reqwest::RequestBuilder;
 
-/// Performs the request and attaches the response body to any error messages.
-pub(super) async fn do_request(request: reqwest::RequestBuilder) -> PolarsResult<bytes::Bytes> {
-    let resp = request.send().await.map_err(to_compute_err)?;
-    let opt_err = resp.error_for_status_ref().map(|_| ());
-    let resp_bytes = resp.bytes().await.map_err(to_compute_err)?;
-
-    opt_err.map_err(|e| {
-        to_compute_err(e).wrap_msg(|e| {
-            let body = String::from_utf8_lossy(&resp_bytes);
-
-            format!(
-                "error: {}, response body: {}",
-                e,
-                TruncateErrorDetail(&body)
-            )
-        })
-    })?;
-
-    Ok(resp_bytes)
-}
+use crate::catalog::utils::do_request;
 
 /// Support for traversing paginated response values that look like:
 /// ```text
@@ -0,0 +1,29 @@
+use polars_error::{PolarsResult, to_compute_err};
+use polars_utils::error::TruncateErrorDetail;
+
+/// Performs the request and attaches the response body to any error messages.
+pub(crate) async fn do_request(request: reqwest::RequestBuilder) -> PolarsResult<bytes::Bytes> {
+    let resp = request.send().await.map_err(to_compute_err)?;
+    read_response(resp).await
+}
+
+/// Reads the response body, returning an error containing the body if the response has an error
+/// status.
+pub(crate) async fn read_response(resp: reqwest::Response) -> PolarsResult<bytes::Bytes> {
+    let opt_err = resp.error_for_status_ref().map(|_| ());
+    let resp_bytes = resp.bytes().await.map_err(to_compute_err)?;
+
+    opt_err.map_err(|e| {
+        to_compute_err(e).wrap_msg(|e| {
+            let body = String::from_utf8_lossy(&resp_bytes);
+
+            format!(
+                "error: {}, response body: {}",
+                e,
+                TruncateErrorDetail(&body)
+            )
+        })
+    })?;
+
+    Ok(resp_bytes)
+}
@@ -0,0 +1,138 @@
+//! Commit manifests for sinks that write multiple files.
+//!
+//! With [`CloudOptions::atomic_commit`] every file is moved into place only once fully uploaded,
+//! but the set of files written by a partitioned sink is not atomic. Once all files are
+//! committed, a manifest listing them is written to the base directory. Readers can use the
+//! manifest to tell the complete output of a job apart from files written by concurrent or failed
+//! jobs to the same prefix.
+use object_store::path::Path;
+use object_store::{PutMode, PutOptions, PutPayload};
+use polars_error::{PolarsResult, polars_err, to_compute_err};
+use polars_utils::pl_path::PlRefPath;
+use serde::{Deserialize, Serialize};
+
+use crate::cloud::{CloudOptions, build_object_store};
+use crate::utils::random_uuid;
+
+/// File name prefix of commit manifests.
+pub const COMMIT_MANIFEST_PREFIX: &str = "_polars_commit_";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitManifest {
+    pub version: u32,
+    /// Paths of the committed files, relative to the directory of the manifest.
+    pub files: Vec<String>,
+}
+
+/// Writes a commit manifest listing `paths` to `base_path`, returning the path of the manifest.
+///
+/// # Errors
+/// Errors if any of `paths` is not under `base_path`.
+pub async fn write_commit_manifest(
+    base_path: &PlRefPath,
+    paths: &[PlRefPath],
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<PlRefPath> {
+    let base = base_path.as_str().trim_end_matches('/');
+
+    let files = paths
+        .iter()
+        .map(|path| {
+            path.as_str()
+                .strip_prefix(base)
+                .and_then(|p| p.strip_prefix('/'))
+                .map(|p| p.to_string())
+                .ok_or_else(|| {
+                    polars_err!(
+                        ComputeError:
+                        "committed path '{}' is not under base path '{}'", path, base_path
+                    )
+                })
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    let manifest = CommitManifest { version: 1, files };
+    let manifest = serde_json::to_vec(&manifest).map_err(to_compute_err)?;
+
+    let (location, store) =
+        build_object_store(PlRefPath::new(format!("{base}/")), cloud_options, false).await?;
+
+    let file_name = format!("{COMMIT_MANIFEST_PREFIX}{}.json", random_uuid());
+    let path = Path::from(format!(
+        "{}/{file_name}",
+        location.prefix.trim_end_matches('/')
+    ));
+    let path = &path;
+    let manifest = PutPayload::from(manifest);
+
+    store
+        .exec_with_rebuild_retry_on_err(|s| {
+            let manifest = manifest.clone();
+            async move {
+                s.put_opts(
+                    path,
+                    manifest,
+                    PutOptions {
+                        mode: PutMode::Create,
+                        ..Default::default()
+                    },
+                )
+                .await
+            }
+        })
+        .await?;
+
+    Ok(PlRefPath::new(format!("{base}/{file_name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_utils::pl_path::PlRefPath;
+
+    use super::{CommitManifest, write_commit_manifest};
+    use crate::pl_async::get_runtime;
+
+    #[test]
+    fn test_write_commit_manifest() {
+        let dir = std::env::temp_dir().join(format!(
+            "polars-test-commit-manifest-{}",
+            crate::utils::random_uuid()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let base_path = PlRefPath::new(dir.to_str().unwrap());
+
+        let manifest_path = get_runtime()
+            .block_on(write_commit_manifest(
+                &base_path,
+                &[
+                    base_path.join("a=1/0.parquet"),
+                    base_path.join("a=2/0.parquet"),
+                ],
+                None,
+            ))
+            .unwrap();
+
+        let manifest: CommitManifest =
+            serde_json::from_slice(&std::fs::read(manifest_path.as_str()).unwrap()).unwrap();
+
+        assert_eq!(
+            manifest,
+            CommitManifest {
+                version: 1,
+                files: vec!["a=1/0.parquet".into(), "a=2/0.parquet".into()],
+            }
+        );
+
+        assert!(
+            get_runtime()
+                .block_on(write_commit_manifest(
+                    &base_path,
+                    &[PlRefPath::new("/elsewhere/0.parquet")],
+                    None,
+                ))
+                .is_err()
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,19 +2,22 @@ use std::num::NonZeroUsize;
 
 use futures::StreamExt as _;
 use futures::stream::FuturesUnordered;
-use object_store::PutPayload;
-use polars_error::{PolarsError, PolarsResult};
+use object_store::{ObjectStoreExt as _, PutPayload};
+use polars_error::{PolarsError, PolarsResult, polars_err};
 use polars_utils::async_utils::error_capture::{ErrorCapture, ErrorHandle};
 use polars_utils::async_utils::tokio_handle_ext;
 
-use crate::cloud::PolarsObjectStore;
 use crate::cloud::cloud_writer::multipart_upload::PlMultipartUpload;
+use crate::cloud::{AtomicCommitMode, PolarsObjectStore};
 use crate::metrics::OptIOMetrics;
 
 /// Cloud writer that provides the `put()` function, does not perform any buffering.
 pub(super) struct InternalCloudWriter {
     pub(super) store: PolarsObjectStore,
+    /// Path that is uploaded to. This is a temporary path if `atomic_commit` is set.
     pub(super) path: object_store::path::Path,
+    /// Destination path and commit mode if the upload is moved into place after completion.
+    pub(super) atomic_commit: Option<(object_store::path::Path, AtomicCommitMode)>,
     pub(super) max_concurrency: NonZeroUsize,
     pub(super) io_metrics: OptIOMetrics,
     pub(super) state: InternalCloudWriterState,
@@ -39,15 +42,27 @@ impl InternalCloudWriter {
     pub(super) async fn start(&mut self) -> PolarsResult<()> {
         if let WriterState::NotStarted = &self.state {
             let path_ref = &self.path;
-            let multipart = PlMultipartUpload::new(
-                self.store
-                    .exec_with_rebuild_retry_on_err(|s| async move {
-                        s.put_multipart_opts(path_ref, object_store::PutMultipartOptions::default())
+            let multipart = if let Some(store) = self.store.to_dyn_multipart_store().await {
+                PlMultipartUpload::new_resumable(
+                    store,
+                    self.path.clone(),
+                    self.store.error_context(),
+                )
+                .await?
+            } else {
+                PlMultipartUpload::new(
+                    self.store
+                        .exec_with_rebuild_retry_on_err(|s| async move {
+                            s.put_multipart_opts(
+                                path_ref,
+                                object_store::PutMultipartOptions::default(),
+                            )
                             .await
-                    })
-                    .await?,
-                self.store.error_context(),
-            );
+                        })
+                        .await?,
+                    self.store.error_context(),
+                )
+            };
 
             let (error_capture, error_handle) = ErrorCapture::new();
 
@@ -99,8 +114,10 @@ impl InternalCloudWriter {
         let state = self.get_or_init_started_state().await?;
 
         if state.error_handle.has_errored() {
-            let state = self.take_started_state().unwrap();
-            return Err(state.error_handle.join().await.unwrap_err());
+            let mut state = self.take_started_state().unwrap();
+            let err = state.error_handle.join().await.unwrap_err();
+            abort_multipart(&mut state.multipart).await;
+            return Err(err);
         }
 
         while state.tasks.len() >= max_concurrency {
@@ -133,7 +150,11 @@ impl InternalCloudWriter {
         };
 
         drop(error_capture);
-        error_handle.join().await?;
+
+        if let Err(err) = error_handle.join().await {
+            abort_multipart(&mut multipart).await;
+            return Err(err);
+        }
 
         for handle in tasks {
             handle.await.unwrap();
@@ -141,6 +162,67 @@ impl InternalCloudWriter {
 
         multipart.finish().await?;
 
+        if let Some((dst, mode)) = &self.atomic_commit {
+            self.commit(dst, *mode).await?;
+        }
+
         Ok(())
     }
+
+    /// Moves the fully uploaded object at `self.path` to `dst`.
+    async fn commit(
+        &self,
+        dst: &object_store::path::Path,
+        mode: AtomicCommitMode,
+    ) -> PolarsResult<()> {
+        let src = &self.path;
+
+        let result = match mode {
+            AtomicCommitMode::Overwrite => {
+                self.store
+                    .exec_with_rebuild_retry_on_err(|s| async move { s.rename(src, dst).await })
+                    .await
+            },
+            AtomicCommitMode::CreateNew => self.commit_create_new(dst).await,
+        };
+
+        if result.is_err() {
+            // Don't leave the temporary object behind.
+            let _ = self.store.to_dyn_object_store().await.delete(src).await;
+        }
+
+        result
+    }
+
+    async fn commit_create_new(&self, dst: &object_store::path::Path) -> PolarsResult<()> {
+        let src = &self.path;
+        let store = self.store.to_dyn_object_store().await;
+
+        match store.rename_if_not_exists(src, dst).await {
+            Ok(()) => Ok(()),
+            Err(object_store::Error::AlreadyExists { .. }) => Err(polars_err!(
+                ComputeError:
+                "atomic commit failed: destination already exists: {}", dst
+            )),
+            // Checking for existence before an unconditional rename would race with concurrent
+            // writers, so stores without conditional copy support cannot create new objects.
+            Err(object_store::Error::NotSupported { .. })
+            | Err(object_store::Error::NotImplemented { .. }) => Err(polars_err!(
+                ComputeError:
+                "atomic commit failed: the store does not support conditional copies, which are \
+                required to commit without overwriting {}; configure them (e.g. \
+                `aws_copy_if_not_exists` for S3) or use the 'overwrite' commit mode", dst
+            )),
+            Err(e) => Err(self.store.error_context().attach_err_info(e).into()),
+        }
+    }
+}
+
+/// Aborts an upload that will not be completed so that its parts do not linger in the store.
+async fn abort_multipart(multipart: &mut PlMultipartUpload) {
+    if let Err(e) = multipart.abort().await
+        && polars_core::config::verbose()
+    {
+        eprintln!("[InternalCloudWriter]: failed to abort multipart upload: {e}");
+    }
 }
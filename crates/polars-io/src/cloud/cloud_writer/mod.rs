@@ -1,8 +1,10 @@
 mod bufferer;
+mod commit_manifest;
 mod internal_writer;
 mod io_trait_wrap;
 mod multipart_upload;
 mod writer;
 
+pub use commit_manifest::{COMMIT_MANIFEST_PREFIX, CommitManifest, write_commit_manifest};
 pub use io_trait_wrap::CloudWriterIoTraitWrap;
 pub use writer::CloudWriter;
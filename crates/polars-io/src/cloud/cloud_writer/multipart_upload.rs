@@ -1,35 +1,282 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use object_store::path::Path;
+use object_store::{MultipartId, MultipartStore, PartId};
 use polars_error::PolarsResult;
+use polars_utils::relaxed_cell::RelaxedCell;
 
 use crate::cloud::ObjectStoreErrorContext;
 
+/// Maximum number of attempts for uploading a single part of a resumable upload.
+const MAX_PART_ATTEMPTS: usize = 4;
+const PART_RETRY_INIT_BACKOFF: Duration = Duration::from_millis(500);
+
 /// Wrapper for [`object_store::MultipartUpload`] that handles error conversion.
+///
+/// If the store implements [`MultipartStore`], parts are uploaded with explicit part indices. A
+/// part that fails with a transient error is then re-uploaded on its own, keeping the parts that
+/// were already uploaded.
 pub struct PlMultipartUpload {
-    inner: Box<dyn object_store::MultipartUpload>,
+    inner: UploadInner,
     error_cx: ObjectStoreErrorContext,
 }
 
+enum UploadInner {
+    Dyn(Box<dyn object_store::MultipartUpload>),
+    Resumable(Arc<ResumableUpload>),
+}
+
+struct ResumableUpload {
+    store: Arc<dyn MultipartStore>,
+    path: Path,
+    id: MultipartId,
+    /// Uploaded parts by part index. `None` for parts that are still in flight.
+    parts: Mutex<Vec<Option<PartId>>>,
+    num_part_retries: RelaxedCell<u64>,
+}
+
 impl PlMultipartUpload {
     pub fn new(
         inner: Box<dyn object_store::MultipartUpload>,
         error_cx: ObjectStoreErrorContext,
     ) -> Self {
-        Self { inner, error_cx }
+        Self {
+            inner: UploadInner::Dyn(inner),
+            error_cx,
+        }
+    }
+
+    pub async fn new_resumable(
+        store: Arc<dyn MultipartStore>,
+        path: Path,
+        error_cx: ObjectStoreErrorContext,
+    ) -> PolarsResult<Self> {
+        let id = store
+            .create_multipart(&path)
+            .await
+            .map_err(|e| error_cx.clone().attach_err_info(e))?;
+
+        Ok(Self {
+            inner: UploadInner::Resumable(Arc::new(ResumableUpload {
+                store,
+                path,
+                id,
+                parts: Mutex::new(vec![]),
+                num_part_retries: RelaxedCell::from(0),
+            })),
+            error_cx,
+        })
     }
 
     pub fn put(
         &mut self,
         payload: object_store::PutPayload,
     ) -> impl Future<Output = PolarsResult<()>> + Send + 'static {
-        let fut = self.inner.put_part(payload);
         let error_cx = self.error_cx.clone();
 
+        let fut: futures::future::BoxFuture<'static, object_store::Result<()>> =
+            match &mut self.inner {
+                UploadInner::Dyn(inner) => inner.put_part(payload),
+                UploadInner::Resumable(upload) => {
+                    let upload = upload.clone();
+
+                    let part_idx = {
+                        let mut parts = upload.parts.lock().unwrap();
+                        parts.push(None);
+                        parts.len() - 1
+                    };
+
+                    Box::pin(async move { upload.put_part(part_idx, payload).await })
+                },
+            };
+
         async move { fut.await.map_err(|e| error_cx.attach_err_info(e).into()) }
     }
 
     pub async fn finish(&mut self) -> PolarsResult<object_store::PutResult> {
-        self.inner
-            .complete()
+        let out = match &mut self.inner {
+            UploadInner::Dyn(inner) => inner.complete().await,
+            UploadInner::Resumable(upload) => {
+                let parts = upload
+                    .parts
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|part| part.clone().expect("part upload in flight at finish"))
+                    .collect();
+
+                upload
+                    .store
+                    .complete_multipart(&upload.path, &upload.id, parts)
+                    .await
+            },
+        };
+
+        out.map_err(|e| self.error_cx.clone().attach_err_info(e).into())
+    }
+
+    /// Aborts the upload, cleaning up any uploaded parts.
+    pub async fn abort(&mut self) -> PolarsResult<()> {
+        let out = match &mut self.inner {
+            UploadInner::Dyn(inner) => inner.abort().await,
+            UploadInner::Resumable(upload) => {
+                upload.store.abort_multipart(&upload.path, &upload.id).await
+            },
+        };
+
+        out.map_err(|e| self.error_cx.clone().attach_err_info(e).into())
+    }
+
+    /// Number of parts that had to be re-uploaded after a transient failure.
+    pub fn num_part_retries(&self) -> u64 {
+        match &self.inner {
+            UploadInner::Dyn(_) => 0,
+            UploadInner::Resumable(upload) => upload.num_part_retries.load(),
+        }
+    }
+}
+
+impl ResumableUpload {
+    async fn put_part(
+        &self,
+        part_idx: usize,
+        payload: object_store::PutPayload,
+    ) -> object_store::Result<()> {
+        let mut backoff = PART_RETRY_INIT_BACKOFF;
+        let mut attempt = 1;
+
+        let part_id = loop {
+            match self
+                .store
+                .put_part(&self.path, &self.id, part_idx, payload.clone())
+                .await
+            {
+                Ok(v) => break v,
+                Err(e) if attempt < MAX_PART_ATTEMPTS && is_transient_error(&e) => {
+                    if polars_core::config::verbose() {
+                        eprintln!(
+                            "[PlMultipartUpload]: upload of part {part_idx} failed \
+                            (attempt {attempt}/{MAX_PART_ATTEMPTS}), retrying: {e}"
+                        );
+                    }
+
+                    self.num_part_retries.fetch_add(1);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                },
+                Err(e) => return Err(e),
+            }
+        };
+
+        self.parts.lock().unwrap()[part_idx] = Some(part_id);
+
+        Ok(())
+    }
+}
+
+/// Whether an upload that failed with `err` may succeed when retried.
+fn is_transient_error(err: &object_store::Error) -> bool {
+    use object_store::Error as E;
+
+    matches!(err, E::Generic { .. } | E::JoinError { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::{
+        MultipartId, MultipartStore, ObjectStoreExt, PartId, PutPayload, PutResult,
+    };
+    use polars_utils::pl_path::PlRefPath;
+
+    use super::PlMultipartUpload;
+    use crate::cloud::ObjectStoreErrorContext;
+    use crate::pl_async::get_runtime;
+
+    /// Fails every other upload attempt of odd parts.
+    #[derive(Debug)]
+    struct FlakyStore {
+        inner: InMemory,
+        num_put_part_calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MultipartStore for FlakyStore {
+        async fn create_multipart(&self, path: &Path) -> object_store::Result<MultipartId> {
+            self.inner.create_multipart(path).await
+        }
+
+        async fn put_part(
+            &self,
+            path: &Path,
+            id: &MultipartId,
+            part_idx: usize,
+            data: PutPayload,
+        ) -> object_store::Result<PartId> {
+            if part_idx % 2 == 1 && self.num_put_part_calls.fetch_add(1, Ordering::Relaxed) % 2 == 0
+            {
+                return Err(object_store::Error::Generic {
+                    store: "FlakyStore",
+                    source: "connection reset".into(),
+                });
+            }
+
+            self.inner.put_part(path, id, part_idx, data).await
+        }
+
+        async fn complete_multipart(
+            &self,
+            path: &Path,
+            id: &MultipartId,
+            parts: Vec<PartId>,
+        ) -> object_store::Result<PutResult> {
+            self.inner.complete_multipart(path, id, parts).await
+        }
+
+        async fn abort_multipart(&self, path: &Path, id: &MultipartId) -> object_store::Result<()> {
+            self.inner.abort_multipart(path, id).await
+        }
+    }
+
+    #[test]
+    fn test_resumable_multipart_upload() {
+        let store = Arc::new(FlakyStore {
+            inner: InMemory::new(),
+            num_put_part_calls: AtomicUsize::new(0),
+        });
+        let path = Path::from("a/b.txt");
+
+        get_runtime().block_on(async {
+            let mut upload = PlMultipartUpload::new_resumable(
+                store.clone(),
+                path.clone(),
+                ObjectStoreErrorContext::new(PlRefPath::new("memory://a/b.txt")),
+            )
             .await
-            .map_err(|e| self.error_cx.clone().attach_err_info(e).into())
+            .unwrap();
+
+            let handles = [&b"abc"[..], b"def", b"ghi"]
+                .into_iter()
+                .map(|bytes| tokio::spawn(upload.put(PutPayload::from_static(bytes))))
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                handle.await.unwrap().unwrap();
+            }
+
+            assert_eq!(upload.num_part_retries(), 1);
+
+            upload.finish().await.unwrap();
+
+            let bytes = store.inner.get(&path).await.unwrap().bytes().await.unwrap();
+            assert_eq!(&bytes[..], b"abcdefghi");
+        });
     }
 }
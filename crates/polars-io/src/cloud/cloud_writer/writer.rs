@@ -4,9 +4,9 @@ use std::sync::Arc;
 use bytes::Bytes;
 use polars_error::PolarsResult;
 
-use crate::cloud::PolarsObjectStore;
 use crate::cloud::cloud_writer::bufferer::BytesBufferer;
 use crate::cloud::cloud_writer::internal_writer::{InternalCloudWriter, InternalCloudWriterState};
+use crate::cloud::{AtomicCommitMode, PolarsObjectStore, object_path_from_str};
 use crate::metrics::{IOMetrics, OptIOMetrics};
 
 pub struct CloudWriter {
@@ -15,25 +15,34 @@ pub struct CloudWriter {
 }
 
 impl CloudWriter {
+    /// If `atomic_commit` is set, the data is uploaded to a temporary key next to `path` and only
+    /// moved to `path` once the upload has completed.
     pub fn new(
         store: PolarsObjectStore,
         path: object_store::path::Path,
         upload_chunk_size: usize,
         max_concurrency: NonZeroUsize,
+        atomic_commit: Option<AtomicCommitMode>,
         io_metrics: Option<Arc<IOMetrics>>,
-    ) -> Self {
+    ) -> PolarsResult<Self> {
         let bufferer = BytesBufferer::new(upload_chunk_size);
 
-        Self {
+        let (path, atomic_commit) = match atomic_commit {
+            None => (path, None),
+            Some(mode) => (temporary_upload_path(&path)?, Some((path, mode))),
+        };
+
+        Ok(Self {
             writer: InternalCloudWriter {
                 store,
                 path,
+                atomic_commit,
                 max_concurrency,
                 io_metrics: OptIOMetrics(io_metrics),
                 state: InternalCloudWriterState::NotStarted,
             },
             bufferer,
-        }
+        })
     }
 
     pub async fn start(&mut self) -> PolarsResult<()> {
@@ -89,3 +98,109 @@ impl CloudWriter {
         self.writer.finish().await
     }
 }
+
+/// Hidden path in the same directory as `path` that is unique to this upload.
+fn temporary_upload_path(
+    path: &object_store::path::Path,
+) -> PolarsResult<object_store::path::Path> {
+    let uuid = crate::utils::random_uuid();
+
+    object_path_from_str(
+        &match path.as_ref().rsplit_once(object_store::path::DELIMITER) {
+            Some((parent, file_name)) => format!("{parent}/.polars-tmp-{uuid}-{file_name}"),
+            None => format!(".polars-tmp-{uuid}-{path}"),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::{ObjectStore, ObjectStoreExt};
+    use polars_error::PolarsResult;
+    use polars_utils::pl_path::PlRefPath;
+
+    use super::{CloudWriter, temporary_upload_path};
+    use crate::cloud::{AtomicCommitMode, build_object_store, register_object_store_scheme};
+    use crate::pl_async::get_runtime;
+
+    #[test]
+    fn test_temporary_upload_path() {
+        let path = temporary_upload_path(&Path::from("a/b/c.parquet")).unwrap();
+        let (parent, file_name) = path.as_ref().rsplit_once('/').unwrap();
+
+        assert_eq!(parent, "a/b");
+        assert!(file_name.starts_with(".polars-tmp-"));
+        assert!(file_name.ends_with("-c.parquet"));
+    }
+
+    #[test]
+    fn test_atomic_commit() {
+        let mem = Arc::new(InMemory::new());
+
+        let factory_store = mem.clone();
+        register_object_store_scheme(
+            "polars-test-atomic",
+            Arc::new(
+                move |_: &str,
+                      _: &[(String, String)]|
+                      -> PolarsResult<Arc<dyn object_store::ObjectStore>> {
+                    Ok(factory_store.clone() as _)
+                },
+            ),
+        )
+        .unwrap();
+
+        let write = |bytes: &'static [u8], mode| {
+            get_runtime().block_on(async {
+                let (location, store) = build_object_store(
+                    PlRefPath::new("polars-test-atomic://bucket/out/data.csv"),
+                    None,
+                    false,
+                )
+                .await
+                .unwrap();
+
+                let mut writer = CloudWriter::new(
+                    store,
+                    Path::from(location.prefix.as_str()),
+                    4,
+                    NonZeroUsize::new(2).unwrap(),
+                    Some(mode),
+                    None,
+                )?;
+                writer.start().await?;
+                writer.write_all_owned(Bytes::from_static(bytes)).await?;
+                writer.finish().await
+            })
+        };
+
+        let read = || {
+            get_runtime().block_on(async {
+                let bytes = mem
+                    .get(&Path::from("out/data.csv"))
+                    .await
+                    .unwrap()
+                    .bytes()
+                    .await
+                    .unwrap();
+                let num_objects = mem.list_with_delimiter(Some(&Path::from("out"))).await;
+                (bytes, num_objects.unwrap().objects.len())
+            })
+        };
+
+        write(b"first file", AtomicCommitMode::CreateNew).unwrap();
+        assert_eq!(read(), (Bytes::from_static(b"first file"), 1));
+
+        assert!(write(b"second file", AtomicCommitMode::CreateNew).is_err());
+        assert_eq!(read(), (Bytes::from_static(b"first file"), 1));
+
+        write(b"third file", AtomicCommitMode::Overwrite).unwrap();
+        assert_eq!(read(), (Bytes::from_static(b"third file"), 1));
+    }
+}
@@ -493,6 +493,35 @@ impl<C: Clone> FetchedCredentialsCache<C> {
     }
 }
 
+/// Split an Azure SAS token into its (key, value) query pairs.
+///
+/// Copied and adjusted from object-store.
+///
+/// https://github.com/apache/arrow-rs-object-store/blob/7a0504b4924fcecee17d768fd7190b8f71b0877f/src/azure/builder.rs#L1072-L1089
+#[cfg(feature = "azure")]
+pub(crate) fn split_sas(sas: &str) -> Result<Vec<(String, String)>, &'static str> {
+    let sas = percent_encoding::percent_decode_str(sas)
+        .decode_utf8()
+        .map_err(|_| "UTF-8 decode error")?;
+
+    let kv_str_pairs = sas
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|s| !s.chars().all(char::is_whitespace));
+
+    let mut pairs = Vec::new();
+
+    for kv_pair_str in kv_str_pairs {
+        let (k, v) = kv_pair_str
+            .trim()
+            .split_once('=')
+            .ok_or("missing SAS component")?;
+        pairs.push((k.into(), v.into()))
+    }
+
+    Ok(pairs)
+}
+
 #[cfg(feature = "python")]
 mod python_impl {
     use std::hash::Hash;
@@ -593,7 +622,7 @@ mod python_impl {
             use polars_error::PolarsResult;
 
             use crate::cloud::credential_provider::{
-                CredentialProviderFunction, ObjectStoreCredential,
+                CredentialProviderFunction, ObjectStoreCredential, split_sas,
             };
 
             let func = self.unwrap_as_provider();
@@ -666,7 +695,6 @@ mod python_impl {
         #[cfg(feature = "azure")]
         fn into_azure_provider(self) -> object_store::azure::AzureCredentialProvider {
             use object_store::azure::AzureAccessKey;
-            use percent_encoding::percent_decode_str;
             use polars_core::config::verbose_print_sensitive;
             use polars_error::PolarsResult;
 
@@ -745,32 +773,6 @@ mod python_impl {
                 })
             }))
             .into_azure_provider();
-
-            /// Copied and adjusted from object-store.
-            ///
-            /// https://github.com/apache/arrow-rs-object-store/blob/7a0504b4924fcecee17d768fd7190b8f71b0877f/src/azure/builder.rs#L1072-L1089
-            fn split_sas(sas: &str) -> Result<Vec<(String, String)>, &'static str> {
-                let sas = percent_decode_str(sas)
-                    .decode_utf8()
-                    .map_err(|_| "UTF-8 decode error")?;
-
-                let kv_str_pairs = sas
-                    .trim_start_matches('?')
-                    .split('&')
-                    .filter(|s| !s.chars().all(char::is_whitespace));
-
-                let mut pairs = Vec::new();
-
-                for kv_pair_str in kv_str_pairs {
-                    let (k, v) = kv_pair_str
-                        .trim()
-                        .split_once('=')
-                        .ok_or("missing SAS component")?;
-                    pairs.push((k.into(), v.into()))
-                }
-
-                Ok(pairs)
-            }
         }
 
         #[cfg(feature = "gcp")]
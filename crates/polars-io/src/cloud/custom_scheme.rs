@@ -0,0 +1,218 @@
+//! Object store backends for custom URI schemes registered at runtime.
+//!
+//! Once a scheme is registered, paths like `myblob://container/key` are treated like any other
+//! cloud path: they are globbed, scanned, sunk and cached through the [`ObjectStore`] built by the
+//! registered factory.
+use std::sync::{Arc, LazyLock, RwLock};
+
+use object_store::ObjectStore;
+use polars_error::{PolarsResult, polars_bail, polars_err};
+use polars_utils::aliases::PlHashMap;
+use polars_utils::pl_path::{CustomCloudScheme, PlPath, register_cloud_scheme};
+
+use super::{CloudConfig, CloudOptions};
+
+static OBJECT_STORE_FACTORIES: LazyLock<
+    RwLock<PlHashMap<CustomCloudScheme, Arc<dyn ObjectStoreFactory>>>,
+> = LazyLock::new(Default::default);
+
+/// Builds the [`ObjectStore`] for paths of a custom URI scheme.
+pub trait ObjectStoreFactory: Send + Sync + 'static {
+    /// Builds the object store serving paths under `url_base` (`{scheme}://{authority}`).
+    ///
+    /// `storage_options` are the untyped options passed by the user for this scheme. Built stores
+    /// are cached per `url_base` and storage options.
+    fn build(
+        &self,
+        url_base: &str,
+        storage_options: &[(String, String)],
+    ) -> PolarsResult<Arc<dyn ObjectStore>>;
+}
+
+impl<F> ObjectStoreFactory for F
+where
+    F: Fn(&str, &[(String, String)]) -> PolarsResult<Arc<dyn ObjectStore>> + Send + Sync + 'static,
+{
+    fn build(
+        &self,
+        url_base: &str,
+        storage_options: &[(String, String)],
+    ) -> PolarsResult<Arc<dyn ObjectStore>> {
+        self(url_base, storage_options)
+    }
+}
+
+/// Registers an object store backend for the URI scheme `scheme` (e.g. `myblob` for
+/// `myblob://` paths).
+///
+/// # Errors
+/// Errors if `scheme` is not a valid URI scheme, is a built-in scheme, or already has a
+/// registered backend.
+pub fn register_object_store_scheme(
+    scheme: &str,
+    factory: Arc<dyn ObjectStoreFactory>,
+) -> PolarsResult<()> {
+    let scheme = register_cloud_scheme(scheme)?;
+
+    let mut factories = OBJECT_STORE_FACTORIES.write().unwrap();
+
+    if factories.contains_key(&scheme) {
+        polars_bail!(
+            InvalidOperation:
+            "an object store backend is already registered for URI scheme '{}'", scheme.as_str()
+        )
+    }
+
+    factories.insert(scheme, factory);
+
+    Ok(())
+}
+
+pub(super) fn build_custom_object_store(
+    scheme: CustomCloudScheme,
+    path: &PlPath,
+    options: &CloudOptions,
+) -> PolarsResult<Arc<dyn ObjectStore>> {
+    let factory = OBJECT_STORE_FACTORIES
+        .read()
+        .unwrap()
+        .get(&scheme)
+        .cloned()
+        .ok_or_else(|| {
+            polars_err!(
+                ComputeError:
+                "no object store backend registered for URI scheme '{}'", scheme.as_str()
+            )
+        })?;
+
+    let storage_options: &[(String, String)] = match &options.config {
+        Some(CloudConfig::Custom(v)) => v,
+        None => &[],
+        #[allow(unreachable_patterns)]
+        Some(_) => polars_bail!(
+            ComputeError:
+            "invalid storage options for URI scheme '{}'", scheme.as_str()
+        ),
+    };
+
+    factory.build(
+        &path.as_str()[..path.authority_end_position()],
+        storage_options,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::{ObjectStore, PutPayload};
+    use polars_error::PolarsResult;
+    use polars_utils::pl_path::PlRefPath;
+
+    use super::register_object_store_scheme;
+    use crate::cloud::{build_object_store, glob};
+    use crate::pl_async::get_runtime;
+
+    #[test]
+    fn test_custom_object_store_scheme() {
+        let store = Arc::new(InMemory::new());
+
+        get_runtime()
+            .block_on(store.put(&Path::from("a/b.txt"), PutPayload::from_static(b"abc")))
+            .unwrap();
+
+        let factory_store = store.clone();
+        register_object_store_scheme(
+            "polars-test-mem",
+            Arc::new(
+                move |url_base: &str,
+                      _: &[(String, String)]|
+                      -> PolarsResult<Arc<dyn ObjectStore>> {
+                    assert_eq!(url_base, "polars-test-mem://bucket");
+                    Ok(factory_store.clone() as Arc<dyn ObjectStore>)
+                },
+            ),
+        )
+        .unwrap();
+
+        assert!(
+            register_object_store_scheme(
+                "polars-test-mem",
+                Arc::new(
+                    |_: &str, _: &[(String, String)]| -> PolarsResult<Arc<dyn ObjectStore>> {
+                        unreachable!()
+                    }
+                ),
+            )
+            .is_err()
+        );
+
+        let (location, store) = get_runtime()
+            .block_on(build_object_store(
+                PlRefPath::new("polars-test-mem://bucket/a/b.txt"),
+                None,
+                false,
+            ))
+            .unwrap();
+
+        assert_eq!(location.bucket.as_str(), "bucket");
+
+        let bytes = get_runtime()
+            .block_on(store.get_range(&Path::from(location.prefix.as_str()), 0..3))
+            .unwrap();
+
+        assert_eq!(&bytes[..], b"abc");
+    }
+
+    #[test]
+    fn test_custom_object_store_scheme_glob() {
+        let store = Arc::new(InMemory::new());
+
+        for key in [
+            "data/a.csv",
+            "data/b.csv",
+            "data/c.txt",
+            "data/sub/d.csv",
+            "other/e.csv",
+        ] {
+            get_runtime()
+                .block_on(store.put(&Path::from(key), PutPayload::from_static(b"a\n1\n")))
+                .unwrap();
+        }
+
+        let factory_store = store.clone();
+        register_object_store_scheme(
+            "polars-test-glob",
+            Arc::new(
+                move |_: &str, _: &[(String, String)]| -> PolarsResult<Arc<dyn ObjectStore>> {
+                    Ok(factory_store.clone() as Arc<dyn ObjectStore>)
+                },
+            ),
+        )
+        .unwrap();
+
+        let expand = |pattern: &str| {
+            get_runtime()
+                .block_on(glob(PlRefPath::new(pattern), None))
+                .unwrap()
+        };
+
+        assert_eq!(
+            expand("polars-test-glob://bucket/data/*.csv"),
+            [
+                "polars-test-glob://bucket/data/a.csv",
+                "polars-test-glob://bucket/data/b.csv",
+            ]
+        );
+        assert_eq!(
+            expand("polars-test-glob://bucket/data/**/*.csv"),
+            [
+                "polars-test-glob://bucket/data/a.csv",
+                "polars-test-glob://bucket/data/b.csv",
+                "polars-test-glob://bucket/data/sub/d.csv",
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Interface with cloud storage through the object_store crate.
 
+#[cfg(feature = "cloud")]
+mod custom_scheme;
 #[cfg(feature = "cloud")]
 mod glob;
 #[cfg(feature = "cloud")]
@@ -8,6 +10,8 @@ pub mod options;
 #[cfg(feature = "cloud")]
 mod polars_object_store;
 
+#[cfg(feature = "cloud")]
+pub use custom_scheme::{ObjectStoreFactory, register_object_store_scheme};
 #[cfg(feature = "cloud")]
 pub use glob::*;
 #[cfg(feature = "cloud")]
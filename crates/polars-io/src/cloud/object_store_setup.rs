@@ -1,7 +1,7 @@
 use std::sync::{Arc, LazyLock};
 
-use object_store::ObjectStore;
 use object_store::local::LocalFileSystem;
+use object_store::{MultipartStore, ObjectStore};
 use polars_core::config::{self, verbose, verbose_print_sensitive};
 use polars_error::{PolarsError, PolarsResult, polars_bail, to_compute_err};
 use polars_utils::aliases::PlHashMap;
@@ -21,10 +21,7 @@ static OBJECT_STORE_CACHE: LazyLock<RwLock<PlHashMap<Vec<u8>, PolarsObjectStore>
     LazyLock::new(Default::default);
 
 #[allow(dead_code)]
-fn err_missing_feature(
-    feature: &str,
-    cloud_type: &CloudType,
-) -> PolarsResult<Arc<dyn ObjectStore>> {
+fn err_missing_feature<T>(feature: &str, cloud_type: &CloudType) -> PolarsResult<T> {
     polars_bail!(
         ComputeError:
         "feature '{}' must be enabled in order to use '{:?}' cloud urls",
@@ -45,6 +42,8 @@ fn path_and_creds_to_key(path: &PlPath, options: Option<&CloudOptions>) -> Vec<u
              retry_config,
              #[cfg(feature = "cloud")]
              credential_provider,
+             // Does not affect the built store.
+             atomic_commit: _,
          }| {
             CloudOptionsKey {
                 #[cfg(feature = "file_cache")]
@@ -109,11 +108,13 @@ impl PolarsObjectStoreBuilder {
         &self.path
     }
 
+    /// Also returns the store as a [`MultipartStore`] if it supports uploading parts with explicit
+    /// part indices.
     pub(super) async fn build_impl(
         &self,
         // Whether to clear cached credentials for Python credential providers.
         clear_cached_credentials: bool,
-    ) -> PolarsResult<Arc<dyn ObjectStore>> {
+    ) -> PolarsResult<(Arc<dyn ObjectStore>, Option<Arc<dyn MultipartStore>>)> {
         let options = self
             .options
             .as_ref()
@@ -132,10 +133,15 @@ impl PolarsObjectStoreBuilder {
             CloudType::Aws => {
                 #[cfg(feature = "aws")]
                 {
-                    let store = options
-                        .build_aws(self.path.clone(), clear_cached_credentials)
-                        .await?;
-                    Ok::<_, PolarsError>(Arc::new(store) as Arc<dyn ObjectStore>)
+                    let store = Arc::new(
+                        options
+                            .build_aws(self.path.clone(), clear_cached_credentials)
+                            .await?,
+                    );
+                    Ok::<_, PolarsError>((
+                        store.clone() as Arc<dyn ObjectStore>,
+                        Some(store as Arc<dyn MultipartStore>),
+                    ))
                 }
                 #[cfg(not(feature = "aws"))]
                 return err_missing_feature("aws", &self.cloud_type);
@@ -143,9 +149,13 @@ impl PolarsObjectStoreBuilder {
             CloudType::Gcp => {
                 #[cfg(feature = "gcp")]
                 {
-                    let store = options.build_gcp(self.path.clone(), clear_cached_credentials)?;
+                    let store =
+                        Arc::new(options.build_gcp(self.path.clone(), clear_cached_credentials)?);
 
-                    Ok::<_, PolarsError>(Arc::new(store) as Arc<dyn ObjectStore>)
+                    Ok::<_, PolarsError>((
+                        store.clone() as Arc<dyn ObjectStore>,
+                        Some(store as Arc<dyn MultipartStore>),
+                    ))
                 }
                 #[cfg(not(feature = "gcp"))]
                 return err_missing_feature("gcp", &self.cloud_type);
@@ -154,9 +164,13 @@ impl PolarsObjectStoreBuilder {
                 {
                     #[cfg(feature = "azure")]
                     {
-                        let store =
-                            options.build_azure(self.path.clone(), clear_cached_credentials)?;
-                        Ok::<_, PolarsError>(Arc::new(store) as Arc<dyn ObjectStore>)
+                        let store = Arc::new(
+                            options.build_azure(self.path.clone(), clear_cached_credentials)?,
+                        );
+                        Ok::<_, PolarsError>((
+                            store.clone() as Arc<dyn ObjectStore>,
+                            Some(store as Arc<dyn MultipartStore>),
+                        ))
                     }
                 }
                 #[cfg(not(feature = "azure"))]
@@ -164,20 +178,24 @@ impl PolarsObjectStoreBuilder {
             },
             CloudType::File => {
                 let local = LocalFileSystem::new();
-                Ok::<_, PolarsError>(Arc::new(local) as Arc<dyn ObjectStore>)
+                Ok::<_, PolarsError>((Arc::new(local) as Arc<dyn ObjectStore>, None))
             },
             CloudType::Http => {
                 {
                     #[cfg(feature = "http")]
                     {
                         let store = options.build_http(self.path.clone())?;
-                        PolarsResult::Ok(Arc::new(store) as Arc<dyn ObjectStore>)
+                        PolarsResult::Ok((Arc::new(store) as Arc<dyn ObjectStore>, None))
                     }
                 }
                 #[cfg(not(feature = "http"))]
                 return err_missing_feature("http", &cloud_location.scheme);
             },
             CloudType::Hf => panic!("impl error: unresolved hf:// path"),
+            CloudType::Custom(scheme) => {
+                super::custom_scheme::build_custom_object_store(scheme, &self.path, options)
+                    .map(|store| (store, None))
+            },
         }?;
 
         Ok(store)
@@ -186,7 +204,7 @@ impl PolarsObjectStoreBuilder {
     /// Note: Use `build_impl` for a non-caching version.
     pub(super) async fn build(self) -> PolarsResult<PolarsObjectStore> {
         let opt_cache_key = match &self.cloud_type {
-            CloudType::Aws | CloudType::Gcp | CloudType::Azure => {
+            CloudType::Aws | CloudType::Gcp | CloudType::Azure | CloudType::Custom(_) => {
                 Some(path_and_creds_to_key(&self.path, self.options.as_ref()))
             },
             CloudType::File | CloudType::Http | CloudType::Hf => None,
@@ -212,8 +230,8 @@ impl PolarsObjectStoreBuilder {
             None
         };
 
-        let store = self.build_impl(false).await?;
-        let store = PolarsObjectStore::new_from_inner(store, self);
+        let (store, multipart_store) = self.build_impl(false).await?;
+        let store = PolarsObjectStore::new_from_inner(store, multipart_store, self);
 
         if let Some(mut cache) = opt_cache_write_guard {
             // Clear the cache if we surpass a certain amount of buckets.
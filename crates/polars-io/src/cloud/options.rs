@@ -22,7 +22,7 @@ pub use object_store::gcp::GoogleConfigKey;
 use polars_error::*;
 #[cfg(feature = "aws")]
 use polars_utils::cache::LruCache;
-use polars_utils::pl_path::{CloudScheme, PlRefPath};
+use polars_utils::pl_path::{CloudScheme, CustomCloudScheme, PlRefPath};
 use polars_utils::total_ord::TotalOrdWrap;
 #[cfg(feature = "http")]
 use reqwest::header::HeaderMap;
@@ -73,6 +73,11 @@ pub(crate) enum CloudConfig {
     ),
     #[cfg(feature = "http")]
     Http { headers: Vec<(String, String)> },
+    /// Untyped storage options of a custom scheme, passed to its [`ObjectStoreFactory`].
+    ///
+    /// [`ObjectStoreFactory`]: super::ObjectStoreFactory
+    #[cfg(feature = "cloud")]
+    Custom(Vec<(String, String)>),
 }
 
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
@@ -89,6 +94,23 @@ pub struct CloudOptions {
     /// Note: In most cases you will want to access this via [`CloudOptions::initialized_credential_provider`]
     /// rather than directly.
     pub(crate) credential_provider: Option<PlCredentialProvider>,
+    /// If set, files are written to a temporary key and only moved to their destination once
+    /// fully uploaded. See [`AtomicCommitMode`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub atomic_commit: Option<AtomicCommitMode>,
+}
+
+/// How a file written with [`CloudOptions::atomic_commit`] is moved from its temporary key to its
+/// destination.
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub enum AtomicCommitMode {
+    /// Replace the destination if it exists.
+    Overwrite,
+    /// Fail if the destination already exists. This requires a store that supports conditional
+    /// copies, e.g. S3 with `aws_copy_if_not_exists` configured.
+    CreateNew,
 }
 
 impl Default for CloudOptions {
@@ -106,6 +128,7 @@ impl CloudOptions {
             retry_config: CloudRetryConfig::default(),
             #[cfg(feature = "cloud")]
             credential_provider: None,
+            atomic_commit: None,
         });
 
         &DEFAULT
@@ -232,6 +255,10 @@ pub enum CloudType {
     Http,
     /// HuggingFace
     Hf,
+    /// Scheme registered with [`register_object_store_scheme`].
+    ///
+    /// [`register_object_store_scheme`]: super::register_object_store_scheme
+    Custom(CustomCloudScheme),
 }
 
 impl CloudType {
@@ -252,6 +279,8 @@ impl CloudType {
             CloudScheme::Http | CloudScheme::Https => Self::Http,
 
             CloudScheme::S3 | CloudScheme::S3a => Self::Aws,
+
+            CloudScheme::Custom(scheme) => Self::Custom(scheme),
         }
     }
 }
@@ -323,6 +352,11 @@ impl CloudOptions {
         self
     }
 
+    pub fn with_atomic_commit(mut self, atomic_commit: Option<AtomicCommitMode>) -> Self {
+        self.atomic_commit = atomic_commit;
+        self
+    }
+
     #[cfg(feature = "cloud")]
     pub fn with_credential_provider(
         mut self,
@@ -350,7 +384,7 @@ impl CloudOptions {
         &self,
         url: PlRefPath,
         clear_cached_credentials: bool,
-    ) -> PolarsResult<impl object_store::ObjectStore> {
+    ) -> PolarsResult<object_store::aws::AmazonS3> {
         use super::credential_provider::IntoCredentialProvider;
 
         let opt_credential_provider =
@@ -520,7 +554,7 @@ impl CloudOptions {
         &self,
         url: PlRefPath,
         clear_cached_credentials: bool,
-    ) -> PolarsResult<impl object_store::ObjectStore> {
+    ) -> PolarsResult<object_store::azure::MicrosoftAzure> {
         use super::credential_provider::IntoCredentialProvider;
         use crate::cloud::ObjectStoreErrorContext;
 
@@ -582,7 +616,7 @@ impl CloudOptions {
         &self,
         url: PlRefPath,
         clear_cached_credentials: bool,
-    ) -> PolarsResult<impl object_store::ObjectStore> {
+    ) -> PolarsResult<object_store::gcp::GoogleCloudStorage> {
         use super::credential_provider::IntoCredentialProvider;
 
         let credential_provider = self.initialized_credential_provider(clear_cached_credentials)?;
@@ -671,6 +705,24 @@ impl CloudOptions {
             },
             CloudType::File => Ok(Self::default()),
             CloudType::Http => Ok(Self::default()),
+            CloudType::Custom(_) => {
+                #[cfg(feature = "cloud")]
+                {
+                    let config = config
+                        .into_iter()
+                        .map(|(k, v)| (k.as_ref().to_string(), v.into()))
+                        .collect::<Vec<_>>();
+
+                    Ok(Self {
+                        config: (!config.is_empty()).then_some(CloudConfig::Custom(config)),
+                        ..Self::default()
+                    })
+                }
+                #[cfg(not(feature = "cloud"))]
+                {
+                    polars_bail!(ComputeError: "'cloud' feature is not enabled");
+                }
+            },
             CloudType::Gcp => {
                 #[cfg(feature = "gcp")]
                 {
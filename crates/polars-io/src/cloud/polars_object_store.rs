@@ -17,6 +17,7 @@ use crate::pl_async::{
     self, MAX_BUDGET_PER_REQUEST, get_concurrency_limit, get_download_chunk_size,
     tune_with_concurrency_budget, with_concurrency_budget,
 };
+use crate::utils::byte_source::RangeCoalesceOptions;
 
 #[derive(Debug)]
 pub struct PolarsObjectStoreError {
@@ -70,7 +71,7 @@ mod inner {
     use std::future::Future;
     use std::sync::Arc;
 
-    use object_store::ObjectStore;
+    use object_store::{MultipartStore, ObjectStore};
     use polars_core::config;
     use polars_error::{PolarsError, PolarsResult};
     use polars_utils::relaxed_cell::RelaxedCell;
@@ -78,12 +79,22 @@ mod inner {
     use crate::cloud::{ObjectStoreErrorContext, PolarsObjectStoreBuilder};
     use crate::metrics::{IOMetrics, OptIOMetrics};
 
-    #[derive(Debug)]
     struct Inner {
         store: tokio::sync::RwLock<Arc<dyn ObjectStore>>,
+        /// The same store as `store` if it supports uploading parts with explicit part indices.
+        multipart_store: tokio::sync::RwLock<Option<Arc<dyn MultipartStore>>>,
         builder: PolarsObjectStoreBuilder,
     }
 
+    impl std::fmt::Debug for Inner {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Inner")
+                .field("store", &self.store)
+                .field("builder", &self.builder)
+                .finish_non_exhaustive()
+        }
+    }
+
     /// Polars wrapper around [`ObjectStore`] functionality. This struct is cheaply cloneable.
     #[derive(Clone, Debug)]
     pub struct PolarsObjectStore {
@@ -99,12 +110,14 @@ mod inner {
     impl PolarsObjectStore {
         pub(crate) fn new_from_inner(
             store: Arc<dyn ObjectStore>,
+            multipart_store: Option<Arc<dyn MultipartStore>>,
             builder: PolarsObjectStoreBuilder,
         ) -> Self {
             let initial_store = store.clone();
             Self {
                 inner: Arc::new(Inner {
                     store: tokio::sync::RwLock::new(store),
+                    multipart_store: tokio::sync::RwLock::new(multipart_store),
                     builder,
                 }),
                 initial_store,
@@ -131,6 +144,12 @@ mod inner {
             }
         }
 
+        /// Gets the underlying store as a [`MultipartStore`] if it supports uploading parts with
+        /// explicit part indices, which allows retrying individual parts of an upload.
+        pub async fn to_dyn_multipart_store(&self) -> Option<Arc<dyn MultipartStore>> {
+            self.inner.multipart_store.read().await.clone()
+        }
+
         pub async fn rebuild_inner(
             &self,
             from_version: &Arc<dyn ObjectStore>,
@@ -139,15 +158,18 @@ mod inner {
 
             // If this does not eq, then `inner` was already re-built by another thread.
             if Arc::ptr_eq(&*current_store, from_version) {
-                *current_store =
-                    self.inner
-                        .builder
-                        .clone()
-                        .build_impl(true)
-                        .await
-                        .map_err(|e| {
-                            e.wrap_msg(|e| format!("attempt to rebuild object store failed: {e}"))
-                        })?;
+                let (store, multipart_store) = self
+                    .inner
+                    .builder
+                    .clone()
+                    .build_impl(true)
+                    .await
+                    .map_err(|e| {
+                        e.wrap_msg(|e| format!("attempt to rebuild object store failed: {e}"))
+                    })?;
+
+                *current_store = store;
+                *self.inner.multipart_store.write().await = multipart_store;
             }
 
             self.rebuilt.store(true);
@@ -269,7 +291,7 @@ impl PolarsObjectStore {
             return Ok(Buffer::new());
         }
 
-        let parts = split_range(range.clone());
+        let parts = split_range(range.clone(), get_download_chunk_size());
 
         if parts.len() == 1 {
             let out = tune_with_concurrency_budget(1, move || async move {
@@ -312,7 +334,7 @@ impl PolarsObjectStore {
     }
 
     /// Fetch byte ranges into a HashMap keyed by the range start. This will mutably sort the
-    /// `ranges` slice for coalescing.
+    /// `ranges` slice for coalescing according to `coalesce_options`.
     ///
     /// # Panics
     /// Panics if the same range start is used by more than 1 range.
@@ -320,6 +342,7 @@ impl PolarsObjectStore {
         &self,
         path: &Path,
         ranges: &mut [Range<usize>],
+        coalesce_options: &RangeCoalesceOptions,
     ) -> PolarsResult<PlHashMap<usize, Buffer<u8>>> {
         if ranges.is_empty() {
             return Ok(Default::default());
@@ -328,7 +351,8 @@ impl PolarsObjectStore {
         ranges.sort_unstable_by_key(|x| x.start);
 
         let ranges_len = ranges.len();
-        let (merged_ranges, merged_ends): (Vec<_>, Vec<_>) = merge_ranges(ranges).unzip();
+        let (merged_ranges, merged_ends): (Vec<_>, Vec<_>) =
+            merge_ranges(ranges, coalesce_options).unzip();
 
         let mut out = PlHashMap::with_capacity(ranges_len);
 
@@ -405,7 +429,7 @@ impl PolarsObjectStore {
 
     pub async fn download(&self, path: &Path, file: &mut tokio::fs::File) -> PolarsResult<()> {
         let size = self.head(path).await?.size;
-        let parts = split_range(0..size as usize);
+        let parts = split_range(0..size as usize, get_download_chunk_size());
 
         tune_with_concurrency_budget(
             parts.len().clamp(0, MAX_BUDGET_PER_REQUEST) as u32,
@@ -475,9 +499,10 @@ impl PolarsObjectStore {
 
 /// Splits a single range into multiple smaller ranges, which can be downloaded concurrently for
 /// much higher throughput.
-fn split_range(range: Range<usize>) -> impl ExactSizeIterator<Item = Range<usize>> {
-    let chunk_size = get_download_chunk_size();
-
+fn split_range(
+    range: Range<usize>,
+    chunk_size: usize,
+) -> impl ExactSizeIterator<Item = Range<usize>> {
     // Calculate n_parts such that we are as close as possible to the `chunk_size`.
     let n_parts = [
         (range.len().div_ceil(chunk_size)).max(1),
@@ -517,8 +542,14 @@ fn split_range(range: Range<usize>) -> impl ExactSizeIterator<Item = Range<usize
 /// * etc..
 ///
 /// Note that if an end value is 0, it means the range is a splitted part and should be combined.
-fn merge_ranges(ranges: &[Range<usize>]) -> impl Iterator<Item = (Range<usize>, usize)> + '_ {
-    let chunk_size = get_download_chunk_size();
+fn merge_ranges<'a>(
+    ranges: &'a [Range<usize>],
+    coalesce_options: &RangeCoalesceOptions,
+) -> impl Iterator<Item = (Range<usize>, usize)> + 'a {
+    let RangeCoalesceOptions {
+        max_gap,
+        split_size: chunk_size,
+    } = *coalesce_options;
 
     let mut current_merged_range = ranges.first().map_or(0..0, Clone::clone);
     // Number of fetched bytes excluding excess.
@@ -557,8 +588,9 @@ fn merge_ranges(ranges: &[Range<usize>]) -> impl Iterator<Item = (Range<usize>,
                 let should_merge = is_overlapping || {
                     let leq_current_len_dist_to_chunk_size = new_merged.len().abs_diff(chunk_size)
                         <= current_merged_range.len().abs_diff(chunk_size);
-                    let gap_tolerance =
-                        (current_n_bytes.max(range.len()) / 8).clamp(1024 * 1024, 8 * 1024 * 1024);
+                    let gap_tolerance = max_gap.unwrap_or_else(|| {
+                        (current_n_bytes.max(range.len()) / 8).clamp(1024 * 1024, 8 * 1024 * 1024)
+                    });
 
                     leq_current_len_dist_to_chunk_size && distance <= gap_tolerance
                 };
@@ -580,10 +612,10 @@ fn merge_ranges(ranges: &[Range<usize>]) -> impl Iterator<Item = (Range<usize>,
                 }
             }
         })
-        .flat_map(|x| {
+        .flat_map(move |x| {
             // Split large individual ranges within the list of ranges.
             let (range, end) = x;
-            let split = split_range(range);
+            let split = split_range(range, chunk_size);
             let len = split.len();
 
             split
@@ -606,8 +638,8 @@ mod tests {
         #[allow(clippy::single_range_in_vec_init)]
         {
             // Round-trip empty ranges.
-            assert_eq!(split_range(0..0).collect::<Vec<_>>(), [0..0]);
-            assert_eq!(split_range(3..3).collect::<Vec<_>>(), [3..3]);
+            assert_eq!(split_range(0..0, chunk_size).collect::<Vec<_>>(), [0..0]);
+            assert_eq!(split_range(3..3, chunk_size).collect::<Vec<_>>(), [3..3]);
         }
 
         // Threshold to start splitting to 2 ranges
@@ -620,11 +652,14 @@ mod tests {
 
         #[allow(clippy::single_range_in_vec_init)]
         {
-            assert_eq!(split_range(0..n).collect::<Vec<_>>(), [0..89478485]);
+            assert_eq!(
+                split_range(0..n, chunk_size).collect::<Vec<_>>(),
+                [0..89478485]
+            );
         }
 
         assert_eq!(
-            split_range(0..n + 1).collect::<Vec<_>>(),
+            split_range(0..n + 1, chunk_size).collect::<Vec<_>>(),
             [0..44739243, 44739243..89478486]
         );
 
@@ -637,64 +672,109 @@ mod tests {
         let n = 12 * chunk_size / 5;
 
         assert_eq!(
-            split_range(0..n).collect::<Vec<_>>(),
+            split_range(0..n, chunk_size).collect::<Vec<_>>(),
             [0..80530637, 80530637..161061273]
         );
 
         assert_eq!(
-            split_range(0..n + 1).collect::<Vec<_>>(),
+            split_range(0..n + 1, chunk_size).collect::<Vec<_>>(),
             [0..53687092, 53687092..107374183, 107374183..161061274]
         );
     }
 
     #[test]
     fn test_merge_ranges() {
-        use super::{get_download_chunk_size, merge_ranges};
+        use super::{RangeCoalesceOptions, get_download_chunk_size, merge_ranges};
 
         let chunk_size = get_download_chunk_size();
 
         assert_eq!(chunk_size, 64 * 1024 * 1024);
 
+        let options = &RangeCoalesceOptions {
+            max_gap: None,
+            split_size: chunk_size,
+        };
+
         // Round-trip empty slice
-        assert_eq!(merge_ranges(&[]).collect::<Vec<_>>(), []);
+        assert_eq!(merge_ranges(&[], options).collect::<Vec<_>>(), []);
 
         // We have 1 tiny request followed by 1 huge request. They are combined as it reduces the
         // `abs_diff()` to the `chunk_size`, but afterwards they are split to 2 evenly sized
         // requests.
         assert_eq!(
-            merge_ranges(&[0..1, 1..127 * 1024 * 1024]).collect::<Vec<_>>(),
+            merge_ranges(&[0..1, 1..127 * 1024 * 1024], options).collect::<Vec<_>>(),
             [(0..66584576, 0), (66584576..133169152, 2)]
         );
 
         // <= 1MiB gap, merge
         assert_eq!(
-            merge_ranges(&[0..1, 1024 * 1024 + 1..1024 * 1024 + 2]).collect::<Vec<_>>(),
+            merge_ranges(&[0..1, 1024 * 1024 + 1..1024 * 1024 + 2], options).collect::<Vec<_>>(),
             [(0..1048578, 2)]
         );
 
         // > 1MiB gap, do not merge
         assert_eq!(
-            merge_ranges(&[0..1, 1024 * 1024 + 2..1024 * 1024 + 3]).collect::<Vec<_>>(),
+            merge_ranges(&[0..1, 1024 * 1024 + 2..1024 * 1024 + 3], options).collect::<Vec<_>>(),
             [(0..1, 1), (1048578..1048579, 2)]
         );
 
         // <= 12.5% gap, merge
         assert_eq!(
-            merge_ranges(&[0..8, 10..11]).collect::<Vec<_>>(),
+            merge_ranges(&[0..8, 10..11], options).collect::<Vec<_>>(),
             [(0..11, 2)]
         );
 
         // <= 12.5% gap relative to RHS, merge
         assert_eq!(
-            merge_ranges(&[0..1, 3..11]).collect::<Vec<_>>(),
+            merge_ranges(&[0..1, 3..11], options).collect::<Vec<_>>(),
             [(0..11, 2)]
         );
 
         // Overlapping range, merge
         assert_eq!(
-            merge_ranges(&[0..80 * 1024 * 1024, 10 * 1024 * 1024..70 * 1024 * 1024])
-                .collect::<Vec<_>>(),
+            merge_ranges(
+                &[0..80 * 1024 * 1024, 10 * 1024 * 1024..70 * 1024 * 1024],
+                options
+            )
+            .collect::<Vec<_>>(),
             [(0..80 * 1024 * 1024, 2)]
         );
+
+        // Configured gap, merge
+        let options = &RangeCoalesceOptions {
+            max_gap: Some(16 * 1024 * 1024),
+            split_size: chunk_size,
+        };
+
+        assert_eq!(
+            merge_ranges(&[0..1, 16 * 1024 * 1024 + 1..16 * 1024 * 1024 + 2], options)
+                .collect::<Vec<_>>(),
+            [(0..16 * 1024 * 1024 + 2, 2)]
+        );
+
+        // Configured gap, do not merge
+        let options = &RangeCoalesceOptions {
+            max_gap: Some(0),
+            split_size: chunk_size,
+        };
+
+        assert_eq!(
+            merge_ranges(&[0..8, 9..11], options).collect::<Vec<_>>(),
+            [(0..8, 1), (9..11, 2)]
+        );
+
+        // Configured split size
+        let options = &RangeCoalesceOptions {
+            max_gap: None,
+            split_size: 4,
+        };
+
+        #[allow(clippy::single_range_in_vec_init)]
+        {
+            assert_eq!(
+                merge_ranges(&[0..8], options).collect::<Vec<_>>(),
+                [(0..4, 0), (4..8, 1)]
+            );
+        }
     }
 }
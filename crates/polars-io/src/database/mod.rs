@@ -0,0 +1,419 @@
+//! # Reading from and writing to databases.
+//!
+//! Databases are accessed through a [`DatabaseDriver`], which executes SQL and yields Arrow record
+//! batches, similar to ADBC. The projection, simple column predicates and row limit of a
+//! [`DatabaseReader`] are pushed down by wrapping the query in a `SELECT` rendered for the driver.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use polars_core::prelude::*;
+//! use polars_io::database::{DatabaseReader, DatabaseWriter, IfTableExists, SqliteDriver};
+//! use polars_io::predicates::SpecializedColumnPredicate;
+//!
+//! fn example(df: &DataFrame) -> PolarsResult<DataFrame> {
+//!     let driver = Arc::new(SqliteDriver::open("example.db")?);
+//!
+//!     DatabaseWriter::new(driver.clone(), "measurements")
+//!         .with_if_table_exists(IfTableExists::Replace)
+//!         .finish(df)?;
+//!
+//!     DatabaseReader::new(driver, "SELECT * FROM measurements")
+//!         .with_columns(Some(vec!["station".into(), "temp".into()]))
+//!         .with_predicates(vec![(
+//!             "station".into(),
+//!             SpecializedColumnPredicate::Equal(Scalar::from(PlSmallStr::from("Utrecht"))),
+//!         )])
+//!         .with_n_rows(Some(100))
+//!         .finish()
+//! }
+//! ```
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+use std::fmt::Write;
+
+use arrow::datatypes::ArrowSchemaRef;
+use arrow::record_batch::RecordBatch;
+use polars_core::prelude::*;
+use polars_utils::itertools::Itertools;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteDriver;
+
+use crate::predicates::SpecializedColumnPredicate;
+
+/// Record batches produced by a query.
+pub struct RecordBatchStream {
+    pub schema: ArrowSchemaRef,
+    pub batches: Box<dyn Iterator<Item = PolarsResult<RecordBatch>> + Send>,
+}
+
+/// Behavior when writing to a table that already exists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum IfTableExists {
+    /// Raise an error.
+    #[default]
+    Fail,
+    /// Append the rows to the existing table.
+    Append,
+    /// Drop the existing table and create a new one.
+    Replace,
+}
+
+/// Connection to a database.
+///
+/// The rendering methods default to ANSI SQL and can be overridden for other dialects.
+pub trait DatabaseDriver: Send + Sync {
+    /// Name of the driver, used in error messages.
+    fn name(&self) -> &str;
+
+    /// Executes `sql`, returning the result in record batches of at most `batch_size` rows.
+    fn execute_query(&self, sql: &str, batch_size: usize) -> PolarsResult<RecordBatchStream>;
+
+    /// Inserts all rows of `df` into `table`, creating the table if it does not exist.
+    ///
+    /// Returns the number of inserted rows.
+    fn bulk_insert(
+        &self,
+        table: &str,
+        df: &DataFrame,
+        if_table_exists: IfTableExists,
+    ) -> PolarsResult<usize>;
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+
+    /// Renders a literal, or returns `None` if the value cannot be represented.
+    fn render_literal(&self, value: &AnyValue) -> Option<String> {
+        use AnyValue as A;
+
+        Some(match value {
+            A::Boolean(v) => if *v { "TRUE" } else { "FALSE" }.to_string(),
+            A::UInt8(v) => v.to_string(),
+            A::UInt16(v) => v.to_string(),
+            A::UInt32(v) => v.to_string(),
+            A::UInt64(v) => v.to_string(),
+            A::Int8(v) => v.to_string(),
+            A::Int16(v) => v.to_string(),
+            A::Int32(v) => v.to_string(),
+            A::Int64(v) => v.to_string(),
+            A::Float32(v) if v.is_finite() => v.to_string(),
+            A::Float64(v) if v.is_finite() => v.to_string(),
+            A::String(v) => quote_string(v),
+            A::StringOwned(v) => quote_string(v),
+            _ => return None,
+        })
+    }
+
+    /// Renders a condition on whether the already quoted `column` starts with `prefix`.
+    fn render_starts_with(&self, column: &str, prefix: &str) -> Option<String> {
+        Some(format!(
+            "{column} LIKE {} ESCAPE '\\'",
+            quote_string(&format!("{}%", escape_like_pattern(prefix)))
+        ))
+    }
+
+    /// Renders a condition on whether the already quoted `column` ends with `suffix`.
+    fn render_ends_with(&self, column: &str, suffix: &str) -> Option<String> {
+        Some(format!(
+            "{column} LIKE {} ESCAPE '\\'",
+            quote_string(&format!("%{}", escape_like_pattern(suffix)))
+        ))
+    }
+}
+
+/// Quotes a string literal.
+pub fn quote_string(v: &str) -> String {
+    format!("'{}'", v.replace('\'', "''"))
+}
+
+fn escape_like_pattern(v: &str) -> String {
+    let mut out = String::with_capacity(v.len());
+
+    for c in v.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+fn render_predicate(
+    driver: &dyn DatabaseDriver,
+    column: &str,
+    predicate: &SpecializedColumnPredicate,
+) -> PolarsResult<String> {
+    use SpecializedColumnPredicate as P;
+
+    let quoted_column = driver.quote_identifier(column);
+    let column_ref = quoted_column.as_str();
+    let literal = |v: &Scalar| {
+        if v.is_null() {
+            None
+        } else {
+            driver.render_literal(v.value())
+        }
+    };
+
+    let out = match predicate {
+        P::Equal(v) if v.is_null() => Some(format!("{column_ref} IS NULL")),
+        P::Equal(v) => literal(v).map(|v| format!("{column_ref} = {v}")),
+        P::Between(low, high) => literal(low)
+            .zip(literal(high))
+            .map(|(low, high)| format!("{column_ref} BETWEEN {low} AND {high}")),
+        P::EqualOneOf(values) if values.is_empty() => Some("1 = 0".to_string()),
+        P::EqualOneOf(values) => values
+            .iter()
+            .map(literal)
+            .collect::<Option<Vec<_>>>()
+            .map(|values| format!("{column_ref} IN ({})", values.join(", "))),
+        P::StartsWith(prefix) => std::str::from_utf8(prefix)
+            .ok()
+            .and_then(|prefix| driver.render_starts_with(column_ref, prefix)),
+        P::EndsWith(suffix) => std::str::from_utf8(suffix)
+            .ok()
+            .and_then(|suffix| driver.render_ends_with(column_ref, suffix)),
+        P::RegexMatch(_) => None,
+    };
+
+    out.ok_or_else(|| {
+        polars_err!(
+            InvalidOperation:
+            "predicate {:?} on column '{}' cannot be rendered as SQL by the '{}' driver",
+            predicate, column, driver.name()
+        )
+    })
+}
+
+/// Reads the result of a SQL query through a [`DatabaseDriver`].
+#[must_use]
+pub struct DatabaseReader {
+    driver: Arc<dyn DatabaseDriver>,
+    query: String,
+    columns: Option<Vec<PlSmallStr>>,
+    predicates: Vec<(PlSmallStr, SpecializedColumnPredicate)>,
+    n_rows: Option<usize>,
+    batch_size: usize,
+}
+
+impl DatabaseReader {
+    pub fn new(driver: Arc<dyn DatabaseDriver>, query: impl Into<String>) -> Self {
+        Self {
+            driver,
+            query: query.into(),
+            columns: None,
+            predicates: vec![],
+            n_rows: None,
+            batch_size: 65536,
+        }
+    }
+
+    /// Only read the given columns of the query result.
+    pub fn with_columns(mut self, columns: Option<Vec<PlSmallStr>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Only read the rows matching all predicates.
+    ///
+    /// The predicates must be representable in SQL, e.g. regex matches are not supported.
+    pub fn with_predicates(
+        mut self,
+        predicates: Vec<(PlSmallStr, SpecializedColumnPredicate)>,
+    ) -> Self {
+        self.predicates = predicates;
+        self
+    }
+
+    /// Stop reading after `n_rows` rows.
+    pub fn with_n_rows(mut self, n_rows: Option<usize>) -> Self {
+        self.n_rows = n_rows;
+        self
+    }
+
+    /// Set the maximum number of rows per record batch. Defaults to 65536.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// The SQL sent to the driver, with the projection, predicates and row limit pushed down.
+    pub fn to_sql(&self) -> PolarsResult<String> {
+        let query = self.query.trim().trim_end_matches(';').trim_end();
+
+        if self.columns.is_none() && self.predicates.is_empty() && self.n_rows.is_none() {
+            return Ok(query.to_string());
+        }
+
+        let driver = self.driver.as_ref();
+
+        let projection = match &self.columns {
+            None => "*".to_string(),
+            Some(columns) => {
+                polars_ensure!(
+                    !columns.is_empty(),
+                    InvalidOperation: "cannot push down an empty projection to a database query"
+                );
+                columns
+                    .iter()
+                    .map(|c| driver.quote_identifier(c))
+                    .join(", ")
+            },
+        };
+
+        let mut sql = format!(
+            "SELECT {projection} FROM ({query}) AS {}",
+            driver.quote_identifier("_polars_query")
+        );
+
+        if !self.predicates.is_empty() {
+            let conditions = self
+                .predicates
+                .iter()
+                .map(|(column, predicate)| render_predicate(driver, column, predicate))
+                .collect::<PolarsResult<Vec<_>>>()?;
+
+            write!(sql, " WHERE {}", conditions.join(" AND ")).unwrap();
+        }
+
+        if let Some(n_rows) = self.n_rows {
+            write!(sql, " LIMIT {n_rows}").unwrap();
+        }
+
+        Ok(sql)
+    }
+
+    /// Executes the query, returning the result as record batches.
+    pub fn batched(self) -> PolarsResult<RecordBatchStream> {
+        let sql = self.to_sql()?;
+
+        if polars_core::config::verbose() {
+            eprintln!("[DatabaseReader]: executing query: {sql}");
+        }
+
+        self.driver.execute_query(&sql, self.batch_size.max(1))
+    }
+
+    pub fn finish(self) -> PolarsResult<DataFrame> {
+        let RecordBatchStream { schema, batches } = self.batched()?;
+        let mut df = DataFrame::empty_with_schema(&Schema::from_arrow_schema(&schema));
+
+        for batch in batches {
+            df.append_record_batch(batch?)?;
+        }
+
+        Ok(df)
+    }
+}
+
+/// Writes a [`DataFrame`] to a table through a [`DatabaseDriver`].
+#[must_use]
+pub struct DatabaseWriter {
+    driver: Arc<dyn DatabaseDriver>,
+    table: String,
+    if_table_exists: IfTableExists,
+}
+
+impl DatabaseWriter {
+    pub fn new(driver: Arc<dyn DatabaseDriver>, table: impl Into<String>) -> Self {
+        Self {
+            driver,
+            table: table.into(),
+            if_table_exists: IfTableExists::default(),
+        }
+    }
+
+    /// Set the behavior if the table already exists. Defaults to [`IfTableExists::Fail`].
+    pub fn with_if_table_exists(mut self, if_table_exists: IfTableExists) -> Self {
+        self.if_table_exists = if_table_exists;
+        self
+    }
+
+    /// Inserts all rows of `df`, returning the number of inserted rows.
+    pub fn finish(self, df: &DataFrame) -> PolarsResult<usize> {
+        self.driver
+            .bulk_insert(&self.table, df, self.if_table_exists)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use polars_core::prelude::*;
+
+    use super::{DatabaseDriver, DatabaseReader, IfTableExists, RecordBatchStream};
+    use crate::predicates::SpecializedColumnPredicate as P;
+
+    struct AnsiDriver;
+
+    impl DatabaseDriver for AnsiDriver {
+        fn name(&self) -> &str {
+            "ansi"
+        }
+
+        fn execute_query(&self, _sql: &str, _batch_size: usize) -> PolarsResult<RecordBatchStream> {
+            unimplemented!()
+        }
+
+        fn bulk_insert(
+            &self,
+            _table: &str,
+            _df: &DataFrame,
+            _if_table_exists: IfTableExists,
+        ) -> PolarsResult<usize> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_database_reader_to_sql() {
+        let reader = || DatabaseReader::new(Arc::new(AnsiDriver), "SELECT * FROM t;");
+
+        assert_eq!(reader().to_sql().unwrap(), "SELECT * FROM t");
+
+        let sql = reader()
+            .with_columns(Some(vec!["a".into(), "b\"c".into()]))
+            .with_predicates(vec![
+                (
+                    "a".into(),
+                    P::Between(Scalar::from(1i64), Scalar::from(5i64)),
+                ),
+                (
+                    "b\"c".into(),
+                    P::EqualOneOf(
+                        [
+                            Scalar::from(PlSmallStr::from("it's")),
+                            Scalar::from(PlSmallStr::from("x")),
+                        ]
+                        .into(),
+                    ),
+                ),
+                ("d".into(), P::StartsWith(b"50%".as_slice().into())),
+                ("e".into(), P::Equal(Scalar::null(DataType::Int64))),
+            ])
+            .with_n_rows(Some(10))
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT \"a\", \"b\"\"c\" FROM (SELECT * FROM t) AS \"_polars_query\" \
+            WHERE \"a\" BETWEEN 1 AND 5 AND \"b\"\"c\" IN ('it''s', 'x') \
+            AND \"d\" LIKE '50\\%%' ESCAPE '\\' AND \"e\" IS NULL LIMIT 10"
+        );
+
+        let regex = regex::bytes::Regex::new("a+").unwrap();
+        assert!(
+            reader()
+                .with_predicates(vec![("a".into(), P::RegexMatch(regex))])
+                .to_sql()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,524 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use arrow::record_batch::RecordBatch;
+use polars_core::prelude::*;
+use polars_error::to_compute_err;
+use polars_utils::itertools::Itertools;
+use rusqlite::types::Value;
+use rusqlite::{Connection, params_from_iter};
+
+use super::{DatabaseDriver, IfTableExists, RecordBatchStream, quote_string};
+
+/// [`DatabaseDriver`] for SQLite databases.
+///
+/// Query results are converted into record batches of `batch_size` rows while they are read.
+/// Columns take the dtype of their declared type, but fall back to the dtype of their values
+/// when these do not fit, e.g. `String` for text in an `INTEGER` or `BOOLEAN` column.
+pub struct SqliteDriver {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteDriver {
+    pub fn open(path: impl AsRef<Path>) -> PolarsResult<Self> {
+        Ok(Self::from_connection(
+            Connection::open(path).map_err(to_compute_err)?,
+        ))
+    }
+
+    pub fn open_in_memory() -> PolarsResult<Self> {
+        Ok(Self::from_connection(
+            Connection::open_in_memory().map_err(to_compute_err)?,
+        ))
+    }
+
+    pub fn from_connection(conn: Connection) -> Self {
+        Self {
+            conn: Mutex::new(conn),
+        }
+    }
+}
+
+/// Maps a declared column type to a dtype, following the SQLite type affinity rules.
+///
+/// Returns `None` if the dtype should be inferred from the values instead.
+fn dtype_from_decl_type(decl_type: &str) -> Option<DataType> {
+    let decl_type = decl_type.to_ascii_uppercase();
+    let contains = |pat: &str| decl_type.contains(pat);
+
+    if contains("BOOL") {
+        Some(DataType::Boolean)
+    } else if contains("INT") {
+        Some(DataType::Int64)
+    } else if contains("CHAR") || contains("CLOB") || contains("TEXT") {
+        Some(DataType::String)
+    } else if contains("BLOB") {
+        Some(DataType::Binary)
+    } else if contains("REAL") || contains("FLOA") || contains("DOUB") {
+        Some(DataType::Float64)
+    } else {
+        None
+    }
+}
+
+/// Builds the values of a result column with a single dtype.
+enum ValueBuilder {
+    Null(PlSmallStr, usize),
+    Boolean(BooleanChunkedBuilder),
+    Int64(PrimitiveChunkedBuilder<Int64Type>),
+    Float64(PrimitiveChunkedBuilder<Float64Type>),
+    String(StringChunkedBuilder),
+    Binary(BinaryChunkedBuilder),
+}
+
+impl ValueBuilder {
+    fn new(name: PlSmallStr, dtype: &DataType, capacity: usize) -> Self {
+        match dtype {
+            DataType::Null => Self::Null(name, 0),
+            DataType::Boolean => Self::Boolean(BooleanChunkedBuilder::new(name, capacity)),
+            DataType::Int64 => Self::Int64(PrimitiveChunkedBuilder::new(name, capacity)),
+            DataType::Float64 => Self::Float64(PrimitiveChunkedBuilder::new(name, capacity)),
+            DataType::String => Self::String(StringChunkedBuilder::new(name, capacity)),
+            DataType::Binary => Self::Binary(BinaryChunkedBuilder::new(name, capacity)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn dtype(&self) -> DataType {
+        match self {
+            Self::Null(..) => DataType::Null,
+            Self::Boolean(_) => DataType::Boolean,
+            Self::Int64(_) => DataType::Int64,
+            Self::Float64(_) => DataType::Float64,
+            Self::String(_) => DataType::String,
+            Self::Binary(_) => DataType::Binary,
+        }
+    }
+
+    /// Appends `value`, or returns it if it does not fit the dtype.
+    fn append(&mut self, value: Value) -> Result<(), Value> {
+        match (self, value) {
+            (Self::Null(_, len), Value::Null) => *len += 1,
+            (Self::Boolean(b), Value::Null) => b.append_null(),
+            (Self::Boolean(b), Value::Integer(v)) => b.append_value(v != 0),
+            (Self::Int64(b), Value::Null) => b.append_null(),
+            (Self::Int64(b), Value::Integer(v)) => b.append_value(v),
+            (Self::Float64(b), Value::Null) => b.append_null(),
+            (Self::Float64(b), Value::Integer(v)) => b.append_value(v as f64),
+            (Self::Float64(b), Value::Real(v)) => b.append_value(v),
+            (Self::String(b), Value::Null) => b.append_null(),
+            (Self::String(b), Value::Integer(v)) => b.append_value(v.to_string()),
+            (Self::String(b), Value::Real(v)) => b.append_value(v.to_string()),
+            (Self::String(b), Value::Text(v)) => b.append_value(v),
+            (Self::Binary(b), Value::Null) => b.append_null(),
+            (Self::Binary(b), Value::Integer(v)) => b.append_value(v.to_string()),
+            (Self::Binary(b), Value::Real(v)) => b.append_value(v.to_string()),
+            (Self::Binary(b), Value::Text(v)) => b.append_value(v),
+            (Self::Binary(b), Value::Blob(v)) => b.append_value(v),
+            (_, value) => return Err(value),
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Series {
+        match self {
+            Self::Null(name, len) => Series::full_null(name, len, &DataType::Null),
+            Self::Boolean(b) => b.finish().into_series(),
+            Self::Int64(b) => b.finish().into_series(),
+            Self::Float64(b) => b.finish().into_series(),
+            Self::String(b) => b.finish().into_series(),
+            Self::Binary(b) => b.finish().into_series(),
+        }
+    }
+}
+
+/// The dtype of a value's storage class.
+fn value_dtype(value: &Value) -> DataType {
+    match value {
+        Value::Null => DataType::Null,
+        Value::Integer(_) => DataType::Int64,
+        Value::Real(_) => DataType::Float64,
+        Value::Text(_) => DataType::String,
+        Value::Blob(_) => DataType::Binary,
+    }
+}
+
+/// Casts values that were read before their column was widened to `dtype`.
+fn cast_values(s: &Series, dtype: &DataType) -> PolarsResult<Series> {
+    match s.dtype() {
+        // Numbers are stored as their text representation in binary columns.
+        dt if dtype == &DataType::Binary && (dt.is_bool() || dt.is_primitive_numeric()) => {
+            s.cast(&DataType::String)?.cast(dtype)
+        },
+        _ => s.cast(dtype),
+    }
+}
+
+/// Builds a result column, widening its dtype when a value does not fit.
+///
+/// SQLite columns can hold values of any storage class, e.g. text in a column declared as
+/// `INTEGER`. Such a column falls back to the dtype of the value, here `String`.
+struct ColumnBuilder {
+    name: PlSmallStr,
+    values: ValueBuilder,
+    /// Values of the current batch that were read before the dtype was widened.
+    parts: Vec<Series>,
+    batch_size: usize,
+}
+
+impl ColumnBuilder {
+    fn new(name: PlSmallStr, dtype: &DataType, batch_size: usize) -> Self {
+        Self {
+            values: ValueBuilder::new(name.clone(), dtype, batch_size),
+            name,
+            parts: vec![],
+            batch_size,
+        }
+    }
+
+    fn append(&mut self, value: Value) {
+        if let Err(value) = self.values.append(value) {
+            let values =
+                ValueBuilder::new(self.name.clone(), &value_dtype(&value), self.batch_size);
+            let part = std::mem::replace(&mut self.values, values).finish();
+            if !part.is_empty() {
+                self.parts.push(part);
+            }
+
+            // Every value fits the dtype of its storage class.
+            self.values.append(value).unwrap();
+        }
+    }
+
+    /// Returns the values of the current batch.
+    fn flush(&mut self) -> PolarsResult<Series> {
+        let dtype = self.values.dtype();
+        let values = ValueBuilder::new(self.name.clone(), &dtype, self.batch_size);
+        let mut out = std::mem::replace(&mut self.values, values).finish();
+
+        if !self.parts.is_empty() {
+            let mut parts = std::mem::take(&mut self.parts).into_iter();
+            let mut values = cast_values(&parts.next().unwrap(), &dtype)?;
+
+            for part in parts {
+                values.append_owned(cast_values(&part, &dtype)?)?;
+            }
+            values.append_owned(out)?;
+            out = values;
+        }
+
+        Ok(out)
+    }
+}
+
+fn flush_batch(columns: &mut [ColumnBuilder], height: usize) -> PolarsResult<DataFrame> {
+    let columns = columns
+        .iter_mut()
+        .map(|c| c.flush().map(Column::from))
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    DataFrame::new(height, columns)
+}
+
+fn sqlite_type(dtype: &DataType) -> &'static str {
+    match dtype {
+        DataType::Boolean => "BOOLEAN",
+        dt if dt.is_integer() => "INTEGER",
+        dt if dt.is_float() => "REAL",
+        DataType::Binary => "BLOB",
+        _ => "TEXT",
+    }
+}
+
+fn any_value_to_sqlite(value: AnyValue) -> PolarsResult<Value> {
+    use AnyValue as A;
+
+    Ok(match value {
+        A::Null => Value::Null,
+        A::Boolean(v) => Value::Integer(v.into()),
+        A::UInt8(v) => Value::Integer(v.into()),
+        A::UInt16(v) => Value::Integer(v.into()),
+        A::UInt32(v) => Value::Integer(v.into()),
+        A::UInt64(v) => Value::Integer(i64::try_from(v).map_err(
+            |_| polars_err!(ComputeError: "value {} does not fit in a SQLite INTEGER", v),
+        )?),
+        A::Int8(v) => Value::Integer(v.into()),
+        A::Int16(v) => Value::Integer(v.into()),
+        A::Int32(v) => Value::Integer(v.into()),
+        A::Int64(v) => Value::Integer(v),
+        A::Float32(v) => Value::Real(v.into()),
+        A::Float64(v) => Value::Real(v),
+        A::String(v) => Value::Text(v.to_string()),
+        A::StringOwned(v) => Value::Text(v.to_string()),
+        A::Binary(v) => Value::Blob(v.to_vec()),
+        A::BinaryOwned(v) => Value::Blob(v),
+        v => Value::Text(v.to_string()),
+    })
+}
+
+impl DatabaseDriver for SqliteDriver {
+    fn name(&self) -> &str {
+        "sqlite"
+    }
+
+    fn execute_query(&self, sql: &str, batch_size: usize) -> PolarsResult<RecordBatchStream> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(sql).map_err(to_compute_err)?;
+
+        let mut columns = stmt
+            .columns()
+            .iter()
+            .map(|c| {
+                let dtype = c.decl_type().and_then(dtype_from_decl_type);
+                ColumnBuilder::new(
+                    PlSmallStr::from_str(c.name()),
+                    &dtype.unwrap_or(DataType::Null),
+                    batch_size,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut batches = vec![];
+        let mut height = 0;
+        let mut rows = stmt.query([]).map_err(to_compute_err)?;
+
+        while let Some(row) = rows.next().map_err(to_compute_err)? {
+            for (i, column) in columns.iter_mut().enumerate() {
+                column.append(row.get::<_, Value>(i).map_err(to_compute_err)?);
+            }
+
+            height += 1;
+            if height == batch_size {
+                batches.push(flush_batch(&mut columns, height)?);
+                height = 0;
+            }
+        }
+
+        if height > 0 {
+            batches.push(flush_batch(&mut columns, height)?);
+        }
+
+        // Batches read before a column was widened are cast to its final dtype.
+        let schema = columns
+            .iter()
+            .map(|c| Field::new(c.name.clone(), c.values.dtype()))
+            .collect::<Schema>();
+
+        let compat_level = CompatLevel::newest();
+        let arrow_schema = Arc::new(schema.to_arrow(compat_level));
+
+        let batches = batches.into_iter().map({
+            let arrow_schema = arrow_schema.clone();
+            move |df| {
+                let columns = df
+                    .columns()
+                    .iter()
+                    .zip(schema.iter_values())
+                    .map(|(c, dtype)| {
+                        cast_values(c.as_materialized_series(), dtype).map(Column::from)
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let df = DataFrame::new(df.height(), columns)?;
+
+                Ok(RecordBatch::new(
+                    df.height(),
+                    arrow_schema.clone(),
+                    df.rechunk_into_arrow(compat_level),
+                ))
+            }
+        });
+
+        Ok(RecordBatchStream {
+            schema: arrow_schema,
+            batches: Box::new(batches),
+        })
+    }
+
+    fn bulk_insert(
+        &self,
+        table: &str,
+        df: &DataFrame,
+        if_table_exists: IfTableExists,
+    ) -> PolarsResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(to_compute_err)?;
+        let table = self.quote_identifier(table);
+
+        if if_table_exists == IfTableExists::Replace {
+            tx.execute(&format!("DROP TABLE IF EXISTS {table}"), [])
+                .map_err(to_compute_err)?;
+        }
+
+        let create = match if_table_exists {
+            IfTableExists::Append => "CREATE TABLE IF NOT EXISTS",
+            IfTableExists::Fail | IfTableExists::Replace => "CREATE TABLE",
+        };
+        let column_defs = df
+            .schema()
+            .iter()
+            .map(|(name, dtype)| format!("{} {}", self.quote_identifier(name), sqlite_type(dtype)))
+            .join(", ");
+        tx.execute(&format!("{create} {table} ({column_defs})"), [])
+            .map_err(to_compute_err)?;
+
+        {
+            let column_names = df
+                .get_column_names()
+                .into_iter()
+                .map(|name| self.quote_identifier(name))
+                .join(", ");
+            let placeholders = (1..=df.width()).map(|i| format!("?{i}")).join(", ");
+            let mut stmt = tx
+                .prepare(&format!(
+                    "INSERT INTO {table} ({column_names}) VALUES ({placeholders})"
+                ))
+                .map_err(to_compute_err)?;
+
+            let mut row = Vec::with_capacity(df.width());
+            for idx in 0..df.height() {
+                row.clear();
+                for c in df.columns() {
+                    row.push(any_value_to_sqlite(c.get(idx)?)?);
+                }
+                stmt.execute(params_from_iter(row.iter()))
+                    .map_err(to_compute_err)?;
+            }
+        }
+
+        tx.commit().map_err(to_compute_err)?;
+        Ok(df.height())
+    }
+
+    fn render_starts_with(&self, column: &str, prefix: &str) -> Option<String> {
+        // `LIKE` is case-insensitive in SQLite.
+        Some(format!(
+            "substr({column}, 1, {}) = {}",
+            prefix.chars().count(),
+            quote_string(prefix)
+        ))
+    }
+
+    fn render_ends_with(&self, column: &str, suffix: &str) -> Option<String> {
+        let n = suffix.chars().count();
+
+        Some(if n == 0 {
+            format!("{column} IS NOT NULL")
+        } else {
+            format!("substr({column}, -{n}) = {}", quote_string(suffix))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use polars_core::df;
+    use polars_core::prelude::*;
+    use rusqlite::Connection;
+
+    use super::SqliteDriver;
+    use crate::database::{DatabaseReader, DatabaseWriter, IfTableExists};
+    use crate::predicates::SpecializedColumnPredicate as P;
+
+    #[test]
+    fn test_sqlite_roundtrip() {
+        let driver = Arc::new(SqliteDriver::open_in_memory().unwrap());
+        let df = df!(
+            "id" => [1i64, 2, 3, 4],
+            "name" => [Some("apple"), Some("Avocado"), None, Some("banana")],
+            "price" => [1.5, 2.0, 0.5, 3.25],
+            "ripe" => [true, false, true, true],
+        )
+        .unwrap();
+
+        let writer = || DatabaseWriter::new(driver.clone(), "fruit");
+        assert_eq!(writer().finish(&df).unwrap(), 4);
+        assert!(writer().finish(&df).is_err());
+        assert_eq!(
+            writer()
+                .with_if_table_exists(IfTableExists::Append)
+                .finish(&df)
+                .unwrap(),
+            4
+        );
+
+        let reader = || DatabaseReader::new(driver.clone(), "SELECT * FROM fruit");
+        assert_eq!(reader().finish().unwrap().height(), 8);
+
+        writer()
+            .with_if_table_exists(IfTableExists::Replace)
+            .finish(&df)
+            .unwrap();
+        let out = reader().with_batch_size(3).finish().unwrap();
+        assert!(out.equals_missing(&df));
+
+        let out = reader()
+            .with_columns(Some(vec!["name".into(), "price".into()]))
+            .with_predicates(vec![
+                ("name".into(), P::StartsWith(b"a".as_slice().into())),
+                (
+                    "price".into(),
+                    P::Between(Scalar::from(1.0f64), Scalar::from(5.0f64)),
+                ),
+            ])
+            .with_n_rows(Some(5))
+            .finish()
+            .unwrap();
+        let expected = df!("name" => ["apple"], "price" => [1.5]).unwrap();
+        assert!(out.equals(&expected));
+
+        let out = reader()
+            .with_columns(Some(vec!["id".into()]))
+            .with_predicates(vec![(
+                "name".into(),
+                P::Equal(Scalar::null(DataType::String)),
+            )])
+            .finish()
+            .unwrap();
+        assert!(out.equals(&df!("id" => [3i64]).unwrap()));
+    }
+
+    #[test]
+    fn test_sqlite_mixed_types() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t (a INTEGER, b BOOLEAN, c);
+            INSERT INTO t VALUES (1, 1, NULL), (2, 0, 1), (3, 1, 2.5), ('x', 'y', 'z');",
+        )
+        .unwrap();
+        let driver = Arc::new(SqliteDriver::from_connection(conn));
+        let reader = |batch_size| {
+            DatabaseReader::new(driver.clone(), "SELECT * FROM t").with_batch_size(batch_size)
+        };
+
+        // Columns holding text fall back to strings, also in batches read before the text.
+        let expected = df!(
+            "a" => ["1", "2", "3", "x"],
+            "b" => ["true", "false", "true", "y"],
+            "c" => [None, Some("1"), Some("2.5"), Some("z")],
+        )
+        .unwrap();
+
+        let batches = reader(2)
+            .batched()
+            .unwrap()
+            .batches
+            .map(|batch| batch.unwrap().height())
+            .collect::<Vec<_>>();
+        assert_eq!(batches, [2, 2]);
+
+        for batch_size in [2, 10] {
+            let out = reader(batch_size).finish().unwrap();
+            assert!(out.equals_missing(&expected));
+        }
+
+        let out = reader(2)
+            .with_columns(Some(vec!["a".into()]))
+            .with_n_rows(Some(2))
+            .finish()
+            .unwrap();
+        assert!(out.equals(&df!("a" => [1i64, 2]).unwrap()));
+    }
+}
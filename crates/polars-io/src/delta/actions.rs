@@ -0,0 +1,212 @@
+//! Actions of the Delta transaction log.
+//!
+//! Commit files store one JSON action per line. Checkpoints store the same actions as Parquet
+//! struct columns; these are converted to JSON lines before deserializing, where maps become
+//! lists of `{"key", "value"}` entries.
+use polars_utils::aliases::PlHashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A single line of a commit file, or row of a checkpoint. Exactly one of the fields is set for
+/// the actions that are relevant when reading.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Action {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub add: Option<Add>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remove: Option<Remove>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_data: Option<Metadata>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<Protocol>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_info: Option<CommitInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Protocol {
+    pub min_reader_version: i32,
+    pub min_writer_version: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reader_features: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub writer_features: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub format: Format,
+    pub schema_string: String,
+    #[serde(default)]
+    pub partition_columns: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_string_map")]
+    pub configuration: PlHashMap<String, Option<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<i64>,
+}
+
+/// Encoding of the data files.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Format {
+    pub provider: String,
+    #[serde(default, deserialize_with = "deserialize_string_map")]
+    pub options: PlHashMap<String, Option<String>>,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            provider: "parquet".to_string(),
+            options: PlHashMap::default(),
+        }
+    }
+}
+
+impl Metadata {
+    pub fn configuration_value(&self, key: &str) -> Option<&str> {
+        self.configuration.get(key)?.as_deref()
+    }
+}
+
+/// A data file that is added to the table.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Add {
+    /// URL-encoded path, relative to the table root unless it is an absolute URI.
+    pub path: String,
+    /// Partition values keyed by the physical column name, serialized as strings.
+    #[serde(default, deserialize_with = "deserialize_string_map")]
+    pub partition_values: PlHashMap<String, Option<String>>,
+    pub size: i64,
+    #[serde(default)]
+    pub modification_time: i64,
+    #[serde(default)]
+    pub data_change: bool,
+    /// JSON encoded file statistics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deletion_vector: Option<DeletionVectorDescriptor>,
+}
+
+/// A data file that is logically removed from the table.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Remove {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deletion_timestamp: Option<i64>,
+    #[serde(default)]
+    pub data_change: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_file_metadata: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_string_map")]
+    pub partition_values: PlHashMap<String, Option<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deletion_vector: Option<DeletionVectorDescriptor>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_commit_timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_parameters: Option<PlHashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_blind_append: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_info: Option<String>,
+}
+
+/// Location of the deleted rows of a data file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletionVectorDescriptor {
+    /// `u` (relative path derived from a UUID), `p` (absolute path) or `i` (inline).
+    pub storage_type: String,
+    pub path_or_inline_dv: String,
+    /// Start of the deletion vector within the file; not set for inline deletion vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i32>,
+    pub size_in_bytes: i32,
+    /// Number of deleted rows.
+    pub cardinality: i64,
+}
+
+impl DeletionVectorDescriptor {
+    /// Identifies the deletion vector, used together with the path to identify logical files.
+    pub fn unique_id(&self) -> String {
+        match self.offset {
+            Some(offset) => format!("{}{}@{}", self.storage_type, self.path_or_inline_dv, offset),
+            None => format!("{}{}", self.storage_type, self.path_or_inline_dv),
+        }
+    }
+}
+
+/// Maps are JSON objects in commit files, but lists of `{"key", "value"}` entries when read from
+/// checkpoints.
+fn deserialize_string_map<'de, D>(
+    deserializer: D,
+) -> Result<PlHashMap<String, Option<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MapRepr {
+        Object(PlHashMap<String, Option<String>>),
+        Entries(Vec<MapEntry>),
+    }
+
+    #[derive(Deserialize)]
+    struct MapEntry {
+        key: String,
+        value: Option<String>,
+    }
+
+    Ok(match Option::<MapRepr>::deserialize(deserializer)? {
+        None => PlHashMap::default(),
+        Some(MapRepr::Object(map)) => map,
+        Some(MapRepr::Entries(entries)) => entries.into_iter().map(|e| (e.key, e.value)).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_action() {
+        let commit: Action = serde_json::from_str(
+            r#"{"add":{"path":"a=1/part-0.parquet","partitionValues":{"a":"1"},"size":10,
+            "modificationTime":0,"dataChange":true}}"#,
+        )
+        .unwrap();
+
+        let checkpoint: Action = serde_json::from_str(
+            r#"{"add":{"path":"a=1/part-0.parquet","partitionValues":[{"key":"a","value":"1"}],
+            "size":10,"modificationTime":0,"dataChange":false},"remove":null,"metaData":null}"#,
+        )
+        .unwrap();
+
+        for action in [commit, checkpoint] {
+            let add = action.add.unwrap();
+            assert_eq!(add.path, "a=1/part-0.parquet");
+            assert_eq!(add.partition_values["a"].as_deref(), Some("1"));
+            assert!(action.remove.is_none());
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Loading of Delta deletion vectors.
+//!
+//! Deletion vectors are 64-bit roaring bitmaps (in the portable serialization format) of the
+//! deleted row positions of a data file. They are either stored inline in the transaction log
+//! (Z85-encoded), or in a separate file at an offset.
+use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err};
+
+use super::actions::DeletionVectorDescriptor;
+use super::read_file;
+use crate::cloud::CloudOptions;
+
+const DELETION_VECTOR_MAGIC: u32 = 1681511377;
+
+const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+const SERIAL_COOKIE: u32 = 12347;
+const NO_OFFSET_THRESHOLD: usize = 4;
+const MAX_ARRAY_CONTAINER_SIZE: usize = 4096;
+
+impl DeletionVectorDescriptor {
+    /// Path of the file storing the deletion vector, or `None` if it is stored inline.
+    pub fn absolute_path(&self, table_uri: &str) -> PolarsResult<Option<String>> {
+        let table_uri = table_uri.trim_end_matches('/');
+
+        Ok(match self.storage_type.as_str() {
+            "u" => {
+                let encoded = &self.path_or_inline_dv;
+
+                // A random prefix followed by the Z85-encoded (20 character) UUID.
+                polars_ensure!(
+                    encoded.len() >= 20 && encoded.is_ascii(),
+                    ComputeError: "invalid delta deletion vector path: '{}'", encoded
+                );
+
+                let (prefix, uuid) = encoded.split_at(encoded.len() - 20);
+                let uuid = format_uuid(&z85_decode(uuid)?);
+
+                if prefix.is_empty() {
+                    Some(format!("{table_uri}/deletion_vector_{uuid}.bin"))
+                } else {
+                    Some(format!("{table_uri}/{prefix}/deletion_vector_{uuid}.bin"))
+                }
+            },
+            "p" => Some(self.path_or_inline_dv.clone()),
+            "i" => None,
+            v => polars_bail!(ComputeError: "unknown delta deletion vector storage type: '{}'", v),
+        })
+    }
+
+    /// Loads the deleted row positions, in ascending order.
+    pub async fn load(
+        &self,
+        table_uri: &str,
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<Vec<u64>> {
+        let size = usize::try_from(self.size_in_bytes)
+            .map_err(|_| polars_err!(ComputeError: "invalid deletion vector size"))?;
+
+        let positions = match self.absolute_path(table_uri)? {
+            None => {
+                let bytes = z85_decode(&self.path_or_inline_dv)?;
+                polars_ensure!(
+                    bytes.len() >= size,
+                    ComputeError: "inline deletion vector is shorter than its size"
+                );
+                parse_deletion_vector(&bytes[..size])?
+            },
+            Some(path) => {
+                // The stored deletion vector is prefixed by its size as a big-endian u32.
+                let offset = usize::try_from(self.offset.unwrap_or(1))
+                    .map_err(|_| polars_err!(ComputeError: "invalid deletion vector offset"))?;
+
+                let bytes =
+                    read_file(&path, Some(offset..offset + 4 + size), cloud_options).await?;
+                let stored_size = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+
+                polars_ensure!(
+                    stored_size as usize == size,
+                    ComputeError:
+                    "deletion vector size mismatch in '{}': expected {}, found {}",
+                    path, size, stored_size
+                );
+
+                parse_deletion_vector(&bytes[4..])?
+            },
+        };
+
+        polars_ensure!(
+            positions.len() as i64 == self.cardinality,
+            ComputeError:
+            "deletion vector cardinality mismatch: expected {}, found {}",
+            self.cardinality, positions.len()
+        );
+
+        Ok(positions)
+    }
+}
+
+fn format_uuid(bytes: &[u8]) -> String {
+    let hex = bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Decodes Z85 (ZeroMQ Base85) encoded data.
+fn z85_decode(encoded: &str) -> PolarsResult<Vec<u8>> {
+    const ALPHABET: &[u8; 85] =
+        b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+    polars_ensure!(
+        encoded.len() % 5 == 0,
+        ComputeError: "invalid Z85 length: {}", encoded.len()
+    );
+
+    let mut lookup = [u8::MAX; 256];
+    for (i, c) in ALPHABET.iter().enumerate() {
+        lookup[*c as usize] = i as u8;
+    }
+
+    let mut out = Vec::with_capacity(encoded.len() / 5 * 4);
+
+    for chunk in encoded.as_bytes().chunks_exact(5) {
+        let mut value: u64 = 0;
+
+        for c in chunk {
+            let digit = lookup[*c as usize];
+            polars_ensure!(
+                digit != u8::MAX,
+                ComputeError: "invalid Z85 character: '{}'", *c as char
+            );
+            value = value * 85 + digit as u64;
+        }
+
+        let value = u32::try_from(value)
+            .map_err(|_| polars_err!(ComputeError: "invalid Z85 block: {:?}", chunk))?;
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+
+    Ok(out)
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> PolarsResult<&'a [u8]> {
+        polars_ensure!(
+            self.0.len() >= n,
+            ComputeError: "unexpected end of deletion vector data"
+        );
+        let (out, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(out)
+    }
+
+    fn u16(&mut self) -> PolarsResult<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> PolarsResult<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> PolarsResult<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// Parses a serialized deletion vector: the magic number followed by a 64-bit roaring bitmap.
+fn parse_deletion_vector(bytes: &[u8]) -> PolarsResult<Vec<u64>> {
+    let mut reader = Reader(bytes);
+
+    let magic = reader.u32()?;
+    polars_ensure!(
+        magic == DELETION_VECTOR_MAGIC,
+        ComputeError: "invalid deletion vector magic number: {}", magic
+    );
+
+    let num_bitmaps = reader.u64()?;
+    let mut out = vec![];
+
+    for _ in 0..num_bitmaps {
+        let high = reader.u32()? as u64;
+        parse_roaring_bitmap(&mut reader, high << 32, &mut out)?;
+    }
+
+    Ok(out)
+}
+
+/// Parses a 32-bit roaring bitmap in the portable serialization format, adding `high` to every
+/// value.
+fn parse_roaring_bitmap(reader: &mut Reader, high: u64, out: &mut Vec<u64>) -> PolarsResult<()> {
+    let cookie = reader.u32()?;
+
+    let (num_containers, run_flags, has_offsets) = if cookie & 0xffff == SERIAL_COOKIE {
+        let num_containers = (cookie >> 16) as usize + 1;
+        let run_flags = reader.take(num_containers.div_ceil(8))?;
+        (
+            num_containers,
+            Some(run_flags),
+            num_containers >= NO_OFFSET_THRESHOLD,
+        )
+    } else if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+        (reader.u32()? as usize, None, true)
+    } else {
+        polars_bail!(ComputeError: "invalid roaring bitmap cookie: {}", cookie)
+    };
+
+    let mut headers = Vec::with_capacity(num_containers);
+    for _ in 0..num_containers {
+        let key = reader.u16()? as u64;
+        let cardinality = reader.u16()? as usize + 1;
+        headers.push((key, cardinality));
+    }
+
+    if has_offsets {
+        reader.take(4 * num_containers)?;
+    }
+
+    for (i, (key, cardinality)) in headers.into_iter().enumerate() {
+        let base = high | (key << 16);
+        let is_run = run_flags.is_some_and(|flags| flags[i / 8] & (1 << (i % 8)) != 0);
+
+        if is_run {
+            let num_runs = reader.u16()?;
+            for _ in 0..num_runs {
+                let start = reader.u16()? as u64;
+                let len = reader.u16()? as u64;
+                out.extend((start..=start + len).map(|v| base | v));
+            }
+        } else if cardinality <= MAX_ARRAY_CONTAINER_SIZE {
+            for _ in 0..cardinality {
+                out.push(base | reader.u16()? as u64);
+            }
+        } else {
+            for word_idx in 0..1024u64 {
+                let mut word = reader.u64()?;
+                while word != 0 {
+                    let bit = word.trailing_zeros() as u64;
+                    out.push(base | (word_idx * 64 + bit));
+                    word &= word - 1;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_z85_decode() {
+        assert_eq!(
+            z85_decode("HelloWorld").unwrap(),
+            [0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B]
+        );
+        assert!(z85_decode("Hello").is_ok());
+        assert!(z85_decode("Hell").is_err());
+        assert!(z85_decode("Hell~").is_err());
+    }
+
+    #[test]
+    fn test_parse_deletion_vector() {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&DELETION_VECTOR_MAGIC.to_le_bytes());
+        bytes.extend_from_slice(&2u64.to_le_bytes());
+
+        // High bits 0: an array container with {1, 3} and a run container with 65536..=65538.
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&(SERIAL_COOKIE | (1 << 16)).to_le_bytes());
+        bytes.push(0b10);
+        for (key, cardinality) in [(0u16, 2u16), (1, 3)] {
+            bytes.extend_from_slice(&key.to_le_bytes());
+            bytes.extend_from_slice(&(cardinality - 1).to_le_bytes());
+        }
+        for v in [1u16, 3] {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        for v in [1u16, 0, 2] {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+
+        // High bits 1: an array container with {7}, without run containers.
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&SERIAL_COOKIE_NO_RUNCONTAINER.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&7u16.to_le_bytes());
+
+        assert_eq!(
+            parse_deletion_vector(&bytes).unwrap(),
+            [1, 3, 65536, 65537, 65538, (1 << 32) | 7]
+        );
+    }
+
+    #[test]
+    fn test_absolute_path() {
+        let dv = DeletionVectorDescriptor {
+            storage_type: "u".into(),
+            path_or_inline_dv: "ab^-aqEH.-t@S}K{vb[*k^".into(),
+            offset: Some(4),
+            size_in_bytes: 40,
+            cardinality: 6,
+        };
+
+        assert_eq!(
+            dv.absolute_path("s3://bucket/table/").unwrap().unwrap(),
+            "s3://bucket/table/ab/deletion_vector_d2c639aa-8816-431a-aaf6-d3fe2512ff61.bin"
+        );
+
+        // Too short, and a multi-byte character where the UUID would start.
+        for path_or_inline_dv in ["^-aqEH.-t@S}K{vb[*k", "\u{e9}^-aqEH.-t@S}K{vb[*k"] {
+            let dv = DeletionVectorDescriptor {
+                path_or_inline_dv: path_or_inline_dv.into(),
+                ..dv.clone()
+            };
+            assert!(dv.absolute_path("s3://bucket/table/").is_err());
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Native reading of Delta Lake tables: replaying the `_delta_log` transaction log (JSON commits
+//! and Parquet checkpoints) into the active files of a table version, and loading deletion
+//! vectors.
+pub mod actions;
+pub mod deletion_vector;
+pub mod schema;
+pub mod snapshot;
+pub mod write;
+
+use polars_error::PolarsResult;
+use polars_utils::pl_path::PlRefPath;
+
+use crate::cloud::CloudOptions;
+use crate::utils::byte_source::{ByteSource, DynByteSourceBuilder};
+
+/// Reads a byte range (or the full contents if `None`) of a local or cloud file.
+async fn read_file(
+    path: &str,
+    range: Option<std::ops::Range<usize>>,
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<polars_buffer::Buffer<u8>> {
+    let path = PlRefPath::new(path);
+
+    let byte_source_builder = if path.has_scheme() {
+        DynByteSourceBuilder::ObjectStore
+    } else {
+        DynByteSourceBuilder::Mmap
+    };
+
+    let source = byte_source_builder
+        .try_build_from_path(path, cloud_options, None)
+        .await?;
+
+    let range = match range {
+        Some(v) => v,
+        None => 0..source.get_size().await?,
+    };
+
+    source.get_range(range).await
+}
@@ -0,0 +1,418 @@
+//! Delta table schemas, as stored in the `schemaString` of the table metadata.
+use arrow::datatypes::{ArrowDataType, ArrowSchema, Field as ArrowField, Metadata, TimeUnit};
+use polars_core::schema::iceberg::IcebergSchema;
+use polars_core::schema::{Schema, SchemaExt};
+use polars_error::{PolarsResult, polars_bail, polars_err, to_compute_err};
+use polars_utils::aliases::PlHashMap;
+use polars_utils::pl_str::PlSmallStr;
+use serde::{Deserialize, Serialize};
+
+use super::actions::Metadata as DeltaMetadata;
+
+const PARQUET_FIELD_ID_KEY: &str = "PARQUET:field_id";
+const COLUMN_MAPPING_ID_KEY: &str = "delta.columnMapping.id";
+const COLUMN_MAPPING_PHYSICAL_NAME_KEY: &str = "delta.columnMapping.physicalName";
+const COLUMN_MAPPING_NESTED_IDS_KEY: &str = "delta.columnMapping.nested.ids";
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename = "struct")]
+pub struct StructType {
+    pub fields: Vec<StructField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StructField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub data_type: DeltaType,
+    #[serde(default = "default_true")]
+    pub nullable: bool,
+    #[serde(default)]
+    pub metadata: PlHashMap<String, serde_json::Value>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Delta field type; primitive types are kept as their string representation, e.g. `"long"` or
+/// `"decimal(10,2)"`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum DeltaType {
+    Primitive(String),
+    Nested(NestedType),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum NestedType {
+    Struct {
+        fields: Vec<StructField>,
+    },
+    Array {
+        #[serde(rename = "elementType")]
+        element_type: Box<DeltaType>,
+        #[serde(rename = "containsNull", default = "default_true")]
+        contains_null: bool,
+    },
+    Map {
+        #[serde(rename = "keyType")]
+        key_type: Box<DeltaType>,
+        #[serde(rename = "valueType")]
+        value_type: Box<DeltaType>,
+        #[serde(rename = "valueContainsNull", default = "default_true")]
+        value_contains_null: bool,
+    },
+}
+
+/// How the columns of the data files are mapped to the table schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnMappingMode {
+    /// Columns are matched by name.
+    None,
+    /// Columns are matched by the field ID stored in the Parquet files.
+    Id,
+    /// Columns are matched by their physical name.
+    Name,
+}
+
+impl DeltaMetadata {
+    pub fn schema(&self) -> PolarsResult<StructType> {
+        serde_json::from_str(&self.schema_string).map_err(to_compute_err)
+    }
+
+    pub fn column_mapping_mode(&self) -> PolarsResult<ColumnMappingMode> {
+        Ok(match self.configuration_value("delta.columnMapping.mode") {
+            None | Some("none") => ColumnMappingMode::None,
+            Some("id") => ColumnMappingMode::Id,
+            Some("name") => ColumnMappingMode::Name,
+            Some(v) => polars_bail!(ComputeError: "unknown delta column mapping mode: '{}'", v),
+        })
+    }
+}
+
+impl StructField {
+    /// Name of the column in the data files.
+    pub fn physical_name(&self, mode: ColumnMappingMode) -> PolarsResult<&str> {
+        if mode == ColumnMappingMode::None {
+            return Ok(&self.name);
+        }
+
+        self.metadata
+            .get(COLUMN_MAPPING_PHYSICAL_NAME_KEY)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                polars_err!(
+                    ComputeError:
+                    "delta field '{}' has no physical name in column mapping mode", self.name
+                )
+            })
+    }
+
+    /// Field ID assigned by column mapping.
+    pub fn column_mapping_id(&self) -> PolarsResult<u32> {
+        self.metadata
+            .get(COLUMN_MAPPING_ID_KEY)
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                polars_err!(
+                    ComputeError:
+                    "delta field '{}' has no column mapping ID", self.name
+                )
+            })
+    }
+}
+
+impl StructType {
+    /// Converts to an arrow schema with the logical column names. With column mapping enabled,
+    /// the column mapping IDs are stored in the `PARQUET:field_id` metadata of every field.
+    pub fn to_arrow_schema(&self, mode: ColumnMappingMode) -> PolarsResult<ArrowSchema> {
+        self.fields
+            .iter()
+            .map(|field| {
+                Ok((
+                    PlSmallStr::from_str(&field.name),
+                    struct_field_to_arrow(field, mode)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub fn to_polars_schema(&self) -> PolarsResult<Schema> {
+        Ok(Schema::from_arrow_schema(
+            &self.to_arrow_schema(ColumnMappingMode::None)?,
+        ))
+    }
+
+    /// Schema keyed by the column mapping ID, used to map the physical columns of data files to
+    /// the table schema.
+    pub fn to_iceberg_schema(&self) -> PolarsResult<IcebergSchema> {
+        IcebergSchema::from_arrow_schema(&self.to_arrow_schema(ColumnMappingMode::Id)?)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&StructField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Converts an arrow schema to a Delta schema, without column mapping.
+    pub fn from_arrow_schema(schema: &ArrowSchema) -> PolarsResult<Self> {
+        Ok(Self {
+            fields: schema
+                .iter_values()
+                .map(arrow_field_to_struct_field)
+                .collect::<PolarsResult<_>>()?,
+        })
+    }
+
+    /// Whether any (nested) field has the given primitive type.
+    pub fn contains_primitive_type(&self, name: &str) -> bool {
+        fn contains(data_type: &DeltaType, name: &str) -> bool {
+            match data_type {
+                DeltaType::Primitive(v) => v == name,
+                DeltaType::Nested(NestedType::Struct { fields }) => {
+                    fields.iter().any(|f| contains(&f.data_type, name))
+                },
+                DeltaType::Nested(NestedType::Array { element_type, .. }) => {
+                    contains(element_type, name)
+                },
+                DeltaType::Nested(NestedType::Map {
+                    key_type,
+                    value_type,
+                    ..
+                }) => contains(key_type, name) || contains(value_type, name),
+            }
+        }
+
+        self.fields.iter().any(|f| contains(&f.data_type, name))
+    }
+}
+
+fn arrow_field_to_struct_field(field: &ArrowField) -> PolarsResult<StructField> {
+    Ok(StructField {
+        name: field.name.to_string(),
+        data_type: arrow_to_delta_type(field.dtype())?,
+        nullable: true,
+        metadata: PlHashMap::default(),
+    })
+}
+
+fn arrow_to_delta_type(dtype: &ArrowDataType) -> PolarsResult<DeltaType> {
+    use ArrowDataType as ADT;
+
+    let primitive = |name: &str| DeltaType::Primitive(name.to_string());
+
+    Ok(match dtype {
+        ADT::Boolean => primitive("boolean"),
+        ADT::Int8 => primitive("byte"),
+        ADT::Int16 => primitive("short"),
+        ADT::Int32 => primitive("integer"),
+        ADT::Int64 => primitive("long"),
+        ADT::Float32 => primitive("float"),
+        ADT::Float64 => primitive("double"),
+        ADT::Date32 => primitive("date"),
+        ADT::Timestamp(_, Some(_)) => primitive("timestamp"),
+        ADT::Timestamp(_, None) => primitive("timestamp_ntz"),
+        ADT::Utf8 | ADT::LargeUtf8 | ADT::Utf8View => primitive("string"),
+        ADT::Binary | ADT::LargeBinary | ADT::BinaryView => primitive("binary"),
+        ADT::Decimal(precision, scale) => primitive(&format!("decimal({precision},{scale})")),
+        ADT::List(field) | ADT::LargeList(field) | ADT::FixedSizeList(field, _) => {
+            DeltaType::Nested(NestedType::Array {
+                element_type: Box::new(arrow_to_delta_type(field.dtype())?),
+                contains_null: true,
+            })
+        },
+        ADT::Struct(fields) => DeltaType::Nested(NestedType::Struct {
+            fields: fields
+                .iter()
+                .map(arrow_field_to_struct_field)
+                .collect::<PolarsResult<_>>()?,
+        }),
+        dtype => polars_bail!(ComputeError: "data type {:?} is not supported by delta", dtype),
+    })
+}
+
+fn struct_field_to_arrow(field: &StructField, mode: ColumnMappingMode) -> PolarsResult<ArrowField> {
+    let field_id = match mode {
+        ColumnMappingMode::None => None,
+        ColumnMappingMode::Id | ColumnMappingMode::Name => Some(field.column_mapping_id()?),
+    };
+
+    let nested_ids = field
+        .metadata
+        .get(COLUMN_MAPPING_NESTED_IDS_KEY)
+        .and_then(|v| v.as_object());
+
+    let nested_id = |suffix: &str| -> PolarsResult<Option<u32>> {
+        if field_id.is_none() {
+            return Ok(None);
+        }
+
+        let key = format!("{}.{suffix}", field.physical_name(mode)?);
+
+        Ok(nested_ids
+            .and_then(|ids| ids.get(&key))
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u32::try_from(v).ok()))
+    };
+
+    let dtype = delta_type_to_arrow(&field.data_type, mode, &nested_id, "")?;
+
+    Ok(with_field_id(
+        ArrowField::new(PlSmallStr::from_str(&field.name), dtype, field.nullable),
+        field_id,
+    ))
+}
+
+fn with_field_id(field: ArrowField, field_id: Option<u32>) -> ArrowField {
+    match field_id {
+        None => field,
+        Some(id) => field.with_metadata(Metadata::from([(
+            PlSmallStr::from_static(PARQUET_FIELD_ID_KEY),
+            PlSmallStr::from(id.to_string()),
+        )])),
+    }
+}
+
+/// `nested_id` looks up the column mapping IDs of array elements and map keys / values by their
+/// path relative to the top-level field, e.g. `element` or `key.element`.
+fn delta_type_to_arrow(
+    data_type: &DeltaType,
+    mode: ColumnMappingMode,
+    nested_id: &dyn Fn(&str) -> PolarsResult<Option<u32>>,
+    path: &str,
+) -> PolarsResult<ArrowDataType> {
+    use ArrowDataType as ADT;
+
+    let child_path = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{path}.{name}")
+        }
+    };
+
+    Ok(match data_type {
+        DeltaType::Primitive(name) => match name.as_str() {
+            "boolean" => ADT::Boolean,
+            "byte" => ADT::Int8,
+            "short" => ADT::Int16,
+            "integer" => ADT::Int32,
+            "long" => ADT::Int64,
+            "float" => ADT::Float32,
+            "double" => ADT::Float64,
+            "date" => ADT::Date32,
+            "timestamp" => ADT::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            "timestamp_ntz" => ADT::Timestamp(TimeUnit::Microsecond, None),
+            "string" => ADT::LargeUtf8,
+            "binary" => ADT::LargeBinary,
+            name if name.starts_with("decimal(") => {
+                let Some((precision, scale)) = name
+                    .strip_prefix("decimal(")
+                    .and_then(|s| s.strip_suffix(')'))
+                    .and_then(|s| s.split_once(','))
+                    .and_then(|(p, s)| Some((p.trim().parse().ok()?, s.trim().parse().ok()?)))
+                else {
+                    polars_bail!(ComputeError: "invalid delta decimal type: '{}'", name)
+                };
+                ADT::Decimal(precision, scale)
+            },
+            name => polars_bail!(ComputeError: "unsupported delta type: '{}'", name),
+        },
+        DeltaType::Nested(NestedType::Struct { fields }) => ADT::Struct(
+            fields
+                .iter()
+                .map(|f| struct_field_to_arrow(f, mode))
+                .collect::<PolarsResult<_>>()?,
+        ),
+        DeltaType::Nested(NestedType::Array {
+            element_type,
+            contains_null,
+        }) => {
+            let path = child_path("element");
+            let dtype = delta_type_to_arrow(element_type, mode, nested_id, &path)?;
+
+            ADT::LargeList(Box::new(with_field_id(
+                ArrowField::new(PlSmallStr::from_static("element"), dtype, *contains_null),
+                nested_id(&path)?,
+            )))
+        },
+        // Maps are read as a list of key / value structs.
+        DeltaType::Nested(NestedType::Map {
+            key_type,
+            value_type,
+            value_contains_null,
+        }) => {
+            let key_path = child_path("key");
+            let value_path = child_path("value");
+            let key_dtype = delta_type_to_arrow(key_type, mode, nested_id, &key_path)?;
+            let value_dtype = delta_type_to_arrow(value_type, mode, nested_id, &value_path)?;
+
+            ADT::LargeList(Box::new(ArrowField::new(
+                PlSmallStr::from_static("entries"),
+                ADT::Struct(vec![
+                    with_field_id(
+                        ArrowField::new(PlSmallStr::from_static("key"), key_dtype, false),
+                        nested_id(&key_path)?,
+                    ),
+                    with_field_id(
+                        ArrowField::new(
+                            PlSmallStr::from_static("value"),
+                            value_dtype,
+                            *value_contains_null,
+                        ),
+                        nested_id(&value_path)?,
+                    ),
+                ]),
+                false,
+            )))
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::prelude::DataType;
+
+    use super::*;
+
+    #[test]
+    fn test_schema_with_column_mapping() {
+        let schema: StructType = serde_json::from_str(
+            r#"{"type":"struct","fields":[
+                {"name":"a","type":"long","nullable":true,"metadata":{
+                    "delta.columnMapping.id":1,
+                    "delta.columnMapping.physicalName":"col-5f422f40"}},
+                {"name":"b","type":{"type":"array","elementType":"integer","containsNull":true},
+                 "nullable":true,"metadata":{
+                    "delta.columnMapping.id":2,
+                    "delta.columnMapping.physicalName":"col-8a2c0d1e",
+                    "delta.columnMapping.nested.ids":{"col-8a2c0d1e.element":3}}}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            schema.to_polars_schema().unwrap(),
+            Schema::from_iter([
+                (PlSmallStr::from_static("a"), DataType::Int64),
+                (
+                    PlSmallStr::from_static("b"),
+                    DataType::List(Box::new(DataType::Int32)),
+                ),
+            ])
+        );
+
+        assert_eq!(
+            schema.fields[1]
+                .physical_name(ColumnMappingMode::Name)
+                .unwrap(),
+            "col-8a2c0d1e"
+        );
+
+        let iceberg_schema = schema.to_iceberg_schema().unwrap();
+        assert_eq!(iceberg_schema.keys().copied().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(iceberg_schema[&2].name, "b");
+    }
+}
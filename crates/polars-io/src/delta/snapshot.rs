@@ -0,0 +1,506 @@
+//! Reconstruction of the state of a table version by replaying the transaction log.
+use std::collections::BTreeMap;
+
+use futures::TryStreamExt;
+use object_store::path::Path;
+use polars_core::prelude::PlIndexMap;
+use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err, to_compute_err};
+use polars_utils::pl_path::PlRefPath;
+
+use super::actions::{Action, Add, Metadata, Protocol};
+use crate::cloud::{CloudOptions, PolarsObjectStore, build_object_store};
+use crate::json::{JsonFormat, JsonWriter};
+use crate::parquet::read::ParquetReader;
+use crate::{SerReader, SerWriter};
+
+/// Reader features of the protocol that are supported.
+const SUPPORTED_READER_FEATURES: &[&str] = &[
+    "columnMapping",
+    "deletionVectors",
+    "timestampNtz",
+    "vacuumProtocolCheck",
+];
+
+/// Version of a Delta table to read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeltaTimeTravel {
+    /// The latest version.
+    #[default]
+    Latest,
+    Version(i64),
+    /// The latest version committed at or before the given time, in milliseconds since the
+    /// epoch. The commit time is the modification time of the commit file.
+    Timestamp(i64),
+}
+
+/// State of a Delta table at a version.
+#[derive(Debug, Clone)]
+pub struct DeltaSnapshot {
+    /// Absolute URI of the table root, without a trailing slash.
+    pub table_uri: String,
+    pub version: i64,
+    pub protocol: Protocol,
+    pub metadata: Metadata,
+    /// Active data files.
+    pub files: Vec<Add>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFileKind {
+    Commit,
+    /// Part of a (possibly multi-part) classic checkpoint.
+    Checkpoint {
+        part: u32,
+        num_parts: u32,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct LogFile {
+    version: i64,
+    kind: LogFileKind,
+    location: Path,
+    size: usize,
+    last_modified_ms: i64,
+}
+
+impl DeltaSnapshot {
+    /// Loads the active files of a table version by replaying the `_delta_log` of the table at
+    /// `table_uri`, starting from the latest checkpoint at or before the version.
+    pub async fn load(
+        table_uri: &str,
+        time_travel: DeltaTimeTravel,
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<Self> {
+        Self::try_load(table_uri, time_travel, cloud_options)
+            .await?
+            .ok_or_else(
+                || polars_err!(ComputeError: "no delta transaction log found at '{}'", table_uri),
+            )
+    }
+
+    /// Loads the table version, returns `None` if there is no transaction log, i.e. if the table
+    /// does not exist.
+    pub async fn try_load(
+        table_uri: &str,
+        time_travel: DeltaTimeTravel,
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<Option<Self>> {
+        let table_uri = PlRefPath::new(table_uri);
+        let table_uri = table_uri
+            .to_absolute_path()?
+            .as_str()
+            .trim_end_matches('/')
+            .to_string();
+
+        let (location, store) = build_object_store(
+            PlRefPath::new(format!("{table_uri}/_delta_log/")),
+            cloud_options,
+            false,
+        )
+        .await?;
+
+        let log_files = list_log_files(&store, &Path::from(location.prefix.as_str())).await?;
+
+        let mut commits = BTreeMap::new();
+        let mut checkpoints: BTreeMap<i64, Vec<&LogFile>> = BTreeMap::new();
+
+        for file in &log_files {
+            match file.kind {
+                LogFileKind::Commit => {
+                    commits.insert(file.version, file);
+                },
+                LogFileKind::Checkpoint { .. } => {
+                    checkpoints.entry(file.version).or_default().push(file);
+                },
+            }
+        }
+
+        let Some(latest_version) = commits.keys().chain(checkpoints.keys()).max().copied() else {
+            return Ok(None);
+        };
+
+        let version = match time_travel {
+            DeltaTimeTravel::Latest => latest_version,
+            DeltaTimeTravel::Version(version) => {
+                polars_ensure!(
+                    (0..=latest_version).contains(&version),
+                    ComputeError:
+                    "delta table version {} does not exist, latest version is {}",
+                    version, latest_version
+                );
+                version
+            },
+            DeltaTimeTravel::Timestamp(timestamp) => commits
+                .values()
+                .rev()
+                .find(|f| f.last_modified_ms <= timestamp)
+                .map(|f| f.version)
+                .ok_or_else(|| {
+                    polars_err!(
+                        ComputeError:
+                        "no delta table version was committed at or before timestamp {}",
+                        timestamp
+                    )
+                })?,
+        };
+
+        let checkpoint = checkpoints
+            .range(..=version)
+            .rev()
+            .find_map(|(_, parts)| complete_checkpoint(parts));
+
+        let first_commit_version = checkpoint.as_ref().map_or(0, |parts| parts[0].version + 1);
+
+        let commit_files = (first_commit_version..=version)
+            .map(|v| {
+                commits.get(&v).copied().ok_or_else(|| {
+                    polars_err!(
+                        ComputeError:
+                        "delta transaction log at '{}' is missing the commit for version {}",
+                        table_uri, v
+                    )
+                })
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let read = |file: &LogFile| store.get_range(&file.location, 0..file.size);
+
+        let checkpoint_actions = futures::future::try_join_all(
+            checkpoint
+                .iter()
+                .flatten()
+                .map(|file| async move { read_checkpoint(read(file).await?) }),
+        )
+        .await?;
+
+        let commit_actions = futures::future::try_join_all(
+            commit_files
+                .iter()
+                .map(|file| async move { parse_json_lines(&read(file).await?) }),
+        )
+        .await?;
+
+        let mut protocol = None;
+        let mut metadata = None;
+        let mut files: PlIndexMap<(String, Option<String>), Add> = PlIndexMap::new();
+
+        // Remove actions in checkpoints are tombstones of files that are no longer active.
+        let actions = checkpoint_actions
+            .into_iter()
+            .flatten()
+            .map(|action| (action, false))
+            .chain(
+                commit_actions
+                    .into_iter()
+                    .flatten()
+                    .map(|action| (action, true)),
+            );
+
+        for (action, apply_remove) in actions {
+            if let Some(v) = action.protocol {
+                protocol = Some(v);
+            }
+
+            if let Some(v) = action.meta_data {
+                metadata = Some(v);
+            }
+
+            if let Some(add) = action.add {
+                let key = file_key(&add.path, add.deletion_vector.as_ref())?;
+                files.insert(key, add);
+            }
+
+            if apply_remove && let Some(remove) = action.remove {
+                let key = file_key(&remove.path, remove.deletion_vector.as_ref())?;
+                files.shift_remove(&key);
+            }
+        }
+
+        let (Some(protocol), Some(metadata)) = (protocol, metadata) else {
+            polars_bail!(
+                ComputeError:
+                "delta transaction log at '{}' has no protocol or metadata for version {}",
+                table_uri, version
+            )
+        };
+
+        check_supported(&protocol)?;
+
+        Ok(Some(Self {
+            table_uri,
+            version,
+            protocol,
+            metadata,
+            files: files.into_values().collect(),
+        }))
+    }
+
+    /// Absolute URI of a data file of the table.
+    pub fn file_uri(&self, add: &Add) -> PolarsResult<String> {
+        let path = decode_path(&add.path)?;
+
+        Ok(if PlRefPath::new(&path).has_scheme() {
+            path
+        } else {
+            format!("{}/{}", self.table_uri, path)
+        })
+    }
+
+    /// Whether the protocol enables the given reader feature.
+    pub fn has_reader_feature(&self, feature: &str) -> bool {
+        self.protocol
+            .reader_features
+            .as_ref()
+            .is_some_and(|features| features.iter().any(|f| f == feature))
+    }
+}
+
+fn check_supported(protocol: &Protocol) -> PolarsResult<()> {
+    polars_ensure!(
+        protocol.min_reader_version <= 3,
+        ComputeError:
+        "unsupported delta reader version: {}", protocol.min_reader_version
+    );
+
+    for feature in protocol.reader_features.iter().flatten() {
+        if !SUPPORTED_READER_FEATURES.contains(&feature.as_str()) {
+            polars_bail!(ComputeError: "unsupported delta reader feature: '{}'", feature)
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn decode_path(path: &str) -> PolarsResult<String> {
+    Ok(percent_encoding::percent_decode_str(path)
+        .decode_utf8()
+        .map_err(to_compute_err)?
+        .into_owned())
+}
+
+/// Logical files are identified by their path and deletion vector.
+fn file_key(
+    path: &str,
+    deletion_vector: Option<&super::actions::DeletionVectorDescriptor>,
+) -> PolarsResult<(String, Option<String>)> {
+    Ok((decode_path(path)?, deletion_vector.map(|dv| dv.unique_id())))
+}
+
+/// Returns the parts of the checkpoint in order, if all parts are present.
+fn complete_checkpoint<'a>(files: &[&'a LogFile]) -> Option<Vec<&'a LogFile>> {
+    // There can be multiple checkpoints for the same version with differing numbers of parts.
+    let mut by_num_parts: BTreeMap<u32, BTreeMap<u32, &LogFile>> = BTreeMap::new();
+
+    for file in files {
+        if let LogFileKind::Checkpoint { part, num_parts } = file.kind {
+            by_num_parts
+                .entry(num_parts)
+                .or_default()
+                .insert(part, file);
+        }
+    }
+
+    by_num_parts.into_iter().find_map(|(num_parts, parts)| {
+        (parts.keys().copied().eq(1..=num_parts)).then(|| parts.into_values().collect())
+    })
+}
+
+async fn list_log_files(store: &PolarsObjectStore, prefix: &Path) -> PolarsResult<Vec<LogFile>> {
+    let num_prefix_parts = prefix.parts().count();
+
+    store
+        .exec_with_rebuild_retry_on_err(|store| async move {
+            store
+                .list(Some(prefix))
+                .try_filter_map(|meta| async move {
+                    // Skip files in subdirectories, e.g. `_delta_log/_commits/`.
+                    if meta.location.parts().count() != num_prefix_parts + 1 {
+                        return Ok(None);
+                    }
+
+                    Ok(meta.location.filename().and_then(parse_log_file_name).map(
+                        |(version, kind)| LogFile {
+                            version,
+                            kind,
+                            size: meta.size as usize,
+                            last_modified_ms: meta.last_modified.timestamp_millis(),
+                            location: meta.location,
+                        },
+                    ))
+                })
+                .try_collect::<Vec<_>>()
+                .await
+        })
+        .await
+}
+
+/// Parses the file names of commits (`{version}.json`) and classic checkpoints
+/// (`{version}.checkpoint.parquet` / `{version}.checkpoint.{part}.{num_parts}.parquet`).
+fn parse_log_file_name(name: &str) -> Option<(i64, LogFileKind)> {
+    let (version, rest) = name.split_at_checked(20)?;
+
+    if !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let version = version.parse().ok()?;
+
+    let kind = match rest {
+        ".json" => LogFileKind::Commit,
+        ".checkpoint.parquet" => LogFileKind::Checkpoint {
+            part: 1,
+            num_parts: 1,
+        },
+        rest => {
+            let (part, num_parts) = rest
+                .strip_prefix(".checkpoint.")?
+                .strip_suffix(".parquet")?
+                .split_once('.')?;
+
+            if part.len() != 10 || num_parts.len() != 10 {
+                return None;
+            }
+
+            LogFileKind::Checkpoint {
+                part: part.parse().ok()?,
+                num_parts: num_parts.parse().ok()?,
+            }
+        },
+    };
+
+    Some((version, kind))
+}
+
+pub(super) fn parse_json_lines(bytes: &[u8]) -> PolarsResult<Vec<Action>> {
+    bytes
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.trim_ascii().is_empty())
+        .map(|line| serde_json::from_slice(line).map_err(to_compute_err))
+        .collect()
+}
+
+/// Reads the actions of a checkpoint part by converting the action columns to JSON lines.
+fn read_checkpoint(bytes: polars_buffer::Buffer<u8>) -> PolarsResult<Vec<Action>> {
+    let df = ParquetReader::new(std::io::Cursor::new(bytes)).finish()?;
+
+    let columns = ["add", "remove", "metaData", "protocol"]
+        .into_iter()
+        .filter(|name| df.schema().contains(name))
+        .collect::<Vec<_>>();
+
+    let mut df = df.select(columns)?;
+
+    let mut buf = vec![];
+    JsonWriter::new(&mut buf)
+        .with_json_format(JsonFormat::JsonLines)
+        .finish(&mut df)?;
+
+    parse_json_lines(&buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+    use crate::pl_async::get_runtime;
+
+    #[test]
+    fn test_parse_log_file_name() {
+        assert_eq!(
+            parse_log_file_name("00000000000000000010.json"),
+            Some((10, LogFileKind::Commit))
+        );
+        assert_eq!(
+            parse_log_file_name("00000000000000000010.checkpoint.parquet"),
+            Some((
+                10,
+                LogFileKind::Checkpoint {
+                    part: 1,
+                    num_parts: 1
+                }
+            ))
+        );
+        assert_eq!(
+            parse_log_file_name("00000000000000000010.checkpoint.0000000002.0000000003.parquet"),
+            Some((
+                10,
+                LogFileKind::Checkpoint {
+                    part: 2,
+                    num_parts: 3
+                }
+            ))
+        );
+        assert_eq!(parse_log_file_name("_last_checkpoint"), None);
+        assert_eq!(parse_log_file_name("00000000000000000010.crc"), None);
+    }
+
+    #[test]
+    fn test_replay_and_time_travel() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_dir = dir.path().join("_delta_log");
+        std::fs::create_dir(&log_dir).unwrap();
+
+        let schema = r#"{\"type\":\"struct\",\"fields\":[{\"name\":\"a\",\"type\":\"long\",\"nullable\":true,\"metadata\":{}}]}"#;
+
+        let commits = [
+            format!(
+                "{}\n{}\n{}\n",
+                r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#,
+                format_args!(
+                    r#"{{"metaData":{{"id":"t","format":{{"provider":"parquet","options":{{}}}},"schemaString":"{schema}","partitionColumns":[],"configuration":{{}}}}}}"#
+                ),
+                r#"{"add":{"path":"part%20-0.parquet","partitionValues":{},"size":1,"modificationTime":0,"dataChange":true}}"#,
+            ),
+            r#"{"add":{"path":"part-1.parquet","partitionValues":{},"size":1,"modificationTime":0,"dataChange":true}}"#
+                .to_string(),
+            format!(
+                "{}\n{}\n",
+                r#"{"remove":{"path":"part -0.parquet","dataChange":true}}"#,
+                r#"{"add":{"path":"part-2.parquet","partitionValues":{},"size":1,"modificationTime":0,"dataChange":true}}"#,
+            ),
+        ];
+
+        for (version, commit) in commits.iter().enumerate() {
+            let path = log_dir.join(format!("{version:020}.json"));
+            std::fs::write(&path, commit).unwrap();
+
+            let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1000 * (version as u64 + 1));
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(mtime)
+                .unwrap();
+        }
+
+        let table_uri = dir.path().to_str().unwrap();
+
+        let load =
+            |time_travel| get_runtime().block_on(DeltaSnapshot::load(table_uri, time_travel, None));
+
+        let file_names = |snapshot: &DeltaSnapshot| {
+            snapshot
+                .files
+                .iter()
+                .map(|add| snapshot.file_uri(add).unwrap())
+                .map(|uri| uri.rsplit('/').next().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let snapshot = load(DeltaTimeTravel::Latest).unwrap();
+        assert_eq!(snapshot.version, 2);
+        assert_eq!(file_names(&snapshot), ["part-1.parquet", "part-2.parquet"]);
+        assert_eq!(snapshot.metadata.schema().unwrap().fields[0].name, "a");
+
+        let snapshot = load(DeltaTimeTravel::Version(1)).unwrap();
+        assert_eq!(file_names(&snapshot), ["part -0.parquet", "part-1.parquet"]);
+
+        let snapshot = load(DeltaTimeTravel::Timestamp(2_500_000)).unwrap();
+        assert_eq!(snapshot.version, 1);
+
+        assert!(load(DeltaTimeTravel::Timestamp(500_000)).is_err());
+        assert!(load(DeltaTimeTravel::Version(3)).is_err());
+    }
+}
@@ -0,0 +1,723 @@
+//! Writing to Delta tables: building the `add` actions of written data files, and committing
+//! them to the transaction log.
+//!
+//! Commits are made atomic by creating the commit file of the next version only if it does not
+//! exist yet. If another writer committed that version first, the winning commit is checked for
+//! conflicts with this write, and the commit is retried at the following version.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use arrow::array::{Array, PrimitiveArray, Utf8ViewArray};
+use arrow::datatypes::{ArrowDataType, ArrowSchema, TimeUnit};
+use arrow::temporal_conversions;
+use object_store::path::Path;
+use object_store::{PutMode, PutOptions, PutPayload};
+use polars_core::prelude::{PlHashMap, PlHashSet};
+use polars_error::{PolarsResult, polars_bail, polars_ensure, to_compute_err};
+use polars_parquet::read::statistics::deserialize_all;
+use polars_utils::pl_path::PlRefPath;
+use serde_json::{Map, Value};
+
+use super::actions::{Action, Add, CommitInfo, Metadata, Protocol, Remove};
+use super::schema::{ColumnMappingMode, StructType};
+use super::snapshot::{DeltaSnapshot, DeltaTimeTravel, decode_path, parse_json_lines};
+use crate::cloud::{CloudOptions, build_object_store};
+use crate::parquet::read::{ParquetObjectStore, infer_schema};
+use crate::utils::random_uuid;
+
+/// Writer features of the protocol that are supported.
+const SUPPORTED_WRITER_FEATURES: &[&str] = &[
+    "appendOnly",
+    "columnMapping",
+    "deletionVectors",
+    "timestampNtz",
+    "vacuumProtocolCheck",
+];
+
+const MAX_COMMIT_ATTEMPTS: usize = 16;
+
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Characters to percent-encode in the relative paths of `add` actions.
+const PATH_ENCODE_CHARSET: &percent_encoding::AsciiSet = &percent_encoding::CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Selects files by partition value: the value of `column` must be one of `values`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeltaPartitionFilter {
+    pub column: String,
+    pub values: Vec<Option<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeltaWriteMode {
+    Append,
+    /// Replace all data of the table.
+    Overwrite,
+    /// Replace the data of the partitions matching all of the filters. The written data must be
+    /// within these partitions.
+    ReplaceWhere(Vec<DeltaPartitionFilter>),
+}
+
+/// A write of data files to a Delta table.
+#[derive(Debug, Clone)]
+pub struct DeltaTableWrite {
+    /// Absolute URI of the table root, without a trailing slash.
+    pub table_uri: String,
+    /// Version of the table the write is based on, or `None` if the write creates the table.
+    pub read_version: Option<i64>,
+    pub mode: DeltaWriteMode,
+    /// Delta schema of the table as JSON, only used when the write creates the table.
+    pub schema_string: String,
+    pub partition_columns: Vec<String>,
+}
+
+impl DeltaSnapshot {
+    /// Checks that this writer supports the table protocol.
+    pub fn check_writable(&self) -> PolarsResult<()> {
+        let protocol = &self.protocol;
+
+        polars_ensure!(
+            protocol.min_writer_version <= 7,
+            ComputeError:
+            "unsupported delta writer version: {}", protocol.min_writer_version
+        );
+
+        // Writer versions 3 to 6 imply features (e.g. check constraints, generated columns) that
+        // would have to be enforced on the written data.
+        polars_ensure!(
+            !(3..7).contains(&protocol.min_writer_version),
+            ComputeError:
+            "unsupported delta writer version: {}", protocol.min_writer_version
+        );
+
+        for feature in protocol.writer_features.iter().flatten() {
+            if !SUPPORTED_WRITER_FEATURES.contains(&feature.as_str()) {
+                polars_bail!(ComputeError: "unsupported delta writer feature: '{}'", feature)
+            }
+        }
+
+        polars_ensure!(
+            self.metadata.column_mapping_mode()? == ColumnMappingMode::None,
+            ComputeError:
+            "writing to delta tables with column mapping is not yet supported"
+        );
+
+        Ok(())
+    }
+
+    fn is_append_only(&self) -> bool {
+        self.metadata.configuration_value("delta.appendOnly") == Some("true")
+    }
+}
+
+impl DeltaTableWrite {
+    /// Commits the written data files to the table. Returns the committed version.
+    pub async fn commit(
+        &self,
+        data_file_paths: &[&str],
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<i64> {
+        let add_actions = futures::future::try_join_all(data_file_paths.iter().map(|path| {
+            parquet_add_action(
+                &self.table_uri,
+                path,
+                &self.partition_columns,
+                cloud_options,
+            )
+        }))
+        .await?;
+
+        if let DeltaWriteMode::ReplaceWhere(filters) = &self.mode {
+            for add in &add_actions {
+                polars_ensure!(
+                    matches_filters(&add.partition_values, filters),
+                    ComputeError:
+                    "written data file '{}' does not match the replaceWhere predicate", add.path
+                );
+            }
+        }
+
+        let now = timestamp_ms();
+
+        let (table_actions, remove_actions) = match self.read_version {
+            None => (self.new_table_actions(now)?, vec![]),
+            Some(version) => {
+                let snapshot = DeltaSnapshot::load(
+                    &self.table_uri,
+                    DeltaTimeTravel::Version(version),
+                    cloud_options,
+                )
+                .await?;
+
+                snapshot.check_writable()?;
+
+                let removed = snapshot
+                    .files
+                    .iter()
+                    .filter(|add| match &self.mode {
+                        DeltaWriteMode::Append => false,
+                        DeltaWriteMode::Overwrite => true,
+                        DeltaWriteMode::ReplaceWhere(filters) => {
+                            matches_filters(&add.partition_values, filters)
+                        },
+                    })
+                    .map(|add| remove_action(add, now))
+                    .collect::<Vec<_>>();
+
+                polars_ensure!(
+                    removed.is_empty() || !snapshot.is_append_only(),
+                    ComputeError:
+                    "cannot remove data from append-only delta table '{}'", self.table_uri
+                );
+
+                (vec![], removed)
+            },
+        };
+
+        let removed_paths = remove_actions
+            .iter()
+            .map(|remove| decode_path(&remove.path))
+            .collect::<PolarsResult<PlHashSet<_>>>()?;
+
+        let mut commit = String::new();
+
+        let actions = table_actions
+            .into_iter()
+            .chain(std::iter::once(Action {
+                commit_info: Some(self.commit_info(now)),
+                ..Default::default()
+            }))
+            .chain(remove_actions.into_iter().map(|remove| Action {
+                remove: Some(remove),
+                ..Default::default()
+            }))
+            .chain(add_actions.into_iter().map(|add| Action {
+                add: Some(add),
+                ..Default::default()
+            }));
+
+        for action in actions {
+            commit.push_str(&serde_json::to_string(&action).map_err(to_compute_err)?);
+            commit.push('\n');
+        }
+
+        let commit = bytes::Bytes::from(commit);
+
+        let (location, store) = build_object_store(
+            PlRefPath::new(format!("{}/_delta_log/", self.table_uri)),
+            cloud_options,
+            false,
+        )
+        .await?;
+
+        let log_dir = location.prefix.trim_end_matches('/').to_string();
+        let commit_path = |version: i64| Path::from(format!("{log_dir}/{version:020}.json"));
+
+        let mut version = self.read_version.map_or(0, |v| v + 1);
+
+        for _ in 0..MAX_COMMIT_ATTEMPTS {
+            let path = commit_path(version);
+
+            let result = store
+                .to_dyn_object_store()
+                .await
+                .put_opts(
+                    &path,
+                    PutPayload::from_bytes(commit.clone()),
+                    PutOptions {
+                        mode: PutMode::Create,
+                        ..Default::default()
+                    },
+                )
+                .await;
+
+            match result {
+                Ok(_) => return Ok(version),
+                Err(object_store::Error::AlreadyExists { .. }) => {},
+                Err(e) => return Err(to_compute_err(e)),
+            }
+
+            polars_ensure!(
+                self.read_version.is_some(),
+                ComputeError:
+                "delta table '{}' was concurrently created", self.table_uri
+            );
+
+            let meta = store.head(&path).await?;
+            let winning_commit = store.get_range(&path, 0..meta.size as usize).await?;
+
+            check_conflict(
+                &self.mode,
+                &removed_paths,
+                &parse_json_lines(&winning_commit)?,
+            )
+            .map_err(|e| {
+                e.wrap_msg(|msg| {
+                    format!("conflict with concurrent commit of version {version}: {msg}")
+                })
+            })?;
+
+            version += 1;
+        }
+
+        polars_bail!(
+            ComputeError:
+            "failed to commit to delta table '{}' after {} attempts",
+            self.table_uri, MAX_COMMIT_ATTEMPTS
+        )
+    }
+
+    fn new_table_actions(&self, now: i64) -> PolarsResult<Vec<Action>> {
+        let schema: StructType =
+            serde_json::from_str(&self.schema_string).map_err(to_compute_err)?;
+
+        let protocol = if schema.contains_primitive_type("timestamp_ntz") {
+            Protocol {
+                min_reader_version: 3,
+                min_writer_version: 7,
+                reader_features: Some(vec!["timestampNtz".to_string()]),
+                writer_features: Some(vec!["timestampNtz".to_string()]),
+            }
+        } else {
+            Protocol {
+                min_reader_version: 1,
+                min_writer_version: 2,
+                reader_features: None,
+                writer_features: None,
+            }
+        };
+
+        let metadata = Metadata {
+            id: random_uuid(),
+            name: None,
+            format: Default::default(),
+            schema_string: self.schema_string.clone(),
+            partition_columns: self.partition_columns.clone(),
+            configuration: PlHashMap::default(),
+            created_time: Some(now),
+        };
+
+        Ok(vec![
+            Action {
+                protocol: Some(protocol),
+                ..Default::default()
+            },
+            Action {
+                meta_data: Some(metadata),
+                ..Default::default()
+            },
+        ])
+    }
+
+    fn commit_info(&self, now: i64) -> CommitInfo {
+        let mut operation_parameters = PlHashMap::default();
+
+        let mode = match &self.mode {
+            DeltaWriteMode::Append => "Append",
+            DeltaWriteMode::Overwrite | DeltaWriteMode::ReplaceWhere(_) => "Overwrite",
+        };
+
+        operation_parameters.insert("mode".to_string(), mode.to_string());
+        operation_parameters.insert(
+            "partitionBy".to_string(),
+            serde_json::to_string(&self.partition_columns).unwrap(),
+        );
+
+        CommitInfo {
+            timestamp: Some(now),
+            in_commit_timestamp: None,
+            operation: Some(
+                if self.read_version.is_none() {
+                    "CREATE TABLE"
+                } else {
+                    "WRITE"
+                }
+                .to_string(),
+            ),
+            operation_parameters: Some(operation_parameters),
+            is_blind_append: Some(self.mode == DeltaWriteMode::Append),
+            engine_info: Some(format!("polars/{}", env!("CARGO_PKG_VERSION"))),
+        }
+    }
+}
+
+fn timestamp_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64)
+}
+
+fn remove_action(add: &Add, now: i64) -> Remove {
+    Remove {
+        path: add.path.clone(),
+        deletion_timestamp: Some(now),
+        data_change: true,
+        extended_file_metadata: Some(true),
+        partition_values: add.partition_values.clone(),
+        size: Some(add.size),
+        deletion_vector: add.deletion_vector.clone(),
+    }
+}
+
+fn matches_filters(
+    partition_values: &PlHashMap<String, Option<String>>,
+    filters: &[DeltaPartitionFilter],
+) -> bool {
+    filters.iter().all(|filter| {
+        let value = partition_values.get(&filter.column).cloned().flatten();
+        filter.values.contains(&value)
+    })
+}
+
+/// Checks whether a commit of another writer that won the race for a version conflicts with this
+/// write.
+fn check_conflict(
+    mode: &DeltaWriteMode,
+    removed_paths: &PlHashSet<String>,
+    winning_actions: &[Action],
+) -> PolarsResult<()> {
+    for action in winning_actions {
+        polars_ensure!(
+            action.protocol.is_none() && action.meta_data.is_none(),
+            ComputeError: "the table protocol or metadata was changed"
+        );
+
+        if let Some(remove) = &action.remove {
+            let path = decode_path(&remove.path)?;
+
+            polars_ensure!(
+                !removed_paths.contains(&path),
+                ComputeError: "data file '{}' was concurrently removed", path
+            );
+        }
+
+        if let Some(add) = &action.add {
+            let conflicts = match mode {
+                DeltaWriteMode::Append => false,
+                DeltaWriteMode::Overwrite => true,
+                DeltaWriteMode::ReplaceWhere(filters) => {
+                    matches_filters(&add.partition_values, filters)
+                },
+            };
+
+            polars_ensure!(
+                !conflicts,
+                ComputeError:
+                "data file '{}' was concurrently added to the replaced data", add.path
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the `add` action of a written Parquet data file, reading the statistics from its
+/// footer. Partition values are parsed from the hive-style path of the file.
+pub async fn parquet_add_action(
+    table_uri: &str,
+    path: &str,
+    partition_columns: &[String],
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<Add> {
+    let table_uri = table_uri.trim_end_matches('/');
+
+    let Some(relative_path) = path
+        .strip_prefix(table_uri)
+        .and_then(|p| p.strip_prefix('/'))
+    else {
+        polars_bail!(
+            ComputeError:
+            "data file '{}' is not within the delta table '{}'", path, table_uri
+        )
+    };
+
+    let partition_values = parse_partition_values(relative_path, partition_columns)?;
+
+    let mut reader =
+        ParquetObjectStore::from_uri(PlRefPath::new(path), cloud_options, None).await?;
+    let size = reader.length().await? as i64;
+    let metadata = reader.get_metadata().await?.clone();
+
+    let stats = file_statistics(
+        &infer_schema(&metadata)?,
+        &metadata.row_groups,
+        metadata.num_rows,
+    )?;
+
+    Ok(Add {
+        path: percent_encoding::utf8_percent_encode(relative_path, PATH_ENCODE_CHARSET).to_string(),
+        partition_values,
+        size,
+        modification_time: timestamp_ms(),
+        data_change: true,
+        stats: Some(stats),
+        deletion_vector: None,
+    })
+}
+
+fn parse_partition_values(
+    relative_path: &str,
+    partition_columns: &[String],
+) -> PolarsResult<PlHashMap<String, Option<String>>> {
+    let mut partition_values = PlHashMap::with_capacity(partition_columns.len());
+
+    for segment in relative_path.split('/') {
+        let Some((key, value)) = segment.split_once('=') else {
+            continue;
+        };
+
+        if !partition_columns.iter().any(|c| c == key) {
+            continue;
+        }
+
+        let value = decode_path(value)?;
+        let value = (value != HIVE_DEFAULT_PARTITION).then_some(value);
+
+        partition_values.insert(key.to_string(), value);
+    }
+
+    for column in partition_columns {
+        polars_ensure!(
+            partition_values.contains_key(column),
+            ComputeError:
+            "partition value of column '{}' not found in path '{}'", column, relative_path
+        );
+    }
+
+    Ok(partition_values)
+}
+
+/// Builds the JSON statistics of a data file from the row group statistics. Statistics are only
+/// collected for top-level columns of primitive types.
+fn file_statistics(
+    schema: &ArrowSchema,
+    row_groups: &[polars_parquet::read::RowGroupMetadata],
+    num_rows: usize,
+) -> PolarsResult<String> {
+    let mut min_values = Map::new();
+    let mut max_values = Map::new();
+    let mut null_count = Map::new();
+
+    if !row_groups.is_empty() {
+        for field in schema.iter_values() {
+            let Some(&[leaf_idx]) = row_groups[0].columns_idxs_under_root_iter(field.name.as_str())
+            else {
+                continue;
+            };
+
+            let Some(stats) = deserialize_all(field, row_groups, leaf_idx)? else {
+                continue;
+            };
+
+            if stats.null_count.null_count() == 0 {
+                let count: u64 = stats.null_count.values_iter().map(|v| *v as u64).sum();
+                null_count.insert(field.name.to_string(), Value::from(count));
+            }
+
+            let (min, max) = min_max_values(field.dtype(), &*stats.min_value, &*stats.max_value);
+
+            if let Some(v) = min {
+                min_values.insert(field.name.to_string(), v);
+            }
+
+            if let Some(v) = max {
+                max_values.insert(field.name.to_string(), v);
+            }
+        }
+    }
+
+    let mut stats = Map::new();
+    stats.insert("numRecords".into(), Value::from(num_rows as u64));
+    stats.insert("minValues".into(), Value::Object(min_values));
+    stats.insert("maxValues".into(), Value::Object(max_values));
+    stats.insert("nullCount".into(), Value::Object(null_count));
+
+    serde_json::to_string(&Value::Object(stats)).map_err(to_compute_err)
+}
+
+/// Reduces the per row group min / max statistics to the JSON min / max values of the file.
+/// Statistics that are missing in any row group are not collected.
+fn min_max_values(
+    dtype: &ArrowDataType,
+    min_arr: &dyn Array,
+    max_arr: &dyn Array,
+) -> (Option<Value>, Option<Value>) {
+    if min_arr.null_count() > 0 || max_arr.null_count() > 0 {
+        return (None, None);
+    }
+
+    fn primitive<T: arrow::types::NativeType + PartialOrd>(
+        min_arr: &dyn Array,
+        max_arr: &dyn Array,
+        to_json: impl Fn(T) -> Option<Value>,
+    ) -> (Option<Value>, Option<Value>) {
+        let reduce = |arr: &dyn Array, take_min: bool| {
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<T>>()?;
+            let v = arr.values_iter().copied().reduce(|a, b| {
+                let a_wins = if take_min { a <= b } else { a >= b };
+                if a_wins { a } else { b }
+            })?;
+            to_json(v)
+        };
+
+        (reduce(min_arr, true), reduce(max_arr, false))
+    }
+
+    use ArrowDataType as ADT;
+
+    match dtype {
+        ADT::Int8 => primitive::<i8>(min_arr, max_arr, |v| Some(v.into())),
+        ADT::Int16 => primitive::<i16>(min_arr, max_arr, |v| Some(v.into())),
+        ADT::Int32 => primitive::<i32>(min_arr, max_arr, |v| Some(v.into())),
+        ADT::Int64 => primitive::<i64>(min_arr, max_arr, |v| Some(v.into())),
+        ADT::Float32 => primitive::<f32>(min_arr, max_arr, |v| {
+            (!v.is_nan()).then(|| Value::from(v as f64))
+        }),
+        ADT::Float64 => {
+            primitive::<f64>(min_arr, max_arr, |v| (!v.is_nan()).then(|| Value::from(v)))
+        },
+        ADT::Date32 => primitive::<i32>(min_arr, max_arr, |v| {
+            let date = temporal_conversions::date32_to_date_opt(v)?;
+            Some(date.format("%Y-%m-%d").to_string().into())
+        }),
+        ADT::Timestamp(unit, tz) => {
+            let unit = *unit;
+            // Timezone-aware timestamps are stored in UTC, `timestamp_ntz` values have no offset.
+            let format = if tz.is_some() {
+                "%Y-%m-%dT%H:%M:%S%.3fZ"
+            } else {
+                "%Y-%m-%dT%H:%M:%S%.3f"
+            };
+
+            primitive::<i64>(min_arr, max_arr, move |v| {
+                let datetime = match unit {
+                    TimeUnit::Second => temporal_conversions::timestamp_s_to_datetime_opt(v),
+                    TimeUnit::Millisecond => temporal_conversions::timestamp_ms_to_datetime_opt(v),
+                    TimeUnit::Microsecond => temporal_conversions::timestamp_us_to_datetime_opt(v),
+                    TimeUnit::Nanosecond => temporal_conversions::timestamp_ns_to_datetime_opt(v),
+                }?;
+                Some(datetime.format(format).to_string().into())
+            })
+        },
+        ADT::Utf8 | ADT::LargeUtf8 | ADT::Utf8View => {
+            let reduce = |arr: &dyn Array, take_min: bool| {
+                let arr = arr.as_any().downcast_ref::<Utf8ViewArray>()?;
+                let v = if take_min {
+                    arr.values_iter().min()
+                } else {
+                    arr.values_iter().max()
+                }?;
+                Some(Value::from(v))
+            };
+
+            (reduce(min_arr, true), reduce(max_arr, false))
+        },
+        _ => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(path: &str, partition_values: &[(&str, Option<&str>)]) -> Action {
+        Action {
+            add: Some(Add {
+                path: path.to_string(),
+                partition_values: partition_values
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.map(str::to_string)))
+                    .collect(),
+                size: 1,
+                modification_time: 0,
+                data_change: true,
+                stats: None,
+                deletion_vector: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_partition_values() {
+        let columns = ["a".to_string(), "b".to_string()];
+
+        let values = parse_partition_values(
+            "a=x%2Fy/b=__HIVE_DEFAULT_PARTITION__/part-00000.parquet",
+            &columns,
+        )
+        .unwrap();
+
+        assert_eq!(values["a"].as_deref(), Some("x/y"));
+        assert_eq!(values["b"], None);
+
+        assert!(parse_partition_values("a=1/part-00000.parquet", &columns).is_err());
+    }
+
+    #[test]
+    fn test_check_conflict() {
+        let replace_where = DeltaWriteMode::ReplaceWhere(vec![DeltaPartitionFilter {
+            column: "a".to_string(),
+            values: vec![Some("1".to_string())],
+        }]);
+
+        let removed_paths = PlHashSet::from_iter(["a=1/part-0.parquet".to_string()]);
+
+        // Concurrent appends to other partitions do not conflict.
+        let winning = [add("a=2/part-1.parquet", &[("a", Some("2"))])];
+        assert!(check_conflict(&DeltaWriteMode::Append, &removed_paths, &winning).is_ok());
+        assert!(check_conflict(&replace_where, &removed_paths, &winning).is_ok());
+        assert!(check_conflict(&DeltaWriteMode::Overwrite, &removed_paths, &winning).is_err());
+
+        let winning = [add("a=1/part-1.parquet", &[("a", Some("1"))])];
+        assert!(check_conflict(&replace_where, &removed_paths, &winning).is_err());
+
+        let winning = [Action {
+            remove: Some(Remove {
+                path: "a=1/part-0.parquet".to_string(),
+                deletion_timestamp: None,
+                data_change: true,
+                extended_file_metadata: None,
+                partition_values: PlHashMap::default(),
+                size: None,
+                deletion_vector: None,
+            }),
+            ..Default::default()
+        }];
+        assert!(check_conflict(&replace_where, &removed_paths, &winning).is_err());
+        assert!(check_conflict(&DeltaWriteMode::Append, &PlHashSet::new(), &winning).is_ok());
+    }
+
+    #[test]
+    fn test_timestamp_min_max_values() {
+        let min_arr = PrimitiveArray::<i64>::from_vec(vec![1_500, 500]);
+        let max_arr = PrimitiveArray::<i64>::from_vec(vec![2_000, 86_400_000]);
+
+        let dtype = ArrowDataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()));
+        assert_eq!(
+            min_max_values(&dtype, &min_arr, &max_arr),
+            (
+                Some("1970-01-01T00:00:00.500Z".into()),
+                Some("1970-01-02T00:00:00.000Z".into())
+            )
+        );
+
+        let dtype = ArrowDataType::Timestamp(TimeUnit::Millisecond, None);
+        assert_eq!(
+            min_max_values(&dtype, &min_arr, &max_arr),
+            (
+                Some("1970-01-01T00:00:00.500".into()),
+                Some("1970-01-02T00:00:00.000".into())
+            )
+        );
+    }
+}
@@ -5,7 +5,9 @@ mod eviction;
 mod file_fetcher;
 mod file_lock;
 mod metadata;
+mod range_cache;
 mod utils;
 pub use cache::{FILE_CACHE, get_env_file_cache_ttl};
 pub use entry::FileCacheEntry;
+pub use range_cache::{RANGE_CACHE, RangeCache, RangeCacheKey};
 pub use utils::{FILE_CACHE_PREFIX, init_entries_from_uri_list};
@@ -0,0 +1,585 @@
+//! Block-granular disk cache for byte ranges of remote files.
+//!
+//! Unlike the file cache, which downloads whole files, this caches fixed-size blocks of remote
+//! files as they are read, so that repeatedly reading the same parts of large files (e.g. the same
+//! row groups of a Parquet file) does not re-download them.
+//!
+//! Blocks are stored under `{FILE_CACHE_PREFIX}/r/`, keyed by the URI, the version of the file
+//! (ETag or last-modified time) and the block index. Blocks are written to a temporary file and
+//! renamed into place, so that readers never observe partial blocks. The total size of the cache
+//! is bounded by evicting the least recently accessed blocks. Writers hold a shared lock on the
+//! cache directory while eviction holds an exclusive lock, so that processes sharing the cache do
+//! not evict blocks that are being written.
+//!
+//! The cache is enabled by setting `POLARS_RANGE_CACHE_MAX_SIZE` to the maximum size in bytes.
+//! The block size can be configured with `POLARS_RANGE_CACHE_BLOCK_SIZE` (default 1 MiB).
+use std::collections::BTreeSet;
+use std::io::{Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+use polars_buffer::Buffer;
+use polars_core::config;
+use polars_error::{PolarsResult, polars_bail, polars_ensure, to_compute_err};
+use polars_utils::aliases::PlHashMap;
+
+use super::file_lock::FileLock;
+use super::utils::FILE_CACHE_PREFIX;
+use crate::metrics::OptIOMetrics;
+use crate::path_utils::ensure_directory_init;
+use crate::pl_async;
+
+/// Sub-directory of the file cache prefix holding the cached blocks.
+const RANGE_CACHE_DIR: &str = "r";
+const LOCK_FILE_NAME: &str = ".lock";
+const DEFAULT_BLOCK_SIZE: usize = 1024 * 1024;
+
+pub static RANGE_CACHE: LazyLock<Option<RangeCache>> = LazyLock::new(|| {
+    let max_size = std::env::var("POLARS_RANGE_CACHE_MAX_SIZE")
+        .ok()?
+        .parse::<u64>()
+        .expect("integer");
+
+    if max_size == 0 {
+        return None;
+    }
+
+    let block_size = std::env::var("POLARS_RANGE_CACHE_BLOCK_SIZE")
+        .map(|x| x.parse::<usize>().expect("integer"))
+        .unwrap_or(DEFAULT_BLOCK_SIZE);
+
+    assert!(
+        block_size > 0,
+        "POLARS_RANGE_CACHE_BLOCK_SIZE must be non-zero"
+    );
+
+    let dir = FILE_CACHE_PREFIX.as_std_path().join(RANGE_CACHE_DIR);
+
+    if let Err(err) = ensure_directory_init(&dir) {
+        panic!(
+            "failed to create range cache directory: path = {}, err = {}",
+            dir.display(),
+            err
+        )
+    }
+
+    if config::verbose() {
+        eprintln!(
+            "[range_cache] enabled: dir = {}, max_size = {}, block_size = {}",
+            dir.display(),
+            max_size,
+            block_size
+        );
+    }
+
+    Some(RangeCache::new(dir, block_size, max_size))
+});
+
+/// Identifies a version of a remote file in the [`RangeCache`].
+#[derive(Debug, Clone)]
+pub struct RangeCacheKey {
+    file_hash: String,
+    file_size: usize,
+}
+
+impl RangeCacheKey {
+    /// `version` must change whenever the contents of the file change, e.g. the ETag.
+    pub fn new(uri: &str, version: &str, file_size: usize) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(uri.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(version.as_bytes());
+        hasher.update(&file_size.to_le_bytes());
+
+        Self {
+            file_hash: hasher.finalize().to_hex()[..32].to_string(),
+            file_size,
+        }
+    }
+}
+
+pub struct RangeCache {
+    dir: PathBuf,
+    block_size: usize,
+    max_size: u64,
+    /// Bytes written since the last eviction pass.
+    bytes_written: AtomicU64,
+}
+
+impl RangeCache {
+    fn new(dir: PathBuf, block_size: usize, max_size: u64) -> Self {
+        Self {
+            dir,
+            block_size,
+            max_size,
+            bytes_written: AtomicU64::new(0),
+        }
+    }
+
+    fn block_path(&self, key: &RangeCacheKey, block_idx: usize) -> PathBuf {
+        self.dir.join(format!("{}_{block_idx}", key.file_hash))
+    }
+
+    fn block_range(&self, key: &RangeCacheKey, block_idx: usize) -> Range<usize> {
+        let start = block_idx * self.block_size;
+        start..(start + self.block_size).min(key.file_size)
+    }
+
+    /// Returns the bytes of `ranges`, keyed by the range start. Blocks that are not cached are
+    /// fetched with `fetch`, which receives the (sorted, non-overlapping) byte ranges of the
+    /// missing blocks.
+    pub async fn get_ranges<F, Fut>(
+        &self,
+        key: &RangeCacheKey,
+        ranges: &[Range<usize>],
+        io_metrics: &OptIOMetrics,
+        fetch: F,
+    ) -> PolarsResult<PlHashMap<usize, Buffer<u8>>>
+    where
+        F: FnOnce(Vec<Range<usize>>) -> Fut,
+        Fut: Future<Output = PolarsResult<PlHashMap<usize, Buffer<u8>>>>,
+    {
+        let block_indices = ranges
+            .iter()
+            .filter(|r| !r.is_empty())
+            .flat_map(|r| r.start / self.block_size..=(r.end - 1) / self.block_size)
+            .collect::<BTreeSet<_>>();
+
+        let mut blocks = {
+            let paths = block_indices
+                .iter()
+                .map(|&i| (i, self.block_path(key, i), self.block_range(key, i).len()))
+                .collect::<Vec<_>>();
+
+            pl_async::get_runtime()
+                .spawn_blocking(move || {
+                    paths
+                        .into_iter()
+                        .filter_map(|(i, path, len)| Some((i, read_block(&path, len)?)))
+                        .collect::<PlHashMap<_, _>>()
+                })
+                .await
+                .unwrap()
+        };
+
+        let missing = block_indices
+            .iter()
+            .copied()
+            .filter(|i| !blocks.contains_key(i))
+            .collect::<Vec<_>>();
+
+        io_metrics.add_range_cache_hits(blocks.len() as u64);
+        io_metrics.add_range_cache_misses(missing.len() as u64);
+
+        if !missing.is_empty() {
+            // Fetch consecutive missing blocks with a single range.
+            let mut fetch_ranges: Vec<Range<usize>> = vec![];
+
+            for &i in &missing {
+                let range = self.block_range(key, i);
+
+                match fetch_ranges.last_mut() {
+                    Some(last) if last.end == range.start => last.end = range.end,
+                    _ => fetch_ranges.push(range),
+                }
+            }
+
+            let fetched = fetch(fetch_ranges.clone()).await?;
+
+            let mut new_blocks = Vec::with_capacity(missing.len());
+
+            for range in fetch_ranges {
+                let Some(bytes) = fetched.get(&range.start) else {
+                    polars_bail!(
+                        ComputeError:
+                        "range cache: fetch did not return the requested range {:?}", range
+                    )
+                };
+
+                polars_ensure!(
+                    bytes.len() == range.len(),
+                    ComputeError:
+                    "range cache: expected {} bytes for range {:?}, got {} (was the file modified?)",
+                    range.len(), range, bytes.len()
+                );
+
+                for i in range.start / self.block_size..range.end.div_ceil(self.block_size) {
+                    let block_range = self.block_range(key, i);
+                    let block = bytes
+                        .clone()
+                        .sliced(block_range.start - range.start..block_range.end - range.start);
+
+                    new_blocks.push((self.block_path(key, i), block.clone()));
+                    blocks.insert(i, block);
+                }
+            }
+
+            self.write_blocks(new_blocks);
+        }
+
+        Ok(ranges
+            .iter()
+            .map(|r| (r.start, self.assemble_range(&blocks, r.clone())))
+            .collect())
+    }
+
+    fn assemble_range(
+        &self,
+        blocks: &PlHashMap<usize, Buffer<u8>>,
+        range: Range<usize>,
+    ) -> Buffer<u8> {
+        if range.is_empty() {
+            return Buffer::new();
+        }
+
+        let first = range.start / self.block_size;
+        let last = (range.end - 1) / self.block_size;
+        let offset = first * self.block_size;
+
+        if first == last {
+            return blocks[&first]
+                .clone()
+                .sliced(range.start - offset..range.end - offset);
+        }
+
+        let mut out = Vec::with_capacity(range.len());
+
+        for i in first..=last {
+            let block_start = i * self.block_size;
+            let block = &blocks[&i];
+
+            let start = range.start.saturating_sub(block_start);
+            let end = (range.end - block_start).min(block.len());
+
+            out.extend_from_slice(&block[start..end]);
+        }
+
+        Buffer::from(out)
+    }
+
+    /// Writes the blocks in the background, and evicts blocks if the cache may have become too
+    /// large.
+    fn write_blocks(&self, blocks: Vec<(PathBuf, Buffer<u8>)>) {
+        let num_bytes = blocks.iter().map(|(_, b)| b.len() as u64).sum::<u64>();
+        let dir = self.dir.clone();
+        let max_size = self.max_size;
+
+        let bytes_written = self.bytes_written.fetch_add(num_bytes, Ordering::Relaxed) + num_bytes;
+        // Eviction lists the whole cache directory, so it is only done after a fraction of the
+        // maximum size has been written.
+        let evict = bytes_written >= max_size / 8
+            && self
+                .bytes_written
+                .compare_exchange(bytes_written, 0, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok();
+
+        pl_async::get_runtime().spawn_blocking(move || {
+            let verbose = config::verbose();
+
+            if let Err(err) = write_blocks_locked(&dir, &blocks)
+                && verbose
+            {
+                eprintln!("[range_cache] failed to write blocks: {err}");
+            }
+
+            if evict
+                && let Err(err) = evict_blocks(&dir, max_size, verbose)
+                && verbose
+            {
+                eprintln!("[range_cache] failed to evict blocks: {err}");
+            }
+        });
+    }
+}
+
+/// Reads a cached block, updating its last accessed time. Returns `None` if the block is not
+/// cached or has an unexpected size.
+fn read_block(path: &Path, expected_len: usize) -> Option<Buffer<u8>> {
+    let mut file = std::fs::File::open(path).ok()?;
+
+    if file.metadata().ok()?.len() != expected_len as u64 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(expected_len);
+    file.read_to_end(&mut out).ok()?;
+
+    if out.len() != expected_len {
+        return None;
+    }
+
+    let _ = file.set_times(std::fs::FileTimes::new().set_accessed(SystemTime::now()));
+
+    Some(Buffer::from(out))
+}
+
+fn write_blocks_locked(dir: &Path, blocks: &[(PathBuf, Buffer<u8>)]) -> PolarsResult<()> {
+    let lock = FileLock::from(dir.join(LOCK_FILE_NAME));
+    let _guard = lock.acquire_shared().map_err(to_compute_err)?;
+
+    for (path, bytes) in blocks {
+        let tmp_path = path.with_extension(format!("tmp{}", crate::utils::random_u64()));
+
+        let mut file = std::fs::File::create(&tmp_path).map_err(to_compute_err)?;
+        file.write_all(bytes).map_err(to_compute_err)?;
+        drop(file);
+
+        std::fs::rename(&tmp_path, path).map_err(to_compute_err)?;
+    }
+
+    Ok(())
+}
+
+/// Removes the least recently accessed blocks until the cache is within `max_size`.
+fn evict_blocks(dir: &Path, max_size: u64, verbose: bool) -> PolarsResult<()> {
+    let lock = FileLock::from(dir.join(LOCK_FILE_NAME));
+    let _guard = lock.acquire_exclusive().map_err(to_compute_err)?;
+
+    let mut entries = vec![];
+    let mut total_size = 0u64;
+
+    for entry in std::fs::read_dir(dir).map_err(to_compute_err)? {
+        let Ok(entry) = entry else {
+            continue;
+        };
+
+        if entry.file_name() == LOCK_FILE_NAME {
+            continue;
+        }
+
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        let last_accessed = metadata
+            .accessed()
+            .or_else(|_| metadata.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        total_size += metadata.len();
+        entries.push((last_accessed, metadata.len(), entry.path()));
+    }
+
+    if total_size <= max_size {
+        return Ok(());
+    }
+
+    entries.sort_unstable_by_key(|(last_accessed, ..)| *last_accessed);
+
+    let mut num_removed = 0usize;
+
+    for (_, size, path) in entries {
+        if total_size <= max_size {
+            break;
+        }
+
+        if std::fs::remove_file(&path).is_ok() {
+            total_size -= size;
+            num_removed += 1;
+        }
+    }
+
+    if verbose {
+        eprintln!("[range_cache] evicted {num_removed} blocks, size after eviction: {total_size}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+    use crate::metrics::IOMetrics;
+
+    #[test]
+    fn test_range_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RangeCache::new(dir.path().to_path_buf(), 4, 1 << 20);
+
+        let data: Vec<u8> = (0..10).collect();
+        let key = RangeCacheKey::new("s3://bucket/file", "etag", data.len());
+
+        let io_metrics = Arc::new(IOMetrics::default());
+        let opt_io_metrics = OptIOMetrics(Some(io_metrics.clone()));
+        let num_fetches = AtomicUsize::new(0);
+
+        let get_ranges = |ranges: Vec<Range<usize>>| {
+            let fetch = |fetch_ranges: Vec<Range<usize>>| {
+                num_fetches.fetch_add(1, Ordering::Relaxed);
+                let data = &data;
+                async move {
+                    PolarsResult::Ok(
+                        fetch_ranges
+                            .into_iter()
+                            .map(|r| (r.start, Buffer::from(data[r].to_vec())))
+                            .collect::<PlHashMap<_, _>>(),
+                    )
+                }
+            };
+
+            pl_async::get_runtime()
+                .block_on(cache.get_ranges(&key, &ranges, &opt_io_metrics, fetch))
+                .unwrap()
+        };
+
+        let out = get_ranges(vec![1..3, 3..9]);
+        assert_eq!(&out[&1][..], &data[1..3]);
+        assert_eq!(&out[&3][..], &data[3..9]);
+        assert_eq!(io_metrics.range_cache_misses.load(), 3);
+
+        // Wait for the background writes.
+        for _ in 0..500 {
+            if (0..3).all(|i| cache.block_path(&key, i).exists()) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let out = get_ranges(vec![0..10]);
+        assert_eq!(&out[&0][..], &data[..]);
+        assert_eq!(io_metrics.range_cache_hits.load(), 3);
+        assert_eq!(num_fetches.load(Ordering::Relaxed), 1);
+    }
+
+    /// Reads `ranges` of `data` through the cache, returning the output and the fetched ranges.
+    fn read_through(
+        cache: &RangeCache,
+        key: &RangeCacheKey,
+        data: &[u8],
+        ranges: &[Range<usize>],
+    ) -> (PlHashMap<usize, Buffer<u8>>, Vec<Range<usize>>) {
+        let mut fetched = vec![];
+        let fetch = |fetch_ranges: Vec<Range<usize>>| {
+            fetched.clone_from(&fetch_ranges);
+            let out = fetch_ranges
+                .into_iter()
+                .map(|r| (r.start, Buffer::from(data[r].to_vec())))
+                .collect::<PlHashMap<_, _>>();
+            async move { PolarsResult::Ok(out) }
+        };
+
+        let out = pl_async::get_runtime()
+            .block_on(cache.get_ranges(key, ranges, &OptIOMetrics(None), fetch))
+            .unwrap();
+
+        (out, fetched)
+    }
+
+    /// Waits for the background writes of `blocks`.
+    fn wait_for_blocks(cache: &RangeCache, key: &RangeCacheKey, blocks: Range<usize>) {
+        for _ in 0..500 {
+            if blocks.clone().all(|i| cache.block_path(key, i).exists()) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        panic!("blocks {blocks:?} were not written");
+    }
+
+    #[test]
+    fn test_range_cache_invalidation() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RangeCache::new(dir.path().to_path_buf(), 4, 1 << 20);
+
+        let old_data: Vec<u8> = (0..8).collect();
+        let new_data: Vec<u8> = (100..108).collect();
+        let old_key = RangeCacheKey::new("s3://bucket/file", "etag-1", 8);
+
+        let (_, fetched) = read_through(&cache, &old_key, &old_data, &[0..8]);
+        assert_eq!(fetched, [0..8]);
+        wait_for_blocks(&cache, &old_key, 0..2);
+
+        let (out, fetched) = read_through(&cache, &old_key, &old_data, &[2..6]);
+        assert!(fetched.is_empty());
+        assert_eq!(&out[&2][..], &old_data[2..6]);
+
+        // A new version of the file is not served the blocks of the previous one.
+        let new_key = RangeCacheKey::new("s3://bucket/file", "etag-2", 8);
+        let (out, fetched) = read_through(&cache, &new_key, &new_data, &[2..6]);
+        assert_eq!(fetched, [0..8]);
+        assert_eq!(&out[&2][..], &new_data[2..6]);
+
+        // Blocks of an unexpected size are fetched again.
+        std::fs::write(cache.block_path(&old_key, 1), [0u8; 2]).unwrap();
+        let (out, fetched) = read_through(&cache, &old_key, &old_data, &[2..6]);
+        assert_eq!(fetched, [4..8]);
+        assert_eq!(&out[&2][..], &old_data[2..6]);
+    }
+
+    #[test]
+    fn test_range_cache_incomplete_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RangeCache::new(dir.path().to_path_buf(), 4, 1 << 20);
+        let key = RangeCacheKey::new("s3://bucket/file", "etag", 8);
+
+        let fetch = |_: Vec<Range<usize>>| async { PolarsResult::Ok(PlHashMap::default()) };
+        let err = pl_async::get_runtime()
+            .block_on(cache.get_ranges(&key, &[0..8], &OptIOMetrics(None), fetch))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("did not return the requested range")
+        );
+
+        let fetch = |_: Vec<Range<usize>>| async {
+            PolarsResult::Ok(PlHashMap::from_iter([(0, Buffer::from(vec![0u8; 3]))]))
+        };
+        let err = pl_async::get_runtime()
+            .block_on(cache.get_ranges(&key, &[0..8], &OptIOMetrics(None), fetch))
+            .unwrap_err();
+        assert!(err.to_string().contains("expected 8 bytes"));
+    }
+
+    #[test]
+    fn test_evict_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let set_accessed = |name: &str, secs: u64| {
+            let time = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+            std::fs::File::options()
+                .write(true)
+                .open(dir.path().join(name))
+                .unwrap()
+                .set_times(
+                    std::fs::FileTimes::new()
+                        .set_accessed(time)
+                        .set_modified(time),
+                )
+                .unwrap();
+        };
+
+        for (i, name) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            std::fs::write(dir.path().join(name), [0u8; 10]).unwrap();
+            set_accessed(name, 1_000 + i as u64);
+        }
+        // Reading a block updates its access time.
+        set_accessed("a", 2_000);
+
+        let remaining = || {
+            let mut names = std::fs::read_dir(dir.path())
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .filter(|name| name != LOCK_FILE_NAME)
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+
+        evict_blocks(dir.path(), 40, false).unwrap();
+        assert_eq!(remaining(), ["a", "b", "c", "d"]);
+
+        // The least recently accessed blocks are removed first.
+        evict_blocks(dir.path(), 25, false).unwrap();
+        assert_eq!(remaining(), ["a", "d"]);
+
+        evict_blocks(dir.path(), 0, false).unwrap();
+        assert!(remaining().is_empty());
+    }
+}
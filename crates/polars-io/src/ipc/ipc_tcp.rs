@@ -0,0 +1,483 @@
+//! # Serving query results as Arrow IPC streams over TCP.
+//!
+//! A minimal protocol that allows thin clients to offload queries to a remote machine:
+//!
+//! 1. The client sends a request: a little-endian `u64` length followed by an opaque payload,
+//!    e.g. a serialized DSL plan. This plays the role of a Flight `Ticket`.
+//! 2. The server answers with a sequence of frames. Every frame consists of a one byte kind, a
+//!    little-endian `u64` length and the frame data. The frame kinds are:
+//!    - `0`: the next bytes of an Arrow IPC stream,
+//!    - `1`: an UTF-8 error message, ending the response,
+//!    - `2`: the end of a successful response.
+//!
+//! The concatenated data frames form a regular Arrow IPC stream, carrying the same messages a
+//! Flight `DoGet` carries in its `FlightData`. The transport is plain TCP rather than gRPC.
+//!
+//! The server executes whatever requests it receives, so it should only be reachable from trusted
+//! clients.
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Condvar, Mutex};
+
+use arrow::io::ipc::write;
+use arrow::io::ipc::write::WriteOptions;
+use polars_core::frame::chunk_df_for_writing;
+use polars_core::prelude::*;
+
+use super::{IpcCompression, IpcStreamReader};
+use crate::SerReader;
+
+const FRAME_DATA: u8 = 0;
+const FRAME_ERROR: u8 = 1;
+const FRAME_END: u8 = 2;
+
+/// Maximum size of a request payload.
+const MAX_REQUEST_LEN: u64 = 1 << 30;
+/// Data frames are coalesced up to this size.
+const FRAME_BUFFER_SIZE: usize = 1 << 20;
+/// Default maximum number of connections served concurrently by [`IpcTcpServer::serve`].
+const DEFAULT_MAX_CONNECTIONS: usize = 64;
+
+/// Result of a request handled by an [`IpcTcpServer`].
+pub struct IpcTcpResponse {
+    pub schema: SchemaRef,
+    pub batches: Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>,
+}
+
+impl IpcTcpResponse {
+    pub fn from_df(df: DataFrame) -> Self {
+        Self {
+            schema: df.schema().clone(),
+            batches: Box::new(std::iter::once(Ok(df))),
+        }
+    }
+}
+
+/// Server streaming the results of requests back to [`IpcTcpClient`]s.
+pub struct IpcTcpServer {
+    listener: TcpListener,
+    compression: Option<IpcCompression>,
+    max_connections: usize,
+}
+
+impl IpcTcpServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> PolarsResult<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            compression: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+        })
+    }
+
+    /// Set the compression used for the record batches. Defaults to None.
+    pub fn with_compression(mut self, compression: Option<IpcCompression>) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Set the maximum number of connections served concurrently. Further connections are not
+    /// accepted until one of the served connections is closed. Defaults to 64.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections.max(1);
+        self
+    }
+
+    pub fn local_addr(&self) -> PolarsResult<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Serves incoming connections, each on its own thread, until accepting a connection fails.
+    pub fn serve<H>(&self, handler: H) -> PolarsResult<()>
+    where
+        H: Fn(&[u8]) -> PolarsResult<IpcTcpResponse> + Send + Sync + 'static,
+    {
+        let handler = Arc::new(handler);
+        let active = Arc::new((Mutex::new(0usize), Condvar::new()));
+
+        loop {
+            {
+                let (count, cvar) = active.as_ref();
+                let mut count = cvar
+                    .wait_while(count.lock().unwrap(), |count| {
+                        *count >= self.max_connections
+                    })
+                    .unwrap();
+                *count += 1;
+            }
+
+            let slot = ConnectionSlot(active.clone());
+            let (stream, _) = self.listener.accept()?;
+            let handler = handler.clone();
+            let compression = self.compression;
+
+            std::thread::spawn(move || {
+                let _slot = slot;
+
+                if let Err(e) = handle_connection(stream, handler.as_ref(), compression) {
+                    if polars_core::config::verbose() {
+                        eprintln!("[IpcTcpServer]: failed to serve connection: {e}");
+                    }
+                }
+            });
+        }
+    }
+
+    /// Accepts and serves a single connection on the current thread.
+    pub fn serve_one<H>(&self, handler: H) -> PolarsResult<()>
+    where
+        H: Fn(&[u8]) -> PolarsResult<IpcTcpResponse>,
+    {
+        let (stream, _) = self.listener.accept()?;
+        handle_connection(stream, &handler, self.compression)
+    }
+}
+
+/// Frees a slot of the concurrent connections of [`IpcTcpServer::serve`] on drop.
+struct ConnectionSlot(Arc<(Mutex<usize>, Condvar)>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        let (count, cvar) = self.0.as_ref();
+        *count.lock().unwrap() -= 1;
+        cvar.notify_one();
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    handler: &dyn Fn(&[u8]) -> PolarsResult<IpcTcpResponse>,
+    compression: Option<IpcCompression>,
+) -> PolarsResult<()> {
+    let request = read_request(&mut stream)?;
+
+    let mut writer = BufWriter::with_capacity(FRAME_BUFFER_SIZE, FrameWriter(stream));
+    let result =
+        handler(&request).and_then(|response| write_response(&mut writer, response, compression));
+    let FrameWriter(mut stream) = writer.into_inner().map_err(|e| e.into_error())?;
+
+    match &result {
+        Ok(()) => write_frame(&mut stream, FRAME_END, &[])?,
+        Err(e) => write_frame(&mut stream, FRAME_ERROR, e.to_string().as_bytes())?,
+    }
+    stream.flush()?;
+
+    result
+}
+
+fn read_request(stream: &mut TcpStream) -> PolarsResult<Vec<u8>> {
+    let mut len = [0u8; 8];
+    stream.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+
+    polars_ensure!(
+        len <= MAX_REQUEST_LEN,
+        ComputeError: "IPC TCP request of {len} bytes exceeds the maximum of {MAX_REQUEST_LEN} bytes"
+    );
+
+    let mut request = vec![0; len as usize];
+    stream.read_exact(&mut request)?;
+    Ok(request)
+}
+
+fn write_response<W: Write>(
+    writer: W,
+    response: IpcTcpResponse,
+    compression: Option<IpcCompression>,
+) -> PolarsResult<()> {
+    let compat_level = CompatLevel::newest();
+
+    let mut ipc_writer = write::StreamWriter::new(
+        writer,
+        WriteOptions {
+            compression: compression.map(|c| c.into()),
+        },
+    );
+    ipc_writer.start(&response.schema.to_arrow(compat_level), None)?;
+
+    for df in response.batches {
+        let mut df = df?;
+        let df = chunk_df_for_writing(&mut df, 512 * 512)?;
+
+        for batch in df.iter_chunks(compat_level, true) {
+            ipc_writer.write(&batch, None)?;
+        }
+    }
+
+    ipc_writer.finish()?;
+    Ok(())
+}
+
+fn write_frame<W: Write>(writer: &mut W, kind: u8, data: &[u8]) -> io::Result<()> {
+    writer.write_all(&[kind])?;
+    writer.write_all(&(data.len() as u64).to_le_bytes())?;
+    writer.write_all(data)
+}
+
+/// Wraps everything written into data frames.
+struct FrameWriter<W>(W);
+
+impl<W: Write> Write for FrameWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !buf.is_empty() {
+            write_frame(&mut self.0, FRAME_DATA, buf)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Client for an [`IpcTcpServer`].
+///
+/// # Example
+///
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::ipc::IpcTcpClient;
+/// use polars_io::SerReader;
+///
+/// fn example(request: &[u8]) -> PolarsResult<DataFrame> {
+///     IpcTcpClient::connect("127.0.0.1:7700")?
+///         .request(request)?
+///         .finish()
+/// }
+/// ```
+pub struct IpcTcpClient {
+    stream: TcpStream,
+}
+
+impl IpcTcpClient {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> PolarsResult<Self> {
+        Ok(Self {
+            stream: TcpStream::connect(addr)?,
+        })
+    }
+
+    /// Sends `request` and returns a reader over the response.
+    ///
+    /// The response is decoded as it is received from the connection. An error reported by the
+    /// server is returned by the reader.
+    pub fn request(
+        mut self,
+        request: &[u8],
+    ) -> PolarsResult<IpcStreamReader<IpcTcpResponseReader>> {
+        self.stream
+            .write_all(&(request.len() as u64).to_le_bytes())?;
+        self.stream.write_all(request)?;
+        self.stream.flush()?;
+
+        Ok(IpcStreamReader::new(IpcTcpResponseReader {
+            reader: BufReader::new(self.stream),
+            position: 0,
+            remaining: 0,
+            finished: false,
+        }))
+    }
+}
+
+/// Reads the Arrow IPC stream carried by the data frames of a response.
+///
+/// Seeking is only supported forward, skipping the bytes in between.
+pub struct IpcTcpResponseReader {
+    reader: BufReader<TcpStream>,
+    /// Position in the Arrow IPC stream.
+    position: u64,
+    /// Bytes left in the current data frame.
+    remaining: u64,
+    /// Whether the end frame was received.
+    finished: bool,
+}
+
+impl IpcTcpResponseReader {
+    /// Reads frame headers until the start of the next data frame or the end of the response.
+    fn next_data_frame(&mut self) -> io::Result<()> {
+        while self.remaining == 0 && !self.finished {
+            let mut header = [0u8; 9];
+            self.reader.read_exact(&mut header).map_err(|e| {
+                if e.kind() == io::ErrorKind::UnexpectedEof {
+                    closed_before_end()
+                } else {
+                    e
+                }
+            })?;
+            let len = u64::from_le_bytes(header[1..].try_into().unwrap());
+
+            match header[0] {
+                FRAME_DATA => self.remaining = len,
+                FRAME_ERROR => {
+                    let mut msg = vec![];
+                    let n = (&mut self.reader).take(len).read_to_end(&mut msg)?;
+
+                    if n as u64 != len {
+                        return Err(closed_mid_frame());
+                    }
+
+                    return Err(io::Error::other(format!(
+                        "IPC TCP server failed to handle request: {}",
+                        String::from_utf8_lossy(&msg)
+                    )));
+                },
+                FRAME_END => self.finished = true,
+                kind => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unknown IPC TCP frame kind: {kind}"),
+                    ));
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Read for IpcTcpResponseReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        self.next_data_frame()?;
+
+        if self.finished {
+            return Ok(0);
+        }
+
+        let len = usize::try_from(self.remaining).map_or(buf.len(), |v| v.min(buf.len()));
+        let n = self.reader.read(&mut buf[..len])?;
+
+        if n == 0 {
+            return Err(closed_mid_frame());
+        }
+
+        self.remaining -= n as u64;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for IpcTcpResponseReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(v) => Some(v),
+            SeekFrom::Current(v) => self.position.checked_add_signed(v),
+            SeekFrom::End(_) => None,
+        };
+
+        match target {
+            Some(target) if target >= self.position => {
+                let len = target - self.position;
+                io::copy(&mut self.by_ref().take(len), &mut io::sink())?;
+
+                if self.position != target {
+                    return Err(closed_before_end());
+                }
+
+                Ok(target)
+            },
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "IPC TCP responses can only be seeked forward",
+            )),
+        }
+    }
+}
+
+// These are not reported as `UnexpectedEof`, which the IPC stream reader treats as the end of
+// the stream.
+fn closed_before_end() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::ConnectionAborted,
+        "IPC TCP connection closed before the end of the response",
+    )
+}
+
+fn closed_mid_frame() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::ConnectionAborted,
+        "IPC TCP connection closed in the middle of a frame",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::df;
+
+    use super::*;
+
+    #[test]
+    fn test_ipc_tcp_roundtrip() {
+        let server = IpcTcpServer::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let handle = std::thread::spawn(move || {
+            for _ in 0..2 {
+                server
+                    .serve_one(|request| {
+                        polars_ensure!(request == b"numbers", ComputeError: "unknown request");
+
+                        let batches = (0..3).map(|i| df!("a" => [i, i + 1], "b" => ["x", "y"]));
+                        let schema = batches.clone().next().unwrap()?.schema().clone();
+
+                        Ok(IpcTcpResponse {
+                            schema,
+                            batches: Box::new(batches),
+                        })
+                    })
+                    .ok();
+            }
+        });
+
+        let df = IpcTcpClient::connect(addr)
+            .unwrap()
+            .request(b"numbers")
+            .unwrap()
+            .finish()
+            .unwrap();
+        let expected = df!(
+            "a" => [0, 1, 1, 2, 2, 3],
+            "b" => ["x", "y", "x", "y", "x", "y"],
+        )
+        .unwrap();
+        assert!(df.equals(&expected));
+
+        let err = IpcTcpClient::connect(addr)
+            .unwrap()
+            .request(b"letters")
+            .unwrap()
+            .finish()
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown request"));
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_ipc_tcp_connection_closed() {
+        let server = IpcTcpServer::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+
+        // Drop the connection after the first data frame.
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = server.listener.accept().unwrap();
+            read_request(&mut stream).unwrap();
+
+            let mut data = vec![];
+            let df = df!("a" => [1, 2, 3]).unwrap();
+            write_response(&mut data, IpcTcpResponse::from_df(df), None).unwrap();
+            write_frame(&mut stream, FRAME_DATA, &data[..data.len() / 2]).unwrap();
+        });
+
+        let err = IpcTcpClient::connect(addr)
+            .unwrap()
+            .request(b"numbers")
+            .unwrap()
+            .finish()
+            .unwrap_err();
+        assert!(err.to_string().contains("closed"));
+
+        handle.join().unwrap();
+    }
+}
@@ -4,6 +4,8 @@ mod ipc_file;
 mod ipc_reader_async;
 #[cfg(feature = "ipc_streaming")]
 mod ipc_stream;
+#[cfg(feature = "ipc_tcp")]
+mod ipc_tcp;
 #[cfg(feature = "ipc")]
 mod mmap;
 mod write;
@@ -13,4 +15,6 @@ pub use ipc_file::{IpcReader, IpcScanOptions};
 pub use ipc_reader_async::*;
 #[cfg(feature = "ipc_streaming")]
 pub use ipc_stream::*;
+#[cfg(feature = "ipc_tcp")]
+pub use ipc_tcp::{IpcTcpClient, IpcTcpResponse, IpcTcpResponseReader, IpcTcpServer};
 pub use write::{BatchedWriter, IpcCompression, IpcWriter, IpcWriterOptions};
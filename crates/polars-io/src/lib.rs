@@ -14,6 +14,10 @@ pub mod catalog;
 pub mod cloud;
 #[cfg(any(feature = "csv", feature = "json"))]
 pub mod csv;
+#[cfg(feature = "database")]
+pub mod database;
+#[cfg(feature = "delta")]
+pub mod delta;
 #[cfg(feature = "file_cache")]
 pub mod file_cache;
 #[cfg(any(feature = "ipc", feature = "ipc_streaming"))]
@@ -34,6 +38,8 @@ pub mod prelude;
 #[cfg(feature = "scan_lines")]
 pub mod scan_lines;
 mod shared;
+#[cfg(feature = "spreadsheet")]
+pub mod spreadsheet;
 pub mod utils;
 
 #[cfg(feature = "cloud")]
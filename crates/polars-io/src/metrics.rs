@@ -11,6 +11,14 @@ pub struct IOMetrics {
     pub bytes_requested: RelaxedCell<u64>,
     pub bytes_received: RelaxedCell<u64>,
     pub bytes_sent: RelaxedCell<u64>,
+    /// Number of read requests issued.
+    pub num_requests: RelaxedCell<u64>,
+    /// Sum of the latencies of read requests, used to derive the average request latency.
+    pub request_time_ns: RelaxedCell<u64>,
+    /// Number of blocks served from the local range cache.
+    pub range_cache_hits: RelaxedCell<u64>,
+    /// Number of blocks fetched because they were not in the local range cache.
+    pub range_cache_misses: RelaxedCell<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +45,18 @@ impl OptIOMetrics {
         self.0.as_ref().map(|x| x.bytes_sent.fetch_add(bytes_sent));
     }
 
+    pub fn add_range_cache_hits(&self, num_blocks: u64) {
+        self.0
+            .as_ref()
+            .map(|x| x.range_cache_hits.fetch_add(num_blocks));
+    }
+
+    pub fn add_range_cache_misses(&self, num_blocks: u64) {
+        self.0
+            .as_ref()
+            .map(|x| x.range_cache_misses.fetch_add(num_blocks));
+    }
+
     pub async fn record_io_read<F, O>(&self, num_bytes: u64, fut: F) -> O
     where
         F: Future<Output = O>,
@@ -44,11 +64,19 @@ impl OptIOMetrics {
         self.add_bytes_requested(num_bytes);
 
         let io_session = self.start_io_session();
+        let start = self.0.is_some().then(std::time::Instant::now);
 
         let out = fut.await;
 
         drop(io_session);
 
+        if let (Some(io_metrics), Some(start)) = (&self.0, start) {
+            io_metrics.num_requests.fetch_add(1);
+            io_metrics
+                .request_time_ns
+                .fetch_add(start.elapsed().as_nanos() as u64);
+        }
+
         self.add_bytes_received(num_bytes);
 
         out
@@ -13,6 +13,34 @@ use crate::path_utils::HiveIdxTracker;
 use crate::pl_async::with_concurrency_budget;
 use crate::utils::{URL_ENCODE_CHARSET, decode_json_response};
 
+#[cfg(feature = "hf_upload")]
+mod upload;
+
+#[cfg(feature = "hf_upload")]
+pub use upload::{HfCommitBatch, HfFileWriter};
+
+const DEFAULT_HF_ENDPOINT: &str = "https://huggingface.co";
+
+/// Base URL of the Hub. This can be set to a self-hosted Hub-compatible server with the
+/// `HF_ENDPOINT` environment variable.
+fn hf_endpoint() -> String {
+    std::env::var("HF_ENDPOINT")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map_or_else(
+            || DEFAULT_HF_ENDPOINT.to_string(),
+            |v| v.trim_end_matches('/').to_string(),
+        )
+}
+
+fn hf_client_builder(endpoint: &str) -> reqwest::ClientBuilder {
+    reqwest::ClientBuilder::new()
+        .user_agent(USER_AGENT)
+        .http1_only()
+        // Only allow plain HTTP if the endpoint was explicitly configured to use it.
+        .https_only(!endpoint.starts_with("http://"))
+}
+
 /// Percent-encoding character set for HF Hub paths.
 ///
 /// This is URL_ENCODE_CHARSET with slashes preserved - by not encoding slashes,
@@ -38,6 +66,10 @@ struct HFRepoLocation {
 
 impl HFRepoLocation {
     fn new(bucket: &str, repository: &str, revision: &str) -> Self {
+        Self::with_endpoint(&hf_endpoint(), bucket, repository, revision)
+    }
+
+    fn with_endpoint(endpoint: &str, bucket: &str, repository: &str, revision: &str) -> Self {
         // * Don't percent-encode bucket/repository - they are path segments where
         //   slashes are separators. E.g. "HuggingFaceFW/fineweb-2" must stay as-is.
         // * DO encode revision - slashes in revisions like "refs/convert/parquet"
@@ -46,12 +78,12 @@ impl HFRepoLocation {
         let encoded_revision =
             percent_encoding::percent_encode(revision.as_bytes(), URL_ENCODE_CHARSET);
         let api_base_path = format!(
-            "https://huggingface.co/api/{}/{}/tree/{}/",
-            bucket, repository, encoded_revision
+            "{}/api/{}/{}/tree/{}/",
+            endpoint, bucket, repository, encoded_revision
         );
         let download_base_path = format!(
-            "https://huggingface.co/{}/{}/resolve/{}/",
-            bucket, repository, encoded_revision
+            "{}/{}/{}/resolve/{}/",
+            endpoint, bucket, repository, encoded_revision
         );
 
         Self {
@@ -226,10 +258,7 @@ pub(super) async fn expand_paths_hf(
 ) -> PolarsResult<(usize, Vec<PlRefPath>)> {
     assert!(!paths.is_empty());
 
-    let client = reqwest::ClientBuilder::new()
-        .user_agent(USER_AGENT)
-        .http1_only()
-        .https_only(true);
+    let client = hf_client_builder(&hf_endpoint());
 
     let client = if let Some(CloudOptions {
         config: Some(CloudConfig::Http { headers }),
@@ -0,0 +1,822 @@
+//! Writing to Hugging Face repositories through the Hub commit API.
+//!
+//! Files are spooled to local disk while they are written. When a file is closed, the Hub decides
+//! through the preupload API whether it is stored inline in the commit or in LFS storage, in which
+//! case it is uploaded right away. The staged files are then added to the repository together in a
+//! single commit.
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use base64::Engine as _;
+use base64::prelude::BASE64_STANDARD;
+use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err, to_compute_err};
+use polars_utils::pl_path::{CloudScheme, PlRefPath};
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use super::{HFPathParts, hf_client_builder, hf_endpoint};
+use crate::cloud::{CloudConfig, CloudOptions, try_build_http_header_map_from_items_slice};
+use crate::path_utils::POLARS_TEMP_DIR_BASE_PATH;
+use crate::pl_async::{get_runtime, with_concurrency_budget};
+use crate::utils::file::WriteableTrait;
+use crate::utils::{URL_ENCODE_CHARSET, decode_json_response, random_uuid};
+
+/// Number of leading bytes of a file sent to the preupload API.
+const PREUPLOAD_SAMPLE_SIZE: usize = 512;
+
+struct HfRepo {
+    endpoint: String,
+    /// `datasets` or `spaces`.
+    bucket: String,
+    repository: String,
+    revision: String,
+}
+
+impl HfRepo {
+    fn api_uri(&self, operation: &str) -> String {
+        format!(
+            "{}/api/{}/{}/{}/{}",
+            self.endpoint,
+            self.bucket,
+            self.repository,
+            operation,
+            percent_encoding::percent_encode(self.revision.as_bytes(), URL_ENCODE_CHARSET)
+        )
+    }
+
+    fn lfs_batch_uri(&self) -> String {
+        format!(
+            "{}/{}/{}.git/info/lfs/objects/batch",
+            self.endpoint, self.bucket, self.repository
+        )
+    }
+}
+
+enum StagedFile {
+    /// Sent inline with the commit.
+    Regular { path: String, content: Vec<u8> },
+    /// Already uploaded to LFS storage.
+    Lfs {
+        path: String,
+        oid: String,
+        size: u64,
+    },
+}
+
+/// Files written to a Hugging Face repository that are added in a single commit.
+///
+/// Authentication uses the `Authorization` header of the HF [`CloudOptions`], which holds the HF
+/// token.
+pub struct HfCommitBatch {
+    repo: HfRepo,
+    client: reqwest::Client,
+    /// Only sent to the Hub, not to the pre-signed LFS upload URLs.
+    auth_headers: HeaderMap,
+    staged: Mutex<Vec<StagedFile>>,
+}
+
+impl HfCommitBatch {
+    /// Creates a batch for files under `base_path` (`hf://{bucket}/{repository}@{revision}/..`).
+    pub fn new(base_path: &PlRefPath, cloud_options: Option<&CloudOptions>) -> PolarsResult<Self> {
+        Self::with_endpoint(base_path, hf_endpoint(), cloud_options)
+    }
+
+    fn with_endpoint(
+        base_path: &PlRefPath,
+        endpoint: String,
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<Self> {
+        let HFPathParts {
+            bucket,
+            repository,
+            revision,
+            path: _,
+        } = HFPathParts::try_from_uri(base_path.as_str())?;
+
+        // Writes need a token, so fall back to the token from the environment like for reads with
+        // storage options.
+        let default_options;
+        let cloud_options = match cloud_options {
+            Some(v) => Some(v),
+            None => {
+                default_options = CloudOptions::from_untyped_config(
+                    Some(CloudScheme::Hf),
+                    std::iter::empty::<(&str, String)>(),
+                )?;
+                Some(&default_options)
+            },
+        };
+
+        let auth_headers = match cloud_options {
+            Some(CloudOptions {
+                config: Some(CloudConfig::Http { headers }),
+                ..
+            }) => try_build_http_header_map_from_items_slice(headers.as_slice())?,
+            _ => HeaderMap::new(),
+        };
+
+        let client = hf_client_builder(&endpoint)
+            .build()
+            .map_err(to_compute_err)?;
+
+        Ok(Self {
+            repo: HfRepo {
+                endpoint,
+                bucket,
+                repository,
+                revision,
+            },
+            client,
+            auth_headers,
+            staged: Mutex::new(vec![]),
+        })
+    }
+
+    /// Opens a file to be written to `path`, which must be in the repository of this batch. The
+    /// file is staged when it is closed.
+    pub fn open_file(self: &Arc<Self>, path: &PlRefPath) -> PolarsResult<HfFileWriter> {
+        self.open_file_impl(path, false)
+    }
+
+    fn open_file_impl(
+        self: &Arc<Self>,
+        path: &PlRefPath,
+        commit_on_close: bool,
+    ) -> PolarsResult<HfFileWriter> {
+        let parts = HFPathParts::try_from_uri(path.as_str())?;
+
+        polars_ensure!(
+            parts.bucket == self.repo.bucket
+                && parts.repository == self.repo.repository
+                && parts.revision == self.repo.revision,
+            ComputeError:
+            "path '{}' is not in Hugging Face repository '{}/{}@{}'",
+            path, self.repo.bucket, self.repo.repository, self.repo.revision
+        );
+        polars_ensure!(
+            !parts.path.is_empty() && !parts.path.ends_with('/'),
+            ComputeError:
+            "Hugging Face sink path must point to a file: '{}'", path
+        );
+
+        let spool_path = POLARS_TEMP_DIR_BASE_PATH.join(format!("hf-upload-{}", random_uuid()));
+        let spool_file = std::fs::File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&spool_path)?;
+
+        Ok(HfFileWriter {
+            batch: self.clone(),
+            path_in_repo: parts.path,
+            spool_file,
+            spool_path,
+            hasher: Sha256::new(),
+            size: 0,
+            sample: Vec::with_capacity(PREUPLOAD_SAMPLE_SIZE),
+            commit_on_close,
+        })
+    }
+
+    /// Adds all staged files to the repository in a single commit. Does nothing if no files were
+    /// staged.
+    pub async fn commit(&self, summary: Option<&str>) -> PolarsResult<()> {
+        let staged = std::mem::take(&mut *self.staged.lock().unwrap());
+
+        if staged.is_empty() {
+            return Ok(());
+        }
+
+        let summary = summary.map_or_else(
+            || match staged.as_slice() {
+                [StagedFile::Regular { path, .. } | StagedFile::Lfs { path, .. }] => {
+                    format!("Upload {path} with polars")
+                },
+                files => format!("Upload {} files with polars", files.len()),
+            },
+            |v| v.to_string(),
+        );
+
+        let mut body = json!({
+            "key": "header",
+            "value": {"summary": summary, "description": ""},
+        })
+        .to_string();
+
+        for file in staged {
+            let line = match file {
+                StagedFile::Regular { path, content } => json!({
+                    "key": "file",
+                    "value": {
+                        "content": BASE64_STANDARD.encode(content),
+                        "path": path,
+                        "encoding": "base64",
+                    },
+                }),
+                StagedFile::Lfs { path, oid, size } => json!({
+                    "key": "lfsFile",
+                    "value": {"path": path, "algo": "sha256", "oid": oid, "size": size},
+                }),
+            };
+
+            body.push('\n');
+            body.push_str(&line.to_string());
+        }
+
+        let _: serde_json::Value = self
+            .send(
+                self.client
+                    .post(self.repo.api_uri("commit"))
+                    .headers(self.auth_headers.clone())
+                    .header("Content-Type", "application/x-ndjson")
+                    .body(body),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn stage(&self, file: StagedFile) {
+        self.staged.lock().unwrap().push(file)
+    }
+
+    /// Asks the Hub whether the file should be stored in LFS storage.
+    async fn should_use_lfs(&self, path: &str, size: u64, sample: &[u8]) -> PolarsResult<bool> {
+        #[derive(Deserialize)]
+        struct PreuploadResponse {
+            files: Vec<PreuploadFile>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct PreuploadFile {
+            path: String,
+            upload_mode: String,
+            #[serde(default)]
+            should_ignore: bool,
+        }
+
+        let response: PreuploadResponse = self
+            .send(
+                self.client
+                    .post(self.repo.api_uri("preupload"))
+                    .headers(self.auth_headers.clone())
+                    .json(&json!({
+                        "files": [{
+                            "path": path,
+                            "size": size,
+                            "sample": BASE64_STANDARD.encode(sample),
+                        }],
+                    })),
+            )
+            .await?;
+
+        let Some(file) = response.files.into_iter().find(|f| f.path == path) else {
+            polars_bail!(ComputeError: "Hugging Face preupload response is missing file '{}'", path)
+        };
+
+        polars_ensure!(
+            !file.should_ignore,
+            ComputeError:
+            "file '{}' is ignored by the .gitignore of Hugging Face repository '{}'",
+            path, self.repo.repository
+        );
+
+        Ok(file.upload_mode == "lfs")
+    }
+
+    /// Uploads the contents of `file` to LFS storage, unless the Hub already has them.
+    async fn upload_lfs(&self, oid: &str, size: u64, file: &mut std::fs::File) -> PolarsResult<()> {
+        #[derive(Deserialize)]
+        struct BatchResponse {
+            objects: Vec<BatchObject>,
+        }
+
+        #[derive(Deserialize)]
+        struct BatchObject {
+            #[serde(default)]
+            actions: Option<BatchActions>,
+            #[serde(default)]
+            error: Option<BatchError>,
+        }
+
+        #[derive(Deserialize)]
+        struct BatchActions {
+            upload: Option<BatchAction>,
+            verify: Option<BatchAction>,
+        }
+
+        #[derive(Deserialize)]
+        struct BatchAction {
+            href: String,
+            #[serde(default)]
+            header: serde_json::Map<String, serde_json::Value>,
+        }
+
+        #[derive(Deserialize)]
+        struct BatchError {
+            code: i64,
+            message: String,
+        }
+
+        let response: BatchResponse = self
+            .send(
+                self.client
+                    .post(self.repo.lfs_batch_uri())
+                    .headers(self.auth_headers.clone())
+                    .header("Accept", "application/vnd.git-lfs+json")
+                    .header("Content-Type", "application/vnd.git-lfs+json")
+                    .body(
+                        json!({
+                            "operation": "upload",
+                            "transfers": ["basic", "multipart"],
+                            "objects": [{"oid": oid, "size": size}],
+                            "hash_algo": "sha256",
+                            "ref": {"name": self.repo.revision},
+                        })
+                        .to_string(),
+                    ),
+            )
+            .await?;
+
+        let Some(object) = response.objects.into_iter().next() else {
+            polars_bail!(ComputeError: "Hugging Face LFS batch response contains no objects")
+        };
+
+        if let Some(BatchError { code, message }) = object.error {
+            polars_bail!(ComputeError: "Hugging Face LFS upload failed ({}): {}", code, message)
+        }
+
+        // No upload action means that the object is already stored.
+        let Some(BatchActions {
+            upload: Some(upload),
+            verify,
+        }) = object.actions
+        else {
+            return Ok(());
+        };
+
+        if let Some(chunk_size) = upload.header.get("chunk_size") {
+            let chunk_size: u64 = match chunk_size {
+                serde_json::Value::Number(v) => v.as_u64(),
+                serde_json::Value::String(v) => v.parse().ok(),
+                _ => None,
+            }
+            .filter(|v| *v > 0)
+            .ok_or_else(|| polars_err!(ComputeError: "invalid LFS chunk size: {}", chunk_size))?;
+
+            let num_parts = size.div_ceil(chunk_size);
+            let mut parts = Vec::with_capacity(num_parts as usize);
+
+            file.seek(SeekFrom::Start(0))?;
+
+            for part_number in 1..=num_parts {
+                let Some(serde_json::Value::String(part_url)) =
+                    upload.header.get(&format!("{part_number:05}"))
+                else {
+                    polars_bail!(ComputeError: "missing upload URL for LFS part {}", part_number)
+                };
+
+                let mut chunk = vec![];
+                file.by_ref().take(chunk_size).read_to_end(&mut chunk)?;
+
+                let response = self.send_raw(self.client.put(part_url).body(chunk)).await?;
+                let etag = response
+                    .headers()
+                    .get("etag")
+                    .and_then(|v| v.to_str().ok())
+                    .ok_or_else(
+                        || polars_err!(ComputeError: "missing ETag for LFS part {}", part_number),
+                    )?;
+
+                parts.push(json!({"partNumber": part_number, "etag": etag}));
+            }
+
+            let _: serde_json::Value = self
+                .send(
+                    self.client
+                        .post(&upload.href)
+                        .header("Accept", "application/vnd.git-lfs+json")
+                        .json(&json!({"oid": oid, "parts": parts})),
+                )
+                .await?;
+        } else {
+            let mut content = Vec::with_capacity(size as usize);
+            file.seek(SeekFrom::Start(0))?;
+            file.read_to_end(&mut content)?;
+
+            self.send_raw(
+                self.client
+                    .put(&upload.href)
+                    .headers(header_map(&upload.header)?)
+                    .body(content),
+            )
+            .await?;
+        }
+
+        if let Some(verify) = verify {
+            self.send_raw(
+                self.client
+                    .post(&verify.href)
+                    .headers(self.auth_headers.clone())
+                    .headers(header_map(&verify.header)?)
+                    .json(&json!({"oid": oid, "size": size})),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn send_raw(&self, request: reqwest::RequestBuilder) -> PolarsResult<reqwest::Response> {
+        let response =
+            with_concurrency_budget(1, || async { request.send().await.map_err(to_compute_err) })
+                .await?;
+
+        let status = response.status();
+
+        if !status.is_success() {
+            let url = response.url().clone();
+            let body = response.text().await.unwrap_or_default();
+            polars_bail!(
+                ComputeError:
+                "Hugging Face request to {} failed with status {}: {}", url, status, body
+            )
+        }
+
+        Ok(response)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> PolarsResult<T> {
+        let bytes = self
+            .send_raw(request)
+            .await?
+            .bytes()
+            .await
+            .map_err(to_compute_err)?;
+
+        decode_json_response(bytes.as_ref())
+    }
+}
+
+/// Builds the headers of an LFS action.
+fn header_map(headers: &serde_json::Map<String, serde_json::Value>) -> PolarsResult<HeaderMap> {
+    let headers = headers
+        .iter()
+        .filter_map(|(k, v)| Some((k.as_str(), v.as_str()?)))
+        // Multipart uploads use the header to pass the chunk size and the part upload URLs.
+        .filter(|(k, _)| *k != "chunk_size" && !k.bytes().all(|c| c.is_ascii_digit()))
+        .collect::<Vec<_>>();
+
+    try_build_http_header_map_from_items_slice(&headers)
+}
+
+/// Writes a single file of an [`HfCommitBatch`]. The data is spooled to a local file, which is
+/// uploaded when the writer is closed.
+pub struct HfFileWriter {
+    batch: Arc<HfCommitBatch>,
+    path_in_repo: String,
+    spool_file: std::fs::File,
+    spool_path: PathBuf,
+    hasher: Sha256,
+    size: u64,
+    sample: Vec<u8>,
+    /// Whether the batch is committed once this file is staged, for sinks writing a single file.
+    commit_on_close: bool,
+}
+
+impl HfFileWriter {
+    /// Creates a writer for a single file that is committed on its own when closed.
+    pub fn new_single_file(
+        path: &PlRefPath,
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<Self> {
+        Arc::new(HfCommitBatch::new(path, cloud_options)?).open_file_impl(path, true)
+    }
+
+    async fn finish(&mut self) -> PolarsResult<()> {
+        let batch = &self.batch;
+        let path = std::mem::take(&mut self.path_in_repo);
+        let size = self.size;
+
+        let file = if batch.should_use_lfs(&path, size, &self.sample).await? {
+            let oid = format!("{:x}", std::mem::take(&mut self.hasher).finalize());
+            batch.upload_lfs(&oid, size, &mut self.spool_file).await?;
+
+            StagedFile::Lfs { path, oid, size }
+        } else {
+            let mut content = Vec::with_capacity(size as usize);
+            self.spool_file.seek(SeekFrom::Start(0))?;
+            self.spool_file.read_to_end(&mut content)?;
+
+            StagedFile::Regular { path, content }
+        };
+
+        batch.stage(file);
+
+        if self.commit_on_close {
+            batch.commit(None).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Write for HfFileWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.spool_file.write(buf)?;
+        let buf = &buf[..n];
+
+        self.hasher.update(buf);
+        self.size += n as u64;
+
+        let sample_len = (PREUPLOAD_SAMPLE_SIZE - self.sample.len()).min(n);
+        self.sample.extend_from_slice(&buf[..sample_len]);
+
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.spool_file.flush()
+    }
+}
+
+impl WriteableTrait for HfFileWriter {
+    fn close(&mut self) -> std::io::Result<()> {
+        get_runtime()
+            .block_in_place_on(self.finish())
+            .map_err(std::io::Error::from)
+    }
+
+    // Files are only durable once committed.
+    fn sync_all(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn sync_data(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for HfFileWriter {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.spool_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use polars_utils::pl_path::PlRefPath;
+    use serde_json::{Value, json};
+    use sha2::{Digest, Sha256};
+
+    use super::HfCommitBatch;
+    use crate::utils::file::WriteableTrait;
+
+    #[derive(Default)]
+    struct MockHub {
+        /// LFS objects by oid.
+        lfs_objects: HashMap<String, Vec<u8>>,
+        lfs_parts: HashMap<String, Vec<u8>>,
+        commits: Vec<Vec<Value>>,
+    }
+
+    /// Minimal stand-in for a Hub serving the dataset `user/repo`. Files of at least 8 bytes are
+    /// stored in LFS storage, using multipart uploads with 4 byte parts if larger than 8 bytes.
+    fn serve_hub(hub: Arc<Mutex<MockHub>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let base = format!("http://{addr}");
+        let endpoint = base.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap().to_string();
+                let target = parts.next().unwrap().to_string();
+
+                let mut content_length = 0;
+                let mut authorization = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((k, v)) = line.split_once(':') {
+                        if k.eq_ignore_ascii_case("content-length") {
+                            content_length = v.trim().parse().unwrap();
+                        } else if k.eq_ignore_ascii_case("authorization") {
+                            authorization = Some(v.trim().to_string());
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let mut hub = hub.lock().unwrap();
+                let mut etag = None;
+
+                let response = match (method.as_str(), target.as_str()) {
+                    ("POST", "/api/datasets/user/repo/preupload/main") => {
+                        assert_eq!(authorization.as_deref(), Some("Bearer token"));
+                        let request: Value = serde_json::from_slice(&body).unwrap();
+                        let file = &request["files"][0];
+                        let upload_mode = if file["size"].as_u64().unwrap() >= 8 {
+                            "lfs"
+                        } else {
+                            "regular"
+                        };
+
+                        json!({"files": [{
+                            "path": file["path"],
+                            "uploadMode": upload_mode,
+                            "shouldIgnore": false,
+                        }]})
+                    },
+                    ("POST", "/datasets/user/repo.git/info/lfs/objects/batch") => {
+                        assert_eq!(authorization.as_deref(), Some("Bearer token"));
+                        let request: Value = serde_json::from_slice(&body).unwrap();
+                        let oid = request["objects"][0]["oid"].as_str().unwrap();
+                        let size = request["objects"][0]["size"].as_u64().unwrap();
+
+                        let upload = if size > 8 {
+                            let mut header = json!({"chunk_size": "4"});
+                            for part_number in 1..=size.div_ceil(4) {
+                                header[format!("{part_number:05}")] =
+                                    json!(format!("{base}/part/{oid}/{part_number}"));
+                            }
+                            json!({"href": format!("{base}/complete/{oid}"), "header": header})
+                        } else {
+                            json!({"href": format!("{base}/upload/{oid}")})
+                        };
+
+                        json!({"objects": [{
+                            "oid": oid,
+                            "size": size,
+                            "actions": {
+                                "upload": upload,
+                                "verify": {"href": format!("{base}/verify")},
+                            },
+                        }]})
+                    },
+                    ("PUT", target) if target.starts_with("/upload/") => {
+                        // Pre-signed URLs must not receive the Hub token.
+                        assert!(authorization.is_none());
+                        hub.lfs_objects
+                            .insert(target["/upload/".len()..].to_string(), body);
+                        json!({})
+                    },
+                    ("PUT", target) if target.starts_with("/part/") => {
+                        assert!(authorization.is_none());
+                        etag = Some(format!("\"{target}\""));
+                        hub.lfs_parts.insert(target.to_string(), body);
+                        json!({})
+                    },
+                    ("POST", target) if target.starts_with("/complete/") => {
+                        let oid = &target["/complete/".len()..];
+                        let request: Value = serde_json::from_slice(&body).unwrap();
+                        let mut object = vec![];
+
+                        for part in request["parts"].as_array().unwrap() {
+                            let etag = part["etag"].as_str().unwrap();
+                            let part_target = etag.trim_matches('"');
+                            assert_eq!(part_target, format!("/part/{oid}/{}", part["partNumber"]));
+                            object.extend_from_slice(&hub.lfs_parts[part_target]);
+                        }
+
+                        hub.lfs_objects.insert(oid.to_string(), object);
+                        json!({})
+                    },
+                    ("POST", "/verify") => {
+                        let request: Value = serde_json::from_slice(&body).unwrap();
+                        let object = &hub.lfs_objects[request["oid"].as_str().unwrap()];
+                        assert_eq!(object.len() as u64, request["size"].as_u64().unwrap());
+                        json!({})
+                    },
+                    ("POST", "/api/datasets/user/repo/commit/main") => {
+                        assert_eq!(authorization.as_deref(), Some("Bearer token"));
+                        hub.commits.push(
+                            body.split(|c| *c == b'\n')
+                                .map(|line| serde_json::from_slice(line).unwrap())
+                                .collect(),
+                        );
+                        json!({"commitOid": "abc"})
+                    },
+                    v => panic!("unexpected request: {v:?}"),
+                };
+
+                let response = response.to_string();
+                let etag = etag.map_or(String::new(), |v| format!("ETag: {v}\r\n"));
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n{etag}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+
+        endpoint
+    }
+
+    #[test]
+    fn test_hf_commit_batch() {
+        let hub = Arc::new(Mutex::new(MockHub::default()));
+        let endpoint = serve_hub(hub.clone());
+
+        let cloud_options = crate::cloud::CloudOptions {
+            config: Some(crate::cloud::CloudConfig::Http {
+                headers: vec![("Authorization".into(), "Bearer token".into())],
+            }),
+            ..Default::default()
+        };
+
+        let batch = Arc::new(
+            HfCommitBatch::with_endpoint(
+                &PlRefPath::new("hf://datasets/user/repo/data/"),
+                endpoint,
+                Some(&cloud_options),
+            )
+            .unwrap(),
+        );
+
+        let files: [(&str, &[u8]); 3] = [
+            ("data/small.csv", b"a,b\n"),
+            ("data/basic.csv", b"a,b\n1,2\n"),
+            ("data/multipart.csv", b"a,b\n1,2\n3,4\n"),
+        ];
+
+        for (path, content) in files {
+            let mut writer = batch
+                .open_file(&PlRefPath::new(format!("hf://datasets/user/repo/{path}")))
+                .unwrap();
+            writer.write_all(content).unwrap();
+            writer.close().unwrap();
+        }
+
+        assert!(
+            batch
+                .open_file(&PlRefPath::new("hf://datasets/user/other/data.csv"))
+                .is_err()
+        );
+
+        crate::pl_async::get_runtime()
+            .block_on(batch.commit(None))
+            .unwrap();
+
+        let hub = hub.lock().unwrap();
+        let oid = |content: &[u8]| format!("{:x}", Sha256::digest(content));
+
+        assert_eq!(hub.commits.len(), 1);
+        assert_eq!(
+            hub.commits[0],
+            vec![
+                json!({
+                    "key": "header",
+                    "value": {"summary": "Upload 3 files with polars", "description": ""},
+                }),
+                json!({
+                    "key": "file",
+                    "value": {"content": "YSxiCg==", "path": "data/small.csv", "encoding": "base64"},
+                }),
+                json!({
+                    "key": "lfsFile",
+                    "value": {
+                        "path": "data/basic.csv",
+                        "algo": "sha256",
+                        "oid": oid(files[1].1),
+                        "size": 8,
+                    },
+                }),
+                json!({
+                    "key": "lfsFile",
+                    "value": {
+                        "path": "data/multipart.csv",
+                        "algo": "sha256",
+                        "oid": oid(files[2].1),
+                        "size": 12,
+                    },
+                }),
+            ]
+        );
+
+        assert_eq!(hub.lfs_objects[&oid(files[1].1)], files[1].1);
+        assert_eq!(hub.lfs_objects[&oid(files[2].1)], files[2].1);
+    }
+}
@@ -12,6 +12,9 @@ use polars_utils::pl_str::PlSmallStr;
 #[cfg(feature = "cloud")]
 mod hugging_face;
 
+#[cfg(feature = "hf_upload")]
+pub use hugging_face::{HfCommitBatch, HfFileWriter};
+
 use crate::cloud::CloudOptions;
 
 #[allow(clippy::bind_instead_of_map)]
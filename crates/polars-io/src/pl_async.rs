@@ -32,6 +32,26 @@ pub(super) fn get_download_chunk_size() -> usize {
     *DOWNLOAD_CHUNK_SIZE
 }
 
+/// Maximum gap in bytes between two ranges for them to be fetched with a single request. If
+/// unset, the gap is derived from the sizes of the ranges.
+static RANGE_COALESCE_MAX_GAP: LazyLock<Option<usize>> = LazyLock::new(|| {
+    let v: Option<usize> = std::env::var("POLARS_RANGE_COALESCE_MAX_GAP")
+        .ok()
+        .map(|x| x.parse().expect("integer"));
+
+    if config::verbose()
+        && let Some(v) = v
+    {
+        eprintln!("async range_coalesce_max_gap: {v}")
+    }
+
+    v
+});
+
+pub(super) fn get_range_coalesce_max_gap() -> Option<usize> {
+    *RANGE_COALESCE_MAX_GAP
+}
+
 pub trait GetSize {
     fn size(&self) -> u64;
 }
@@ -12,4 +12,6 @@ pub use crate::options::*;
 pub use crate::parquet::{metadata::*, read::*, write::*};
 pub use crate::path_utils::*;
 pub use crate::shared::{SerReader, SerWriter};
+#[cfg(feature = "spreadsheet")]
+pub use crate::spreadsheet::*;
 pub use crate::utils::*;
@@ -0,0 +1,157 @@
+//! # Reading and writing spreadsheets.
+//!
+//! Excel (xlsx) and OpenDocument (ods) spreadsheets are read with [`SpreadsheetReader`], which
+//! supports selecting a sheet and a range of cells, detects the header row and infers the column
+//! types. Data frames are written to xlsx workbooks with [`XlsxWriter`].
+mod read;
+mod write;
+
+pub use read::{
+    CellRange, SheetSelection, SpreadsheetFormat, SpreadsheetHeader, SpreadsheetReader,
+};
+pub use write::XlsxWriter;
+
+/// Days between the Excel epoch (1899-12-30) and the Unix epoch.
+const EXCEL_UNIX_EPOCH_DAYS: f64 = 25569.0;
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use polars_core::df;
+    use polars_core::prelude::*;
+
+    use super::{CellRange, SheetSelection, SpreadsheetHeader, SpreadsheetReader, XlsxWriter};
+    use crate::{SerReader, SerWriter};
+
+    #[test]
+    fn test_cell_range_parse() {
+        let range = "B2:AA10".parse::<CellRange>().unwrap();
+        assert_eq!(range.start, (1, 1));
+        assert_eq!(range.end, Some((9, 26)));
+
+        let range = "c3".parse::<CellRange>().unwrap();
+        assert_eq!(range.start, (2, 2));
+        assert_eq!(range.end, None);
+
+        assert!("A0".parse::<CellRange>().is_err());
+        assert!("3B".parse::<CellRange>().is_err());
+        assert!("C3:A1".parse::<CellRange>().is_err());
+    }
+
+    #[test]
+    fn test_xlsx_roundtrip() {
+        let mut df = df!(
+            "id" => [1i64, 2, 3],
+            "name" => [Some("a"), None, Some("c")],
+            "score" => [0.5, 1.25, 2.0],
+            "passed" => [true, false, true],
+            "code" => ["1", "2", "3"],
+        )
+        .unwrap();
+        let day = Series::new("day".into(), [19000i32, 19001, 19002])
+            .cast(&DataType::Date)
+            .unwrap();
+        df.with_column(day.into_column()).unwrap();
+
+        let mut buf = vec![];
+        XlsxWriter::new(&mut buf)
+            .with_sheet_name(Some("data".into()))
+            .with_column_format("score", "0.00")
+            .finish(&mut df)
+            .unwrap();
+
+        let reader = || SpreadsheetReader::new(Cursor::new(buf.clone()));
+
+        // Text cells holding numbers are parsed like CSV fields.
+        let mut expected = df.clone();
+        expected
+            .with_column(Series::new("code".into(), [1i64, 2, 3]).into_column())
+            .unwrap();
+        let out = reader().finish().unwrap();
+        assert!(out.equals_missing(&expected));
+
+        let out = reader()
+            .with_sheet(SheetSelection::Name("data".into()))
+            .with_n_rows(Some(1))
+            .finish()
+            .unwrap();
+        assert_eq!(out.height(), 1);
+        assert!(
+            reader()
+                .with_sheet(SheetSelection::Name("missing".into()))
+                .finish()
+                .is_err()
+        );
+        assert!(
+            reader()
+                .with_sheet(SheetSelection::Index(1))
+                .finish()
+                .is_err()
+        );
+
+        let out = reader()
+            .with_cell_range(Some("A2:B3".parse().unwrap()))
+            .with_header(SpreadsheetHeader::None)
+            .finish()
+            .unwrap();
+        let expected = df!("column_1" => [1i64, 2], "column_2" => [Some("a"), None]).unwrap();
+        assert!(out.equals_missing(&expected));
+
+        // The first row of the range only holds text, so it is used as header.
+        let out = reader()
+            .with_cell_range(Some("B1:C4".parse().unwrap()))
+            .finish()
+            .unwrap();
+        let expected = df!(
+            "name" => [Some("a"), None, Some("c")],
+            "score" => [0.5, 1.25, 2.0],
+        )
+        .unwrap();
+        assert!(out.equals_missing(&expected));
+    }
+
+    #[test]
+    fn test_xlsx_header_inference() {
+        let write = |df: &mut DataFrame| {
+            let mut buf = vec![];
+            XlsxWriter::new(&mut buf).finish(df).unwrap();
+            SpreadsheetReader::new(Cursor::new(buf))
+        };
+
+        // The header holds the same type of values as the rows below.
+        let mut df = df!("a" => ["x", "y"], "b" => ["z", "w"]).unwrap();
+        let out = write(&mut df).finish().unwrap();
+        let expected = df!(
+            "column_1" => ["a", "x", "y"],
+            "column_2" => ["b", "z", "w"],
+        )
+        .unwrap();
+        assert!(out.equals(&expected));
+
+        // Only a header row.
+        let mut df = DataFrame::empty_with_schema(&Schema::from_iter([Field::new(
+            "a".into(),
+            DataType::String,
+        )]));
+        let out = write(&mut df).finish().unwrap();
+        assert_eq!(out.get_column_names(), ["a"]);
+        assert_eq!(out.height(), 0);
+    }
+
+    #[test]
+    fn test_xlsx_lossy_cells() {
+        let mut df = df!("id" => [1, 2, 3], "x" => [1.0, 2.0, 2.5]).unwrap();
+        let mut buf = vec![];
+        XlsxWriter::new(&mut buf).finish(&mut df).unwrap();
+        let reader = || SpreadsheetReader::new(Cursor::new(buf.clone()));
+
+        // The last value does not fit the type inferred from the first rows.
+        let err = reader().infer_schema(Some(2)).finish().unwrap_err();
+        assert!(matches!(err, PolarsError::SchemaMismatch(_)));
+        assert!(err.to_string().contains("in row 4 of column 'x'"));
+
+        let out = reader().infer_schema(None).finish().unwrap();
+        assert_eq!(out.column("x").unwrap().dtype(), &DataType::Float64);
+    }
+}
@@ -0,0 +1,513 @@
+use std::fmt::Display;
+use std::io::{Read, Seek};
+use std::str::FromStr;
+
+use calamine::{Data, ExcelDateTime, Ods, Range, Reader, Xlsx};
+use polars_core::prelude::*;
+use polars_error::to_compute_err;
+use polars_utils::format_pl_smallstr;
+
+use crate::csv::read::schema_inference::{finish_infer_field_schema, infer_field_schema};
+use crate::shared::SerReader;
+
+/// File format of a spreadsheet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpreadsheetFormat {
+    /// Office Open XML workbook (`.xlsx`).
+    Xlsx,
+    /// OpenDocument spreadsheet (`.ods`).
+    Ods,
+}
+
+/// Sheet of a workbook to read.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SheetSelection {
+    /// Zero-based position of the sheet in the workbook.
+    Index(usize),
+    Name(PlSmallStr),
+}
+
+impl Default for SheetSelection {
+    fn default() -> Self {
+        Self::Index(0)
+    }
+}
+
+/// Location of the header row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SpreadsheetHeader {
+    /// Skip leading empty rows and use the first row as header if it only contains text, and the
+    /// types of its cells differ from those of the rows below.
+    #[default]
+    Infer,
+    /// Zero-based row, relative to the start of the cell range, holding the header. The data
+    /// starts at the next row.
+    Row(usize),
+    /// The sheet has no header, columns are named `column_1`, `column_2`, ...
+    None,
+}
+
+/// Rectangular range of cells, e.g. `B2:F100`.
+///
+/// Positions are zero-based `(row, column)` pairs. An open range extends to the last used cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CellRange {
+    pub start: (u32, u32),
+    pub end: Option<(u32, u32)>,
+}
+
+impl FromStr for CellRange {
+    type Err = PolarsError;
+
+    /// Parses a range in A1 notation, e.g. `A1:C10`, or a single start cell, e.g. `B2`.
+    fn from_str(s: &str) -> PolarsResult<Self> {
+        let (start, end) = match s.split_once(':') {
+            Some((start, end)) => (start, Some(end)),
+            None => (s, None),
+        };
+
+        let start = parse_a1_cell(start)?;
+        let end = end.map(parse_a1_cell).transpose()?;
+
+        if let Some(end) = end {
+            polars_ensure!(
+                start.0 <= end.0 && start.1 <= end.1,
+                InvalidOperation: "cell range '{}' ends before it starts", s
+            );
+        }
+
+        Ok(Self { start, end })
+    }
+}
+
+fn parse_a1_cell(cell: &str) -> PolarsResult<(u32, u32)> {
+    let err =
+        || polars_err!(InvalidOperation: "invalid cell reference '{}', expected e.g. 'B2'", cell);
+
+    let cell = cell.trim();
+    let split = cell.find(|c: char| c.is_ascii_digit()).ok_or_else(err)?;
+    let (letters, digits) = cell.split_at(split);
+
+    if letters.is_empty() || !letters.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return Err(err());
+    }
+
+    let col = letters.bytes().try_fold(0u32, |acc, b| {
+        acc.checked_mul(26)?
+            .checked_add((b.to_ascii_uppercase() - b'A') as u32 + 1)
+    });
+    let row = digits.parse::<u32>().ok().filter(|row| *row > 0);
+
+    match (row, col) {
+        (Some(row), Some(col)) => Ok((row - 1, col - 1)),
+        _ => Err(err()),
+    }
+}
+
+/// Read Excel (xlsx) and OpenDocument (ods) spreadsheets into a [`DataFrame`].
+///
+/// Column types are inferred from the cells with the same rules as the CSV reader, text cells
+/// such as `"12"` are parsed into numbers.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use polars_core::prelude::*;
+/// use polars_io::prelude::*;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     let file = File::open("report.xlsx")?;
+///
+///     SpreadsheetReader::new(file)
+///         .with_sheet(SheetSelection::Name("Q3".into()))
+///         .with_cell_range(Some("B3:H200".parse()?))
+///         .finish()
+/// }
+/// ```
+#[must_use]
+pub struct SpreadsheetReader<R: Read + Seek> {
+    reader: R,
+    format: Option<SpreadsheetFormat>,
+    sheet: SheetSelection,
+    header: SpreadsheetHeader,
+    cell_range: Option<CellRange>,
+    n_rows: Option<usize>,
+    infer_schema_length: Option<usize>,
+    schema_overwrite: Option<SchemaRef>,
+    try_parse_dates: bool,
+}
+
+impl<R: Read + Seek> SpreadsheetReader<R> {
+    /// Set the file format. Defaults to detecting it from the file contents.
+    pub fn with_format(mut self, format: Option<SpreadsheetFormat>) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the sheet to read. Defaults to the first sheet.
+    pub fn with_sheet(mut self, sheet: SheetSelection) -> Self {
+        self.sheet = sheet;
+        self
+    }
+
+    pub fn with_header(mut self, header: SpreadsheetHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Only read the cells in this range, including the header.
+    pub fn with_cell_range(mut self, cell_range: Option<CellRange>) -> Self {
+        self.cell_range = cell_range;
+        self
+    }
+
+    /// Stop reading after `n_rows` data rows.
+    pub fn with_n_rows(mut self, n_rows: Option<usize>) -> Self {
+        self.n_rows = n_rows;
+        self
+    }
+
+    /// Set the number of rows used to infer the column types. `None` uses all rows. Defaults to
+    /// 100.
+    pub fn infer_schema(mut self, infer_schema_length: Option<usize>) -> Self {
+        self.infer_schema_length = infer_schema_length;
+        self
+    }
+
+    /// Overwrite the inferred types of the columns in the schema.
+    pub fn with_schema_overwrite(mut self, schema: Option<SchemaRef>) -> Self {
+        self.schema_overwrite = schema;
+        self
+    }
+
+    /// Try to parse text cells as dates and datetimes.
+    pub fn with_try_parse_dates(mut self, try_parse_dates: bool) -> Self {
+        self.try_parse_dates = try_parse_dates;
+        self
+    }
+}
+
+fn detect_format<R: Read + Seek>(reader: &mut R) -> PolarsResult<SpreadsheetFormat> {
+    // An ods file is a zip archive starting with an uncompressed `mimetype` entry, xlsx files
+    // start with `[Content_Types].xml` instead.
+    let mut magic = Vec::with_capacity(38);
+    reader.by_ref().take(38).read_to_end(&mut magic)?;
+    reader.rewind()?;
+
+    Ok(if magic.get(30..) == Some(b"mimetype".as_slice()) {
+        SpreadsheetFormat::Ods
+    } else {
+        SpreadsheetFormat::Xlsx
+    })
+}
+
+fn read_sheet<R, W>(mut workbook: W, sheet: &SheetSelection) -> PolarsResult<Range<Data>>
+where
+    R: Read + Seek,
+    W: Reader<R>,
+    W::Error: Display,
+{
+    let sheet_names = workbook.sheet_names();
+
+    let name = match sheet {
+        SheetSelection::Index(idx) => sheet_names.get(*idx).ok_or_else(|| {
+            polars_err!(
+                OutOfBounds: "sheet index {} is out of bounds for a workbook with {} sheets",
+                idx, sheet_names.len()
+            )
+        })?,
+        SheetSelection::Name(name) => sheet_names
+            .iter()
+            .find(|s| s.as_str() == name.as_str())
+            .ok_or_else(|| {
+                polars_err!(
+                    ComputeError: "sheet '{}' not found, available sheets: {:?}",
+                    name, sheet_names
+                )
+            })?,
+    };
+
+    workbook.worksheet_range(name).map_err(to_compute_err)
+}
+
+fn is_empty_cell(cell: &Data) -> bool {
+    matches!(cell, Data::Empty) || matches!(cell, Data::String(s) if s.is_empty())
+}
+
+fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::String(s) | Data::DateTimeIso(s) | Data::DurationIso(s) => s.clone(),
+        Data::DateTime(dt) if !dt.is_duration() => dt
+            .as_datetime()
+            .map_or_else(|| dt.as_f64().to_string(), |dt| dt.to_string()),
+        cell => cell.to_string(),
+    }
+}
+
+fn column_name(i: usize) -> PlSmallStr {
+    format_pl_smallstr!("column_{}", i + 1)
+}
+
+fn header_names(header: Option<&[Data]>, width: usize) -> Vec<PlSmallStr> {
+    let mut names = Vec::with_capacity(width);
+    let mut counts = PlHashMap::with_capacity(width);
+
+    for i in 0..width {
+        let name = match header.and_then(|row| row.get(i)) {
+            Some(cell) if !is_empty_cell(cell) => PlSmallStr::from_string(cell_to_string(cell)),
+            _ => column_name(i),
+        };
+
+        let count = counts.entry(name.clone()).or_insert(0usize);
+        if *count != 0 {
+            names.push(format_pl_smallstr!("{}_duplicated_{}", name, *count - 1))
+        } else {
+            names.push(name)
+        }
+        *count += 1;
+    }
+
+    names
+}
+
+/// Converts an Excel serial date to a date or datetime, or a duration if the cell holds a time
+/// span.
+fn excel_datetime_to_any_value(dt: &ExcelDateTime) -> Option<AnyValue<'static>> {
+    if dt.is_duration() {
+        let us = (dt.as_f64() * 86_400_000_000.0).round() as i64;
+        return Some(AnyValue::Duration(us, TimeUnit::Microseconds));
+    }
+
+    let us = dt.as_datetime()?.and_utc().timestamp_micros();
+    Some(if us % 86_400_000_000 == 0 {
+        AnyValue::Date((us / 86_400_000_000) as i32)
+    } else {
+        AnyValue::Datetime(us, TimeUnit::Microseconds, None)
+    })
+}
+
+fn infer_cell_dtype(cell: &Data, try_parse_dates: bool) -> Option<DataType> {
+    Some(match cell {
+        cell if is_empty_cell(cell) => return None,
+        Data::Empty | Data::Error(_) => return None,
+        Data::Int(_) => DataType::Int64,
+        // Numbers are mostly stored as floats, whole numbers are treated as integers.
+        Data::Float(v) if v.fract() == 0.0 && v.abs() < (1i64 << 53) as f64 => DataType::Int64,
+        Data::Float(_) => DataType::Float64,
+        Data::Bool(_) => DataType::Boolean,
+        Data::String(s) | Data::DateTimeIso(s) => infer_field_schema(s, try_parse_dates, false),
+        Data::DurationIso(_) => DataType::String,
+        Data::DateTime(dt) => match excel_datetime_to_any_value(dt) {
+            Some(av) => av.dtype(),
+            None => DataType::Float64,
+        },
+    })
+}
+
+fn infer_column_dtype<'a>(
+    cells: impl Iterator<Item = &'a Data>,
+    try_parse_dates: bool,
+) -> DataType {
+    let mut possibilities = PlHashSet::with_capacity(4);
+    possibilities.extend(cells.filter_map(|cell| infer_cell_dtype(cell, try_parse_dates)));
+
+    // Excel stores dates and datetimes alike, a column mixing both is a datetime column.
+    if possibilities.contains(&DataType::Date)
+        && possibilities.contains(&DataType::Datetime(TimeUnit::Microseconds, None))
+    {
+        possibilities.remove(&DataType::Date);
+    }
+
+    finish_infer_field_schema(&possibilities)
+}
+
+/// A text-only row is a header if it is followed by no data, or if the type of at least one of its
+/// cells differs from the type inferred for the rows below it.
+fn is_inferred_header(
+    row: &[Data],
+    below: &[&[Data]],
+    infer_schema_length: Option<usize>,
+    try_parse_dates: bool,
+) -> bool {
+    if !row
+        .iter()
+        .all(|cell| matches!(cell, Data::String(_) | Data::Empty))
+    {
+        return false;
+    }
+
+    let below = &below[..infer_schema_length.map_or(below.len(), |n| n.min(below.len()))];
+    below.is_empty()
+        || row.iter().enumerate().any(|(i, cell)| {
+            infer_cell_dtype(cell, try_parse_dates).is_some_and(|dtype| {
+                dtype != infer_column_dtype(below.iter().map(|r| &r[i]), try_parse_dates)
+            })
+        })
+}
+
+fn cell_to_any_value(cell: &Data, dtype: &DataType) -> AnyValue<'static> {
+    match cell {
+        cell if is_empty_cell(cell) => AnyValue::Null,
+        Data::Empty | Data::Error(_) => AnyValue::Null,
+        cell if dtype == &DataType::String => AnyValue::StringOwned(cell_to_string(cell).into()),
+        Data::Int(v) => AnyValue::Int64(*v),
+        Data::Float(v) => AnyValue::Float64(*v),
+        Data::Bool(v) => AnyValue::Boolean(*v),
+        Data::String(s) if dtype == &DataType::Boolean => {
+            if s.eq_ignore_ascii_case("true") {
+                AnyValue::Boolean(true)
+            } else if s.eq_ignore_ascii_case("false") {
+                AnyValue::Boolean(false)
+            } else {
+                AnyValue::Null
+            }
+        },
+        Data::String(s) | Data::DateTimeIso(s) | Data::DurationIso(s) => {
+            AnyValue::StringOwned(s.as_str().into())
+        },
+        Data::DateTime(dt) => {
+            excel_datetime_to_any_value(dt).unwrap_or(AnyValue::Float64(dt.as_f64()))
+        },
+    }
+}
+
+/// Builds a column of `dtype`, `first_row` is the one-based sheet row of the first cell.
+fn build_column<'a>(
+    name: PlSmallStr,
+    cells: impl Iterator<Item = &'a Data>,
+    dtype: &DataType,
+    first_row: usize,
+) -> PolarsResult<Column> {
+    let values = cells
+        .map(|cell| cell_to_any_value(cell, dtype))
+        .collect::<Vec<_>>();
+
+    // Text cells are parsed by the cast.
+    let s = Series::from_any_values(name, &values, false)?;
+    let out = s.cast(dtype)?;
+
+    // Cells that cannot be represented in `dtype` would become null or be truncated.
+    let mut lossy = &s.is_not_null() & &out.is_null();
+    if s.dtype().is_float() && dtype.is_integer() {
+        lossy = &lossy | &out.cast(s.dtype())?.not_equal_missing(&s)?;
+    }
+    if let Some(idx) = lossy.into_iter().position(|v| v == Some(true)) {
+        polars_bail!(
+            SchemaMismatch:
+            "cannot read the value {} in row {} of column '{}' as {}; set the dtype of the \
+            column with a schema overwrite or increase the schema inference length",
+            values[idx], first_row + idx, s.name(), dtype
+        );
+    }
+
+    Ok(out.into_column())
+}
+
+impl<R: Read + Seek> SerReader<R> for SpreadsheetReader<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            format: None,
+            sheet: SheetSelection::default(),
+            header: SpreadsheetHeader::default(),
+            cell_range: None,
+            n_rows: None,
+            infer_schema_length: Some(100),
+            schema_overwrite: None,
+            try_parse_dates: false,
+        }
+    }
+
+    fn finish(mut self) -> PolarsResult<DataFrame> {
+        let format = match self.format {
+            Some(format) => format,
+            None => detect_format(&mut self.reader)?,
+        };
+
+        let range = match format {
+            SpreadsheetFormat::Xlsx => {
+                read_sheet(Xlsx::new(self.reader).map_err(to_compute_err)?, &self.sheet)?
+            },
+            SpreadsheetFormat::Ods => {
+                read_sheet(Ods::new(self.reader).map_err(to_compute_err)?, &self.sheet)?
+            },
+        };
+
+        let range = match (self.cell_range, range.end()) {
+            (Some(CellRange { start, end }), Some(used_end)) => {
+                range.range(start, end.unwrap_or(used_end))
+            },
+            (Some(_), None) => Range::default(),
+            (None, _) => range,
+        };
+
+        let width = range.width();
+        let rows = range.rows().collect::<Vec<_>>();
+
+        let (header, data_rows) = match self.header {
+            SpreadsheetHeader::None => (None, rows.as_slice()),
+            SpreadsheetHeader::Row(idx) => {
+                polars_ensure!(
+                    idx < rows.len(),
+                    OutOfBounds: "header row {} is out of bounds for a sheet range of {} rows",
+                    idx, rows.len()
+                );
+                (Some(rows[idx]), &rows[idx + 1..])
+            },
+            SpreadsheetHeader::Infer => {
+                let first = rows
+                    .iter()
+                    .position(|row| !row.iter().all(is_empty_cell))
+                    .unwrap_or(rows.len());
+
+                match rows.get(first) {
+                    Some(row)
+                        if is_inferred_header(
+                            row,
+                            &rows[first + 1..],
+                            self.infer_schema_length,
+                            self.try_parse_dates,
+                        ) =>
+                    {
+                        (Some(*row), &rows[first + 1..])
+                    },
+                    _ => (None, &rows[first..]),
+                }
+            },
+        };
+
+        let first_data_row =
+            range.start().map_or(0, |(row, _)| row as usize) + rows.len() - data_rows.len() + 1;
+
+        let n_rows = self
+            .n_rows
+            .map_or(data_rows.len(), |n| n.min(data_rows.len()));
+        let data_rows = &data_rows[..n_rows];
+        let infer_length = self
+            .infer_schema_length
+            .map_or(data_rows.len(), |n| n.min(data_rows.len()));
+
+        let columns = header_names(header, width)
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let cells = || data_rows.iter().map(move |row| &row[i]);
+
+                let dtype = match self
+                    .schema_overwrite
+                    .as_deref()
+                    .and_then(|schema| schema.get(&name))
+                {
+                    Some(dtype) => dtype.clone(),
+                    None => infer_column_dtype(cells().take(infer_length), self.try_parse_dates),
+                };
+
+                build_column(name, cells(), &dtype, first_data_row)
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        DataFrame::new(n_rows, columns)
+    }
+}
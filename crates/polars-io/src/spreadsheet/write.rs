@@ -0,0 +1,198 @@
+use std::io::Write;
+
+use polars_core::prelude::*;
+use polars_error::to_compute_err;
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+
+use super::EXCEL_UNIX_EPOCH_DAYS;
+use crate::shared::SerWriter;
+
+/// Maximum number of rows of an xlsx worksheet.
+const MAX_ROWS: usize = 1_048_576;
+/// Maximum number of columns of an xlsx worksheet.
+const MAX_COLUMNS: usize = 16_384;
+
+const DEFAULT_DATE_FORMAT: &str = "yyyy-mm-dd";
+const DEFAULT_DATETIME_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+
+/// Write a [`DataFrame`] to a single sheet of an Excel (xlsx) workbook.
+///
+/// Dates and datetimes are written as Excel dates, timezone-aware datetimes are written in UTC.
+/// Types without an Excel counterpart are written as text.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use polars_core::prelude::*;
+/// use polars_io::prelude::*;
+///
+/// fn example(df: &mut DataFrame) -> PolarsResult<()> {
+///     let file = File::create("report.xlsx")?;
+///
+///     XlsxWriter::new(file)
+///         .with_sheet_name(Some("Q3".into()))
+///         .with_column_format("revenue", "#,##0.00")
+///         .finish(df)
+/// }
+/// ```
+#[must_use]
+pub struct XlsxWriter<W: Write> {
+    writer: W,
+    sheet_name: Option<PlSmallStr>,
+    include_header: bool,
+    column_formats: PlHashMap<PlSmallStr, PlSmallStr>,
+}
+
+impl<W: Write> XlsxWriter<W> {
+    /// Set the name of the sheet. Defaults to `Sheet1`.
+    pub fn with_sheet_name(mut self, sheet_name: Option<PlSmallStr>) -> Self {
+        self.sheet_name = sheet_name;
+        self
+    }
+
+    /// Set whether to write the column names as a header row. Defaults to true.
+    pub fn include_header(mut self, include_header: bool) -> Self {
+        self.include_header = include_header;
+        self
+    }
+
+    /// Set the Excel number format of a column, e.g. `"0.00%"` or `"dd/mm/yyyy"`.
+    pub fn with_column_format(
+        mut self,
+        column: impl Into<PlSmallStr>,
+        num_format: impl Into<PlSmallStr>,
+    ) -> Self {
+        self.column_formats.insert(column.into(), num_format.into());
+        self
+    }
+
+    fn column_format(&self, name: &str, dtype: &DataType) -> Option<Format> {
+        let num_format = match self.column_formats.get(name) {
+            Some(num_format) => num_format.as_str(),
+            None => match dtype {
+                DataType::Date => DEFAULT_DATE_FORMAT,
+                DataType::Datetime(_, _) => DEFAULT_DATETIME_FORMAT,
+                _ => return None,
+            },
+        };
+
+        Some(Format::new().set_num_format(num_format))
+    }
+}
+
+fn write_cell(
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    value: AnyValue,
+    format: Option<&Format>,
+) -> Result<(), XlsxError> {
+    let write_number = |worksheet: &mut Worksheet, v: f64| {
+        match format {
+            Some(format) => worksheet.write_number_with_format(row, col, v, format),
+            None => worksheet.write_number(row, col, v),
+        }
+        .map(|_| ())
+    };
+    let write_string = |worksheet: &mut Worksheet, v: &str| {
+        match format {
+            Some(format) => worksheet.write_string_with_format(row, col, v, format),
+            None => worksheet.write_string(row, col, v),
+        }
+        .map(|_| ())
+    };
+
+    match value {
+        AnyValue::Null => Ok(()),
+        AnyValue::Boolean(v) => match format {
+            Some(format) => worksheet.write_boolean_with_format(row, col, v, format),
+            None => worksheet.write_boolean(row, col, v),
+        }
+        .map(|_| ()),
+        AnyValue::String(v) => write_string(worksheet, v),
+        AnyValue::StringOwned(v) => write_string(worksheet, v.as_str()),
+        AnyValue::Date(days) => write_number(worksheet, days as f64 + EXCEL_UNIX_EPOCH_DAYS),
+        AnyValue::Datetime(v, tu, _) | AnyValue::DatetimeOwned(v, tu, _) => {
+            let units_per_day = match tu {
+                TimeUnit::Nanoseconds => 86_400_000_000_000.0,
+                TimeUnit::Microseconds => 86_400_000_000.0,
+                TimeUnit::Milliseconds => 86_400_000.0,
+            };
+            write_number(worksheet, v as f64 / units_per_day + EXCEL_UNIX_EPOCH_DAYS)
+        },
+        // Excel has no representation for NaN and infinity.
+        v if v.is_primitive_numeric() => match v.extract::<f64>() {
+            Some(v) if v.is_finite() => write_number(worksheet, v),
+            _ => write_string(worksheet, &v.to_string()),
+        },
+        v => write_string(worksheet, &v.to_string()),
+    }
+}
+
+impl<W: Write> SerWriter<W> for XlsxWriter<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            sheet_name: None,
+            include_header: true,
+            column_formats: PlHashMap::default(),
+        }
+    }
+
+    fn finish(&mut self, df: &mut DataFrame) -> PolarsResult<()> {
+        let header_rows = self.include_header as usize;
+
+        polars_ensure!(
+            df.height() + header_rows <= MAX_ROWS,
+            ComputeError: "cannot write {} rows to an xlsx sheet, the maximum is {} rows",
+            df.height() + header_rows, MAX_ROWS
+        );
+        polars_ensure!(
+            df.width() <= MAX_COLUMNS,
+            ComputeError: "cannot write {} columns to an xlsx sheet, the maximum is {} columns",
+            df.width(), MAX_COLUMNS
+        );
+
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+
+        if let Some(sheet_name) = &self.sheet_name {
+            worksheet
+                .set_name(sheet_name.as_str())
+                .map_err(to_compute_err)?;
+        }
+
+        let header_format = Format::new().set_bold();
+
+        for (col, column) in df.columns().iter().enumerate() {
+            let col = col as u16;
+
+            if self.include_header {
+                worksheet
+                    .write_string_with_format(0, col, column.name().as_str(), &header_format)
+                    .map_err(to_compute_err)?;
+            }
+
+            let format = self.column_format(column.name(), column.dtype());
+
+            for (row, value) in column.as_materialized_series().iter().enumerate() {
+                write_cell(
+                    worksheet,
+                    (row + header_rows) as u32,
+                    col,
+                    value,
+                    format.as_ref(),
+                )
+                .map_err(to_compute_err)?;
+            }
+        }
+
+        let buf = workbook.save_to_buffer().map_err(to_compute_err)?;
+        self.writer.write_all(&buf)?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
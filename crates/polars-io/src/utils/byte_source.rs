@@ -1,20 +1,31 @@
 use std::ops::Range;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "cloud")]
+use object_store::ObjectMeta;
 use polars_buffer::Buffer;
+use polars_core::config;
 use polars_core::prelude::PlHashMap;
 use polars_error::{PolarsResult, feature_gated};
 use polars_utils::_limit_path_len_io_err;
 use polars_utils::mmap::MMapSemaphore;
+#[cfg(feature = "file_cache")]
+use polars_utils::pl_path::CloudScheme;
 use polars_utils::pl_path::PlRefPath;
+use tokio::sync::Semaphore;
 
 use crate::cloud::options::CloudOptions;
 #[cfg(feature = "cloud")]
 use crate::cloud::{
     CloudLocation, ObjectStorePath, PolarsObjectStore, build_object_store, object_path_from_str,
 };
+#[cfg(feature = "file_cache")]
+use crate::file_cache::{RANGE_CACHE, RangeCache, RangeCacheKey};
 use crate::metrics::IOMetrics;
+#[cfg(feature = "file_cache")]
+use crate::metrics::OptIOMetrics;
 
 #[allow(async_fn_in_trait)]
 pub trait ByteSource: Send + Sync {
@@ -72,10 +83,40 @@ impl ByteSource for BufferByteSource {
     }
 }
 
+/// Controls how byte-range requests to object stores are coalesced and split.
+#[cfg(feature = "cloud")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeCoalesceOptions {
+    /// Ranges separated by at most this many bytes are fetched with a single request. If `None`,
+    /// the gap is 1/8th of the size of the ranges, clamped to 1-8 MiB.
+    pub max_gap: Option<usize>,
+    /// Target size of a single request. Larger (coalesced) ranges are split into requests of
+    /// around this size that are fetched concurrently.
+    pub split_size: usize,
+}
+
+#[cfg(feature = "cloud")]
+impl Default for RangeCoalesceOptions {
+    /// Configured by `POLARS_RANGE_COALESCE_MAX_GAP` and `POLARS_DOWNLOAD_CHUNK_SIZE`.
+    fn default() -> Self {
+        Self {
+            max_gap: crate::pl_async::get_range_coalesce_max_gap(),
+            split_size: crate::pl_async::get_download_chunk_size(),
+        }
+    }
+}
+
 #[cfg(feature = "cloud")]
 pub struct ObjectStoreByteSource {
     store: PolarsObjectStore,
     path: ObjectStorePath,
+    coalesce_options: RangeCoalesceOptions,
+    meta: tokio::sync::OnceCell<ObjectMeta>,
+    /// URI of the remote file, `None` for local files which are not range-cached.
+    #[cfg(feature = "file_cache")]
+    cache_uri: Option<PlRefPath>,
+    #[cfg(feature = "file_cache")]
+    io_metrics: OptIOMetrics,
 }
 
 #[cfg(feature = "cloud")]
@@ -85,23 +126,79 @@ impl ObjectStoreByteSource {
         cloud_options: Option<&CloudOptions>,
         io_metrics: Option<Arc<IOMetrics>>,
     ) -> PolarsResult<Self> {
+        #[cfg(feature = "file_cache")]
+        let cache_uri = (!matches!(
+            path.scheme(),
+            None | Some(CloudScheme::File | CloudScheme::FileNoHostname)
+        ))
+        .then(|| path.clone());
+
         let (CloudLocation { prefix, .. }, mut store) =
             build_object_store(path, cloud_options, false).await?;
         let path = object_path_from_str(&prefix)?;
 
-        store.set_io_metrics(io_metrics);
+        store.set_io_metrics(io_metrics.clone());
+
+        Ok(Self {
+            store,
+            path,
+            coalesce_options: RangeCoalesceOptions::default(),
+            meta: Default::default(),
+            #[cfg(feature = "file_cache")]
+            cache_uri,
+            #[cfg(feature = "file_cache")]
+            io_metrics: OptIOMetrics(io_metrics),
+        })
+    }
+
+    async fn head(&self) -> PolarsResult<&ObjectMeta> {
+        self.meta
+            .get_or_try_init(|| self.store.head(&self.path))
+            .await
+    }
+
+    /// Returns the range cache and the key of this file if range caching is enabled.
+    #[cfg(feature = "file_cache")]
+    async fn range_cache_key(&self) -> PolarsResult<Option<(&'static RangeCache, RangeCacheKey)>> {
+        let (Some(cache), Some(uri)) = (RANGE_CACHE.as_ref(), &self.cache_uri) else {
+            return Ok(None);
+        };
+
+        let meta = self.head().await?;
+        let version = meta
+            .e_tag
+            .clone()
+            .or_else(|| meta.version.clone())
+            .unwrap_or_else(|| meta.last_modified.to_rfc3339());
 
-        Ok(Self { store, path })
+        Ok(Some((
+            cache,
+            RangeCacheKey::new(uri.as_str(), &version, meta.size as usize),
+        )))
     }
 }
 
 #[cfg(feature = "cloud")]
 impl ByteSource for ObjectStoreByteSource {
     async fn get_size(&self) -> PolarsResult<usize> {
-        Ok(self.store.head(&self.path).await?.size as usize)
+        Ok(self.head().await?.size as usize)
     }
 
     async fn get_range(&self, range: Range<usize>) -> PolarsResult<Buffer<u8>> {
+        #[cfg(feature = "file_cache")]
+        if let Some((cache, key)) = self.range_cache_key().await? {
+            let start = range.start;
+            let mut out = cache
+                .get_ranges(&key, &[range], &self.io_metrics, |mut ranges| async move {
+                    self.store
+                        .get_ranges_sort(&self.path, &mut ranges, &self.coalesce_options)
+                        .await
+                })
+                .await?;
+
+            return Ok(out.remove(&start).unwrap());
+        }
+
         self.store.get_range(&self.path, range).await
     }
 
@@ -109,7 +206,139 @@ impl ByteSource for ObjectStoreByteSource {
         &self,
         ranges: &mut [Range<usize>],
     ) -> PolarsResult<PlHashMap<usize, Buffer<u8>>> {
-        self.store.get_ranges_sort(&self.path, ranges).await
+        #[cfg(feature = "file_cache")]
+        if let Some((cache, key)) = self.range_cache_key().await? {
+            return cache
+                .get_ranges(&key, ranges, &self.io_metrics, |mut ranges| async move {
+                    self.store
+                        .get_ranges_sort(&self.path, &mut ranges, &self.coalesce_options)
+                        .await
+                })
+                .await;
+        }
+
+        self.store
+            .get_ranges_sort(&self.path, ranges, &self.coalesce_options)
+            .await
+    }
+}
+
+/// Adapts the number of concurrent prefetches to the throughput and request latency observed in
+/// [`IOMetrics`].
+///
+/// The prefetch depth is the number of permits of a semaphore that is acquired per prefetch. After
+/// every tuning interval, the depth is increased if throughput increased while all permits were in
+/// use, and decreased if request latency rose without a gain in throughput, as the store is then
+/// saturated and deeper prefetching only adds memory pressure.
+pub struct PrefetchTuner {
+    semaphore: Arc<Semaphore>,
+    min_depth: usize,
+    max_depth: usize,
+    state: Mutex<PrefetchTunerState>,
+}
+
+struct PrefetchTunerState {
+    depth: usize,
+    window_start: Instant,
+    bytes_received: u64,
+    num_requests: u64,
+    request_time_ns: u64,
+    prev_throughput: f64,
+    min_latency_ns: u64,
+}
+
+impl PrefetchTuner {
+    const TUNE_INTERVAL: Duration = Duration::from_millis(250);
+
+    /// `semaphore` must have `initial_depth` permits available.
+    pub fn new(semaphore: Arc<Semaphore>, initial_depth: usize, max_depth: usize) -> Self {
+        assert!(initial_depth > 0 && initial_depth <= max_depth);
+
+        Self {
+            semaphore,
+            min_depth: initial_depth.div_ceil(4),
+            max_depth,
+            state: Mutex::new(PrefetchTunerState {
+                depth: initial_depth,
+                window_start: Instant::now(),
+                bytes_received: 0,
+                num_requests: 0,
+                request_time_ns: 0,
+                prev_throughput: 0.0,
+                min_latency_ns: u64::MAX,
+            }),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.state.lock().unwrap().depth
+    }
+
+    /// Updates the prefetch depth if the tuning interval has elapsed. This is cheap enough to be
+    /// called for every prefetch.
+    pub fn tune(&self, io_metrics: &IOMetrics) {
+        self.tune_at(io_metrics, Instant::now())
+    }
+
+    fn tune_at(&self, io_metrics: &IOMetrics, now: Instant) {
+        let Ok(mut state) = self.state.try_lock() else {
+            return;
+        };
+
+        let elapsed = now.saturating_duration_since(state.window_start);
+
+        if elapsed < Self::TUNE_INTERVAL {
+            return;
+        }
+
+        let bytes_received = io_metrics.bytes_received.load();
+        let num_requests = io_metrics.num_requests.load();
+        let request_time_ns = io_metrics.request_time_ns.load();
+
+        let window_requests = num_requests.saturating_sub(state.num_requests);
+
+        // Nothing to tune on if no requests were made, e.g. for local files.
+        if window_requests > 0 {
+            let throughput =
+                bytes_received.saturating_sub(state.bytes_received) as f64 / elapsed.as_secs_f64();
+            let latency_ns =
+                request_time_ns.saturating_sub(state.request_time_ns) / window_requests;
+
+            state.min_latency_ns = state.min_latency_ns.min(latency_ns);
+
+            let throughput_increased = throughput > state.prev_throughput * 1.05;
+            let latency_increased = latency_ns > state.min_latency_ns.saturating_mul(2);
+            let saturated = self.semaphore.available_permits() == 0;
+
+            let depth = state.depth;
+
+            if throughput_increased && saturated && depth < self.max_depth {
+                let new_depth = (depth + depth.div_ceil(4)).min(self.max_depth);
+                self.semaphore.add_permits(new_depth - depth);
+                state.depth = new_depth;
+            } else if latency_increased && !throughput_increased && depth > self.min_depth {
+                let new_depth = (depth - depth / 4).max(self.min_depth);
+                state.depth -= self.semaphore.forget_permits(depth - new_depth);
+            }
+
+            if config::verbose() && state.depth != depth {
+                eprintln!(
+                    "[PrefetchTuner]: depth {} -> {} \
+                    (throughput: {:.0} B/s, avg request latency: {:?})",
+                    depth,
+                    state.depth,
+                    throughput,
+                    Duration::from_nanos(latency_ns)
+                );
+            }
+
+            state.prev_throughput = throughput;
+        }
+
+        state.window_start = now;
+        state.bytes_received = bytes_received;
+        state.num_requests = num_requests;
+        state.request_time_ns = request_time_ns;
     }
 }
 
@@ -212,3 +441,106 @@ impl DynByteSourceBuilder {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use tokio::sync::Semaphore;
+
+    use super::PrefetchTuner;
+    use crate::metrics::IOMetrics;
+
+    #[test]
+    fn test_prefetch_tuner() {
+        let semaphore = Arc::new(Semaphore::new(4));
+        let tuner = PrefetchTuner::new(semaphore.clone(), 4, 8);
+        let io_metrics = IOMetrics::default();
+
+        let mut permits = (0..4)
+            .map(|_| semaphore.clone().try_acquire_owned().unwrap())
+            .collect::<Vec<_>>();
+
+        let record_window = |num_bytes: u64, num_requests: u64, latency_ms: u64| {
+            io_metrics.bytes_received.fetch_add(num_bytes);
+            io_metrics.num_requests.fetch_add(num_requests);
+            io_metrics
+                .request_time_ns
+                .fetch_add(num_requests * latency_ms * 1_000_000);
+        };
+
+        let mut now = Instant::now();
+        let mut next_window = || {
+            now += Duration::from_millis(300);
+            now
+        };
+
+        // Not enough time elapsed.
+        record_window(1 << 20, 4, 50);
+        tuner.tune_at(&io_metrics, Instant::now());
+        assert_eq!(tuner.depth(), 4);
+
+        // Throughput increased with all permits in use.
+        tuner.tune_at(&io_metrics, next_window());
+        assert_eq!(tuner.depth(), 5);
+        permits.push(semaphore.clone().try_acquire_owned().unwrap());
+
+        // Throughput increased further.
+        record_window(4 << 20, 8, 50);
+        tuner.tune_at(&io_metrics, next_window());
+        assert_eq!(tuner.depth(), 7);
+        permits.extend((0..2).map(|_| semaphore.clone().try_acquire_owned().unwrap()));
+
+        // Latency increased without a gain in throughput.
+        permits.truncate(4);
+        record_window(4 << 20, 8, 200);
+        tuner.tune_at(&io_metrics, next_window());
+        assert_eq!(tuner.depth(), 6);
+        assert_eq!(semaphore.available_permits(), 2);
+
+        // No requests, e.g. local reads.
+        tuner.tune_at(&io_metrics, next_window());
+        assert_eq!(tuner.depth(), 6);
+    }
+
+    #[test]
+    fn test_prefetch_tuner_latency_bound() {
+        // Every request takes 50ms regardless of concurrency, so throughput grows with the number
+        // of concurrent requests.
+        const CHUNK_SIZE: u64 = 16 * 1024;
+        const LATENCY_MS: u64 = 50;
+        const WINDOW_MS: u64 = 300;
+
+        let semaphore = Arc::new(Semaphore::new(2));
+        let tuner = PrefetchTuner::new(semaphore.clone(), 2, 16);
+        let io_metrics = IOMetrics::default();
+
+        let mut now = Instant::now();
+        let mut depths = vec![];
+
+        for _ in 0..10 {
+            // Every prefetch holds a permit for the whole window.
+            let permits = (0..tuner.depth())
+                .map(|_| semaphore.clone().try_acquire_owned().unwrap())
+                .collect::<Vec<_>>();
+
+            let num_requests = permits.len() as u64 * WINDOW_MS / LATENCY_MS;
+            io_metrics
+                .bytes_received
+                .fetch_add(num_requests * CHUNK_SIZE);
+            io_metrics.num_requests.fetch_add(num_requests);
+            io_metrics
+                .request_time_ns
+                .fetch_add(num_requests * LATENCY_MS * 1_000_000);
+
+            now += Duration::from_millis(WINDOW_MS);
+            tuner.tune_at(&io_metrics, now);
+            depths.push(tuner.depth());
+        }
+
+        // Grows by a quarter per window until the maximum depth.
+        assert_eq!(depths, [3, 4, 5, 7, 9, 12, 15, 16, 16, 16]);
+        assert_eq!(semaphore.available_permits(), 16);
+    }
+}
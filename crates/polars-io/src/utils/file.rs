@@ -5,12 +5,12 @@ use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 
 #[cfg(feature = "cloud")]
-pub use async_writeable::{AsyncDynWriteable, AsyncWriteable};
-use polars_error::{PolarsResult, feature_gated, polars_err};
+pub use async_writeable::{AsyncDynWriteable, AsyncWriteBridge, AsyncWriteable};
+use polars_error::{PolarsResult, feature_gated, polars_bail, polars_err};
 use polars_utils::create_file;
 use polars_utils::file::close_file;
 use polars_utils::mmap::ensure_not_mapped;
-use polars_utils::pl_path::{PlRefPath, format_file_uri};
+use polars_utils::pl_path::{CloudScheme, PlRefPath, format_file_uri};
 
 use super::sync_on_close::SyncOnCloseType;
 use crate::cloud::CloudOptions;
@@ -24,6 +24,38 @@ pub trait WriteableTrait: std::io::Write {
     fn sync_data(&self) -> std::io::Result<()>;
 }
 
+/// Adapts an arbitrary [`std::io::Write`] (e.g. stdout, a pipe or a socket) into a
+/// [`WriteableTrait`]. The writer is flushed on close.
+pub struct WriteAdapter<W>(pub W);
+
+impl<W: io::Write> io::Write for WriteAdapter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0.write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<W: io::Write> WriteableTrait for WriteAdapter<W> {
+    fn close(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+
+    fn sync_all(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn sync_data(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Holds a non-async writeable file, abstracted over local files or cloud files.
 ///
 /// This implements `DerefMut` to a trait object implementing [`std::io::Write`].
@@ -35,20 +67,55 @@ pub enum Writeable {
     ///
     /// This is used to implement writing to in-memory and arbitrary file descriptors.
     Dyn(Box<dyn WriteableTrait + Send>),
+    /// An arbitrary async writer, e.g. a socket or an HTTP response body.
+    ///
+    /// This is written to natively by async sinks, synchronous writes block on the async runtime.
+    #[cfg(feature = "cloud")]
+    AsyncDyn(AsyncWriteBridge),
     Local(std::fs::File),
     #[cfg(feature = "cloud")]
     Cloud(crate::cloud::cloud_writer::CloudWriterIoTraitWrap),
 }
 
 impl Writeable {
+    /// Writeable for an arbitrary writer, e.g. stdout or a pipe.
+    pub fn from_writer<W: io::Write + Send + 'static>(writer: W) -> Self {
+        Self::Dyn(Box::new(WriteAdapter(writer)))
+    }
+
+    /// Writeable for an arbitrary async writer, e.g. a socket or an HTTP response body.
+    #[cfg(feature = "cloud")]
+    pub fn from_async_writer<W: tokio::io::AsyncWrite + Send + Unpin + 'static>(writer: W) -> Self {
+        Self::AsyncDyn(AsyncWriteBridge(Box::new(writer)))
+    }
+
     pub fn try_new(
         path: PlRefPath,
-        #[cfg_attr(not(feature = "cloud"), expect(unused))] cloud_options: Option<&CloudOptions>,
+        cloud_options: Option<&CloudOptions>,
         #[cfg_attr(not(feature = "cloud"), expect(unused))] cloud_upload_chunk_size: usize,
         #[cfg_attr(not(feature = "cloud"), expect(unused))] cloud_upload_concurrency: usize,
         io_metrics: Option<Arc<IOMetrics>>,
     ) -> PolarsResult<Self> {
-        Ok(if path.has_scheme() {
+        let atomic_commit = cloud_options.is_some_and(|o| o.atomic_commit.is_some());
+
+        Ok(if path.scheme() == Some(CloudScheme::Hf) {
+            #[cfg(feature = "hf_upload")]
+            {
+                use crate::path_utils::HfFileWriter;
+
+                Self::Dyn(Box::new(HfFileWriter::new_single_file(
+                    &path,
+                    cloud_options,
+                )?))
+            }
+            #[cfg(not(feature = "hf_upload"))]
+            {
+                polars_bail!(
+                    ComputeError:
+                    "writing to Hugging Face repositories requires the 'hf_upload' feature"
+                )
+            }
+        } else if path.has_scheme() {
             feature_gated!("cloud", {
                 use crate::cloud::cloud_writer::CloudWriterIoTraitWrap;
                 use crate::pl_async::get_runtime;
@@ -63,13 +130,35 @@ impl Writeable {
 
                 Self::Cloud(CloudWriterIoTraitWrap::from(writer))
             })
-        } else if polars_config::config().force_async() {
+        } else if polars_config::config().force_async() || atomic_commit {
             feature_gated!("cloud", {
                 let path = resolve_homedir(path.as_std_path());
-                create_file(&path)?;
-                let path = std::fs::canonicalize(&path)?;
 
-                ensure_not_mapped(&path.metadata()?)?;
+                let path = if atomic_commit {
+                    // The file itself is only created when the upload is committed.
+                    let file_name = path.file_name().ok_or_else(
+                        || polars_err!(ComputeError: "invalid file path: {}", path.display()),
+                    )?;
+                    let parent = match path.parent() {
+                        Some(p) if !p.as_os_str().is_empty() => p,
+                        _ => std::path::Path::new("."),
+                    };
+                    std::fs::create_dir_all(parent)?;
+                    let path = std::fs::canonicalize(parent)?.join(file_name);
+
+                    if let Ok(metadata) = path.metadata() {
+                        ensure_not_mapped(&metadata)?;
+                    }
+
+                    path
+                } else {
+                    create_file(&path)?;
+                    let path = std::fs::canonicalize(&path)?;
+
+                    ensure_not_mapped(&path.metadata()?)?;
+
+                    path
+                };
 
                 let path = path.to_str().ok_or_else(|| polars_err!(non_utf8_path))?;
                 let path = format_file_uri(path);
@@ -103,6 +192,7 @@ impl Writeable {
 
         match self {
             Self::Dyn(v) => Ok(AsyncWriteable::Dyn(AsyncDynWriteable(v))),
+            Self::AsyncDyn(v) => Ok(AsyncWriteable::AsyncDyn(v.0)),
             Self::Local(v) => Ok(AsyncWriteable::Local(tokio::fs::File::from_std(v))),
             Self::Cloud(v) => Ok(AsyncWriteable::Cloud(v)),
         }
@@ -111,6 +201,8 @@ impl Writeable {
     pub fn as_buffered(&mut self) -> BufferedWriteable<'_> {
         match self {
             Writeable::Dyn(v) => BufferedWriteable::BufWriter(std::io::BufWriter::new(v.as_mut())),
+            #[cfg(feature = "cloud")]
+            Writeable::AsyncDyn(v) => BufferedWriteable::BufWriter(std::io::BufWriter::new(v)),
             Writeable::Local(v) => BufferedWriteable::BufWriter(std::io::BufWriter::new(v)),
             #[cfg(feature = "cloud")]
             Writeable::Cloud(v) => BufferedWriteable::Direct(v as _),
@@ -120,6 +212,8 @@ impl Writeable {
     pub fn sync_all(&self) -> io::Result<()> {
         match self {
             Self::Dyn(v) => v.sync_all(),
+            #[cfg(feature = "cloud")]
+            Self::AsyncDyn(_) => Ok(()),
             Self::Local(v) => v.sync_all(),
             #[cfg(feature = "cloud")]
             Self::Cloud(v) => v.sync_all(),
@@ -129,6 +223,8 @@ impl Writeable {
     pub fn sync_data(&self) -> io::Result<()> {
         match self {
             Self::Dyn(v) => v.sync_data(),
+            #[cfg(feature = "cloud")]
+            Self::AsyncDyn(_) => Ok(()),
             Self::Local(v) => v.sync_data(),
             #[cfg(feature = "cloud")]
             Self::Cloud(v) => v.sync_data(),
@@ -144,6 +240,8 @@ impl Writeable {
 
         match self {
             Self::Dyn(mut v) => v.close(),
+            #[cfg(feature = "cloud")]
+            Self::AsyncDyn(mut v) => v.shutdown(),
             Self::Local(v) => close_file(v),
             #[cfg(feature = "cloud")]
             Self::Cloud(mut v) => v.close(),
@@ -155,6 +253,8 @@ impl io::Write for Writeable {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         match self {
             Self::Dyn(v) => v.write(buf),
+            #[cfg(feature = "cloud")]
+            Self::AsyncDyn(v) => v.write(buf),
             Self::Local(v) => v.write(buf),
             #[cfg(feature = "cloud")]
             Self::Cloud(v) => v.write(buf),
@@ -172,6 +272,8 @@ impl Deref for Writeable {
     fn deref(&self) -> &Self::Target {
         match self {
             Self::Dyn(v) => v,
+            #[cfg(feature = "cloud")]
+            Self::AsyncDyn(v) => v,
             Self::Local(v) => v,
             #[cfg(feature = "cloud")]
             Self::Cloud(v) => v,
@@ -183,6 +285,8 @@ impl DerefMut for Writeable {
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
             Self::Dyn(v) => v,
+            #[cfg(feature = "cloud")]
+            Self::AsyncDyn(v) => v,
             Self::Local(v) => v,
             #[cfg(feature = "cloud")]
             Self::Cloud(v) => v,
@@ -235,8 +339,9 @@ async fn new_cloud_writer(
         object_path_from_str(&cloud_location.prefix)?,
         cloud_upload_chunk_size,
         cloud_upload_concurrency,
+        cloud_options.and_then(|o| o.atomic_commit),
         io_metrics,
-    );
+    )?;
 
     writer.start().await?;
 
@@ -261,8 +366,32 @@ mod async_writeable {
     use super::{Writeable, WriteableTrait};
     use crate::cloud::CloudOptions;
     use crate::metrics::IOMetrics;
+    use crate::pl_async::get_runtime;
     use crate::utils::sync_on_close::SyncOnCloseType;
 
+    /// Blocking [`std::io::Write`] interface to an async writer, for writing from synchronous code.
+    pub struct AsyncWriteBridge(pub Box<dyn tokio::io::AsyncWrite + Send + Unpin>);
+
+    impl AsyncWriteBridge {
+        pub fn shutdown(&mut self) -> io::Result<()> {
+            get_runtime().block_in_place_on(self.0.shutdown())
+        }
+    }
+
+    impl io::Write for AsyncWriteBridge {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            get_runtime().block_in_place_on(self.0.write(buf))
+        }
+
+        fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+            get_runtime().block_in_place_on(self.0.write_all(buf))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            get_runtime().block_in_place_on(self.0.flush())
+        }
+    }
+
     /// Turn an abstract io::Write into an abstract tokio::io::AsyncWrite.
     pub struct AsyncDynWriteable(pub Box<dyn WriteableTrait + Send>);
 
@@ -294,6 +423,7 @@ mod async_writeable {
     /// You should instead call the [`AsyncWriteable::close`] at the end.
     pub enum AsyncWriteable {
         Dyn(AsyncDynWriteable),
+        AsyncDyn(Box<dyn tokio::io::AsyncWrite + Send + Unpin>),
         Local(tokio::fs::File),
         Cloud(crate::cloud::cloud_writer::CloudWriterIoTraitWrap),
     }
@@ -326,13 +456,16 @@ mod async_writeable {
         {
             match self {
                 Self::Cloud(v) => v.write_all_owned(Bytes::from(std::mem::take(src))).await,
-                Self::Dyn(_) | Self::Local(_) => self.write_all(src.as_ref()).await,
+                Self::Dyn(_) | Self::AsyncDyn(_) | Self::Local(_) => {
+                    self.write_all(src.as_ref()).await
+                },
             }
         }
 
         pub async fn sync_all(&mut self) -> io::Result<()> {
             match self {
                 Self::Dyn(v) => task::block_in_place(|| v.0.as_ref().sync_all()),
+                Self::AsyncDyn(_) => Ok(()),
                 Self::Local(v) => v.sync_all().await,
                 Self::Cloud(_) => Ok(()),
             }
@@ -341,6 +474,7 @@ mod async_writeable {
         pub async fn sync_data(&mut self) -> io::Result<()> {
             match self {
                 Self::Dyn(v) => task::block_in_place(|| v.0.as_ref().sync_data()),
+                Self::AsyncDyn(_) => Ok(()),
                 Self::Local(v) => v.sync_data().await,
                 Self::Cloud(_) => Ok(()),
             }
@@ -358,6 +492,7 @@ mod async_writeable {
                     v.shutdown().await.map_err(PolarsError::from)?;
                     Ok(task::block_in_place(|| v.0.close())?)
                 },
+                Self::AsyncDyn(mut v) => v.shutdown().await.map_err(PolarsError::from),
                 Self::Local(v) => async {
                     let f = v.into_std().await;
                     close_file(f)
@@ -375,6 +510,7 @@ mod async_writeable {
         fn deref(&self) -> &Self::Target {
             match self {
                 Self::Dyn(v) => v,
+                Self::AsyncDyn(v) => v,
                 Self::Local(v) => v,
                 Self::Cloud(v) => v,
             }
@@ -385,9 +521,73 @@ mod async_writeable {
         fn deref_mut(&mut self) -> &mut Self::Target {
             match self {
                 Self::Dyn(v) => v,
+                Self::AsyncDyn(v) => v,
                 Self::Local(v) => v,
                 Self::Cloud(v) => v,
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use super::Writeable;
+    use crate::utils::sync_on_close::SyncOnCloseType;
+
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<(Vec<u8>, usize)>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.0.lock().unwrap().1 += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_writeable_from_writer() {
+        let buf = SharedBuf::default();
+
+        let mut writeable = Writeable::from_writer(buf.clone());
+        writeable.write_all(b"a,b\n").unwrap();
+        writeable.as_buffered().write_all(b"1,2\n").unwrap();
+        writeable.close(SyncOnCloseType::None).unwrap();
+
+        let (bytes, num_flushes) = &*buf.0.lock().unwrap();
+        assert_eq!(bytes, b"a,b\n1,2\n");
+        assert!(*num_flushes >= 1);
+    }
+
+    #[cfg(feature = "cloud")]
+    #[test]
+    fn test_writeable_from_async_writer() {
+        use tokio::io::AsyncReadExt;
+
+        use crate::pl_async::get_runtime;
+
+        let (writer, mut reader) = tokio::io::duplex(1024);
+
+        get_runtime().block_on(async {
+            let mut writeable = Writeable::from_async_writer(writer)
+                .try_into_async_writeable()
+                .unwrap();
+            writeable
+                .write_all_owned(&mut b"a,b\n1,2\n".to_vec())
+                .await
+                .unwrap();
+            writeable.close(SyncOnCloseType::None).await.unwrap();
+
+            let mut out = vec![];
+            reader.read_to_end(&mut out).await.unwrap();
+            assert_eq!(out, b"a,b\n1,2\n");
+        });
+    }
+}
@@ -214,6 +214,29 @@ where
         })
 }
 
+#[cfg(any(feature = "iceberg", feature = "delta", feature = "cloud"))]
+pub(crate) fn random_u64() -> u64 {
+    use std::hash::{BuildHasher, RandomState};
+
+    RandomState::new().hash_one(std::time::SystemTime::now())
+}
+
+/// Random (version 4) UUID, formatted as a hyphenated lowercase string.
+#[cfg(any(feature = "iceberg", feature = "delta", feature = "cloud"))]
+pub(crate) fn random_uuid() -> String {
+    let hi = (random_u64() & !0xf000) | 0x4000;
+    let lo = (random_u64() & !(0xc << 60)) | (0x8 << 60);
+
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        hi >> 32,
+        (hi >> 16) & 0xffff,
+        hi & 0xffff,
+        lo >> 48,
+        lo & 0xffff_ffff_ffff
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::FLOAT_RE;
@@ -0,0 +1,42 @@
+use std::net::ToSocketAddrs;
+
+use polars_io::SerReader;
+use polars_io::ipc::{IpcTcpClient, IpcTcpResponse, IpcTcpServer};
+use polars_plan::dsl::PlanSerializationContext;
+
+use super::*;
+
+/// Handles an [`IpcTcpServer`] request containing a serialized DSL plan by executing the plan and
+/// streaming back its result in batches.
+///
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::ipc::IpcTcpServer;
+/// use polars_lazy::frame::dsl_plan_ipc_tcp_handler;
+///
+/// fn example() -> PolarsResult<()> {
+///     IpcTcpServer::bind("0.0.0.0:7700")?.serve(dsl_plan_ipc_tcp_handler)
+/// }
+/// ```
+pub fn dsl_plan_ipc_tcp_handler(request: &[u8]) -> PolarsResult<IpcTcpResponse> {
+    let mut lf = LazyFrame::from(DslPlan::deserialize_versioned(request)?);
+    let schema = lf.collect_schema()?;
+    let batches = lf.collect_batches(Engine::Auto, true, None, false)?;
+
+    Ok(IpcTcpResponse {
+        schema,
+        batches: Box::new(batches),
+    })
+}
+
+impl LazyFrame {
+    /// Execute the query on a remote [`IpcTcpServer`] serving [`dsl_plan_ipc_tcp_handler`] and
+    /// collect the result into a [`DataFrame`].
+    pub fn collect_remote<A: ToSocketAddrs>(self, addr: A) -> PolarsResult<DataFrame> {
+        let mut request = vec![];
+        self.logical_plan
+            .serialize_versioned(&mut request, PlanSerializationContext::default())?;
+
+        IpcTcpClient::connect(addr)?.request(&request)?.finish()
+    }
+}
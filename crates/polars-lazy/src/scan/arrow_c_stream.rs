@@ -0,0 +1,148 @@
+use std::sync::Mutex;
+
+use arrow::array::{Array, StructArray};
+use arrow::datatypes::{ArrowDataType, ArrowSchema};
+use arrow::ffi::{ArrowArrayStream, ArrowArrayStreamReader};
+use polars_core::prelude::*;
+
+use crate::prelude::*;
+
+type StreamReader = ArrowArrayStreamReader<Box<ArrowArrayStream>>;
+
+/// Scan over an imported [Arrow C stream](https://arrow.apache.org/docs/format/CStreamInterface.html).
+///
+/// The stream can only be consumed once.
+struct ArrowCStreamScan {
+    reader: Mutex<Option<StreamReader>>,
+    schema: SchemaRef,
+}
+
+impl ArrowCStreamScan {
+    fn take_reader(&self) -> PolarsResult<StreamReader> {
+        self.reader.lock().unwrap().take().ok_or_else(
+            || polars_err!(ComputeError: "arrow C stream was already consumed by a previous scan"),
+        )
+    }
+}
+
+/// Converts an array produced by the stream into a [`DataFrame`].
+///
+/// Streams of struct arrays carry record batches, any other stream is read as a single column.
+fn array_to_df(field: &ArrowField, array: Box<dyn Array>) -> PolarsResult<DataFrame> {
+    match field.dtype() {
+        ArrowDataType::Struct(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<StructArray>()
+                .unwrap()
+                .clone();
+            DataFrame::try_from(array)
+        },
+        _ => {
+            let s = Series::try_from((field, array))?;
+            DataFrame::new(s.len(), vec![s.into_column()])
+        },
+    }
+}
+
+fn stream_schema(field: &ArrowField) -> SchemaRef {
+    let schema = match field.dtype() {
+        ArrowDataType::Struct(fields) => ArrowSchema::from_iter(fields.iter().cloned()),
+        _ => ArrowSchema::from_iter([field.clone()]),
+    };
+
+    Arc::new(Schema::from_arrow_schema(&schema))
+}
+
+impl AnonymousScan for ArrowCStreamScan {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let mut batch_fn = self.batch_fn(scan_opts)?.unwrap();
+        let mut out = DataFrame::empty_with_schema(&self.schema);
+
+        while let Some(df) = batch_fn()? {
+            out.vstack_mut_owned(df)?;
+        }
+
+        Ok(out)
+    }
+
+    fn batch_fn(
+        &self,
+        _scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Option<AnonymousScanBatchFn>> {
+        let mut reader = self.take_reader()?;
+
+        Ok(Some(Box::new(move || {
+            // SAFETY: the stream was valid when the scan was created.
+            let Some(array) = (unsafe { reader.next() }) else {
+                return Ok(None);
+            };
+
+            array_to_df(reader.field(), array?).map(Some)
+        })))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
+impl LazyFrame {
+    /// Lazily scan an [Arrow C stream](https://arrow.apache.org/docs/format/CStreamInterface.html)
+    /// produced by another library.
+    ///
+    /// Batches are only pulled from the stream once the query runs, the streaming engine reads
+    /// them one at a time. Projections and predicates are applied after import. The stream can
+    /// only be consumed by a single query.
+    ///
+    /// # Safety
+    /// The [`ArrowArrayStream`] must fulfill the invariants of the C stream interface and the
+    /// arrays it produces must fulfill the C data interface.
+    pub unsafe fn scan_arrow_c_stream(stream: Box<ArrowArrayStream>) -> PolarsResult<Self> {
+        let reader = unsafe { ArrowArrayStreamReader::try_new(stream)? };
+        let schema = stream_schema(reader.field());
+
+        let function = Arc::new(ArrowCStreamScan {
+            reader: Mutex::new(Some(reader)),
+            schema: schema.clone(),
+        });
+
+        Self::anonymous_scan(
+            function,
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name: "ARROW C STREAM SCAN",
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Export the result of the query as an
+    /// [Arrow C stream](https://arrow.apache.org/docs/format/CStreamInterface.html) of record
+    /// batches.
+    ///
+    /// The query starts running once the consumer requests the first batch.
+    #[cfg(feature = "async")]
+    pub fn sink_arrow_c_stream(mut self) -> PolarsResult<ArrowArrayStream> {
+        let compat_level = CompatLevel::newest();
+        let schema = self.collect_schema()?.to_arrow(compat_level);
+        let dtype = ArrowDataType::Struct(schema.into_iter_values().collect());
+
+        let batches = self.collect_batches(Engine::Auto, true, None, true)?;
+
+        let field = ArrowField::new(PlSmallStr::EMPTY, dtype.clone(), false);
+        let iter = batches.map(move |df| {
+            let df = df?;
+            let height = df.height();
+            let arrays = df.rechunk_into_arrow(compat_level);
+
+            Ok(Box::new(StructArray::new(dtype.clone(), height, arrays, None)) as Box<dyn Array>)
+        });
+
+        Ok(arrow::ffi::export_iterator(Box::new(iter), field))
+    }
+}
@@ -0,0 +1,231 @@
+use polars_core::prelude::*;
+use polars_io::database::{DatabaseDriver, DatabaseReader};
+use polars_io::predicates::SpecializedColumnPredicate;
+#[cfg(feature = "is_between")]
+use polars_ops::prelude::ClosedInterval;
+
+use crate::prelude::*;
+
+/// Scan over the result of a SQL query.
+///
+/// The projection, row limit and the conjuncts of the predicate that compare a column to
+/// literals are pushed down into the query.
+#[derive(Clone)]
+struct DatabaseScan {
+    driver: Arc<dyn DatabaseDriver>,
+    query: String,
+    schema: SchemaRef,
+}
+
+impl DatabaseScan {
+    fn reader(&self) -> DatabaseReader {
+        DatabaseReader::new(self.driver.clone(), self.query.clone())
+    }
+
+    /// Casts the query result to the inferred schema.
+    fn cast_to_schema(&self, df: DataFrame) -> PolarsResult<DataFrame> {
+        let height = df.height();
+        let columns = df
+            .into_columns()
+            .into_iter()
+            .map(|c| {
+                let dtype = self.schema.try_get(c.name())?;
+                c.strict_cast(dtype).map_err(|_| {
+                    polars_err!(
+                        SchemaMismatch:
+                        "column '{}' of the database query does not match the inferred type {}, \
+                        consider increasing 'infer_schema_length'",
+                        c.name(), dtype
+                    )
+                })
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        DataFrame::new(height, columns)
+    }
+}
+
+/// Translates the conjuncts of `predicate` that compare a column to literals.
+///
+/// Conjuncts that cannot be translated are skipped, the full predicate is applied to the query
+/// result afterwards.
+fn column_predicates(predicate: &Expr, out: &mut Vec<(PlSmallStr, SpecializedColumnPredicate)>) {
+    use SpecializedColumnPredicate as P;
+
+    let scalar = |lv: &LiteralValue| {
+        let av = lv.to_any_value()?.into_static();
+        (!av.is_null()).then(|| Scalar::new(av.dtype(), av))
+    };
+
+    match predicate {
+        Expr::BinaryExpr {
+            left,
+            op: Operator::And | Operator::LogicalAnd,
+            right,
+        } => {
+            column_predicates(left, out);
+            column_predicates(right, out);
+        },
+        // Comparing to a null never matches, which the filter takes care of.
+        Expr::BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        } => match (left.as_ref(), right.as_ref()) {
+            (Expr::Column(name), Expr::Literal(lv)) | (Expr::Literal(lv), Expr::Column(name)) => {
+                if let Some(value) = scalar(lv) {
+                    out.push((name.clone(), P::Equal(value)));
+                }
+            },
+            _ => {},
+        },
+        Expr::Function { input, function } => match (function, input.as_slice()) {
+            (FunctionExpr::Boolean(BooleanFunction::IsNull), [Expr::Column(name)]) => {
+                out.push((name.clone(), P::Equal(Scalar::null(DataType::Null))));
+            },
+            #[cfg(feature = "is_between")]
+            (
+                FunctionExpr::Boolean(BooleanFunction::IsBetween {
+                    closed: ClosedInterval::Both,
+                }),
+                [Expr::Column(name), Expr::Literal(low), Expr::Literal(high)],
+            ) => {
+                if let Some((low, high)) = scalar(low).zip(scalar(high)) {
+                    out.push((name.clone(), P::Between(low, high)));
+                }
+            },
+            #[cfg(feature = "strings")]
+            (
+                FunctionExpr::StringExpr(
+                    f @ (StringFunction::StartsWith | StringFunction::EndsWith),
+                ),
+                [Expr::Column(name), Expr::Literal(lv)],
+            ) => {
+                if let Some(v) = lv.extract_str() {
+                    let v: Box<[u8]> = v.as_bytes().into();
+                    out.push((
+                        name.clone(),
+                        match f {
+                            StringFunction::StartsWith => P::StartsWith(v),
+                            _ => P::EndsWith(v),
+                        },
+                    ));
+                }
+            },
+            _ => {},
+        },
+        _ => {},
+    }
+}
+
+impl AnonymousScan for DatabaseScan {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        // The row limit applies before the predicate, so the predicate is only pushed down into
+        // the query without one.
+        let mut predicates = vec![];
+        if let (Some(predicate), None) = (&scan_opts.predicate, scan_opts.n_rows) {
+            column_predicates(predicate, &mut predicates);
+        }
+
+        let columns = scan_opts.with_columns.as_deref().map(<[_]>::to_vec);
+        let is_empty_projection = columns.as_ref().is_some_and(Vec::is_empty);
+
+        let df = self
+            .reader()
+            .with_columns(columns.filter(|columns| !columns.is_empty()))
+            .with_predicates(predicates)
+            .with_n_rows(scan_opts.n_rows)
+            .finish()?;
+        let mut df = self.cast_to_schema(df)?;
+
+        if let Some(predicate) = scan_opts.predicate {
+            df = df.lazy().filter(predicate).collect()?;
+        }
+
+        if is_empty_projection {
+            df = DataFrame::empty_with_height(df.height());
+        }
+
+        Ok(df)
+    }
+
+    fn batch_fn(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<Option<AnonymousScanBatchFn>> {
+        if scan_opts.predicate.is_some() {
+            return Ok(None);
+        }
+
+        let columns = scan_opts.with_columns.as_deref().map(<[_]>::to_vec);
+        if columns.as_ref().is_some_and(Vec::is_empty) {
+            return Ok(None);
+        }
+
+        let stream = self
+            .reader()
+            .with_columns(columns)
+            .with_n_rows(scan_opts.n_rows)
+            .batched()?;
+        let schema = Schema::from_arrow_schema(&stream.schema);
+        let mut batches = stream.batches;
+
+        let scan = self.clone();
+
+        Ok(Some(Box::new(move || {
+            let Some(batch) = batches.next() else {
+                return Ok(None);
+            };
+
+            let mut df = DataFrame::empty_with_schema(&schema);
+            df.append_record_batch(batch?)?;
+            scan.cast_to_schema(df).map(Some)
+        })))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_predicate_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+}
+
+impl LazyFrame {
+    /// Lazily read the result of a SQL query through a [`DatabaseDriver`].
+    ///
+    /// The schema is inferred from the first `infer_schema_length` rows of the query, or from
+    /// all rows if `None`. The selected columns, the row limit and predicates comparing a column
+    /// to literals are pushed down into the query.
+    pub fn scan_database(
+        driver: Arc<dyn DatabaseDriver>,
+        query: impl Into<String>,
+        infer_schema_length: Option<usize>,
+    ) -> PolarsResult<Self> {
+        let query = query.into();
+        let df = DatabaseReader::new(driver.clone(), query.clone())
+            .with_n_rows(infer_schema_length)
+            .finish()?;
+
+        let function = Arc::new(DatabaseScan {
+            driver,
+            query,
+            schema: df.schema().clone(),
+        });
+
+        Self::anonymous_scan(
+            function.clone(),
+            ScanArgsAnonymous {
+                schema: Some(function.schema.clone()),
+                name: "DATABASE SCAN",
+                ..Default::default()
+            },
+        )
+    }
+}
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use polars_buffer::Buffer;
+use polars_core::prelude::*;
+use polars_io::HiveOptions;
+use polars_io::cloud::CloudOptions;
+use polars_io::delta::schema::ColumnMappingMode;
+use polars_io::delta::snapshot::{DeltaSnapshot, DeltaTimeTravel};
+use polars_io::pl_async::get_runtime;
+use polars_io::prelude::ParquetOptions;
+use polars_plan::dsl::default_values::{
+    DefaultFieldValues, IcebergIdentityTransformedPartitionFields,
+};
+use polars_plan::dsl::deletion::{
+    DeletionFilesList, DeltaDeletionVectorDescriptor, DeltaDeletionVectors,
+};
+use polars_plan::dsl::delta_sink::{DeltaSinkMode, DeltaSinkOptions};
+use polars_utils::pl_path::PlRefPath;
+
+use crate::prelude::*;
+
+impl LazyFrame {
+    /// Scans a Delta Lake table by reading its transaction log.
+    pub fn scan_delta(
+        table_uri: &str,
+        time_travel: DeltaTimeTravel,
+        cloud_options: Option<CloudOptions>,
+    ) -> PolarsResult<Self> {
+        let snapshot = get_runtime().block_in_place_on(DeltaSnapshot::load(
+            table_uri,
+            time_travel,
+            cloud_options.as_ref(),
+        ))?;
+
+        let table_schema = snapshot.metadata.schema()?;
+        let column_mapping_mode = snapshot.metadata.column_mapping_mode()?;
+        let schema = Arc::new(table_schema.to_polars_schema()?);
+
+        if snapshot.files.is_empty() {
+            return Ok(DataFrame::empty_with_arc_schema(schema).lazy());
+        }
+
+        let sources = ScanSources::Paths(
+            snapshot
+                .files
+                .iter()
+                .map(|add| snapshot.file_uri(add).map(PlRefPath::new))
+                .collect::<PolarsResult<Buffer<_>>>()?,
+        );
+
+        let parquet_options = ParquetOptions {
+            schema: Some(schema.clone()),
+            parallel: Default::default(),
+            low_memory: false,
+            use_statistics: true,
+        };
+
+        let partition_columns = &snapshot.metadata.partition_columns;
+
+        // Partition columns are not stored in the data files. Without column mapping they are
+        // parsed from the hive-style file paths, otherwise they are provided as default values
+        // for the missing fields.
+        let (hive_options, column_mapping, default_values) = match column_mapping_mode {
+            ColumnMappingMode::None => {
+                let hive_options = if partition_columns.is_empty() {
+                    HiveOptions::new_disabled()
+                } else {
+                    HiveOptions {
+                        schema: Some(Arc::new(
+                            schema
+                                .iter()
+                                .filter(|(name, _)| partition_columns.iter().any(|c| c == *name))
+                                .map(|(name, dtype)| (name.clone(), dtype.clone()))
+                                .collect(),
+                        )),
+                        try_parse_dates: false,
+                        ..HiveOptions::new_enabled()
+                    }
+                };
+
+                (hive_options, None, None)
+            },
+            ColumnMappingMode::Id | ColumnMappingMode::Name => {
+                let mut partition_values = PlIndexMap::with_capacity(partition_columns.len());
+
+                for name in partition_columns {
+                    let Some(field) = table_schema.field(name) else {
+                        polars_bail!(ComputeError: "delta partition column not found: '{}'", name)
+                    };
+
+                    let physical_name = field.physical_name(column_mapping_mode)?;
+                    let dtype = schema.try_get(name)?;
+
+                    let values = Column::new(
+                        PlSmallStr::from_str(name),
+                        snapshot
+                            .files
+                            .iter()
+                            .map(|add| add.partition_values.get(physical_name).cloned().flatten())
+                            .collect::<Vec<_>>(),
+                    )
+                    .strict_cast(dtype)
+                    .map_err(|e| e.to_string());
+
+                    partition_values.insert(field.column_mapping_id()?, values);
+                }
+
+                (
+                    HiveOptions::new_disabled(),
+                    Some(ColumnMapping::Iceberg(Arc::new(
+                        table_schema.to_iceberg_schema()?,
+                    ))),
+                    (!partition_values.is_empty()).then(|| {
+                        DefaultFieldValues::Iceberg(Arc::new(
+                            IcebergIdentityTransformedPartitionFields(partition_values),
+                        ))
+                    }),
+                )
+            },
+        };
+
+        let deletion_vectors = DeltaDeletionVectors {
+            table_uri: snapshot.table_uri.clone(),
+            descriptors: snapshot
+                .files
+                .iter()
+                .enumerate()
+                .filter_map(|(i, add)| {
+                    let dv = add.deletion_vector.as_ref()?;
+
+                    Some((
+                        i,
+                        DeltaDeletionVectorDescriptor {
+                            storage_type: dv.storage_type.clone(),
+                            path_or_inline_dv: dv.path_or_inline_dv.clone(),
+                            offset: dv.offset,
+                            size_in_bytes: dv.size_in_bytes,
+                            cardinality: dv.cardinality,
+                        },
+                    ))
+                })
+                .collect(),
+        };
+
+        let unified_scan_args = UnifiedScanArgs {
+            cloud_options,
+            hive_options,
+            glob: false,
+            column_mapping,
+            default_values,
+            cast_columns_policy: CastColumnsPolicy::DEFAULT_ICEBERG,
+            missing_columns_policy: MissingColumnsPolicy::Insert,
+            extra_columns_policy: ExtraColumnsPolicy::Ignore,
+            deletion_files: DeletionFilesList::filter_empty(Some(
+                DeletionFilesList::DeltaDeletionVectors(Arc::new(deletion_vectors)),
+            )),
+            ..Default::default()
+        };
+
+        Ok(
+            DslBuilder::scan_parquet(sources, parquet_options, unified_scan_args)?
+                .build()
+                .into(),
+        )
+    }
+
+    /// Writes to a Delta Lake table, creating it if it does not exist.
+    ///
+    /// The data files are written with the sink, after which they are committed to the
+    /// transaction log. `partition_by` sets the partition columns of a new table.
+    pub fn sink_delta(
+        mut self,
+        table_uri: &str,
+        mode: DeltaSinkMode,
+        partition_by: Vec<PlSmallStr>,
+        parquet_options: Arc<ParquetWriteOptions>,
+        cloud_options: Option<CloudOptions>,
+    ) -> PolarsResult<Self> {
+        polars_ensure!(
+            !matches!(self.logical_plan, DslPlan::Sink { .. }),
+            InvalidOperation: "cannot create a sink on top of another sink"
+        );
+
+        self.logical_plan = DslPlan::Sink {
+            input: Arc::new(self.logical_plan),
+            payload: SinkType::Delta(DeltaSinkOptions {
+                table_uri: PlRefPath::new(table_uri),
+                mode,
+                partition_by,
+                file_format: FileWriteFormat::Parquet(parquet_options),
+                unified_sink_args: UnifiedSinkArgs {
+                    cloud_options: cloud_options.map(Arc::new),
+                    ..Default::default()
+                },
+            }),
+        };
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use polars_buffer::Buffer;
+use polars_core::prelude::*;
+use polars_io::HiveOptions;
+use polars_io::catalog::iceberg::commit::{
+    IcebergCatalog, SnapshotOperation, commit_data_files, parquet_data_file,
+};
+use polars_io::catalog::iceberg::models::LoadTableResult;
+use polars_io::cloud::CloudOptions;
+use polars_io::pl_async::get_runtime;
+use polars_io::prelude::ParquetOptions;
+use polars_plan::dsl::deletion::{
+    DeletionFilesList, IcebergDeletionFiles, IcebergEqualityDeleteFile,
+};
+use polars_plan::dsl::file_provider::{FileProviderType, IcebergPathProvider};
+use polars_plan::dsl::iceberg_sink_state::IcebergCommitMode;
+use polars_plan::dsl::sink::{SinkedPathsCallback, SinkedPathsCallbackArgs};
+use polars_utils::IdxSize;
+use polars_utils::pl_path::PlRefPath;
+
+use crate::prelude::*;
+
+impl LazyFrame {
+    /// Scans a table loaded from an Iceberg REST catalog.
+    ///
+    /// Reads the given snapshot, or the current snapshot of the `main` branch if `None`. If
+    /// `cloud_options` is `None`, the storage configuration returned by the catalog is used.
+    pub fn scan_iceberg_table(
+        table: &LoadTableResult,
+        snapshot_id: Option<i64>,
+        cloud_options: Option<CloudOptions>,
+    ) -> PolarsResult<Self> {
+        let metadata = &table.metadata;
+
+        let cloud_options = match cloud_options {
+            Some(v) => Some(v),
+            None => table.cloud_options()?,
+        };
+
+        let table_schema = match snapshot_id {
+            Some(snapshot_id) => match metadata.snapshot_by_id(snapshot_id) {
+                Some(snapshot) => metadata.schema_for_snapshot(snapshot)?,
+                None => polars_bail!(ComputeError: "iceberg snapshot not found: {}", snapshot_id),
+            },
+            None => metadata.current_schema()?,
+        };
+
+        let schema = Arc::new(table_schema.to_polars_schema()?);
+
+        let scan_files = get_runtime()
+            .block_in_place_on(metadata.resolve_scan_files(snapshot_id, cloud_options.as_ref()))?;
+
+        if scan_files.data_files.is_empty() {
+            return Ok(DataFrame::empty_with_arc_schema(schema).lazy());
+        }
+
+        let sources = ScanSources::Paths(Buffer::from_iter(
+            scan_files.data_files.iter().map(|p| PlRefPath::new(p)),
+        ));
+
+        let parquet_options = ParquetOptions {
+            schema: Some(schema),
+            parallel: Default::default(),
+            low_memory: false,
+            use_statistics: true,
+        };
+
+        // The deleted row count is only known after reading the delete files.
+        let row_count = (scan_files.position_deletes.is_empty()
+            && scan_files.equality_deletes.is_empty())
+        .then_some((scan_files.physical_row_count, 0));
+
+        let deletion_files = DeletionFilesList::IcebergDelete(Arc::new(IcebergDeletionFiles {
+            position_deletes: scan_files.position_deletes,
+            equality_deletes: scan_files
+                .equality_deletes
+                .into_iter()
+                .map(|(i, files)| {
+                    let files = files
+                        .iter()
+                        .map(|f| IcebergEqualityDeleteFile {
+                            path: f.path.clone(),
+                            equality_ids: f.equality_ids.clone(),
+                        })
+                        .collect();
+                    (i, files)
+                })
+                .collect(),
+        }));
+
+        let unified_scan_args = UnifiedScanArgs {
+            cloud_options,
+            hive_options: HiveOptions::new_disabled(),
+            glob: false,
+            column_mapping: Some(ColumnMapping::Iceberg(Arc::new(
+                table_schema.to_iceberg_schema()?,
+            ))),
+            cast_columns_policy: CastColumnsPolicy::DEFAULT_ICEBERG,
+            missing_columns_policy: MissingColumnsPolicy::Insert,
+            extra_columns_policy: ExtraColumnsPolicy::Ignore,
+            deletion_files: DeletionFilesList::filter_empty(Some(deletion_files)),
+            row_count,
+            ..Default::default()
+        };
+
+        Ok(
+            DslBuilder::scan_parquet(sources, parquet_options, unified_scan_args)?
+                .build()
+                .into(),
+        )
+    }
+}
+
+impl LazyFrame {
+    /// Sinks to a table of an Iceberg catalog, committing the written data files as a new
+    /// snapshot once all files are written.
+    ///
+    /// If `cloud_options` is `None`, the storage configuration returned by the catalog is used.
+    pub fn sink_iceberg_table(
+        self,
+        catalog: Arc<dyn IcebergCatalog>,
+        namespace: Vec<String>,
+        table_name: String,
+        mode: IcebergCommitMode,
+        cloud_options: Option<CloudOptions>,
+    ) -> PolarsResult<Self> {
+        let table = get_runtime().block_in_place_on(catalog.load_table(
+            &namespace.iter().map(String::as_str).collect::<Vec<_>>(),
+            &table_name,
+        ))?;
+        let metadata = &table.metadata;
+
+        if metadata
+            .default_partition_spec()
+            .is_some_and(|s| !s.fields.is_empty())
+        {
+            polars_bail!(ComputeError: "sink to partitioned iceberg tables is not yet supported")
+        }
+
+        let cloud_options = match cloud_options {
+            Some(v) => Some(Arc::new(v)),
+            None => table.cloud_options()?.map(Arc::new),
+        };
+
+        let arrow_schema = Arc::new(metadata.current_schema()?.to_arrow_schema()?);
+
+        // Files are split by the in-memory size, which is larger than the compressed file size.
+        let approximate_bytes_per_file = match metadata
+            .properties
+            .get("write.target-file-size-bytes")
+            .and_then(|v| v.parse::<u64>().ok())
+        {
+            Some(v) => v.saturating_mul(4),
+            None => 2 * 1024 * 1024 * 1024,
+        };
+
+        let operation = match mode {
+            IcebergCommitMode::Append => SnapshotOperation::Append,
+            IcebergCommitMode::Overwrite => SnapshotOperation::Overwrite,
+        };
+
+        let commit_cloud_options = cloud_options.clone();
+
+        let commit = move |args: SinkedPathsCallbackArgs| -> PolarsResult<()> {
+            let cloud_options = commit_cloud_options.as_deref();
+            let namespace = namespace.iter().map(String::as_str).collect::<Vec<_>>();
+
+            get_runtime().block_on(async {
+                let data_files = futures::future::try_join_all(
+                    args.path_info_list
+                        .iter()
+                        .map(|info| parquet_data_file(info.path.as_str(), cloud_options)),
+                )
+                .await?;
+
+                commit_data_files(
+                    catalog.as_ref(),
+                    &namespace,
+                    &table_name,
+                    &data_files,
+                    operation,
+                    cloud_options,
+                )
+                .await
+            })?;
+
+            Ok(())
+        };
+
+        self.sink(
+            SinkDestination::Partitioned {
+                base_path: PlRefPath::new(metadata.new_write_data_path()),
+                file_path_provider: Some(FileProviderType::Iceberg(IcebergPathProvider {
+                    extension: PlSmallStr::from_static("parquet"),
+                    file_part_prefix: String::new(),
+                })),
+                partition_strategy: PartitionStrategy::FileSize,
+                max_rows_per_file: IdxSize::MAX,
+                approximate_bytes_per_file,
+            },
+            FileWriteFormat::Parquet(Arc::new(ParquetWriteOptions {
+                arrow_schema: Some(arrow_schema),
+                ..Default::default()
+            })),
+            UnifiedSinkArgs {
+                cloud_options,
+                sinked_paths_callback: Some(SinkedPathsCallback::Callback(PlanCallback::Rust(
+                    SpecialEq::new(Arc::new(commit)),
+                ))),
+                ..Default::default()
+            },
+        )
+    }
+}
@@ -1,6 +1,9 @@
 pub(super) mod anonymous_scan;
+pub(super) mod arrow_c_stream;
 #[cfg(feature = "csv")]
 pub(super) mod csv;
+#[cfg(feature = "database")]
+pub(super) mod database;
 pub(super) mod file_list_reader;
 #[cfg(feature = "ipc")]
 pub(super) mod ipc;
@@ -11,3 +14,7 @@ pub(super) mod parquet;
 
 #[cfg(feature = "catalog")]
 mod catalog;
+#[cfg(feature = "delta")]
+mod delta;
+#[cfg(feature = "iceberg")]
+mod iceberg;
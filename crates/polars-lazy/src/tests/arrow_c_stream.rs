@@ -0,0 +1,74 @@
+use arrow::array::{Array, StructArray};
+use arrow::ffi::{ArrowArrayStream, export_iterator};
+
+use super::*;
+
+fn export_df_batches(df: &DataFrame, batch_size: usize) -> Box<ArrowArrayStream> {
+    let schema = df.schema().to_arrow(CompatLevel::newest());
+    let dtype = ArrowDataType::Struct(schema.into_iter_values().collect());
+
+    let batches = (0..df.height())
+        .step_by(batch_size)
+        .map(|offset| df.slice(offset as i64, batch_size))
+        .collect::<Vec<_>>();
+
+    let iter = batches.into_iter().map({
+        let dtype = dtype.clone();
+        move |df| {
+            let height = df.height();
+            let arrays = df.rechunk_into_arrow(CompatLevel::newest());
+            Ok(Box::new(StructArray::new(dtype.clone(), height, arrays, None)) as Box<dyn Array>)
+        }
+    });
+
+    Box::new(export_iterator(
+        Box::new(iter),
+        ArrowField::new(PlSmallStr::EMPTY, dtype, false),
+    ))
+}
+
+#[test]
+fn test_scan_arrow_c_stream() -> PolarsResult<()> {
+    let df = load_df();
+
+    for engine in [
+        Engine::InMemory,
+        #[cfg(feature = "new_streaming")]
+        Engine::Streaming,
+    ] {
+        let lf = unsafe { LazyFrame::scan_arrow_c_stream(export_df_batches(&df, 2))? };
+        let out = lf
+            .filter(col("a").gt(lit(1)))
+            .select([col("c"), col("b")])
+            .collect_with_engine(engine)?
+            .unwrap_single();
+
+        let expected = df
+            .clone()
+            .lazy()
+            .filter(col("a").gt(lit(1)))
+            .select([col("c"), col("b")])
+            .collect()?;
+        assert!(out.equals(&expected));
+    }
+
+    // The stream can only be consumed once.
+    let lf = unsafe { LazyFrame::scan_arrow_c_stream(export_df_batches(&df, 2))? };
+    lf.clone().collect()?;
+    assert!(lf.collect().is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "async")]
+fn test_sink_arrow_c_stream_roundtrip() -> PolarsResult<()> {
+    let df = load_df();
+
+    let stream = df.clone().lazy().sink_arrow_c_stream()?;
+    let out = unsafe { LazyFrame::scan_arrow_c_stream(Box::new(stream))? }.collect()?;
+
+    assert!(out.equals(&df));
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use object_store::memory::InMemory;
+use object_store::path::Path;
+use object_store::{ObjectStore, ObjectStoreExt};
+use polars_io::cloud::register_object_store_scheme;
+use polars_io::pl_async::get_runtime;
+
+use super::*;
+
+fn sink_path(lf: LazyFrame, path: &str, format: FileWriteFormat) -> PolarsResult<()> {
+    lf.sink(
+        SinkDestination::File {
+            target: SinkTarget::Path(PlRefPath::new(path)),
+        },
+        format,
+        UnifiedSinkArgs::default(),
+    )?
+    .collect_with_engine(Engine::Streaming)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_scheme_scan_and_sink() -> PolarsResult<()> {
+    let store = Arc::new(InMemory::new());
+
+    let factory_store = store.clone();
+    register_object_store_scheme(
+        "polars-test-lazy",
+        Arc::new(
+            move |_: &str, _: &[(String, String)]| -> PolarsResult<Arc<dyn ObjectStore>> {
+                Ok(factory_store.clone() as Arc<dyn ObjectStore>)
+            },
+        ),
+    )?;
+
+    let df = df!("a" => [1i64, 2, 3], "b" => ["x", "y", "z"])?;
+
+    for (i, part) in [df.slice(0, 2), df.slice(2, 1)].into_iter().enumerate() {
+        sink_path(
+            part.clone().lazy(),
+            &format!("polars-test-lazy://bucket/data/{i}.parquet"),
+            FileWriteFormat::Parquet(Arc::default()),
+        )?;
+        sink_path(
+            part.lazy(),
+            &format!("polars-test-lazy://bucket/data/{i}.csv"),
+            FileWriteFormat::Csv(CsvWriterOptions::default()),
+        )?;
+    }
+
+    // The sinks wrote into the registered store.
+    let bytes = get_runtime()
+        .block_on(async { store.get(&Path::from("data/0.csv")).await?.bytes().await })
+        .unwrap();
+    assert_eq!(&bytes[..], b"a,b\n1,x\n2,y\n");
+
+    // The globs only match the files of their own format.
+    let out = LazyFrame::scan_parquet(
+        PlRefPath::new("polars-test-lazy://bucket/data/*.parquet"),
+        ScanArgsParquet::default(),
+    )?
+    .collect_with_engine(Engine::Streaming)?
+    .unwrap_single();
+    assert_eq!(out, df);
+
+    let out = LazyCsvReader::new(PlRefPath::new("polars-test-lazy://bucket/data/*.csv"))
+        .finish()?
+        .filter(col("a").gt(lit(1i64)))
+        .collect_with_engine(Engine::Streaming)?
+        .unwrap_single();
+    assert_eq!(out, df!("a" => [2i64, 3], "b" => ["y", "z"])?);
+
+    Ok(())
+}
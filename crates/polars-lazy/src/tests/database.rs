@@ -0,0 +1,78 @@
+use polars_io::database::{DatabaseWriter, SqliteDriver};
+
+use super::*;
+
+fn fruit_driver() -> PolarsResult<Arc<SqliteDriver>> {
+    let driver = Arc::new(SqliteDriver::open_in_memory()?);
+    let df = df!(
+        "id" => [1i64, 2, 3, 4, 5],
+        "name" => [Some("apple"), Some("Avocado"), None, Some("banana"), Some("apricot")],
+        "price" => [1.5, 2.0, 0.5, 3.25, 4.0],
+    )?;
+    DatabaseWriter::new(driver.clone(), "fruit").finish(&df)?;
+
+    Ok(driver)
+}
+
+#[test]
+fn test_scan_database() -> PolarsResult<()> {
+    let driver = fruit_driver()?;
+    let scan = || LazyFrame::scan_database(driver.clone(), "SELECT * FROM fruit", Some(100));
+
+    for engine in [
+        Engine::InMemory,
+        #[cfg(feature = "new_streaming")]
+        Engine::Streaming,
+    ] {
+        let out = scan()?
+            .filter(
+                col("name")
+                    .str()
+                    .starts_with(lit("a"))
+                    .and(col("price").gt(lit(1.0))),
+            )
+            .select([col("id")])
+            .collect_with_engine(engine)?
+            .unwrap_single();
+        assert_eq!(out, df!("id" => [1i64, 5])?);
+
+        let out = scan()?
+            .filter(col("name").is_null().or(col("id").eq(lit(2i64))))
+            .select([col("id"), col("price")])
+            .collect_with_engine(engine)?
+            .unwrap_single();
+        assert_eq!(out, df!("id" => [2i64, 3], "price" => [2.0, 0.5])?);
+
+        let out = scan()?
+            .select([col("name")])
+            .limit(2)
+            .collect_with_engine(engine)?
+            .unwrap_single();
+        assert_eq!(out, df!("name" => ["apple", "Avocado"])?);
+
+        let out = scan()?
+            .select([len()])
+            .collect_with_engine(engine)?
+            .unwrap_single();
+        assert_eq!(out, df!("len" => [5 as IdxSize])?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_scan_database_schema_mismatch() -> PolarsResult<()> {
+    let driver = fruit_driver()?;
+    let query = "SELECT CASE WHEN id < 3 THEN id ELSE 'x' END AS v FROM fruit";
+
+    // The type is inferred from the first two rows, which only hold integers.
+    let err = LazyFrame::scan_database(driver.clone(), query, Some(2))?
+        .collect()
+        .unwrap_err();
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)));
+
+    let out = LazyFrame::scan_database(driver, query, None)?.collect()?;
+    assert_eq!(out, df!("v" => ["1", "2", "x", "x", "x"])?);
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use std::path::Path;
+
+use polars_io::delta::snapshot::DeltaTimeTravel;
+use polars_plan::dsl::delta_sink::DeltaSinkMode;
+
+use super::*;
+
+/// Serializes a deletion vector of row positions below 65536: the magic number followed by a
+/// roaring bitmap with a single array container.
+fn serialize_deletion_vector(positions: &[u16]) -> Vec<u8> {
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&1681511377u32.to_le_bytes());
+    bytes.extend_from_slice(&1u64.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    // Cookie without run containers, the container count, the key and cardinality of the
+    // container, and its offset.
+    bytes.extend_from_slice(&12346u32.to_le_bytes());
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&(positions.len() as u16 - 1).to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+
+    for position in positions {
+        bytes.extend_from_slice(&position.to_le_bytes());
+    }
+
+    bytes
+}
+
+/// Writes a Delta table with a single data file, of which the rows at `deleted` are removed by
+/// a deletion vector stored in a separate file.
+fn write_table_with_deletion_vector(dir: &Path, df: &mut DataFrame, deleted: &[u16]) {
+    let data_path = dir.join("part-0.parquet");
+    ParquetWriter::new(std::fs::File::create(&data_path).unwrap())
+        .finish(df)
+        .unwrap();
+    let data_size = std::fs::metadata(&data_path).unwrap().len();
+
+    // A format version byte, followed by the size-prefixed deletion vector and its checksum.
+    let dv = serialize_deletion_vector(deleted);
+    let mut dv_file = vec![1u8];
+    dv_file.extend_from_slice(&(dv.len() as u32).to_be_bytes());
+    dv_file.extend_from_slice(&dv);
+    dv_file.extend_from_slice(&0u32.to_be_bytes());
+
+    let dv_path = dir.join("deletion_vector.bin");
+    std::fs::write(&dv_path, dv_file).unwrap();
+
+    let schema = r#"{\"type\":\"struct\",\"fields\":[{\"name\":\"a\",\"type\":\"long\",\"nullable\":true,\"metadata\":{}}]}"#;
+    let commit = [
+        r#"{"protocol":{"minReaderVersion":3,"minWriterVersion":7,"readerFeatures":["deletionVectors"],"writerFeatures":["deletionVectors"]}}"#.to_string(),
+        format!(
+            r#"{{"metaData":{{"id":"t","format":{{"provider":"parquet","options":{{}}}},"schemaString":"{schema}","partitionColumns":[],"configuration":{{}}}}}}"#
+        ),
+        format!(
+            r#"{{"add":{{"path":"part-0.parquet","partitionValues":{{}},"size":{data_size},"modificationTime":0,"dataChange":true,"deletionVector":{{"storageType":"p","pathOrInlineDv":"{}","offset":1,"sizeInBytes":{},"cardinality":{}}}}}}}"#,
+            dv_path.to_str().unwrap(),
+            dv.len(),
+            deleted.len(),
+        ),
+    ]
+    .join("\n");
+
+    let log_dir = dir.join("_delta_log");
+    std::fs::create_dir(&log_dir).unwrap();
+    std::fs::write(log_dir.join("00000000000000000000.json"), commit).unwrap();
+}
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_scan_delta_deletion_vector() -> PolarsResult<()> {
+    let dir = tempfile::tempdir().unwrap();
+    let mut df = df!("a" => (0..10i64).collect::<Vec<_>>())?;
+    write_table_with_deletion_vector(dir.path(), &mut df, &[1, 3, 4]);
+
+    let scan =
+        || LazyFrame::scan_delta(dir.path().to_str().unwrap(), DeltaTimeTravel::Latest, None);
+
+    // Rows past the last deleted row are kept.
+    let out = scan()?
+        .collect_with_engine(Engine::Streaming)?
+        .unwrap_single();
+    assert_eq!(out, df!("a" => [0i64, 2, 5, 6, 7, 8, 9])?);
+
+    let out = scan()?
+        .filter(col("a").gt(lit(2i64)))
+        .slice(1, 2)
+        .collect_with_engine(Engine::Streaming)?
+        .unwrap_single();
+    assert_eq!(out, df!("a" => [6i64, 7])?);
+
+    Ok(())
+}
+
+#[cfg(feature = "new_streaming")]
+fn sink_delta(df: DataFrame, table_uri: &str, mode: DeltaSinkMode) -> PolarsResult<()> {
+    df.lazy()
+        .sink_delta(
+            table_uri,
+            mode,
+            vec!["p".into()],
+            Arc::new(ParquetWriteOptions::default()),
+            None,
+        )?
+        .collect_with_engine(Engine::Streaming)?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_sink_delta_roundtrip() -> PolarsResult<()> {
+    let dir = tempfile::tempdir().unwrap();
+    let table_uri = dir.path().to_str().unwrap();
+
+    let scan = |time_travel| {
+        LazyFrame::scan_delta(table_uri, time_travel, None)?
+            .sort(["a"], Default::default())
+            .collect_with_engine(Engine::Streaming)
+            .map(|out| out.unwrap_single())
+    };
+
+    // Creates the table.
+    sink_delta(
+        df!("a" => [1i64, 2, 3], "p" => ["x", "y", "x"])?,
+        table_uri,
+        DeltaSinkMode::Append,
+    )?;
+    sink_delta(
+        df!("a" => [4i64, 5], "p" => ["y", "z"])?,
+        table_uri,
+        DeltaSinkMode::Append,
+    )?;
+    assert_eq!(
+        scan(DeltaTimeTravel::Latest)?,
+        df!("a" => [1i64, 2, 3, 4, 5], "p" => ["x", "y", "x", "y", "z"])?
+    );
+
+    // Only the partition matching the predicate is replaced.
+    sink_delta(
+        df!("a" => [6i64], "p" => ["x"])?,
+        table_uri,
+        DeltaSinkMode::ReplaceWhere(col("p").eq(lit("x"))),
+    )?;
+    assert_eq!(
+        scan(DeltaTimeTravel::Latest)?,
+        df!("a" => [2i64, 4, 5, 6], "p" => ["y", "y", "z", "x"])?
+    );
+
+    sink_delta(
+        df!("a" => [7i64], "p" => ["y"])?,
+        table_uri,
+        DeltaSinkMode::Overwrite,
+    )?;
+    assert_eq!(
+        scan(DeltaTimeTravel::Latest)?,
+        df!("a" => [7i64], "p" => ["y"])?
+    );
+
+    // Earlier versions remain readable.
+    assert_eq!(
+        scan(DeltaTimeTravel::Version(0))?,
+        df!("a" => [1i64, 2, 3], "p" => ["x", "y", "x"])?
+    );
+
+    Ok(())
+}
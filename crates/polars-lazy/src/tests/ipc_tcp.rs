@@ -0,0 +1,24 @@
+use polars_io::ipc::IpcTcpServer;
+
+use super::*;
+
+#[test]
+fn test_collect_remote() -> PolarsResult<()> {
+    let server = IpcTcpServer::bind("127.0.0.1:0")?;
+    let addr = server.local_addr()?;
+    let handle = std::thread::spawn(move || server.serve_one(dsl_plan_ipc_tcp_handler));
+
+    let q = load_df()
+        .lazy()
+        .filter(col("a").gt(lit(1)))
+        .group_by([col("b")])
+        .agg([col("c").sum()])
+        .sort(["b"], Default::default());
+
+    let expected = q.clone().collect()?;
+    let out = q.collect_remote(addr)?;
+    assert!(out.equals(&expected));
+
+    handle.join().unwrap()?;
+    Ok(())
+}
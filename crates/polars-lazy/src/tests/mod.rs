@@ -1,9 +1,23 @@
 mod aggregations;
 mod arity;
+mod arrow_c_stream;
 #[cfg(all(feature = "strings", feature = "cse"))]
 mod cse;
+#[cfg(all(
+    feature = "cloud",
+    feature = "csv",
+    feature = "parquet",
+    feature = "new_streaming"
+))]
+mod custom_scheme;
+#[cfg(all(feature = "sqlite", feature = "strings"))]
+mod database;
+#[cfg(feature = "delta")]
+mod delta;
 #[cfg(feature = "parquet")]
 mod io;
+#[cfg(feature = "ipc_tcp")]
+mod ipc_tcp;
 mod logical;
 mod optimization_checks;
 #[cfg(all(feature = "strings", feature = "cse"))]
@@ -12,6 +26,8 @@ mod predicate_queries;
 mod projection_queries;
 mod queries;
 mod schema;
+#[cfg(all(feature = "new_streaming", any(feature = "csv", feature = "ipc")))]
+mod sink_target;
 
 fn get_arenas() -> (Arena<AExpr>, Arena<IR>) {
     let expr_arena = Arena::with_capacity(16);
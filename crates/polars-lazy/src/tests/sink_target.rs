@@ -0,0 +1,95 @@
+use std::io::Write;
+use std::sync::Mutex;
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn sink_target(lf: LazyFrame, target: SinkTarget, format: FileWriteFormat) -> PolarsResult<()> {
+    lf.sink(
+        SinkDestination::File { target },
+        format,
+        UnifiedSinkArgs::default(),
+    )?
+    .collect_with_engine(Engine::Streaming)?;
+
+    Ok(())
+}
+
+fn sink_into_buf(lf: LazyFrame, format: FileWriteFormat) -> PolarsResult<Vec<u8>> {
+    let buf = SharedBuf::default();
+    sink_target(lf, SinkTarget::from_writer(buf.clone()), format)?;
+
+    Ok(std::mem::take(&mut *buf.0.lock().unwrap()))
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_sink_csv_into_writer() -> PolarsResult<()> {
+    let lf = df!("a" => [1i64, 2, 3], "b" => ["x", "y", "z"])?.lazy();
+    let bytes = sink_into_buf(
+        lf.clone().filter(col("a").gt(lit(1i64))),
+        FileWriteFormat::Csv(CsvWriterOptions::default()),
+    )?;
+    assert_eq!(bytes, b"a,b\n2,y\n3,z\n");
+
+    // A writer can only be sunk into once.
+    let target = SinkTarget::from_writer(SharedBuf::default());
+    let format = || FileWriteFormat::Csv(CsvWriterOptions::default());
+    sink_target(lf.clone(), target.clone(), format())?;
+    assert!(sink_target(lf, target, format()).is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "ipc")]
+fn test_sink_ipc_into_writer() -> PolarsResult<()> {
+    let df = df!("a" => [1i64, 2, 3], "b" => ["x", "y", "z"])?;
+    let bytes = sink_into_buf(
+        df.clone().lazy(),
+        FileWriteFormat::Ipc(IpcWriterOptions::default()),
+    )?;
+
+    // An IPC file starts and ends with its magic bytes.
+    assert!(bytes.starts_with(b"ARROW1") && bytes.ends_with(b"ARROW1"));
+    assert_eq!(IpcReader::new(Cursor::new(bytes)).finish()?, df);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "cloud", feature = "csv"))]
+fn test_sink_csv_into_async_writer() -> PolarsResult<()> {
+    use tokio::io::AsyncReadExt;
+
+    let (writer, mut reader) = tokio::io::duplex(1 << 16);
+
+    let lf = df!("a" => [1i64, 2], "b" => ["x", "y"])?.lazy();
+    sink_target(
+        lf,
+        SinkTarget::from_async_writer(writer),
+        FileWriteFormat::Csv(CsvWriterOptions::default()),
+    )?;
+
+    // The writer is shut down once the sink finishes, which ends the stream.
+    let mut bytes = vec![];
+    polars_io::pl_async::get_runtime()
+        .block_on(reader.read_to_end(&mut bytes))
+        .unwrap();
+    assert_eq!(bytes, b"a,b\n1,x\n2,y\n");
+
+    Ok(())
+}
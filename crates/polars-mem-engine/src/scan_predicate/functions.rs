@@ -535,19 +535,15 @@ where
     });
 
     *deletion_files = deletion_files.take().and_then(|x| match x {
-        DeletionFilesList::IcebergPositionDelete(deletions) => {
-            let mut out = None;
-
-            for (out_idx, source_idx) in selected_path_indices.clone().enumerate() {
-                if let Some(v) = deletions.get(&source_idx) {
-                    out.get_or_insert_with(|| {
-                        PlIndexMap::with_capacity(selected_path_indices.size_hint().0 - out_idx)
-                    })
-                    .insert(out_idx, v.clone());
-                }
-            }
-
-            out.map(|x| DeletionFilesList::IcebergPositionDelete(Arc::new(x)))
+        DeletionFilesList::IcebergDelete(files) => {
+            DeletionFilesList::filter_empty(Some(DeletionFilesList::IcebergDelete(Arc::new(
+                files.select_sources(selected_path_indices.clone()),
+            ))))
+        },
+        DeletionFilesList::DeltaDeletionVectors(dvs) => {
+            DeletionFilesList::filter_empty(Some(DeletionFilesList::DeltaDeletionVectors(
+                Arc::new(dvs.select_sources(selected_path_indices.clone())),
+            )))
         },
         // No-op - Delta takes scan paths at the execution stage.
         #[cfg(feature = "python")]
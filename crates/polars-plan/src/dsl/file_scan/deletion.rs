@@ -23,8 +23,10 @@ pub enum DeletionFilesList {
     // Other possible options:
     // * ListArray(inner: Utf8Array)
     //
-    /// Iceberg positional deletes
-    IcebergPositionDelete(Arc<PlIndexMap<usize, Arc<[String]>>>),
+    /// Iceberg positional and equality deletes
+    IcebergDelete(Arc<IcebergDeletionFiles>),
+    /// Delta deletion vectors read from the transaction log
+    DeltaDeletionVectors(Arc<DeltaDeletionVectors>),
     /// Delta deletion vector
     #[cfg(feature = "python")]
     Delta(DeltaDeletionVectorProvider),
@@ -36,8 +38,9 @@ impl DeletionFilesList {
         use DeletionFilesList::*;
 
         match this {
-            Some(IcebergPositionDelete(paths)) => {
-                (!paths.is_empty()).then_some(IcebergPositionDelete(paths))
+            Some(IcebergDelete(files)) => (!files.is_empty()).then_some(IcebergDelete(files)),
+            Some(DeltaDeletionVectors(dvs)) => {
+                (!dvs.descriptors.is_empty()).then_some(DeltaDeletionVectors(dvs))
             },
             #[cfg(feature = "python")]
             Some(Delta(provider)) => Some(Delta(provider)),
@@ -45,12 +48,22 @@ impl DeletionFilesList {
         }
     }
 
+    /// Returns `true` if rows are deleted by matching column values. Which rows are deleted is
+    /// then only known after reading the data files.
+    pub fn has_equality_deletes(&self) -> bool {
+        match self {
+            Self::IcebergDelete(files) => !files.equality_deletes.is_empty(),
+            _ => false,
+        }
+    }
+
     /// Returns the number of files with deletions, but only if known at plan time.
     pub fn num_files_with_deletions(&self) -> Option<usize> {
         use DeletionFilesList::*;
 
         match self {
-            IcebergPositionDelete(paths) => Some(paths.len()),
+            IcebergDelete(files) => Some(files.num_files_with_deletions()),
+            DeltaDeletionVectors(dvs) => Some(dvs.descriptors.len()),
             #[cfg(feature = "python")]
             Delta(_) => None,
         }
@@ -64,13 +77,8 @@ impl std::hash::Hash for DeletionFilesList {
         std::mem::discriminant(self).hash(state);
 
         match self {
-            IcebergPositionDelete(paths) => {
-                let addr = paths
-                    .first()
-                    .map_or(0, |(_, paths)| Arc::as_ptr(paths) as *const () as usize);
-
-                addr.hash(state)
-            },
+            IcebergDelete(files) => (Arc::as_ptr(files) as usize).hash(state),
+            DeltaDeletionVectors(dvs) => (Arc::as_ptr(dvs) as usize).hash(state),
             #[cfg(feature = "python")]
             Delta(provider) => provider.hash(state),
         }
@@ -82,9 +90,20 @@ impl std::fmt::Display for DeletionFilesList {
         use DeletionFilesList::*;
 
         match self {
-            IcebergPositionDelete(paths) => {
-                let s = if paths.len() == 1 { "" } else { "s" };
-                write!(f, "iceberg-position-delete: {} source{s}", paths.len())?;
+            IcebergDelete(files) => {
+                let n = files.num_files_with_deletions();
+                let s = if n == 1 { "" } else { "s" };
+
+                if files.equality_deletes.is_empty() {
+                    write!(f, "iceberg-position-delete: {n} source{s}")?;
+                } else {
+                    write!(f, "iceberg-delete: {n} source{s}")?;
+                }
+            },
+            DeltaDeletionVectors(dvs) => {
+                let n = dvs.descriptors.len();
+                let s = if n == 1 { "" } else { "s" };
+                write!(f, "delta-deletion-vector: {n} source{s}")?;
             },
             #[cfg(feature = "python")]
             Delta(_) => {
@@ -95,3 +114,106 @@ impl std::fmt::Display for DeletionFilesList {
         Ok(())
     }
 }
+
+/// Iceberg deletion files, keyed by the scan source index.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct IcebergDeletionFiles {
+    pub position_deletes: PlIndexMap<usize, Arc<[String]>>,
+    pub equality_deletes: PlIndexMap<usize, Arc<[IcebergEqualityDeleteFile]>>,
+}
+
+impl IcebergDeletionFiles {
+    pub fn from_position_deletes(position_deletes: PlIndexMap<usize, Arc<[String]>>) -> Self {
+        Self {
+            position_deletes,
+            equality_deletes: PlIndexMap::default(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position_deletes.is_empty() && self.equality_deletes.is_empty()
+    }
+
+    pub fn num_files_with_deletions(&self) -> usize {
+        self.position_deletes.len()
+            + self
+                .equality_deletes
+                .keys()
+                .filter(|idx| !self.position_deletes.contains_key(*idx))
+                .count()
+    }
+
+    /// Re-keys the deletion files by the position of their source in `selected_source_indices`.
+    pub fn select_sources<I>(&self, selected_source_indices: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut out = Self::default();
+
+        for (out_idx, source_idx) in selected_source_indices.into_iter().enumerate() {
+            if let Some(v) = self.position_deletes.get(&source_idx) {
+                out.position_deletes.insert(out_idx, v.clone());
+            }
+
+            if let Some(v) = self.equality_deletes.get(&source_idx) {
+                out.equality_deletes.insert(out_idx, v.clone());
+            }
+        }
+
+        out
+    }
+}
+
+/// An Iceberg equality delete file. Rows of the data file whose values in the `equality_ids`
+/// columns match a row of the delete file are deleted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct IcebergEqualityDeleteFile {
+    pub path: String,
+    /// Iceberg field IDs of the columns used for matching.
+    pub equality_ids: Arc<[u32]>,
+}
+
+/// Delta deletion vectors of the data files of a table, keyed by the scan source index.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct DeltaDeletionVectors {
+    /// Root of the table, used to resolve relative deletion vector paths.
+    pub table_uri: String,
+    pub descriptors: PlIndexMap<usize, DeltaDeletionVectorDescriptor>,
+}
+
+impl DeltaDeletionVectors {
+    /// Re-keys the deletion vectors by the position of their source in `selected_source_indices`.
+    pub fn select_sources<I>(&self, selected_source_indices: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        Self {
+            table_uri: self.table_uri.clone(),
+            descriptors: selected_source_indices
+                .into_iter()
+                .enumerate()
+                .filter_map(|(out_idx, source_idx)| {
+                    Some((out_idx, self.descriptors.get(&source_idx)?.clone()))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The `deletionVector` descriptor of an `add` action in the Delta transaction log.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct DeltaDeletionVectorDescriptor {
+    pub storage_type: String,
+    pub path_or_inline_dv: String,
+    pub offset: Option<i32>,
+    pub size_in_bytes: i32,
+    pub cardinality: i64,
+}
@@ -251,6 +251,22 @@ impl CastColumnsPolicy {
         missing_struct_fields: MissingColumnsPolicy::Raise,
         extra_struct_fields: ExtraColumnsPolicy::Raise,
     };
+
+    /// Configuration suitable for table formats such as Iceberg / Delta Lake, where data files
+    /// may have been written with an older version of the table schema.
+    pub const DEFAULT_ICEBERG: Self = Self {
+        integer_upcast: true,
+        integer_to_float_cast: false,
+        float_upcast: true,
+        float_downcast: true,
+        datetime_nanoseconds_downcast: true,
+        datetime_microseconds_downcast: false,
+        datetime_convert_timezone: true,
+        null_upcast: true,
+        categorical_to_string: true,
+        missing_struct_fields: MissingColumnsPolicy::Insert,
+        extra_struct_fields: ExtraColumnsPolicy::Ignore,
+    };
 }
 
 impl Default for CastColumnsPolicy {
@@ -0,0 +1,392 @@
+use std::sync::Arc;
+
+use polars_io::cloud::CloudOptions;
+use polars_utils::pl_path::PlRefPath;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::FileWriteFormat;
+use super::sink::UnifiedSinkArgs;
+use crate::dsl::Expr;
+
+/// Sink to a Delta table. Resolved to a partitioned sink that commits the written files to the
+/// transaction log of the table.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct DeltaSinkOptions {
+    pub table_uri: PlRefPath,
+    pub mode: DeltaSinkMode,
+    /// Partition columns of a new table. If non-empty when writing to an existing table, they
+    /// must equal the partition columns of the table.
+    pub partition_by: Vec<PlSmallStr>,
+    pub file_format: FileWriteFormat,
+    pub unified_sink_args: UnifiedSinkArgs,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub enum DeltaSinkMode {
+    Append,
+    Overwrite,
+    /// Replace the data matching the predicate. The predicate must only select on partition
+    /// columns, as a conjunction of equalities (`col == lit`, `col.is_null()`) or disjunctions of
+    /// equalities on a single column.
+    ReplaceWhere(Expr),
+}
+
+/// Commit of the sinked files to a Delta table, resolved from [`DeltaSinkOptions`].
+#[derive(Clone, Debug, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct DeltaCommitState {
+    pub table_uri: String,
+    /// Version of the table the write is based on, or `None` if the write creates the table.
+    pub read_version: Option<i64>,
+    pub mode: DeltaCommitMode,
+    /// Delta schema of the table, as JSON.
+    pub schema_string: String,
+    pub partition_columns: Vec<String>,
+    pub cloud_options: Option<Arc<CloudOptions>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub enum DeltaCommitMode {
+    Append,
+    Overwrite,
+    /// `(column, values)` partition filters, see [`DeltaSinkMode::ReplaceWhere`].
+    ReplaceWhere(Vec<(String, Vec<Option<String>>)>),
+}
+
+#[cfg(feature = "delta")]
+mod _delta_impl {
+    use std::sync::Arc;
+
+    use arrow::datatypes::ArrowSchema;
+    use polars_core::prelude::*;
+    use polars_error::to_compute_err;
+    use polars_io::delta::schema::{ColumnMappingMode, StructType};
+    use polars_io::delta::snapshot::{DeltaSnapshot, DeltaTimeTravel};
+    use polars_io::delta::write::{DeltaPartitionFilter, DeltaTableWrite, DeltaWriteMode};
+    use polars_io::pl_async::get_runtime;
+    use polars_utils::IdxSize;
+    use polars_utils::pl_path::PlRefPath;
+    use polars_utils::pl_str::PlSmallStr;
+
+    use super::{DeltaCommitMode, DeltaCommitState, DeltaSinkMode, DeltaSinkOptions};
+    use crate::dsl::file_provider::{DeltaPathProvider, FileProviderType};
+    use crate::dsl::sink::{
+        PartitionStrategy, PartitionedSinkOptions, SinkedPathsCallback, SinkedPathsCallbackArgs,
+    };
+    use crate::dsl::{BooleanFunction, Expr, FileWriteFormat, FunctionExpr, Operator};
+
+    /// Default of the `delta.targetFileSize` table property.
+    const DEFAULT_TARGET_FILE_SIZE: u64 = 100 * 1024 * 1024;
+
+    impl DeltaSinkOptions {
+        /// Resolves to a partitioned sink writing the data files of the table, after checking the
+        /// input schema against the schema of the table (if it exists).
+        pub(crate) fn resolve(self, input_schema: &Schema) -> PolarsResult<PartitionedSinkOptions> {
+            let DeltaSinkOptions {
+                table_uri,
+                mode,
+                partition_by,
+                mut file_format,
+                mut unified_sink_args,
+            } = self;
+
+            let FileWriteFormat::Parquet(parquet_options) = &mut file_format else {
+                polars_bail!(InvalidOperation: "delta sink requires the parquet file format")
+            };
+
+            polars_ensure!(
+                unified_sink_args.sinked_paths_callback.is_none(),
+                InvalidOperation: "delta sink does not support a sinked paths callback"
+            );
+
+            let table_uri = table_uri
+                .to_absolute_path()?
+                .as_str()
+                .trim_end_matches('/')
+                .to_string();
+
+            let cloud_options = unified_sink_args.cloud_options.clone();
+
+            let snapshot = get_runtime().block_in_place_on(DeltaSnapshot::try_load(
+                &table_uri,
+                DeltaTimeTravel::Latest,
+                cloud_options.as_deref(),
+            ))?;
+
+            let input_delta_schema =
+                StructType::from_arrow_schema(&input_schema.to_arrow(CompatLevel::newest()))?;
+
+            let (read_version, schema_string, partition_columns, approximate_bytes_per_file) =
+                match &snapshot {
+                    None => {
+                        for name in &partition_by {
+                            polars_ensure!(
+                                input_schema.contains(name),
+                                ColumnNotFound:
+                                "delta partition column '{}' not found in the input", name
+                            );
+                        }
+
+                        (
+                            None,
+                            serde_json::to_string(&input_delta_schema).map_err(to_compute_err)?,
+                            partition_by
+                                .iter()
+                                .map(|c| c.to_string())
+                                .collect::<Vec<_>>(),
+                            DEFAULT_TARGET_FILE_SIZE,
+                        )
+                    },
+                    Some(snapshot) => {
+                        snapshot.check_writable()?;
+
+                        let metadata = &snapshot.metadata;
+                        let table_schema = metadata.schema()?;
+
+                        polars_ensure!(
+                            partition_by.is_empty()
+                                || partition_by
+                                    .iter()
+                                    .map(PlSmallStr::as_str)
+                                    .eq(metadata.partition_columns.iter().map(String::as_str)),
+                            InvalidOperation:
+                            "partition columns {:?} differ from the partition columns {:?} of \
+                            delta table '{}'",
+                            partition_by, metadata.partition_columns, table_uri
+                        );
+
+                        check_input_schema(&input_delta_schema, &table_schema)?;
+
+                        let target_file_size = metadata
+                            .configuration_value("delta.targetFileSize")
+                            .and_then(|v| v.parse::<u64>().ok())
+                            .unwrap_or(DEFAULT_TARGET_FILE_SIZE);
+
+                        // Write with the table types, in the column order of the input.
+                        let table_arrow_schema =
+                            table_schema.to_arrow_schema(ColumnMappingMode::None)?;
+                        let file_schema: ArrowSchema = input_schema
+                            .iter_names()
+                            .filter(|name| !metadata.partition_columns.iter().any(|c| c == *name))
+                            .map(|name| {
+                                (name.clone(), table_arrow_schema.get(name).unwrap().clone())
+                            })
+                            .collect();
+
+                        Arc::make_mut(parquet_options).arrow_schema = Some(Arc::new(file_schema));
+
+                        (
+                            Some(snapshot.version),
+                            metadata.schema_string.clone(),
+                            metadata.partition_columns.clone(),
+                            target_file_size,
+                        )
+                    },
+                };
+
+            let mode = match mode {
+                DeltaSinkMode::Append => DeltaCommitMode::Append,
+                DeltaSinkMode::Overwrite => DeltaCommitMode::Overwrite,
+                DeltaSinkMode::ReplaceWhere(predicate) => {
+                    let mut filters = vec![];
+                    collect_partition_filters(
+                        &predicate,
+                        input_schema,
+                        &partition_columns,
+                        &mut filters,
+                    )?;
+                    DeltaCommitMode::ReplaceWhere(filters)
+                },
+            };
+
+            let partition_strategy = if partition_columns.is_empty() {
+                PartitionStrategy::FileSize
+            } else {
+                PartitionStrategy::Keyed {
+                    keys: partition_columns
+                        .iter()
+                        .map(|c| Expr::Column(PlSmallStr::from_str(c)))
+                        .collect(),
+                    include_keys: false,
+                    keys_pre_grouped: false,
+                }
+            };
+
+            unified_sink_args.sinked_paths_callback =
+                Some(SinkedPathsCallback::DeltaCommit(DeltaCommitState {
+                    table_uri: table_uri.clone(),
+                    read_version,
+                    mode,
+                    schema_string,
+                    partition_columns,
+                    cloud_options,
+                }));
+
+            Ok(PartitionedSinkOptions {
+                base_path: PlRefPath::new(format!("{table_uri}/")),
+                file_path_provider: Some(FileProviderType::Delta(DeltaPathProvider {
+                    extension: PlSmallStr::from_static("parquet"),
+                    file_part_prefix: String::new(),
+                })),
+                partition_strategy,
+                file_format,
+                unified_sink_args,
+                max_rows_per_file: IdxSize::MAX,
+                // Files are split by the in-memory size, which is larger than the compressed file
+                // size.
+                approximate_bytes_per_file: approximate_bytes_per_file.saturating_mul(4),
+            })
+        }
+    }
+
+    /// Checks that the input has the columns of the table, with the same types.
+    fn check_input_schema(input: &StructType, table: &StructType) -> PolarsResult<()> {
+        polars_ensure!(
+            input.fields.len() == table.fields.len(),
+            SchemaMismatch:
+            "input has {} columns, but the delta table has {} columns",
+            input.fields.len(), table.fields.len()
+        );
+
+        for field in &input.fields {
+            let Some(table_field) = table.field(&field.name) else {
+                polars_bail!(SchemaMismatch: "column '{}' not found in the delta table", field.name)
+            };
+
+            polars_ensure!(
+                field.data_type == table_field.data_type,
+                SchemaMismatch:
+                "type {:?} of column '{}' differs from the type {:?} in the delta table",
+                field.data_type, field.name, table_field.data_type
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Converts a `replaceWhere` predicate into partition filters, see
+    /// [`DeltaSinkMode::ReplaceWhere`].
+    fn collect_partition_filters(
+        predicate: &Expr,
+        schema: &Schema,
+        partition_columns: &[String],
+        out: &mut Vec<(String, Vec<Option<String>>)>,
+    ) -> PolarsResult<()> {
+        if let Expr::BinaryExpr { left, op, right } = predicate
+            && matches!(op, Operator::And | Operator::LogicalAnd)
+        {
+            collect_partition_filters(left, schema, partition_columns, out)?;
+            return collect_partition_filters(right, schema, partition_columns, out);
+        }
+
+        let Some((column, values)) = column_values(predicate, schema)? else {
+            polars_bail!(
+                InvalidOperation:
+                "unsupported delta replaceWhere predicate: {}", predicate
+            )
+        };
+
+        polars_ensure!(
+            partition_columns.iter().any(|c| c == column.as_str()),
+            InvalidOperation:
+            "delta replaceWhere predicate must only select on partition columns, got '{}'", column
+        );
+
+        out.push((column.to_string(), values));
+
+        Ok(())
+    }
+
+    fn column_values(
+        expr: &Expr,
+        schema: &Schema,
+    ) -> PolarsResult<Option<(PlSmallStr, Vec<Option<String>>)>> {
+        let out = match expr {
+            Expr::BinaryExpr { left, op, right } => match (op, left.as_ref(), right.as_ref()) {
+                (Operator::Eq, Expr::Column(name), Expr::Literal(lv))
+                | (Operator::Eq, Expr::Literal(lv), Expr::Column(name)) => {
+                    let Some(av) = lv.to_any_value() else {
+                        return Ok(None);
+                    };
+
+                    polars_ensure!(
+                        !av.is_null(),
+                        InvalidOperation:
+                        "use `is_null()` to select null partitions in delta replaceWhere predicates"
+                    );
+
+                    let value = Series::from_any_values(name.clone(), &[av], true)?
+                        .strict_cast(schema.try_get(name)?)?
+                        .cast(&DataType::String)?;
+
+                    Some((name.clone(), vec![value.str()?.get(0).map(str::to_string)]))
+                },
+                (Operator::Or | Operator::LogicalOr, left, right) => {
+                    match (column_values(left, schema)?, column_values(right, schema)?) {
+                        (Some((l_name, mut l_values)), Some((r_name, r_values)))
+                            if l_name == r_name =>
+                        {
+                            l_values.extend(r_values);
+                            Some((l_name, l_values))
+                        },
+                        _ => None,
+                    }
+                },
+                _ => None,
+            },
+            Expr::Function {
+                input,
+                function: FunctionExpr::Boolean(BooleanFunction::IsNull),
+            } => match input.as_slice() {
+                [Expr::Column(name)] => Some((name.clone(), vec![None])),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        Ok(out)
+    }
+
+    impl DeltaCommitState {
+        pub fn commit(&self, args: SinkedPathsCallbackArgs) -> PolarsResult<()> {
+            let mode = match &self.mode {
+                DeltaCommitMode::Append => DeltaWriteMode::Append,
+                DeltaCommitMode::Overwrite => DeltaWriteMode::Overwrite,
+                DeltaCommitMode::ReplaceWhere(filters) => DeltaWriteMode::ReplaceWhere(
+                    filters
+                        .iter()
+                        .map(|(column, values)| DeltaPartitionFilter {
+                            column: column.clone(),
+                            values: values.clone(),
+                        })
+                        .collect(),
+                ),
+            };
+
+            let write = DeltaTableWrite {
+                table_uri: self.table_uri.clone(),
+                read_version: self.read_version,
+                mode,
+                schema_string: self.schema_string.clone(),
+                partition_columns: self.partition_columns.clone(),
+            };
+
+            let paths = args
+                .path_info_list
+                .iter()
+                .map(|info| info.path.as_str())
+                .collect::<Vec<_>>();
+
+            get_runtime().block_on(write.commit(&paths, self.cloud_options.as_deref()))?;
+
+            Ok(())
+        }
+    }
+}
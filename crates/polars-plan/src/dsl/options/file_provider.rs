@@ -30,6 +30,7 @@ pub type FileProviderFunction = PlanCallback<FileProviderArgs, FileProviderRetur
 pub enum FileProviderType {
     Hive(HivePathProvider),
     Iceberg(IcebergPathProvider),
+    Delta(DeltaPathProvider),
     Function(FileProviderFunction),
 }
 
@@ -52,6 +53,7 @@ impl FileProviderType {
 
         match self {
             Iceberg(p) => Some(p.file_part_prefix_mut()),
+            Delta(p) => Some(p.file_part_prefix_mut()),
             Hive(_) | Function(_) => None,
         }
     }
@@ -62,6 +64,7 @@ impl FileProviderType {
         match self {
             Hive(p) => p.get_path(args).map(FileProviderReturn::Path),
             Iceberg(p) => p.get_path(args).map(FileProviderReturn::Path),
+            Delta(p) => p.get_path(args).map(FileProviderReturn::Path),
             Function(p) => p.get_path_or_file(args),
         }
     }
@@ -157,6 +160,53 @@ impl IcebergPathProvider {
     }
 }
 
+/// Hive-style partitioned paths with Spark-style file names, as written by Delta Lake writers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct DeltaPathProvider {
+    pub extension: PlSmallStr,
+    pub file_part_prefix: String,
+}
+
+impl DeltaPathProvider {
+    pub fn file_part_prefix_mut(&mut self) -> &mut String {
+        &mut self.file_part_prefix
+    }
+
+    /// # Panics
+    /// Panics if `self.file_part_prefix` is empty.
+    pub fn get_path(&self, args: FileProviderArgs) -> PolarsResult<String> {
+        use std::fmt::Write;
+
+        let DeltaPathProvider {
+            extension,
+            file_part_prefix,
+        } = self;
+
+        assert!(!file_part_prefix.is_empty());
+
+        let FileProviderArgs {
+            index_in_partition,
+            partition_keys,
+        } = args;
+
+        let mut path = String::new();
+
+        let partition_keys: &[Column] = partition_keys.columns();
+
+        write!(&mut path, "{}", HivePathFormatter::new(partition_keys)).unwrap();
+
+        write!(
+            &mut path,
+            "part-{index_in_partition:05}-{file_part_prefix}.{extension}"
+        )
+        .unwrap();
+
+        Ok(path)
+    }
+}
+
 impl FileProviderFunction {
     pub fn get_path_or_file(&self, args: FileProviderArgs) -> PolarsResult<FileProviderReturn> {
         match self {
@@ -3,6 +3,7 @@ use std::hash::Hash;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
+pub mod delta_sink;
 pub mod file_provider;
 pub mod iceberg_sink_state;
 pub mod sink;
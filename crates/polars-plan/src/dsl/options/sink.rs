@@ -8,7 +8,7 @@ use polars_core::error::PolarsResult;
 use polars_core::frame::DataFrame;
 use polars_core::prelude::PlHashSet;
 use polars_core::schema::Schema;
-use polars_error::feature_gated;
+use polars_error::{feature_gated, polars_err};
 use polars_io::cloud::CloudOptions;
 use polars_io::metrics::IOMetrics;
 use polars_io::utils::file::Writeable;
@@ -19,6 +19,7 @@ use polars_utils::pl_path::{CloudScheme, PlRefPath};
 use polars_utils::pl_str::PlSmallStr;
 
 use super::FileWriteFormat;
+use crate::dsl::delta_sink::{DeltaCommitState, DeltaSinkOptions};
 use crate::dsl::file_provider::FileProviderType;
 use crate::dsl::iceberg_sink_state::IcebergSinkState;
 use crate::dsl::{AExpr, Expr, SpecialEq};
@@ -80,6 +81,42 @@ pub enum SinkTarget {
 }
 
 impl SinkTarget {
+    /// Sink into an already opened [`Writeable`].
+    pub fn from_writeable(writeable: Writeable) -> Self {
+        Self::Dyn(SpecialEq::new(Arc::new(std::sync::Mutex::new(Some(
+            writeable,
+        )))))
+    }
+
+    /// Sink into an arbitrary writer, e.g. a pipe, a socket or an in-memory buffer.
+    ///
+    /// The writer is flushed once the sink finishes.
+    pub fn from_writer<W: std::io::Write + Send + 'static>(writer: W) -> Self {
+        Self::from_writeable(Writeable::from_writer(writer))
+    }
+
+    /// Sink into an arbitrary async writer, e.g. an HTTP response body.
+    ///
+    /// The writer is shut down once the sink finishes.
+    #[cfg(feature = "cloud")]
+    pub fn from_async_writer<W: tokio::io::AsyncWrite + Send + Unpin + 'static>(writer: W) -> Self {
+        Self::from_writeable(Writeable::from_async_writer(writer))
+    }
+
+    /// Sink into the standard output of the process.
+    pub fn stdout() -> Self {
+        Self::from_writer(std::io::stdout())
+    }
+
+    fn take_dyn_writeable(target: &DynSinkTarget) -> PolarsResult<Writeable> {
+        target.lock().unwrap().take().ok_or_else(|| {
+            polars_err!(
+                ComputeError:
+                "sink target writer was already consumed; a writer can only be sunk into once"
+            )
+        })
+    }
+
     pub fn cloud_scheme(&self) -> Option<CloudScheme> {
         match self {
             SinkTarget::Path(p) => CloudScheme::from_path(p.as_str()),
@@ -109,7 +146,7 @@ impl SinkTarget {
                     io_metrics,
                 )
             },
-            SinkTarget::Dyn(memory_writer) => Ok(memory_writer.lock().unwrap().take().unwrap()),
+            SinkTarget::Dyn(memory_writer) => Self::take_dyn_writeable(memory_writer),
         }
     }
 
@@ -137,7 +174,7 @@ impl SinkTarget {
                         io_metrics,
                     )
                 },
-                SinkTarget::Dyn(memory_writer) => Ok(memory_writer.lock().unwrap().take().unwrap()),
+                SinkTarget::Dyn(memory_writer) => Self::take_dyn_writeable(memory_writer),
             }
         }
 
@@ -230,6 +267,7 @@ pub enum SinkType {
     File(FileSinkOptions),
     Partitioned(PartitionedSinkOptions),
     Iceberg(IcebergSinkState),
+    Delta(DeltaSinkOptions),
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -473,6 +511,7 @@ pub struct FileSinkOptions {
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub enum SinkedPathsCallback {
     IcebergCommit(IcebergSinkState),
+    DeltaCommit(DeltaCommitState),
     Callback(PlanCallback<SinkedPathsCallbackArgs, ()>),
 }
 
@@ -525,6 +564,7 @@ impl SinkedPathsCallback {
                     })
                 })
             },
+            Self::DeltaCommit(commit_state) => feature_gated!("delta", commit_state.commit(args)),
             Self::Callback(CB::Rust(func)) => (func)(args),
             #[cfg(feature = "python")]
             Self::Callback(CB::Python(object)) => pyo3::Python::attach(|py| {
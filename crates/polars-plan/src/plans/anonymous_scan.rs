@@ -13,11 +13,26 @@ pub struct AnonymousScanArgs {
     pub predicate: Option<Expr>,
 }
 
+/// Produces the next batch of an [`AnonymousScan`], or `None` once the scan is exhausted.
+pub type AnonymousScanBatchFn = Box<dyn FnMut() -> PolarsResult<Option<DataFrame>> + Send>;
+
 pub trait AnonymousScan: Send + Sync {
     fn as_any(&self) -> &dyn Any;
     /// Creates a DataFrame from the supplied function & scan options.
     fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame>;
 
+    /// Creates a function producing the output of the scan in batches, which allows the streaming
+    /// engine to read the source lazily.
+    ///
+    /// Defaults to `None`, in which case the output of [`AnonymousScan::scan`] is used as a single
+    /// batch.
+    fn batch_fn(
+        &self,
+        _scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Option<AnonymousScanBatchFn>> {
+        Ok(None)
+    }
+
     /// function to supply the schema.
     /// Allows for an optional infer schema argument for data sources with dynamic schemas
     fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
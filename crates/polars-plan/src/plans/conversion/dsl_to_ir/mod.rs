@@ -1401,8 +1401,18 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
             let input =
                 to_alp_impl(owned(input), ctxt).map_err(|e| e.context(failed_here!(sink)))?;
             let input_schema = ctxt.lp_arena.get(input).schema(ctxt.lp_arena);
+
+            #[cfg(feature = "delta")]
+            let payload = match payload {
+                SinkType::Delta(options) => {
+                    SinkType::Partitioned(options.resolve(input_schema.as_ref())?)
+                },
+                payload => payload,
+            };
+
             let payload = match payload {
                 SinkType::Iceberg(_) => unreachable!(),
+                SinkType::Delta(_) => feature_gated!("delta", unreachable!()),
                 SinkType::Memory => SinkTypeIR::Memory,
                 SinkType::Callback(f) => SinkTypeIR::Callback(f),
                 SinkType::File(mut options) => {
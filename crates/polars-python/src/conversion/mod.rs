@@ -20,7 +20,9 @@ use polars::prelude::ColumnMapping;
 use polars::prelude::default_values::{
     DefaultFieldValues, IcebergIdentityTransformedPartitionFields,
 };
-use polars::prelude::deletion::{DeletionFilesList, DeltaDeletionVectorProvider};
+use polars::prelude::deletion::{
+    DeletionFilesList, DeltaDeletionVectorProvider, IcebergDeletionFiles,
+};
 use polars::series::ops::NullBehavior;
 use polars_buffer::Buffer;
 use polars_compute::decimal::dec128_verify_prec_scale;
@@ -1848,7 +1850,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for Wrap<DeletionFilesList> {
                     }
                 }
 
-                DeletionFilesList::IcebergPositionDelete(Arc::new(out))
+                DeletionFilesList::IcebergDelete(Arc::new(
+                    IcebergDeletionFiles::from_position_deletes(out),
+                ))
             },
 
             "delta-deletion-vector" => {
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use polars::prelude::CloudScheme;
 use polars_core::config::verbose_print_sensitive;
-use polars_io::cloud::{CloudOptions, CloudRetryConfig};
+use polars_io::cloud::{AtomicCommitMode, CloudOptions, CloudRetryConfig};
 use polars_utils::total_ord::TotalOrdWrap;
 use pyo3::exceptions::PyValueError;
 use pyo3::intern;
@@ -38,6 +38,7 @@ impl OptPyCloudOptions<'_> {
         let mut storage_options: Vec<(PyBackedStr, String)> = vec![];
         let mut file_cache_ttl: u64 = 2;
         let mut retry_config = CloudRetryConfig::default();
+        let mut atomic_commit: Option<AtomicCommitMode> = None;
 
         let storage_options_dict: Option<Bound<'_, PyDict>> = self.0.extract()?;
 
@@ -105,6 +106,22 @@ impl OptPyCloudOptions<'_> {
                                 .map_err(expected_type!("retry_base_multiplier", "float"))?,
                         ));
                     },
+                    "atomic_commit" => {
+                        let value: String = value
+                            .extract()
+                            .map_err(expected_type!("atomic_commit", "str"))?;
+
+                        atomic_commit = Some(match value.as_str() {
+                            "overwrite" => AtomicCommitMode::Overwrite,
+                            "create_new" => AtomicCommitMode::CreateNew,
+                            v => {
+                                return Err(PyValueError::new_err(format!(
+                                    "invalid value for 'atomic_commit': '{v}' (expected one of \
+                                    'overwrite', 'create_new')"
+                                )));
+                            },
+                        });
+                    },
                     _ => {
                         let value: String = value.extract().map_err(expected_type!(&key, "str"))?;
                         storage_options.push((key, value))
@@ -115,7 +132,8 @@ impl OptPyCloudOptions<'_> {
 
         let cloud_options = CloudOptions::from_untyped_config(cloud_scheme, storage_options)
             .map_err(to_py_err)?
-            .with_retry_config(retry_config);
+            .with_retry_config(retry_config)
+            .with_atomic_commit(atomic_commit);
 
         #[cfg(feature = "cloud")]
         let mut cloud_options =
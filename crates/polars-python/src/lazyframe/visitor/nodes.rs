@@ -142,9 +142,13 @@ impl PyFileOptions {
     fn deletion_files(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
         Ok(match &self.inner.deletion_files {
             None => py.None().into_any(),
-            Some(DeletionFilesList::IcebergPositionDelete(paths)) => {
+            Some(DeletionFilesList::IcebergDelete(files)) => {
+                if !files.equality_deletes.is_empty() {
+                    return Err(PyNotImplementedError::new_err("iceberg equality deletes"));
+                }
+
                 let out = PyDict::new(py);
-                for (k, v) in paths.iter() {
+                for (k, v) in files.position_deletes.iter() {
                     out.set_item(*k, v.as_ref())?;
                 }
                 ("iceberg-position-delete", out)
@@ -152,6 +156,11 @@ impl PyFileOptions {
                     .into_any()
                     .unbind()
             },
+            Some(DeletionFilesList::DeltaDeletionVectors(_)) => {
+                return Err(PyNotImplementedError::new_err(
+                    "native delta deletion vectors",
+                ));
+            },
             Some(DeletionFilesList::Delta(provider)) => {
                 ("delta-deletion-vector", provider.callback().0.clone_ref(py))
                     .into_pyobject(py)?
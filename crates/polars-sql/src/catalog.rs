@@ -0,0 +1,126 @@
+//! Catalog providers, allowing tables to be resolved from an external catalog (on demand)
+//! using three-part `catalog.schema.table` names.
+
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+
+/// A source of tables that can be mounted in a [`SQLContext`](crate::SQLContext) under a
+/// catalog name, via [`SQLContext::register_catalog`](crate::SQLContext::register_catalog).
+///
+/// Tables are resolved lazily, when a query references them by three-part name; for
+/// example `SELECT * FROM main.sales.orders` will call `get_table("sales", "orders")`
+/// on the provider registered as "main".
+pub trait CatalogProvider: Send + Sync {
+    /// Resolve the given table (within the given schema) into a [`LazyFrame`],
+    /// returning `None` if the table does not exist.
+    fn get_table(&self, schema_name: &str, table_name: &str) -> PolarsResult<Option<LazyFrame>>;
+}
+
+#[cfg(feature = "catalog")]
+pub use unity::UnityCatalogProvider;
+
+#[cfg(feature = "catalog")]
+mod unity {
+    use std::sync::Arc;
+
+    use polars_io::catalog::unity::client::CatalogClient;
+    use polars_io::cloud::CloudOptions;
+    use polars_io::cloud::credential_provider::PlCredentialProvider;
+    use polars_io::pl_async;
+    use polars_utils::pl_path::CloudScheme;
+
+    use super::*;
+
+    /// A [`CatalogProvider`] that resolves tables from a Unity catalog, scanning them
+    /// with temporary credentials vended by the catalog.
+    pub struct UnityCatalogProvider {
+        client: Arc<CatalogClient>,
+        catalog_name: String,
+        cloud_options: Option<CloudOptions>,
+    }
+
+    impl UnityCatalogProvider {
+        /// Create a provider for the named catalog, using the given client.
+        pub fn new(client: CatalogClient, catalog_name: impl Into<String>) -> Self {
+            Self {
+                client: Arc::new(client),
+                catalog_name: catalog_name.into(),
+                cloud_options: None,
+            }
+        }
+
+        /// Set the cloud options used to scan tables; if not set, temporary credentials
+        /// are vended (and refreshed) by the catalog.
+        pub fn with_cloud_options(mut self, cloud_options: Option<CloudOptions>) -> Self {
+            self.cloud_options = cloud_options;
+            self
+        }
+
+        /// Credential provider that requests (read-only) temporary credentials for the table.
+        fn table_credential_provider(&self, table_id: &str) -> PlCredentialProvider {
+            let client = self.client.clone();
+            let table_id = table_id.to_string();
+
+            PlCredentialProvider::from_func(move || {
+                let client = client.clone();
+                let table_id = table_id.clone();
+
+                // spawn, as the request future is not `Sync`
+                let handle = pl_async::get_runtime().spawn(async move {
+                    client
+                        .get_table_credentials(&table_id, false)
+                        .await?
+                        .into_object_store_credential()
+                });
+                Box::pin(async move { handle.await.map_err(to_compute_err)? })
+            })
+        }
+    }
+
+    impl CatalogProvider for UnityCatalogProvider {
+        fn get_table(
+            &self,
+            schema_name: &str,
+            table_name: &str,
+        ) -> PolarsResult<Option<LazyFrame>> {
+            let runtime = pl_async::get_runtime();
+            let table_info = runtime.block_in_place_on(self.client.get_table_info(
+                &self.catalog_name,
+                schema_name,
+                table_name,
+            ))?;
+            let Some(storage_location) = table_info.storage_location.as_deref() else {
+                polars_bail!(
+                    ComputeError: "cannot scan catalog table '{}.{}.{}': no storage_location found",
+                    self.catalog_name, schema_name, table_name
+                )
+            };
+
+            let cloud_options = match &self.cloud_options {
+                Some(opts) => opts.clone(),
+                None => {
+                    // AWS access points must be set as the endpoint, so we request an initial
+                    // set of credentials up-front (this also validates access to the table)
+                    let credentials = runtime.block_in_place_on(
+                        self.client
+                            .get_table_credentials(&table_info.table_id, false),
+                    )?;
+                    let opts = match credentials
+                        .aws_temp_credentials
+                        .and_then(|aws| aws.access_point)
+                    {
+                        Some(endpoint) => CloudOptions::from_untyped_config(
+                            CloudScheme::from_path(storage_location),
+                            [("aws_endpoint_url", endpoint)],
+                        )?,
+                        None => CloudOptions::default(),
+                    };
+                    opts.with_credential_provider(Some(
+                        self.table_credential_provider(&table_info.table_id),
+                    ))
+                },
+            };
+            LazyFrame::scan_catalog_table(&table_info, Some(cloud_options)).map(Some)
+        }
+    }
+}
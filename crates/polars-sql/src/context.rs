@@ -1,33 +1,43 @@
 use std::ops::Deref;
 use std::sync::RwLock;
 
-use polars_core::frame::row::Row;
+use polars_core::frame::PivotColumnNaming;
 use polars_core::prelude::*;
 use polars_lazy::prelude::*;
-use polars_ops::frame::{JoinCoalesce, MaintainOrderJoin};
+use polars_ops::frame::{JoinCoalesce, JoinValidation, MaintainOrderJoin};
 use polars_plan::dsl::function_expr::StructFunction;
 use polars_plan::prelude::*;
+use polars_time::ClosedWindow;
 use polars_utils::aliases::{PlHashSet, PlIndexSet};
 use polars_utils::format_pl_smallstr;
 use sqlparser::ast::{
-    BinaryOperator as SQLBinaryOperator, CreateTable, CreateTableLikeKind, Delete, Distinct,
-    ExcludeSelectItem, Expr as SQLExpr, Fetch, FromTable, FunctionArg, GroupByExpr, Ident,
-    JoinConstraint, JoinOperator, LimitClause, NamedWindowDefinition, NamedWindowExpr, ObjectName,
-    ObjectType, OrderBy, OrderByKind, Query, RenameSelectItem, Select, SelectFlavor, SelectItem,
-    SelectItemQualifiedWildcardKind, SetExpr, SetOperator, SetQuantifier, Statement, TableAlias,
-    TableFactor, TableWithJoins, Truncate, UnaryOperator as SQLUnaryOperator, Value as SQLValue,
-    ValueWithSpan, Values, Visit, WildcardAdditionalOptions, WindowSpec,
+    BinaryOperator as SQLBinaryOperator, CastKind, CreateFunction, CreateTable,
+    CreateTableLikeKind, Delete, Distinct, ExcludeSelectItem, Expr as SQLExpr, ExprWithAlias,
+    Fetch, FromTable, FunctionArg, FunctionArgExpr, GroupByExpr, Ident, JoinConstraint,
+    JoinOperator, LimitClause, MacroDefinition, NamedWindowDefinition, NamedWindowExpr,
+    NullInclusion, ObjectName, ObjectType, OrderBy, OrderByKind, PivotValueSource, Query,
+    RenameSelectItem, Select, SelectFlavor, SelectItem, SelectItemQualifiedWildcardKind, SetExpr,
+    SetOperator, SetQuantifier, Statement, TableAlias, TableFactor, TableWithJoins, Truncate,
+    UnaryOperator as SQLUnaryOperator, Value as SQLValue, ValueWithSpan, Values, Visit,
+    WildcardAdditionalOptions, WindowSpec,
 };
 use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::{Parser, ParserOptions};
+use sqlparser::tokenizer::{Span, Token};
 
-use crate::function_registry::{DefaultFunctionRegistry, FunctionRegistry};
+use crate::catalog::CatalogProvider;
+use crate::function_registry::{
+    DefaultFunctionRegistry, FunctionRegistry, SQLMacro, SQLMacroBody, SQLMacroParam,
+};
+use crate::functions::PolarsSQLFunctions;
+use crate::prepared::PreparedStatement;
 use crate::sql_expr::{
-    parse_sql_array, parse_sql_expr, resolve_compound_identifier, to_sql_interface_err,
+    interval_to_duration, parse_sql_array, parse_sql_expr, resolve_compound_identifier,
+    to_sql_interface_err,
 };
 use crate::sql_visitors::{
     QualifyExpression, TableIdentifierCollector, check_for_ambiguous_column_refs,
-    expr_has_window_functions, expr_refers_to_table,
+    expr_has_aggregates, expr_has_window_functions, expr_refers_to_table, first_expr,
 };
 use crate::table_functions::PolarsTableFunctions;
 use crate::types::map_sql_dtype_to_polars;
@@ -144,14 +154,23 @@ fn disambiguate_projection_cols(
 #[derive(Clone)]
 pub struct SQLContext {
     pub(crate) table_map: Arc<RwLock<PlHashMap<String, LazyFrame>>>,
+    pub(crate) macro_map: Arc<RwLock<PlHashMap<String, SQLMacro>>>,
+    pub(crate) catalog_map: Arc<RwLock<PlHashMap<String, Arc<dyn CatalogProvider>>>>,
     pub(crate) function_registry: Arc<dyn FunctionRegistry>,
     pub(crate) lp_arena: Arena<IR>,
     pub(crate) expr_arena: Arena<AExpr>,
 
     cte_map: PlHashMap<String, LazyFrame>,
+    catalog_tables: PlHashMap<String, LazyFrame>,
+    /// Aliased PIVOT/UNPIVOT results, which are only visible to the current query.
+    scoped_tables: PlHashMap<String, LazyFrame>,
     table_aliases: PlHashMap<String, String>,
     joined_aliases: PlHashMap<String, PlHashMap<String, String>>,
     pub(crate) named_windows: PlHashMap<String, WindowSpec>,
+    pub(crate) bound_params: PlHashMap<String, AnyValue<'static>>,
+    /// Plan bound parameters as marker literals that can be rebound (see [`PreparedStatement`]).
+    pub(crate) param_markers: bool,
+    in_lateral: bool,
 }
 
 impl Default for SQLContext {
@@ -159,10 +178,17 @@ impl Default for SQLContext {
         Self {
             function_registry: Arc::new(DefaultFunctionRegistry {}),
             table_map: Default::default(),
+            macro_map: Default::default(),
+            catalog_map: Default::default(),
             cte_map: Default::default(),
+            catalog_tables: Default::default(),
+            scoped_tables: Default::default(),
             table_aliases: Default::default(),
             joined_aliases: Default::default(),
             named_windows: Default::default(),
+            bound_params: Default::default(),
+            param_markers: false,
+            in_lateral: false,
             lp_arena: Default::default(),
             expr_arena: Default::default(),
         }
@@ -212,6 +238,29 @@ impl SQLContext {
         self.table_map.write().unwrap().remove(&name.to_owned());
     }
 
+    /// Register a [`CatalogProvider`] in the SQLContext under the given catalog name.
+    ///
+    /// Tables in the catalog are resolved on demand, when referenced in a query
+    /// using a three-part `catalog.schema.table` name.
+    pub fn register_catalog(&self, name: &str, provider: Arc<dyn CatalogProvider>) {
+        self.catalog_map
+            .write()
+            .unwrap()
+            .insert(name.to_owned(), provider);
+    }
+
+    /// Unregister a [`CatalogProvider`] from the [`SQLContext`].
+    pub fn unregister_catalog(&self, name: &str) {
+        self.catalog_map.write().unwrap().remove(name);
+    }
+
+    /// Get the names of all registered catalogs, in sorted order.
+    pub fn get_catalogs(&self) -> Vec<String> {
+        let mut catalogs = Vec::from_iter(self.catalog_map.read().unwrap().keys().cloned());
+        catalogs.sort_unstable();
+        catalogs
+    }
+
     /// Execute a SQL query, returning a [`LazyFrame`].
     /// ```rust
     /// # use polars_sql::SQLContext;
@@ -231,34 +280,38 @@ impl SQLContext {
     /// # }
     ///```
     pub fn execute(&mut self, query: &str) -> PolarsResult<LazyFrame> {
-        let mut parser = Parser::new(&GenericDialect);
-        parser = parser.with_options(ParserOptions {
-            trailing_commas: true,
-            ..Default::default()
-        });
-
-        let ast = parser
-            .try_with_sql(query)
-            .map_err(to_sql_interface_err)?
-            .parse_statements()
-            .map_err(to_sql_interface_err)?;
-
+        let ast = parse_sql_statements(query)?;
         polars_ensure!(ast.len() == 1, SQLInterface: "one (and only one) statement can be parsed at a time");
-        let res = self.execute_statement(ast.first().unwrap())?;
-
-        // Ensure the result uses the proper arenas.
-        // This will instantiate new arenas with a new version.
-        let lp_arena = std::mem::take(&mut self.lp_arena);
-        let expr_arena = std::mem::take(&mut self.expr_arena);
-        res.set_cached_arena(lp_arena, expr_arena);
-
-        // Every execution should clear the statement-level maps.
-        self.cte_map.clear();
-        self.table_aliases.clear();
-        self.joined_aliases.clear();
-        self.named_windows.clear();
+        self.execute_parsed(ast.first().unwrap())
+    }
 
-        Ok(res)
+    /// Prepare a SQL query containing parameter placeholders, returning a
+    /// [`PreparedStatement`] that can be executed repeatedly with different values.
+    ///
+    /// The query is parsed once; placeholders can be positional (`?`, `$1`, `?1`)
+    /// or named (`:name`, `$name`), and are bound as [`AnyValue`]s on execution.
+    /// ```rust
+    /// # use polars_sql::SQLContext;
+    /// # use polars_core::prelude::*;
+    /// # use polars_lazy::prelude::*;
+    /// # fn main() {
+    ///
+    /// let ctx = SQLContext::new();
+    /// let df = df! {
+    ///    "a" =>  [1, 2, 3],
+    /// }
+    /// .unwrap();
+    ///
+    /// ctx.register("df", df.lazy());
+    /// let mut stmt = ctx.prepare("SELECT * FROM df WHERE a > $1").unwrap();
+    /// let sql_df = stmt.execute(&[AnyValue::Int32(1)]).unwrap().collect().unwrap();
+    /// assert_eq!(sql_df.height(), 2);
+    /// # }
+    ///```
+    pub fn prepare(&self, query: &str) -> PolarsResult<PreparedStatement> {
+        let ast = parse_sql_statements(query)?;
+        polars_ensure!(ast.len() == 1, SQLInterface: "one (and only one) statement can be prepared at a time");
+        PreparedStatement::new(self.clone(), ast.into_iter().next().unwrap())
     }
 
     /// Add a function registry to the SQLContext.
@@ -284,19 +337,58 @@ impl SQLContext {
         Self {
             // Deep clone to isolate
             table_map: Arc::new(RwLock::new(self.table_map.read().unwrap().clone())),
+            macro_map: self.macro_map.clone(),
+            catalog_map: self.catalog_map.clone(),
             named_windows: self.named_windows.clone(),
             cte_map: self.cte_map.clone(),
+            catalog_tables: self.catalog_tables.clone(),
+            scoped_tables: self.scoped_tables.clone(),
+            bound_params: self.bound_params.clone(),
+            param_markers: self.param_markers,
+            in_lateral: self.in_lateral,
 
             ..Default::default()
         }
     }
 
+    /// Execute an already-parsed statement, resetting the statement-level state afterwards.
+    pub(crate) fn execute_parsed(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        let res = self.execute_statement(stmt)?;
+
+        // Ensure the result uses the proper arenas.
+        // This will instantiate new arenas with a new version.
+        let lp_arena = std::mem::take(&mut self.lp_arena);
+        let expr_arena = std::mem::take(&mut self.expr_arena);
+        res.set_cached_arena(lp_arena, expr_arena);
+
+        // Every execution should clear the statement-level maps.
+        self.cte_map.clear();
+        self.catalog_tables.clear();
+        self.scoped_tables.clear();
+        self.table_aliases.clear();
+        self.joined_aliases.clear();
+        self.named_windows.clear();
+
+        Ok(res)
+    }
+
+    /// Get the value bound to the given parameter placeholder (see [`PreparedStatement`]).
+    pub(crate) fn get_bound_param(&self, placeholder: &str) -> PolarsResult<&AnyValue<'static>> {
+        self.bound_params.get(placeholder).ok_or_else(|| {
+            polars_err!(
+                SQLInterface: "no value bound for placeholder '{}' (parameterised queries must be executed via `SQLContext::prepare`)", placeholder
+            )
+        })
+    }
+
     pub(crate) fn execute_statement(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
         let ast = stmt;
         Ok(match ast {
             Statement::Query(query) => self.execute_query(query)?,
             stmt @ Statement::ShowTables { .. } => self.execute_show_tables(stmt)?,
             stmt @ Statement::CreateTable { .. } => self.execute_create_table(stmt)?,
+            stmt @ Statement::CreateFunction { .. } => self.execute_create_function(stmt)?,
+            stmt @ Statement::CreateMacro { .. } => self.execute_create_macro(stmt)?,
             stmt @ Statement::Drop {
                 object_type: ObjectType::Table,
                 ..
@@ -328,8 +420,18 @@ impl SQLContext {
 
     pub(super) fn get_table_from_current_scope(&self, name: &str) -> Option<LazyFrame> {
         // Resolve the table name in the current scope; multi-stage fallback
+        // * catalog table (referenced by its name or alias)
+        // * aliased PIVOT/UNPIVOT result
         // * table name → cte name
         // * table alias → cte alias
+        if let Some(lf) = self
+            .table_aliases
+            .get(name)
+            .and_then(|full_name| self.catalog_tables.get(full_name))
+            .or_else(|| self.scoped_tables.get(name))
+        {
+            return Some(lf.clone());
+        }
         self.table_map
             .read()
             .unwrap()
@@ -366,6 +468,18 @@ impl SQLContext {
         Ok(lf)
     }
 
+    /// Plan the body of a LATERAL subquery, which is kept fully lazy (so nothing
+    /// inside it may be collected at plan time).
+    fn plan_lateral<F>(&mut self, query: F) -> PolarsResult<LazyFrame>
+    where
+        F: FnOnce(&mut Self) -> PolarsResult<LazyFrame>,
+    {
+        let in_lateral = std::mem::replace(&mut self.in_lateral, true);
+        let res = query(self);
+        self.in_lateral = in_lateral;
+        res
+    }
+
     fn expr_or_ordinal(
         &mut self,
         e: &SQLExpr,
@@ -687,22 +801,65 @@ impl SQLContext {
     }
 
     fn process_values(&mut self, values: &[Vec<SQLExpr>]) -> PolarsResult<LazyFrame> {
-        let frame_rows: Vec<Row> = values.iter().map(|row| {
-            let row_data: Result<Vec<_>, _> = row.iter().map(|expr| {
-                let expr = parse_sql_expr(expr, self, None)?;
-                match expr {
-                    Expr::Literal(value) => {
-                        value.to_any_value()
-                            .ok_or_else(|| polars_err!(SQLInterface: "invalid literal value: {:?}", value))
-                            .map(|av| av.into_static())
-                    },
-                    _ => polars_bail!(SQLInterface: "VALUES clause expects literals; found {}", expr),
-                }
-            }).collect();
-            row_data.map(Row::new)
-        }).collect::<Result<_, _>>()?;
+        let n_cols = values.first().map_or(0, |row| row.len());
+        let names: Vec<PlSmallStr> = (0..n_cols)
+            .map(|idx| format_pl_smallstr!("column_{}", idx))
+            .collect();
+        let rows = values
+            .iter()
+            .map(|row| {
+                polars_ensure!(
+                    row.len() == n_cols,
+                    SQLSyntax: "VALUES rows must all have the same number of columns; found {} and {}", n_cols, row.len()
+                );
+                row.iter()
+                    .map(|expr| parse_sql_expr(expr, self, None))
+                    .collect::<PolarsResult<Vec<_>>>()
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        // non-literal values are evaluated lazily, one (single-row) frame per row;
+        // in both cases each column takes the supertype of its values (eg: mixed
+        // integers/floats)
+        if !rows
+            .iter()
+            .flatten()
+            .all(|expr| matches!(expr, Expr::Literal(_)))
+        {
+            let frames = rows
+                .into_iter()
+                .map(|row| {
+                    DataFrame::empty().lazy().select(
+                        row.into_iter()
+                            .zip(&names)
+                            .map(|(expr, name)| expr.alias(name.clone()))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let args = UnionArgs {
+                to_supertypes: true,
+                maintain_order: true,
+                ..Default::default()
+            };
+            return concat(frames, args);
+        }
+
+        let mut column_values: Vec<Vec<AnyValue<'static>>> = vec![vec![]; n_cols];
+        for row in rows {
+            for (expr, col_values) in row.into_iter().zip(column_values.iter_mut()) {
+                col_values.push(evaluate_constant_expr(expr, "VALUES clause")?);
+            }
+        }
+        let columns = column_values
+            .iter()
+            .zip(names)
+            .map(|(col_values, name)| {
+                Series::from_any_values(name, col_values, false).map(Column::from)
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
 
-        Ok(DataFrame::from_rows(frame_rows.as_ref())?.lazy())
+        Ok(DataFrame::new_infer_height(columns)?.lazy())
     }
 
     // EXPLAIN SELECT * FROM DF
@@ -875,7 +1032,27 @@ impl SQLContext {
     fn execute_from_statement(&mut self, tbl_expr: &TableWithJoins) -> PolarsResult<LazyFrame> {
         let (l_name, mut lf) = self.get_table(&tbl_expr.relation)?;
         if !tbl_expr.joins.is_empty() {
+            let mut outer_names = vec![l_name.clone()];
             for join in &tbl_expr.joins {
+                // Handle "[CROSS|LEFT] JOIN LATERAL (subquery)" by decorrelating the subquery
+                if let TableFactor::Derived {
+                    lateral: true,
+                    subquery,
+                    alias,
+                } = &join.relation
+                {
+                    let (r_name, joined) = self.process_lateral_join(
+                        lf,
+                        &outer_names,
+                        subquery,
+                        alias.as_ref(),
+                        &join.join_operator,
+                    )?;
+                    lf = joined;
+                    outer_names.push(r_name);
+                    continue;
+                }
+
                 // Handle "CROSS JOIN UNNEST(col)" as a lateral join op
                 if let (
                     JoinOperator::CrossJoin(JoinConstraint::None),
@@ -976,27 +1153,615 @@ impl SQLContext {
 
                 // track join-aliased columns so we can resolve/check them later
                 let joined_schema = self.get_frame_schema(&mut lf)?;
+                self.track_joined_aliases(&r_name, &left_schema, &right_schema, &joined_schema);
+                outer_names.push(r_name);
+            }
+        };
+        Ok(lf)
+    }
 
-                self.joined_aliases.insert(
-                    r_name.clone(),
-                    right_schema
-                        .iter_names()
-                        .filter_map(|name| {
-                            // col exists in both tables and is aliased in the joined result
-                            let aliased_name = format!("{name}:{r_name}");
-                            if left_schema.contains(name)
-                                && joined_schema.contains(aliased_name.as_str())
-                            {
-                                Some((name.to_string(), aliased_name))
-                            } else {
-                                None
-                            }
+    /// Record the columns from the right side of a join that were suffixed (with the
+    /// relation name) in the joined result, due to a name collision with the left side.
+    fn track_joined_aliases(
+        &mut self,
+        r_name: &str,
+        left_schema: &Schema,
+        right_schema: &Schema,
+        joined_schema: &Schema,
+    ) {
+        self.joined_aliases.insert(
+            r_name.to_string(),
+            right_schema
+                .iter_names()
+                .filter_map(|name| {
+                    // col exists in both tables and is aliased in the joined result
+                    let aliased_name = format!("{name}:{r_name}");
+                    if left_schema.contains(name) && joined_schema.contains(aliased_name.as_str()) {
+                        Some((name.to_string(), aliased_name))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<PlHashMap<String, String>>(),
+        );
+    }
+
+    /// Join a `LATERAL` subquery against the outer relation(s).
+    ///
+    /// The subquery is decorrelated; equality predicates in its WHERE clause that
+    /// reference an outer relation become join keys, and any LIMIT/OFFSET is applied
+    /// per key (eg: "top-n per group") instead of to the subquery as a whole.
+    fn process_lateral_join(
+        &mut self,
+        mut lf: LazyFrame,
+        outer_names: &[String],
+        subquery: &Query,
+        alias: Option<&TableAlias>,
+        join_operator: &JoinOperator,
+    ) -> PolarsResult<(String, LazyFrame)> {
+        let is_on_true = |constraint: &JoinConstraint| match constraint {
+            JoinConstraint::None => true,
+            JoinConstraint::On(SQLExpr::Value(ValueWithSpan {
+                value: SQLValue::Boolean(b),
+                ..
+            })) => *b,
+            _ => false,
+        };
+        let join_type = match join_operator {
+            JoinOperator::CrossJoin(JoinConstraint::None) => JoinType::Inner,
+            JoinOperator::Join(c) | JoinOperator::Inner(c) if is_on_true(c) => JoinType::Inner,
+            JoinOperator::Left(c) | JoinOperator::LeftOuter(c) if is_on_true(c) => JoinType::Left,
+            _ => polars_bail!(
+                SQLInterface:
+                "LATERAL subqueries support CROSS JOIN, or INNER/LEFT JOIN with an 'ON TRUE' constraint; found {:?}",
+                join_operator
+            ),
+        };
+        let Some(alias) = alias else {
+            polars_bail!(SQLSyntax: "LATERAL subquery must have an alias")
+        };
+        let r_name = alias.name.value.clone();
+        let SetExpr::Select(select) = subquery.body.as_ref() else {
+            polars_bail!(SQLInterface: "LATERAL subquery must be a simple SELECT; found {}", subquery.body)
+        };
+        polars_ensure!(
+            subquery.fetch.is_none(),
+            SQLInterface: "FETCH is not supported in LATERAL subqueries; use LIMIT instead"
+        );
+
+        // split the WHERE clause into correlated join keys and (uncorrelated) filters
+        let (join_keys, selection) =
+            decorrelate_predicates(select, outer_names, "LATERAL subquery")?;
+
+        // uncorrelated; evaluate as a standard derived table
+        if join_keys.is_empty() {
+            let mut rf = self.plan_lateral(|ctx| ctx.execute_query_no_ctes(subquery))?;
+            rf = self.rename_columns_from_table_alias(rf, alias)?;
+            self.table_map
+                .write()
+                .unwrap()
+                .insert(r_name.clone(), rf.clone());
+
+            let left_schema = self.get_frame_schema(&mut lf)?;
+            let right_schema = self.get_frame_schema(&mut rf)?;
+            lf = lf.cross_join(rf, Some(format_pl_smallstr!(":{}", r_name)));
+            let joined_schema = self.get_frame_schema(&mut lf)?;
+            self.track_joined_aliases(&r_name, &left_schema, &right_schema, &joined_schema);
+            return Ok((r_name, lf));
+        }
+
+        // extract the (literal) LIMIT/OFFSET values, to be applied per join key
+        let as_count = |expr: &SQLExpr, clause: &str| match expr {
+            SQLExpr::Value(ValueWithSpan {
+                value: SQLValue::Number(n, _),
+                ..
+            }) => n.parse::<i64>().map_err(
+                |_| polars_err!(SQLSyntax: "invalid {} value in LATERAL subquery: {}", clause, n),
+            ),
+            _ => {
+                polars_bail!(SQLInterface: "LATERAL subquery {} must be an integer literal; found {}", clause, expr)
+            },
+        };
+        let (limit, offset) = match &subquery.limit_clause {
+            None => (None, 0),
+            Some(LimitClause::LimitOffset {
+                limit,
+                offset,
+                limit_by,
+            }) if limit_by.is_empty() => (
+                limit.as_ref().map(|l| as_count(l, "LIMIT")).transpose()?,
+                offset
+                    .as_ref()
+                    .map_or(Ok(0), |o| as_count(&o.value, "OFFSET"))?,
+            ),
+            Some(LimitClause::OffsetCommaLimit { offset, limit }) => {
+                (Some(as_count(limit, "LIMIT")?), as_count(offset, "OFFSET")?)
+            },
+            Some(clause) => {
+                polars_bail!(SQLInterface: "unsupported LATERAL subquery clause: {}", clause)
+            },
+        };
+
+        // rewrite the subquery without the correlated predicates, projecting the
+        // inner side of each join key so that we can join it to the outer relation
+        let key_names: Vec<PlSmallStr> = (0..join_keys.len())
+            .map(|idx| format_pl_smallstr!("__POLARS_LATERAL_KEY_{}", idx))
+            .collect();
+        let mut inner_select = (**select).clone();
+        inner_select.selection = selection;
+
+        // aggregating subqueries are evaluated per key; without a GROUP BY clause
+        // they return exactly one row for each outer row (even if nothing matches)
+        let has_group_by = match &select.group_by {
+            GroupByExpr::All(_) => true,
+            GroupByExpr::Expressions(group_by_exprs, _) => !group_by_exprs.is_empty(),
+        };
+        let implicit_group_by = !has_group_by
+            && (select.having.is_some()
+                || select.projection.iter().any(|item| match item {
+                    SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                        expr_has_aggregates(expr, self)
+                    },
+                    _ => false,
+                }));
+
+        for ((inner_expr, _), key_name) in join_keys.iter().zip(&key_names) {
+            inner_select.projection.push(SelectItem::ExprWithAlias {
+                expr: (*inner_expr).clone(),
+                alias: Ident::new(key_name.as_str()),
+            });
+            if let GroupByExpr::Expressions(group_by_exprs, _) = &mut inner_select.group_by {
+                if has_group_by || implicit_group_by {
+                    group_by_exprs.push((*inner_expr).clone());
+                }
+            }
+        }
+        let mut inner_query = subquery.clone();
+        inner_query.body = Box::new(SetExpr::Select(Box::new(inner_select)));
+        inner_query.limit_clause = None;
+
+        let mut rf = self.plan_lateral(|ctx| ctx.execute_query_no_ctes(&inner_query))?;
+        let left_schema = self.get_frame_schema(&mut lf)?;
+        let left_on = join_keys
+            .iter()
+            .map(|(_, outer_expr)| parse_sql_expr(outer_expr, self, Some(&left_schema)))
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        if implicit_group_by {
+            // outer keys without matching inner rows take the result of the
+            // aggregate over an empty input (eg: a count of zero)
+            let mut empty_select = (**select).clone();
+            empty_select.selection = Some(SQLExpr::Value(ValueWithSpan {
+                value: SQLValue::Boolean(false),
+                span: Span::empty(),
+            }));
+            let mut empty_query = subquery.clone();
+            empty_query.body = Box::new(SetExpr::Select(Box::new(empty_select)));
+            empty_query.limit_clause = None;
+
+            let mut empty_rf = self.plan_lateral(|ctx| ctx.execute_query_no_ctes(&empty_query))?;
+            let value_names: Vec<PlSmallStr> = self
+                .get_frame_schema(&mut empty_rf)?
+                .iter_names_cloned()
+                .collect();
+            let empty_name =
+                |name: &PlSmallStr| format_pl_smallstr!("__POLARS_LATERAL_EMPTY_{}", name);
+            let matched = PlSmallStr::from_static("__POLARS_LATERAL_MATCHED");
+
+            let outer_keys = lf
+                .clone()
+                .select(
+                    left_on
+                        .iter()
+                        .zip(&key_names)
+                        .map(|(expr, name)| expr.clone().alias(name.clone()))
+                        .collect::<Vec<_>>(),
+                )
+                .unique(None, UniqueKeepStrategy::Any);
+            let empty_rf = empty_rf.select(
+                value_names
+                    .iter()
+                    .map(|name| col(name.clone()).alias(empty_name(name)))
+                    .collect::<Vec<_>>(),
+            );
+            rf = outer_keys
+                .join_builder()
+                .with(rf.with_column(lit(true).alias(matched.clone())))
+                .on(key_names
+                    .iter()
+                    .map(|name| col(name.clone()))
+                    .collect::<Vec<_>>())
+                .how(JoinType::Left)
+                .finish()
+                .cross_join(empty_rf, None)
+                .select(
+                    value_names
+                        .iter()
+                        .map(|name| {
+                            when(col(matched.clone()).is_null())
+                                .then(col(empty_name(name)))
+                                .otherwise(col(name.clone()))
+                                .alias(name.clone())
                         })
-                        .collect::<PlHashMap<String, String>>(),
+                        .chain(key_names.iter().map(|name| col(name.clone())))
+                        .collect::<Vec<_>>(),
                 );
+        }
+        if limit.is_some() || offset > 0 {
+            let row_idx = int_range(lit(0i64), len(), 1, DataType::Int64).over(
+                key_names
+                    .iter()
+                    .map(|name| col(name.clone()))
+                    .collect::<Vec<_>>(),
+            )?;
+            let mut in_window = row_idx.clone().gt_eq(lit(offset));
+            if let Some(limit) = limit {
+                in_window = in_window.and(row_idx.lt(lit(offset + limit)));
+            }
+            rf = rf.filter(in_window);
+        }
+        if !alias.columns.is_empty() {
+            let schema = self.get_frame_schema(&mut rf)?;
+            let existing: Vec<PlSmallStr> = schema
+                .iter_names()
+                .filter(|name| !key_names.contains(name))
+                .cloned()
+                .collect();
+            polars_ensure!(
+                alias.columns.len() == existing.len(),
+                SQLSyntax: "number of columns ({}) in alias '{}' does not match the number of columns in the table/query ({})",
+                alias.columns.len(), r_name, existing.len()
+            );
+            let new_names: Vec<String> =
+                alias.columns.iter().map(|c| c.name.value.clone()).collect();
+            rf = rf.rename(existing, new_names, true);
+        }
+
+        // register the (key-less) subquery result under its alias so that
+        // qualified references to its columns can be resolved
+        let mut rf_visible = rf.clone().drop(cols(key_names.clone()));
+        let right_schema = self.get_frame_schema(&mut rf_visible)?;
+        self.table_map
+            .write()
+            .unwrap()
+            .insert(r_name.clone(), rf_visible);
+
+        let right_on: Vec<Expr> = key_names.iter().map(|name| col(name.clone())).collect();
+        lf = lf
+            .join_builder()
+            .with(rf)
+            .left_on(left_on)
+            .right_on(right_on)
+            .how(join_type)
+            .join_nulls(implicit_group_by)
+            .suffix(format!(":{r_name}"))
+            .coalesce(JoinCoalesce::KeepColumns)
+            .finish()
+            .drop(cols(key_names));
+
+        let joined_schema = self.get_frame_schema(&mut lf)?;
+        self.track_joined_aliases(&r_name, &left_schema, &right_schema, &joined_schema);
+        Ok((r_name, lf))
+    }
+
+    /// Rewrite `EXISTS` predicates and correlated subqueries in a WHERE clause as joins
+    /// against the given frame; each subquery is replaced by a reference to a (temporary)
+    /// column holding its result, and the names of these columns are collected.
+    fn decorrelate_subqueries(
+        &mut self,
+        lf: &mut LazyFrame,
+        expr: &mut SQLExpr,
+        outer_names: &[String],
+        subquery_cols: &mut Vec<PlSmallStr>,
+    ) -> PolarsResult<()> {
+        let col_name = format_pl_smallstr!("__POLARS_SUBQUERY_{}", subquery_cols.len());
+        let col_ref = SQLExpr::Identifier(Ident::new(col_name.as_str()));
+        let negate = |expr: SQLExpr, negated: bool| {
+            if negated {
+                SQLExpr::UnaryOp {
+                    op: SQLUnaryOperator::Not,
+                    expr: Box::new(expr),
+                }
+            } else {
+                expr
             }
         };
-        Ok(lf)
+        match expr {
+            SQLExpr::BinaryOp { left, right, .. } => {
+                self.decorrelate_subqueries(lf, left, outer_names, subquery_cols)?;
+                self.decorrelate_subqueries(lf, right, outer_names, subquery_cols)
+            },
+            SQLExpr::UnaryOp { expr, .. }
+            | SQLExpr::Nested(expr)
+            | SQLExpr::IsFalse(expr)
+            | SQLExpr::IsNotFalse(expr)
+            | SQLExpr::IsTrue(expr)
+            | SQLExpr::IsNotTrue(expr)
+            | SQLExpr::IsNull(expr)
+            | SQLExpr::IsNotNull(expr) => {
+                self.decorrelate_subqueries(lf, expr, outer_names, subquery_cols)
+            },
+            SQLExpr::Exists { subquery, negated } => {
+                let negated = *negated;
+                *lf =
+                    self.join_subquery(lf.clone(), subquery, None, false, outer_names, &col_name)?;
+                subquery_cols.push(col_name);
+                *expr = negate(col_ref, negated);
+                Ok(())
+            },
+            SQLExpr::InSubquery {
+                expr: in_expr,
+                subquery,
+                negated,
+            } if is_correlated_subquery(subquery, outer_names) => {
+                // note: "x IN (correlated subquery)" is evaluated as an EXISTS with an
+                // additional join key, and yields NULL (rather than false) if 'x' is NULL
+                // or the subquery returns a NULL; "NOT IN" then has the same semantics
+                let negated = *negated;
+                *lf = self.join_subquery(
+                    lf.clone(),
+                    subquery,
+                    Some(in_expr.as_ref()),
+                    false,
+                    outer_names,
+                    &col_name,
+                )?;
+                subquery_cols.push(col_name);
+                *expr = negate(col_ref, negated);
+                Ok(())
+            },
+            SQLExpr::Subquery(subquery) if is_correlated_subquery(subquery, outer_names) => {
+                *lf =
+                    self.join_subquery(lf.clone(), subquery, None, true, outer_names, &col_name)?;
+                subquery_cols.push(col_name);
+                *expr = col_ref;
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Evaluate a (potentially correlated) subquery and left-join its result to the
+    /// outer frame as a new column; scalar subqueries yield (at most) one value per
+    /// correlated key, otherwise the column flags the existence of matching rows
+    /// (following the three-valued logic of `IN` if an `in_expr` is given).
+    fn join_subquery(
+        &mut self,
+        mut lf: LazyFrame,
+        subquery: &Query,
+        in_expr: Option<&SQLExpr>,
+        scalar: bool,
+        outer_names: &[String],
+        col_name: &PlSmallStr,
+    ) -> PolarsResult<LazyFrame> {
+        // uncorrelated EXISTS; check for any rows at all
+        if !scalar && in_expr.is_none() && !is_correlated_subquery(subquery, outer_names) {
+            let rf = self.execute_isolated(|ctx| ctx.execute_query_no_ctes(subquery))?;
+            let rf = rf
+                .limit(1)
+                .select([len().gt(lit(0)).alias(col_name.clone())]);
+            return Ok(lf.cross_join(rf, None));
+        }
+        let SetExpr::Select(select) = subquery.body.as_ref() else {
+            polars_bail!(SQLInterface: "correlated subquery must be a simple SELECT; found {}", subquery.body)
+        };
+        let (mut join_keys, selection) = decorrelate_predicates(select, outer_names, "subquery")?;
+
+        let value_expr = if scalar || in_expr.is_some() {
+            match select.projection.as_slice() {
+                [SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. }] => {
+                    Some(expr)
+                },
+                _ => polars_bail!(SQLSyntax: "SQL subquery returns more than one column"),
+            }
+        } else {
+            None
+        };
+        if let (Some(in_expr), Some(value_expr)) = (in_expr, value_expr) {
+            join_keys.push((value_expr, in_expr));
+        }
+
+        // rewrite the subquery without the correlated predicates, projecting the
+        // inner side of each join key (and the scalar result, if applicable)
+        let key_names: Vec<PlSmallStr> = (0..join_keys.len())
+            .map(|idx| format_pl_smallstr!("__POLARS_SUBQUERY_KEY_{}", idx))
+            .collect();
+        let key_exprs: Vec<SQLExpr> = join_keys
+            .iter()
+            .map(|(inner, _)| (*inner).clone())
+            .collect();
+
+        let mut inner_select = (**select).clone();
+        inner_select.selection = selection;
+        inner_select.projection = key_exprs
+            .iter()
+            .zip(&key_names)
+            .map(|(expr, name)| SelectItem::ExprWithAlias {
+                expr: expr.clone(),
+                alias: Ident::new(name.as_str()),
+            })
+            .collect();
+
+        // aggregating subqueries without a GROUP BY clause return exactly one row for
+        // each outer row (even if nothing matches); other scalar subqueries must not
+        // return more than one row
+        let has_group_by = match &select.group_by {
+            GroupByExpr::All(_) => true,
+            GroupByExpr::Expressions(group_by_exprs, _) => !group_by_exprs.is_empty(),
+        };
+        let implicit_group_by = scalar
+            && !has_group_by
+            && (select.having.is_some() || expr_has_aggregates(value_expr.unwrap(), self));
+
+        let mut inner_query = subquery.clone();
+        if scalar {
+            inner_select.projection.push(SelectItem::ExprWithAlias {
+                expr: value_expr.unwrap().clone(),
+                alias: Ident::new(col_name.as_str()),
+            });
+            match &mut inner_select.group_by {
+                GroupByExpr::Expressions(group_by_exprs, _)
+                    if has_group_by || implicit_group_by =>
+                {
+                    group_by_exprs.extend(key_exprs)
+                },
+                _ => {},
+            }
+        } else {
+            // only existence matters, so any ordering/limit can be ignored
+            inner_select.distinct = Some(Distinct::Distinct);
+            inner_query.order_by = None;
+            inner_query.limit_clause = None;
+            inner_query.fetch = None;
+        }
+        inner_query.body = Box::new(SetExpr::Select(Box::new(inner_select)));
+
+        let mut rf = self.execute_isolated(|ctx| ctx.execute_query_no_ctes(&inner_query))?;
+        let left_schema = self.get_frame_schema(&mut lf)?;
+        let left_on = join_keys
+            .iter()
+            .map(|(_, outer_expr)| parse_sql_expr(outer_expr, self, Some(&left_schema)))
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        if in_expr.is_some() {
+            return Ok(Self::join_in_subquery(
+                lf, rf, left_on, &key_names, col_name,
+            ));
+        }
+        let matched = PlSmallStr::from_static("__POLARS_SUBQUERY_MATCHED");
+        if !scalar {
+            rf = rf.with_column(lit(true).alias(col_name.clone()));
+        } else if implicit_group_by {
+            rf = rf.with_column(lit(true).alias(matched.clone()));
+        }
+        let right_on: Vec<Expr> = key_names.iter().map(|name| col(name.clone())).collect();
+        let lf = lf
+            .join_builder()
+            .with(rf)
+            .left_on(left_on)
+            .right_on(right_on)
+            .how(JoinType::Left)
+            // a scalar subquery returning more than one row for an outer row is an error
+            .validate(if scalar && !implicit_group_by {
+                JoinValidation::ManyToOne
+            } else {
+                JoinValidation::ManyToMany
+            })
+            .coalesce(JoinCoalesce::KeepColumns)
+            .maintain_order(MaintainOrderJoin::Left)
+            .finish()
+            .drop(cols(key_names));
+
+        if !scalar {
+            return Ok(lf.with_column(col(col_name.clone()).fill_null(lit(false))));
+        }
+        if !implicit_group_by {
+            return Ok(lf);
+        }
+
+        // outer rows without matching inner rows take the result of the aggregate
+        // over an empty input (eg: a COUNT of zero, or COALESCE(MAX(x), 0))
+        let mut empty_select = (**select).clone();
+        empty_select.selection = Some(SQLExpr::Value(ValueWithSpan {
+            value: SQLValue::Boolean(false),
+            span: Span::empty(),
+        }));
+        empty_select.projection = vec![SelectItem::ExprWithAlias {
+            expr: value_expr.unwrap().clone(),
+            alias: Ident::new(col_name.as_str()),
+        }];
+        let mut empty_query = subquery.clone();
+        empty_query.body = Box::new(SetExpr::Select(Box::new(empty_select)));
+        empty_query.order_by = None;
+        empty_query.limit_clause = None;
+        empty_query.fetch = None;
+
+        // note: HAVING can filter out the empty group, in which case the result is NULL
+        let empty_name = PlSmallStr::from_static("__POLARS_SUBQUERY_EMPTY");
+        let empty_rf = self
+            .execute_isolated(|ctx| ctx.execute_query_no_ctes(&empty_query))?
+            .select([col(col_name.clone()).first().alias(empty_name.clone())]);
+
+        Ok(lf
+            .cross_join(empty_rf, None)
+            .with_column(
+                when(col(matched.clone()).is_null())
+                    .then(col(empty_name.clone()))
+                    .otherwise(col(col_name.clone()))
+                    .alias(col_name.clone()),
+            )
+            .drop(cols([matched, empty_name])))
+    }
+
+    /// Evaluate "x IN (correlated subquery)" for each outer row, given the (distinct)
+    /// subquery rows; the last key holds the subquery values, and the other keys are
+    /// the correlated join keys.
+    ///
+    /// The result is true if 'x' matches a value in the correlated rows, false if
+    /// there are no correlated rows, and otherwise NULL if either 'x' or one of the
+    /// values is NULL (or false if neither is).
+    fn join_in_subquery(
+        lf: LazyFrame,
+        rf: LazyFrame,
+        left_on: Vec<Expr>,
+        key_names: &[PlSmallStr],
+        col_name: &PlSmallStr,
+    ) -> LazyFrame {
+        let has_null = PlSmallStr::from_static("__POLARS_SUBQUERY_HAS_NULL");
+        let non_empty = PlSmallStr::from_static("__POLARS_SUBQUERY_NON_EMPTY");
+        let (value_name, group_names) = key_names.split_last().unwrap();
+        let group_cols: Vec<Expr> = group_names.iter().map(|name| col(name.clone())).collect();
+        let in_expr = left_on.last().unwrap().clone();
+
+        // NULL values never match (but are tracked per group, below)
+        let matches = rf
+            .clone()
+            .filter(col(value_name.clone()).is_not_null())
+            .with_column(lit(true).alias(col_name.clone()));
+        let right_on: Vec<Expr> = key_names.iter().map(|name| col(name.clone())).collect();
+        let mut lf = lf
+            .join_builder()
+            .with(matches)
+            .left_on(left_on.clone())
+            .right_on(right_on)
+            .how(JoinType::Left)
+            .coalesce(JoinCoalesce::KeepColumns)
+            .maintain_order(MaintainOrderJoin::Left)
+            .finish()
+            .drop(cols(key_names.to_vec()));
+
+        let group_stats = [
+            col(value_name.clone())
+                .is_null()
+                .any(false)
+                .alias(has_null.clone()),
+            len().gt(lit(0)).alias(non_empty.clone()),
+        ];
+        lf = if group_names.is_empty() {
+            lf.cross_join(rf.select(group_stats), None)
+        } else {
+            lf.join_builder()
+                .with(rf.group_by(group_cols.clone()).agg(group_stats))
+                .left_on(left_on[..group_names.len()].to_vec())
+                .right_on(group_cols)
+                .how(JoinType::Left)
+                .coalesce(JoinCoalesce::KeepColumns)
+                .maintain_order(MaintainOrderJoin::Left)
+                .finish()
+                .drop(cols(group_names.to_vec()))
+        };
+
+        lf.with_column(
+            when(col(col_name.clone()).fill_null(lit(false)))
+                .then(lit(true))
+                .when(col(non_empty.clone()).fill_null(lit(false)).not())
+                .then(lit(false))
+                .when(in_expr.is_null().or(col(has_null.clone())))
+                .then(lit(LiteralValue::untyped_null()).cast(DataType::Boolean))
+                .otherwise(lit(false))
+                .alias(col_name.clone()),
+        )
+        .drop(cols([has_null, non_empty]))
     }
 
     /// Check that the SELECT statement only contains supported clauses.
@@ -1142,9 +1907,25 @@ impl SQLContext {
             }
         }
 
-        // Apply `WHERE` constraint
+        // Apply `WHERE` constraint (decorrelating any correlated/EXISTS subqueries into joins)
         let mut schema = self.get_frame_schema(&mut lf)?;
-        lf = self.process_where(lf, &select_stmt.selection, false, Some(schema.clone()))?;
+        let mut subquery_cols = vec![];
+        let selection = match &select_stmt.selection {
+            Some(expr) if !select_stmt.from.is_empty() => {
+                let outer_names: Vec<String> =
+                    get_relation_names(&select_stmt.from).into_iter().collect();
+                let mut expr = expr.clone();
+                self.decorrelate_subqueries(&mut lf, &mut expr, &outer_names, &mut subquery_cols)?;
+                Some(expr)
+            },
+            selection => selection.clone(),
+        };
+        if subquery_cols.is_empty() {
+            lf = self.process_where(lf, &selection, false, Some(schema.clone()))?;
+        } else {
+            lf = self.process_where(lf, &selection, false, None)?;
+            lf = lf.drop(cols(subquery_cols));
+        }
 
         // Determine projections
         let mut select_modifiers = SelectModifiers {
@@ -1774,14 +2555,257 @@ impl SQLContext {
         }
     }
 
+    // CREATE [OR REPLACE] [TEMP] FUNCTION <name>(<arg> <type>, ...) [RETURNS <type>] AS '<expr>'
+    fn execute_create_function(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        let Statement::CreateFunction(CreateFunction {
+            or_replace,
+            if_not_exists,
+            name,
+            args,
+            return_type,
+            function_body,
+            language,
+            ..
+        }) = stmt
+        else {
+            unreachable!()
+        };
+        let fn_name = name
+            .0
+            .first()
+            .unwrap()
+            .as_ident()
+            .unwrap()
+            .value
+            .to_lowercase();
+        if let Some(language) = language {
+            polars_ensure!(
+                language.value.eq_ignore_ascii_case("sql"),
+                SQLInterface: "CREATE FUNCTION only supports SQL functions (found LANGUAGE {})", language
+            );
+        }
+        let params = args
+            .iter()
+            .flatten()
+            .map(|arg| {
+                let Some(arg_name) = &arg.name else {
+                    polars_bail!(SQLSyntax: "CREATE FUNCTION parameters must be named (found '{}')", arg)
+                };
+                Ok(SQLMacroParam {
+                    name: arg_name.value.clone(),
+                    data_type: Some(arg.data_type.clone()),
+                    default: arg.default_expr.clone(),
+                })
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        // the function body is either an expression, or a string containing one
+        let body = function_body.as_ref().and_then(first_expr).ok_or_else(
+            || polars_err!(SQLSyntax: "CREATE FUNCTION '{}' has no function body", fn_name),
+        )?;
+        let mut body = match body {
+            SQLExpr::Value(ValueWithSpan {
+                value: SQLValue::SingleQuotedString(s),
+                ..
+            }) => parse_sql_expr_str(&s)?,
+            SQLExpr::Value(ValueWithSpan {
+                value: SQLValue::DollarQuotedString(s),
+                ..
+            }) => parse_sql_expr_str(&s.value)?,
+            expr => expr,
+        };
+        if let Some(data_type) = return_type {
+            body = SQLExpr::Cast {
+                kind: CastKind::Cast,
+                expr: Box::new(body),
+                data_type: data_type.clone(),
+                format: None,
+            };
+        }
+        let sql_macro = SQLMacro {
+            params,
+            body: SQLMacroBody::Scalar(body),
+        };
+        if self.register_macro(&fn_name, sql_macro, *or_replace, *if_not_exists)? {
+            Ok(df! { "Response" => [format!("CREATE FUNCTION {fn_name}")] }?.lazy())
+        } else {
+            Ok(DataFrame::empty().lazy())
+        }
+    }
+
+    // CREATE [OR REPLACE] [TEMP] MACRO <name>(<arg> [:= <default>], ...) AS [TABLE] <expr|query>
+    fn execute_create_macro(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        let Statement::CreateMacro {
+            or_replace,
+            name,
+            args,
+            definition,
+            ..
+        } = stmt
+        else {
+            unreachable!()
+        };
+        let macro_name = name
+            .0
+            .first()
+            .unwrap()
+            .as_ident()
+            .unwrap()
+            .value
+            .to_lowercase();
+        let params = args
+            .iter()
+            .flatten()
+            .map(|arg| SQLMacroParam {
+                name: arg.name.value.clone(),
+                data_type: None,
+                default: arg.default_expr.clone(),
+            })
+            .collect();
+        let body = match definition {
+            MacroDefinition::Expr(expr) => SQLMacroBody::Scalar(expr.clone()),
+            MacroDefinition::Table(query) => SQLMacroBody::Table(query.clone()),
+        };
+        let sql_macro = SQLMacro { params, body };
+        self.register_macro(&macro_name, sql_macro, *or_replace, false)?;
+        Ok(df! { "Response" => [format!("CREATE MACRO {macro_name}")] }?.lazy())
+    }
+
+    /// Register a SQL macro, returning `false` if it already exists (and `if_not_exists` is set).
+    fn register_macro(
+        &mut self,
+        name: &str,
+        sql_macro: SQLMacro,
+        or_replace: bool,
+        if_not_exists: bool,
+    ) -> PolarsResult<bool> {
+        let is_table_macro = matches!(sql_macro.body, SQLMacroBody::Table(_));
+        if (!is_table_macro && PolarsSQLFunctions::keywords().contains(&name))
+            || (is_table_macro && PolarsTableFunctions::keywords().contains(&name))
+        {
+            polars_bail!(SQLInterface: "cannot redefine built-in function '{}'", name)
+        }
+        let mut macro_map = self.macro_map.write().unwrap();
+        if macro_map.contains_key(name) && !or_replace {
+            if if_not_exists {
+                return Ok(false);
+            }
+            polars_bail!(SQLInterface: "function '{}' already exists", name)
+        }
+
+        // macros are expanded inline, so cannot (directly or indirectly) call themselves
+        let mut pending: Vec<String> = sql_macro.dependencies().into_iter().collect();
+        let mut seen = PlHashSet::new();
+        while let Some(dep) = pending.pop() {
+            polars_ensure!(
+                dep != name,
+                SQLInterface: "recursive function definitions are not supported (found in '{}')", name
+            );
+            if seen.insert(dep.clone()) {
+                if let Some(dep_macro) = macro_map.get(&dep) {
+                    pending.extend(dep_macro.dependencies());
+                }
+            }
+        }
+        macro_map.insert(name.to_string(), sql_macro);
+        Ok(true)
+    }
+
+    /// Get a registered SQL macro by (lowercase) name.
+    pub(crate) fn get_macro(&self, name: &str) -> PolarsResult<SQLMacro> {
+        self.macro_map
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| polars_err!(SQLInterface: "function '{}' was not found", name))
+    }
+
+    fn execute_table_macro(
+        &mut self,
+        macro_name: &str,
+        alias: &Option<TableAlias>,
+        args: &[FunctionArg],
+    ) -> PolarsResult<(String, LazyFrame)> {
+        let query = self.get_macro(macro_name)?.expand_query(macro_name, args)?;
+        let mut lf = self.execute_isolated(|ctx| ctx.execute_query(&query))?;
+        let tbl_name = match alias {
+            Some(alias) => {
+                lf = self.rename_columns_from_table_alias(lf, alias)?;
+                alias.name.value.clone()
+            },
+            None => macro_name.to_string(),
+        };
+        self.table_map
+            .write()
+            .unwrap()
+            .insert(tbl_name.clone(), lf.clone());
+        Ok((tbl_name, lf))
+    }
+
+    /// Resolve a three-part `catalog.schema.table` name from a registered catalog
+    /// (returns `None` if the first part does not name a registered catalog).
+    fn get_catalog_table(
+        &mut self,
+        name: &ObjectName,
+        alias: &Option<TableAlias>,
+    ) -> PolarsResult<Option<(String, LazyFrame)>> {
+        let parts = name
+            .0
+            .iter()
+            .map(|part| part.as_ident().map(|ident| ident.value.as_str()))
+            .collect::<Option<Vec<_>>>();
+        let Some([catalog_name, schema_name, table_name]) = parts.as_deref() else {
+            return Ok(None);
+        };
+        let Some(provider) = self.catalog_map.read().unwrap().get(*catalog_name).cloned() else {
+            return Ok(None);
+        };
+        // catalog tables are only visible to the current query (under their fully
+        // qualified name), and are resolved once per query
+        let full_name = format!("{catalog_name}.{schema_name}.{table_name}");
+        let lf = match self.catalog_tables.get(&full_name) {
+            Some(lf) => lf.clone(),
+            None => {
+                let Some(lf) = provider.get_table(schema_name, table_name)? else {
+                    polars_bail!(SQLInterface: "relation '{}' was not found", name);
+                };
+                self.catalog_tables.insert(full_name.clone(), lf.clone());
+                lf
+            },
+        };
+        let tbl_name = match alias {
+            Some(alias) => alias.name.value.clone(),
+            None => table_name.to_string(),
+        };
+        self.table_aliases.insert(tbl_name.clone(), full_name);
+        Ok(Some((tbl_name, lf)))
+    }
+
     fn get_table(&mut self, relation: &TableFactor) -> PolarsResult<(String, LazyFrame)> {
         match relation {
             TableFactor::Table {
                 name, alias, args, ..
             } => {
                 if let Some(args) = args {
+                    let fn_name = name
+                        .0
+                        .first()
+                        .unwrap()
+                        .as_ident()
+                        .unwrap()
+                        .value
+                        .to_lowercase();
+                    if self.macro_map.read().unwrap().contains_key(&fn_name) {
+                        return self.execute_table_macro(&fn_name, alias, &args.args);
+                    }
                     return self.execute_table_function(name, alias, &args.args);
                 }
+                if name.0.len() == 3 {
+                    if let Some(res) = self.get_catalog_table(name, alias)? {
+                        return Ok(res);
+                    }
+                }
                 let tbl_name = name.0.first().unwrap().as_ident().unwrap().value.as_str();
                 if let Some(lf) = self.get_table_from_current_scope(tbl_name) {
                     match alias {
@@ -1801,7 +2825,7 @@ impl SQLContext {
                 subquery,
                 alias,
             } => {
-                polars_ensure!(!(*lateral), SQLInterface: "LATERAL not supported");
+                polars_ensure!(!(*lateral), SQLInterface: "LATERAL subquery must be the right side of a join");
                 if let Some(alias) = alias {
                     let mut lf = self.execute_query_no_ctes(subquery)?;
                     lf = self.rename_columns_from_table_alias(lf, alias)?;
@@ -1890,11 +2914,272 @@ impl SQLContext {
                     None => Ok(("".to_string(), lf)),
                 }
             },
+            TableFactor::Pivot {
+                table,
+                aggregate_functions,
+                value_column,
+                value_source,
+                default_on_null,
+                alias,
+            } => {
+                let (tbl_name, lf) = self.get_table(table)?;
+                let lf = self.process_pivot(
+                    lf,
+                    aggregate_functions,
+                    value_column,
+                    value_source,
+                    default_on_null.as_ref(),
+                )?;
+                self.register_table_factor_alias(tbl_name, lf, alias.as_ref())
+            },
+            TableFactor::Unpivot {
+                table,
+                value,
+                name,
+                columns,
+                null_inclusion,
+                alias,
+            } => {
+                let (tbl_name, lf) = self.get_table(table)?;
+                let include_nulls = matches!(null_inclusion, Some(NullInclusion::IncludeNulls));
+                let lf = self.process_unpivot(lf, value, name, columns, include_nulls)?;
+                self.register_table_factor_alias(tbl_name, lf, alias.as_ref())
+            },
             // Support bare table, optionally with an alias, for now
             _ => polars_bail!(SQLInterface: "not yet implemented: {}", relation),
         }
     }
 
+    /// Register the result of a PIVOT/UNPIVOT table factor under its alias (if any);
+    /// the alias is only visible to the current query.
+    fn register_table_factor_alias(
+        &mut self,
+        tbl_name: String,
+        lf: LazyFrame,
+        alias: Option<&TableAlias>,
+    ) -> PolarsResult<(String, LazyFrame)> {
+        match alias {
+            Some(alias) => {
+                let lf = self.rename_columns_from_table_alias(lf, alias)?;
+                self.scoped_tables
+                    .insert(alias.name.value.clone(), lf.clone());
+                Ok((alias.name.value.clone(), lf))
+            },
+            None => Ok((tbl_name, lf)),
+        }
+    }
+
+    /// Apply a `PIVOT (agg(value) FOR col IN (...))` table factor; the remaining
+    /// columns (those not referenced by the aggregate or the pivot column) form the
+    /// index, and a new column is created for each of the given pivot values.
+    fn process_pivot(
+        &mut self,
+        mut lf: LazyFrame,
+        aggregate_functions: &[ExprWithAlias],
+        value_column: &[SQLExpr],
+        value_source: &PivotValueSource,
+        default_on_null: Option<&SQLExpr>,
+    ) -> PolarsResult<LazyFrame> {
+        let schema = self.get_frame_schema(&mut lf)?;
+        let on_col = match value_column {
+            [SQLExpr::Identifier(ident)] => PlSmallStr::from_str(ident.value.as_str()),
+            [SQLExpr::CompoundIdentifier(idents)] => {
+                PlSmallStr::from_str(idents.last().unwrap().value.as_str())
+            },
+            _ => polars_bail!(
+                SQLInterface: "PIVOT expects a single column name after FOR; found {}",
+                value_column.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", ")
+            ),
+        };
+        let on_dtype = schema
+            .get(&on_col)
+            .ok_or_else(|| polars_err!(ColumnNotFound: "PIVOT column '{}' not found", on_col))?
+            .clone();
+
+        // the aggregate operates on a single value column (via `element()`)
+        let [agg_fn] = aggregate_functions else {
+            polars_bail!(
+                SQLInterface: "PIVOT currently supports a single aggregate function; found {}",
+                aggregate_functions.len()
+            )
+        };
+        let agg = parse_sql_expr(&agg_fn.expr, self, Some(&schema))?;
+        let value_col = match expr_to_leaf_column_names(&agg).as_slice() {
+            [name] => name.clone(),
+            _ => polars_bail!(
+                SQLInterface: "PIVOT aggregate must reference exactly one column; found {}",
+                agg_fn.expr
+            ),
+        };
+        let agg = agg.map_expr(|e| match e {
+            Expr::Column(name) if name == value_col => element(),
+            e => e,
+        });
+
+        // determine the pivot values (and any aliases for the resulting columns); as
+        // these define the output columns, `ANY` and subquery values are collected now
+        let (on_values, aliases): (Vec<AnyValue<'static>>, Vec<Option<String>>) = match value_source
+        {
+            PivotValueSource::List(values) => values
+                .iter()
+                .map(|v| {
+                    let value = parse_sql_expr(&v.expr, self, None)?;
+                    let value = evaluate_constant_expr(value, "PIVOT")?;
+                    Ok((value, v.alias.as_ref().map(|a| a.value.clone())))
+                })
+                .collect::<PolarsResult<Vec<_>>>()?
+                .into_iter()
+                .unzip(),
+            PivotValueSource::Any(order_by) => {
+                polars_ensure!(
+                    order_by.is_empty(),
+                    SQLInterface: "PIVOT ... IN (ANY ORDER BY ...) is not supported"
+                );
+                polars_ensure!(
+                    !self.in_lateral,
+                    SQLInterface: "PIVOT ... IN (ANY) is not supported in LATERAL subqueries; list the pivot values explicitly"
+                );
+                let values = lf
+                    .clone()
+                    .select([col(on_col.clone()).unique().sort(Default::default())])
+                    .collect()?;
+                let values = values.columns()[0].as_materialized_series().clone();
+                let n_values = values.len();
+                (
+                    values.iter().map(|v| v.into_static()).collect(),
+                    vec![None; n_values],
+                )
+            },
+            PivotValueSource::Subquery(subquery) => {
+                polars_ensure!(
+                    !self.in_lateral,
+                    SQLInterface: "PIVOT ... IN (subquery) is not supported in LATERAL subqueries; list the pivot values explicitly"
+                );
+                let values = self
+                    .execute_isolated(|ctx| ctx.execute_query_no_ctes(subquery))?
+                    .collect()?;
+                polars_ensure!(
+                    values.width() == 1,
+                    SQLSyntax: "SQL subquery returns more than one column"
+                );
+                let values = values.columns()[0]
+                    .as_materialized_series()
+                    .unique_stable()?;
+                let n_values = values.len();
+                (
+                    values.iter().map(|v| v.into_static()).collect(),
+                    vec![None; n_values],
+                )
+            },
+        };
+        let on_values =
+            Series::from_any_values(on_col.clone(), &on_values, false)?.strict_cast(&on_dtype)?;
+        let value_names = on_values.cast(&DataType::String)?;
+        let value_names: Vec<PlSmallStr> = value_names
+            .str()?
+            .iter()
+            .map(|v| PlSmallStr::from_str(v.unwrap_or("null")))
+            .collect();
+
+        let index: Vec<PlSmallStr> = schema
+            .iter_names()
+            .filter(|name| **name != on_col && **name != value_col)
+            .cloned()
+            .collect();
+
+        lf = lf.pivot(
+            cols([on_col]),
+            Arc::new(DataFrame::new_infer_height(vec![on_values.into_column()])?),
+            cols(index),
+            cols([value_col]),
+            agg,
+            true,
+            PlSmallStr::from_static("_"),
+            PivotColumnNaming::Auto,
+        );
+        if let Some(default) = default_on_null {
+            let default = parse_sql_expr(default, self, None)?;
+            lf = lf.with_columns(
+                value_names
+                    .iter()
+                    .map(|name| col(name.clone()).fill_null(default.clone()))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        let (existing, new): (Vec<_>, Vec<_>) = value_names
+            .iter()
+            .zip(aliases)
+            .filter_map(|(name, alias)| alias.map(|alias| (name.clone(), alias)))
+            .unzip();
+        if !existing.is_empty() {
+            lf = lf.rename(existing, new, true);
+        }
+        Ok(lf)
+    }
+
+    /// Apply an `UNPIVOT (value FOR name IN (...))` table factor; the columns that are
+    /// not being unpivoted form the index, and NULL values are excluded by default.
+    fn process_unpivot(
+        &mut self,
+        mut lf: LazyFrame,
+        value: &SQLExpr,
+        name: &Ident,
+        columns: &[ExprWithAlias],
+        include_nulls: bool,
+    ) -> PolarsResult<LazyFrame> {
+        let value_name = match value {
+            SQLExpr::Identifier(ident) => PlSmallStr::from_str(ident.value.as_str()),
+            _ => polars_bail!(SQLInterface: "UNPIVOT expects a value column name; found {}", value),
+        };
+        let variable_name = PlSmallStr::from_str(name.value.as_str());
+
+        let mut on = Vec::with_capacity(columns.len());
+        let mut renamed = vec![];
+        for c in columns {
+            let col_name = match &c.expr {
+                SQLExpr::Identifier(ident) => PlSmallStr::from_str(ident.value.as_str()),
+                SQLExpr::CompoundIdentifier(idents) => {
+                    PlSmallStr::from_str(idents.last().unwrap().value.as_str())
+                },
+                expr => polars_bail!(SQLInterface: "UNPIVOT expects column names; found {}", expr),
+            };
+            if let Some(alias) = &c.alias {
+                renamed.push((col_name.clone(), alias.value.clone()));
+            }
+            on.push(col_name);
+        }
+        let schema = self.get_frame_schema(&mut lf)?;
+        let index: Vec<PlSmallStr> = schema
+            .iter_names()
+            .filter(|name| !on.contains(name))
+            .cloned()
+            .collect();
+
+        lf = lf.unpivot(UnpivotArgsDSL {
+            on: Some(cols(on)),
+            index: cols(index),
+            variable_name: Some(variable_name.clone()),
+            value_name: Some(value_name.clone()),
+        });
+        if !include_nulls {
+            lf = lf.filter(col(value_name).is_not_null());
+        }
+        if !renamed.is_empty() {
+            // map the unpivoted column names to their aliases
+            let variable = col(variable_name.clone());
+            let name_expr = renamed
+                .into_iter()
+                .rev()
+                .fold(variable.clone(), |expr, (from, to)| {
+                    when(variable.clone().eq(lit(from.as_str())))
+                        .then(lit(to))
+                        .otherwise(expr)
+                });
+            lf = lf.with_column(name_expr.alias(variable_name));
+        }
+        Ok(lf)
+    }
+
     fn execute_table_function(
         &mut self,
         name: &ObjectName,
@@ -1902,13 +3187,25 @@ impl SQLContext {
         args: &[FunctionArg],
     ) -> PolarsResult<(String, LazyFrame)> {
         let tbl_fn = name.0.first().unwrap().as_ident().unwrap().value.as_str();
-        let read_fn = tbl_fn.parse::<PolarsTableFunctions>()?;
-        let (tbl_name, lf) = read_fn.execute(args)?;
-        #[allow(clippy::useless_asref)]
-        let tbl_name = alias
-            .as_ref()
-            .map(|a| a.name.value.clone())
-            .unwrap_or_else(|| tbl_name.to_string());
+        let (tbl_name, mut lf) = match tbl_fn.parse::<PolarsTableFunctions>()? {
+            table_fn @ (PolarsTableFunctions::GenerateSeries | PolarsTableFunctions::Range) => {
+                let fn_name = tbl_fn.to_lowercase();
+                let inclusive = matches!(table_fn, PolarsTableFunctions::GenerateSeries);
+                let lf = self.process_generate_series(&fn_name, args, inclusive)?;
+                (fn_name, lf)
+            },
+            read_fn => {
+                let (path, lf) = read_fn.execute(args)?;
+                (path.to_string(), lf)
+            },
+        };
+        let tbl_name = match alias {
+            Some(alias) => {
+                lf = self.rename_columns_from_table_alias(lf, alias)?;
+                alias.name.value.clone()
+            },
+            None => tbl_name,
+        };
 
         self.table_map
             .write()
@@ -1917,6 +3214,93 @@ impl SQLContext {
         Ok((tbl_name, lf))
     }
 
+    /// Generate a single-column frame from a `generate_series` or `range` table function;
+    /// integer series take an optional (non-zero) integer step, and date/datetime series
+    /// require an INTERVAL step.
+    fn process_generate_series(
+        &mut self,
+        fn_name: &str,
+        args: &[FunctionArg],
+        inclusive: bool,
+    ) -> PolarsResult<LazyFrame> {
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+                _ => polars_bail!(SQLSyntax: "{} does not support named or wildcard arguments; found {}", fn_name, arg),
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let series = match args.as_slice() {
+            [start, end, SQLExpr::Interval(interval)] => {
+                let interval = interval_to_duration(interval, false)?;
+                polars_ensure!(
+                    !interval.is_zero() && !interval.negative(),
+                    SQLSyntax: "{} interval must be positive; found {}", fn_name, interval
+                );
+                let start = parse_sql_expr(start, self, None)?;
+                let end = parse_sql_expr(end, self, None)?;
+                let closed = if inclusive {
+                    ClosedWindow::Both
+                } else {
+                    ClosedWindow::Left
+                };
+                let empty_schema = Schema::default();
+                let is_date = |e: &Expr| {
+                    e.to_field(&empty_schema)
+                        .is_ok_and(|fld| fld.dtype == DataType::Date)
+                };
+                // date bounds stepped by whole days produce a date series
+                if interval.is_full_days() && is_date(&start) && is_date(&end) {
+                    date_range(Some(start), Some(end), Some(interval), None, closed)?
+                } else {
+                    datetime_range(
+                        Some(start),
+                        Some(end),
+                        Some(interval),
+                        None,
+                        closed,
+                        None,
+                        None,
+                    )?
+                }
+            },
+            [_, _] | [_, _, _] | [_] => {
+                let (start, end) = match args.as_slice() {
+                    [end] => (lit(0i64), parse_sql_expr(end, self, None)?),
+                    [start, end, ..] => (
+                        parse_sql_expr(start, self, None)?,
+                        parse_sql_expr(end, self, None)?,
+                    ),
+                    _ => unreachable!(),
+                };
+                let step = match args.get(2) {
+                    Some(step) => match parse_sql_expr(step, self, None)? {
+                        Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Int(n))) => {
+                            i64::try_from(n).ok().filter(|n| *n != 0)
+                        },
+                        _ => None,
+                    }
+                    .ok_or_else(|| {
+                        polars_err!(SQLSyntax: "{} step must be a non-zero integer or an INTERVAL; found {}", fn_name, step)
+                    })?,
+                    None => 1,
+                };
+                // SQL series are inclusive of the end value, whereas `int_range` is not
+                let end = if inclusive {
+                    end.cast(DataType::Int64) + lit(step.signum())
+                } else {
+                    end
+                };
+                int_range(start, end, step, DataType::Int64)
+            },
+            _ => polars_bail!(SQLSyntax: "{} expects 1-3 arguments; found {}", fn_name, args.len()),
+        };
+        Ok(DataFrame::empty()
+            .lazy()
+            .select([series.alias(PlSmallStr::from_str(fn_name))]))
+    }
+
     fn process_order_by(
         &mut self,
         mut lf: LazyFrame,
@@ -2500,6 +3884,131 @@ fn get_table_name(factor: &TableFactor) -> Option<String> {
     }
 }
 
+/// Evaluate a constant (scalar) expression, such as a literal, typed literal, or cast.
+fn evaluate_constant_expr(expr: Expr, clause: &str) -> PolarsResult<AnyValue<'static>> {
+    match expr {
+        Expr::Literal(value) => Ok(value
+            .to_any_value()
+            .ok_or_else(|| polars_err!(SQLInterface: "invalid literal value: {:?}", value))?
+            .into_static()),
+        _ => {
+            let df = DataFrame::empty().lazy().select([expr.clone()]).collect()?;
+            polars_ensure!(
+                df.height() == 1,
+                SQLInterface: "{} expects scalar values; found {}", clause, expr
+            );
+            Ok(df.columns()[0].get(0)?.into_static())
+        },
+    }
+}
+
+/// Split a (possibly nested) conjunction into its individual predicates.
+fn split_conjunctions(expr: &SQLExpr) -> Vec<&SQLExpr> {
+    match expr {
+        SQLExpr::BinaryOp {
+            left,
+            op: SQLBinaryOperator::And,
+            right,
+        } => {
+            let mut preds = split_conjunctions(left);
+            preds.extend(split_conjunctions(right));
+            preds
+        },
+        SQLExpr::Nested(inner) => split_conjunctions(inner),
+        _ => vec![expr],
+    }
+}
+
+/// Get the names (or aliases) of all relations in the given FROM clause.
+fn get_relation_names(from: &[TableWithJoins]) -> PlHashSet<String> {
+    from.iter()
+        .flat_map(|tbl| std::iter::once(&tbl.relation).chain(tbl.joins.iter().map(|j| &j.relation)))
+        .filter_map(get_table_name)
+        .collect()
+}
+
+/// Check if a subquery expression references any of the given outer relations
+/// (relations named in the subquery itself shadow those of the outer query).
+fn refers_to_outer(
+    expr: &SQLExpr,
+    outer_names: &[String],
+    inner_names: &PlHashSet<String>,
+) -> bool {
+    outer_names
+        .iter()
+        .any(|name| !inner_names.contains(name) && expr_refers_to_table(expr, name))
+}
+
+/// Check if the given subquery is correlated with any of the outer relations.
+fn is_correlated_subquery(subquery: &Query, outer_names: &[String]) -> bool {
+    match subquery.body.as_ref() {
+        SetExpr::Select(select) => {
+            let inner_names = get_relation_names(&select.from);
+            select
+                .selection
+                .as_ref()
+                .is_some_and(|expr| refers_to_outer(expr, outer_names, &inner_names))
+        },
+        _ => false,
+    }
+}
+
+/// Split the WHERE clause of a (potentially correlated) subquery into the equality
+/// predicates that reference an outer relation, returned as `(inner, outer)` join key
+/// pairs, and the remaining uncorrelated predicates; other forms of correlation
+/// (and outer references in the projection) are not currently supported.
+fn decorrelate_predicates<'a>(
+    select: &'a Select,
+    outer_names: &[String],
+    subquery_kind: &str,
+) -> PolarsResult<(Vec<(&'a SQLExpr, &'a SQLExpr)>, Option<SQLExpr>)> {
+    let inner_names = get_relation_names(&select.from);
+    let is_outer = |expr: &SQLExpr| refers_to_outer(expr, outer_names, &inner_names);
+
+    let mut join_keys = vec![];
+    let mut predicates = vec![];
+    for pred in select.selection.iter().flat_map(split_conjunctions) {
+        if !is_outer(pred) {
+            predicates.push(pred.clone());
+            continue;
+        }
+        match pred {
+            SQLExpr::BinaryOp {
+                left,
+                op: SQLBinaryOperator::Eq,
+                right,
+            } if is_outer(left) != is_outer(right) => {
+                if is_outer(left) {
+                    join_keys.push((right.as_ref(), left.as_ref()));
+                } else {
+                    join_keys.push((left.as_ref(), right.as_ref()));
+                }
+            },
+            _ => polars_bail!(
+                SQLInterface:
+                "{} correlation only supports equality predicates; found {}",
+                subquery_kind, pred
+            ),
+        }
+    }
+    let correlated_projection = select.projection.iter().any(|item| match item {
+        SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => is_outer(expr),
+        _ => false,
+    });
+    polars_ensure!(
+        !correlated_projection,
+        SQLInterface: "{} can only reference outer relations in its WHERE clause", subquery_kind
+    );
+    let selection = predicates
+        .into_iter()
+        .reduce(|left, right| SQLExpr::BinaryOp {
+            left: Box::new(left),
+            op: SQLBinaryOperator::And,
+            right: Box::new(right),
+        });
+    Ok((join_keys, selection))
+}
+
 /// Check if an expression is a simple column reference (with optional alias) to the given name.
 fn is_simple_col_ref(expr: &Expr, col_name: &PlSmallStr) -> bool {
     match expr {
@@ -2723,24 +4232,42 @@ fn process_join_constraint(
     }
 }
 
-/// Extract table identifiers referenced in a SQL query; uses a visitor to
-/// collect all table names that appear in FROM clauses, JOINs, TABLE refs
-/// in set operations, and subqueries.
-pub fn extract_table_identifiers(
-    query: &str,
-    include_schema: bool,
-    unique: bool,
-) -> PolarsResult<Vec<String>> {
+/// Parse a SQL string into statements (using the generic dialect, with trailing commas allowed).
+pub(crate) fn parse_sql_statements(query: &str) -> PolarsResult<Vec<Statement>> {
     let mut parser = Parser::new(&GenericDialect);
     parser = parser.with_options(ParserOptions {
         trailing_commas: true,
         ..Default::default()
     });
-    let ast = parser
+    parser
         .try_with_sql(query)
         .map_err(to_sql_interface_err)?
         .parse_statements()
+        .map_err(to_sql_interface_err)
+}
+
+/// Parse a SQL string as a single expression.
+fn parse_sql_expr_str(sql: &str) -> PolarsResult<SQLExpr> {
+    let mut parser = Parser::new(&GenericDialect)
+        .try_with_sql(sql)
         .map_err(to_sql_interface_err)?;
+    let expr = parser.parse_expr().map_err(to_sql_interface_err)?;
+    polars_ensure!(
+        parser.peek_token().token == Token::EOF,
+        SQLSyntax: "expected a single expression; found '{}'", sql
+    );
+    Ok(expr)
+}
+
+/// Extract table identifiers referenced in a SQL query; uses a visitor to
+/// collect all table names that appear in FROM clauses, JOINs, TABLE refs
+/// in set operations, and subqueries.
+pub fn extract_table_identifiers(
+    query: &str,
+    include_schema: bool,
+    unique: bool,
+) -> PolarsResult<Vec<String>> {
+    let ast = parse_sql_statements(query)?;
 
     let mut collector = TableIdentifierCollector {
         include_schema,
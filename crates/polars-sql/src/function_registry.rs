@@ -1,8 +1,17 @@
-//! This module defines a FunctionRegistry for supported SQL functions and UDFs.
+//! This module defines a FunctionRegistry for supported SQL functions and UDFs,
+//! along with the SQL-defined macros created via `CREATE FUNCTION` and `CREATE MACRO`.
 
-use polars_error::{PolarsResult, polars_bail};
+use std::ops::ControlFlow;
+
+use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err};
 pub use polars_plan::prelude::FunctionOptions;
 use polars_plan::prelude::udf::UserDefinedFunction;
+use polars_utils::aliases::{PlHashMap, PlHashSet};
+use sqlparser::ast::{
+    CastKind, DataType as SQLDataType, Expr as SQLExpr, FunctionArg, FunctionArgExpr, Query,
+    TableFactor, Visit, VisitMut, Visitor as SQLVisitor, visit_expressions_mut,
+};
+
 /// A registry that holds user defined functions.
 pub trait FunctionRegistry: Send + Sync {
     /// Register a function.
@@ -28,3 +37,181 @@ impl FunctionRegistry for DefaultFunctionRegistry {
         false
     }
 }
+
+/// A SQL-defined function (macro), created with `CREATE FUNCTION` or `CREATE MACRO`.
+///
+/// Macros are expanded inline when called: arguments are substituted for references
+/// to the macro parameters in the (scalar expression or table query) body.
+#[derive(Clone, Debug)]
+pub(crate) struct SQLMacro {
+    pub(crate) params: Vec<SQLMacroParam>,
+    pub(crate) body: SQLMacroBody,
+}
+
+/// A named macro parameter, with optional type (cast applied to the argument) and default.
+#[derive(Clone, Debug)]
+pub(crate) struct SQLMacroParam {
+    pub(crate) name: String,
+    pub(crate) data_type: Option<SQLDataType>,
+    pub(crate) default: Option<SQLExpr>,
+}
+
+/// The body of a SQL macro.
+#[derive(Clone, Debug)]
+pub(crate) enum SQLMacroBody {
+    /// Scalar macro; expands to an expression.
+    Scalar(SQLExpr),
+    /// Table macro; expands to a query (usable in the FROM clause).
+    Table(Box<Query>),
+}
+
+impl SQLMacro {
+    /// Expand a scalar macro call into the equivalent SQL expression.
+    pub(crate) fn expand_expr(&self, name: &str, args: &[FunctionArg]) -> PolarsResult<SQLExpr> {
+        let SQLMacroBody::Scalar(body) = &self.body else {
+            polars_bail!(SQLSyntax: "table macro '{}' can only be used in a FROM clause", name)
+        };
+        let bound = self.bind_args(name, args)?;
+        let mut expr = body.clone();
+        substitute_macro_params(&mut expr, &bound);
+        Ok(expr)
+    }
+
+    /// Expand a table macro call into the equivalent SQL query.
+    pub(crate) fn expand_query(&self, name: &str, args: &[FunctionArg]) -> PolarsResult<Query> {
+        let SQLMacroBody::Table(body) = &self.body else {
+            polars_bail!(SQLSyntax: "scalar macro '{}' cannot be used as a table", name)
+        };
+        let bound = self.bind_args(name, args)?;
+        let mut query = body.as_ref().clone();
+        substitute_macro_params(&mut query, &bound);
+        Ok(query)
+    }
+
+    /// Names of the functions and table functions called from the macro body.
+    pub(crate) fn dependencies(&self) -> PlHashSet<String> {
+        let mut collector = MacroDependencies::default();
+        let _ = match &self.body {
+            SQLMacroBody::Scalar(expr) => expr.visit(&mut collector),
+            SQLMacroBody::Table(query) => query.visit(&mut collector),
+        };
+        collector.names
+    }
+
+    /// Bind the call arguments (positional, then named) to the macro parameters,
+    /// falling back to parameter defaults.
+    fn bind_args(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+    ) -> PolarsResult<PlHashMap<String, SQLExpr>> {
+        let mut bound: PlHashMap<String, SQLExpr> = PlHashMap::new();
+        let mut n_positional = 0;
+        for arg in args {
+            let (arg_name, arg) = match arg {
+                FunctionArg::Unnamed(arg) => (None, arg),
+                FunctionArg::Named { name, arg, .. } => (Some(name.value.as_str()), arg),
+                FunctionArg::ExprNamed {
+                    name: SQLExpr::Identifier(ident),
+                    arg,
+                    ..
+                } => (Some(ident.value.as_str()), arg),
+                _ => polars_bail!(SQLSyntax: "invalid argument to macro '{}': {}", name, arg),
+            };
+            let FunctionArgExpr::Expr(arg) = arg else {
+                polars_bail!(SQLSyntax: "invalid argument to macro '{}': {}", name, arg)
+            };
+            let param = match arg_name {
+                None => {
+                    polars_ensure!(
+                        bound.len() == n_positional,
+                        SQLSyntax: "positional arguments to macro '{}' cannot follow named arguments", name
+                    );
+                    n_positional += 1;
+                    self.params.get(n_positional - 1).ok_or_else(|| {
+                        polars_err!(
+                            SQLSyntax: "macro '{}' expects at most {} argument(s); found {}",
+                            name, self.params.len(), args.len()
+                        )
+                    })?
+                },
+                Some(arg_name) => self
+                    .params
+                    .iter()
+                    .find(|p| p.name == arg_name)
+                    .ok_or_else(|| {
+                        polars_err!(SQLSyntax: "macro '{}' has no parameter named '{}'", name, arg_name)
+                    })?,
+            };
+            polars_ensure!(
+                !bound.contains_key(&param.name),
+                SQLSyntax: "macro '{}' parameter '{}' is given more than once", name, param.name
+            );
+            bound.insert(param.name.clone(), param.bind(arg.clone()));
+        }
+        for param in &self.params {
+            if !bound.contains_key(&param.name) {
+                let Some(default) = &param.default else {
+                    polars_bail!(SQLSyntax: "missing argument for parameter '{}' of macro '{}'", param.name, name)
+                };
+                bound.insert(param.name.clone(), param.bind(default.clone()));
+            }
+        }
+        Ok(bound)
+    }
+}
+
+impl SQLMacroParam {
+    fn bind(&self, arg: SQLExpr) -> SQLExpr {
+        match &self.data_type {
+            Some(data_type) => SQLExpr::Cast {
+                kind: CastKind::Cast,
+                expr: Box::new(arg),
+                data_type: data_type.clone(),
+                format: None,
+            },
+            None => arg,
+        }
+    }
+}
+
+/// Replace references to macro parameters with the bound argument expressions.
+fn substitute_macro_params<V: VisitMut>(node: &mut V, bound: &PlHashMap<String, SQLExpr>) {
+    let _ = visit_expressions_mut(node, |expr| {
+        if let SQLExpr::Identifier(ident) = expr {
+            if let Some(arg) = bound.get(&ident.value) {
+                *expr = arg.clone();
+            }
+        }
+        ControlFlow::<()>::Continue(())
+    });
+}
+
+/// Visitor that collects the (lowercased) names of called functions and table functions.
+#[derive(Default)]
+struct MacroDependencies {
+    names: PlHashSet<String>,
+}
+
+impl SQLVisitor for MacroDependencies {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &SQLExpr) -> ControlFlow<Self::Break> {
+        if let SQLExpr::Function(func) = expr {
+            self.names.insert(func.name.to_string().to_lowercase());
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<Self::Break> {
+        if let TableFactor::Table {
+            name,
+            args: Some(_),
+            ..
+        } = table_factor
+        {
+            self.names.insert(name.to_string().to_lowercase());
+        }
+        ControlFlow::Continue(())
+    }
+}
@@ -11,8 +11,8 @@ use polars_lazy::prelude::{RankMethod, RankOptions};
 use polars_ops::chunked_array::UnicodeForm;
 use polars_ops::series::RoundMode;
 use polars_plan::dsl::functions::{
-    as_struct, coalesce, col, cols, concat_str, element, int_range, len, lit, max_horizontal,
-    min_horizontal, when,
+    DatetimeArgs, as_struct, coalesce, col, cols, concat_str, datetime, element, int_range, len,
+    lit, max_horizontal, min_horizontal, when,
 };
 use polars_plan::plans::{DynLiteralValue, LiteralValue, typed_lit};
 use polars_plan::prelude::StrptimeOptions;
@@ -27,12 +27,18 @@ use sqlparser::ast::{
 use sqlparser::tokenizer::Span;
 
 use crate::SQLContext;
-use crate::sql_expr::{adjust_one_indexed_param, parse_extract_date_part, parse_sql_expr};
+use crate::sql_expr::{
+    adjust_one_indexed_param, interval_to_duration, parse_extract_date_part, parse_sql_expr,
+};
+#[cfg(feature = "json")]
+use crate::sql_expr::{json_encode, json_path_from_elems, resolve_expr_dtype};
 
 pub(crate) struct SQLFunctionVisitor<'a> {
     pub(crate) func: &'a SQLFunction,
     pub(crate) ctx: &'a mut SQLContext,
     pub(crate) active_schema: Option<&'a Schema>,
+    /// Condition of an aggregate `FILTER (WHERE ...)` clause, applied to its input.
+    pub(crate) agg_filter: Option<Expr>,
 }
 
 /// SQL functions that are supported by Polars
@@ -303,12 +309,43 @@ pub(crate) enum PolarsSQLFunctions {
     // ----
     // Temporal functions
     // ----
+    /// SQL 'date_add' function (aliased as 'dateadd').
+    /// Offsets a date (or datetime) by an interval, a number of days, or a number of the given part.
+    /// ```sql
+    /// SELECT DATE_ADD(col1, INTERVAL '3 days') FROM df;
+    /// SELECT DATEADD('month', 2, col1) FROM df;
+    /// ```
+    DateAdd,
+    /// SQL 'datediff' function (aliased as 'date_diff').
+    /// Returns the number of part boundaries crossed between two dates (or datetimes).
+    /// ```sql
+    /// SELECT DATEDIFF('day', col1, col2) FROM df;
+    /// ```
+    DateDiff,
     /// SQL 'date_part' function.
     /// Extracts a part of a date (or datetime) such as 'year', 'month', etc.
     /// ```sql
     /// SELECT DATE_PART('year', col1) FROM df;
     /// SELECT DATE_PART('day', col1) FROM df;
     DatePart,
+    /// SQL 'date_trunc' function.
+    /// Truncates a date (or datetime) to the given part (eg: 'month', 'hour').
+    /// ```sql
+    /// SELECT DATE_TRUNC('month', col1) FROM df;
+    /// ```
+    DateTrunc,
+    /// SQL 'last_day' function.
+    /// Returns the last day of the month for the given date (or datetime).
+    /// ```sql
+    /// SELECT LAST_DAY(col1) FROM df;
+    /// ```
+    LastDay,
+    /// SQL 'make_date' function.
+    /// Creates a date from year, month, and day values.
+    /// ```sql
+    /// SELECT MAKE_DATE(col1, col2, col3) FROM df;
+    /// ```
+    MakeDate,
     /// SQL 'strftime' function.
     /// Converts a datetime to a string using a format string.
     /// ```sql
@@ -403,12 +440,45 @@ pub(crate) enum PolarsSQLFunctions {
     /// SELECT OCTET_LENGTH(col1) FROM df;
     /// ```
     OctetLength,
+    /// SQL 'regexp_count' function.
+    /// Returns the number of times `pattern` matches the value (optional: `start`, `flags`).
+    /// ```sql
+    /// SELECT REGEXP_COUNT(col1, '[aeiou]') FROM df;
+    /// SELECT REGEXP_COUNT(col1, 'x', 3, 'i') FROM df;
+    /// ```
+    RegexpCount,
+    /// SQL 'regexp_extract' function.
+    /// Returns the first match of `pattern` (or of the given capture group).
+    /// ```sql
+    /// SELECT REGEXP_EXTRACT(col1, '(\w+)-(\d+)', 2) FROM df;
+    /// ```
+    RegexpExtract,
     /// SQL 'regexp_like' function.
     /// True if `pattern` matches the value (optional: `flags`).
     /// ```sql
     /// SELECT REGEXP_LIKE(col1, 'xyz', 'i') FROM df;
     /// ```
     RegexpLike,
+    /// SQL 'regexp_replace' function.
+    /// Replaces the first match of `pattern` with `replacement`; replaces all
+    /// matches if the 'g' flag is given (optional: `flags`).
+    /// ```sql
+    /// SELECT REGEXP_REPLACE(col1, '(\d+)', '<\1>', 'g') FROM df;
+    /// ```
+    RegexpReplace,
+    /// SQL 'regexp_split_to_array' function.
+    /// Splits the value into an array of strings using `pattern` as the delimiter.
+    /// ```sql
+    /// SELECT REGEXP_SPLIT_TO_ARRAY(col1, '\s+') FROM df;
+    /// ```
+    RegexpSplitToArray,
+    /// SQL 'regexp_substr' function.
+    /// Returns the substring matching `pattern` (optional: `start`, `occurrence`,
+    /// `flags`, and capture `group`).
+    /// ```sql
+    /// SELECT REGEXP_SUBSTR(col1, '\d+', 1, 2) FROM df;
+    /// ```
+    RegexpSubstr,
     /// SQL 'replace' function.
     /// Replace a given substring with another string.
     /// ```sql
@@ -630,6 +700,14 @@ pub(crate) enum PolarsSQLFunctions {
     /// SELECT STDDEV(col1) FROM df;
     /// ```
     StdDev,
+    /// SQL 'string_agg' function (aliased as 'listagg').
+    /// Concatenates the input values into a string, using the given separator
+    /// (optional: `ORDER BY` inside the aggregate, or `WITHIN GROUP (ORDER BY ...)`).
+    /// ```sql
+    /// SELECT STRING_AGG(col1, ', ' ORDER BY col2) FROM df;
+    /// SELECT LISTAGG(col1, ', ') WITHIN GROUP (ORDER BY col2) FROM df;
+    /// ```
+    StringAgg,
     /// SQL 'sum' function.
     /// Returns the sum of all the elements in the grouping.
     /// ```sql
@@ -720,6 +798,38 @@ pub(crate) enum PolarsSQLFunctions {
     /// ```
     ArrayContains,
 
+    // ----
+    // JSON functions
+    // ----
+    /// SQL 'json_arrayagg' function.
+    /// Aggregates the input values into a JSON array string.
+    /// ```sql
+    /// SELECT JSON_ARRAYAGG(col1 ORDER BY col2) FROM df;
+    /// ```
+    #[cfg(feature = "json")]
+    JsonArrayAgg,
+    /// SQL 'json_extract' function.
+    /// Extracts the value at the given JSONPath from a JSON string (or struct).
+    /// ```sql
+    /// SELECT JSON_EXTRACT(col1, '$.a.b[0]') FROM df;
+    /// ```
+    #[cfg(feature = "json")]
+    JsonExtract,
+    /// SQL 'json_extract_path_text' function.
+    /// Extracts the value at the given path of keys as a string.
+    /// ```sql
+    /// SELECT JSON_EXTRACT_PATH_TEXT(col1, 'a', 'b') FROM df;
+    /// ```
+    #[cfg(feature = "json")]
+    JsonExtractPathText,
+    /// SQL 'json_object' function.
+    /// Builds a JSON object string from the given key/value pairs.
+    /// ```sql
+    /// SELECT JSON_OBJECT('a', col1, 'b', col2) FROM df;
+    /// ```
+    #[cfg(feature = "json")]
+    JsonObject,
+
     // ----
     // Window functions
     // ----
@@ -773,6 +883,39 @@ pub(crate) enum PolarsSQLFunctions {
     /// ```
     #[cfg(feature = "rank")]
     DenseRank,
+    /// SQL 'percent_rank' function.
+    /// Returns the relative rank of each row within a window partition, as a value
+    /// between 0 and 1; computed as `(rank - 1) / (partition_rows - 1)`.
+    /// ```sql
+    /// SELECT PERCENT_RANK() OVER (ORDER BY col1) FROM df;
+    /// SELECT PERCENT_RANK() OVER (PARTITION BY col1 ORDER BY col2 DESC) FROM df;
+    /// ```
+    #[cfg(feature = "rank")]
+    PercentRank,
+    /// SQL 'cume_dist' function.
+    /// Returns the cumulative distribution of each row within a window partition; the
+    /// fraction of partition rows that are ordered before (or are peers of) the current row.
+    /// ```sql
+    /// SELECT CUME_DIST() OVER (ORDER BY col1) FROM df;
+    /// SELECT CUME_DIST() OVER (PARTITION BY col1 ORDER BY col2) FROM df;
+    /// ```
+    #[cfg(feature = "rank")]
+    CumeDist,
+    /// SQL 'ntile' function.
+    /// Divides the rows of each window partition into `n` buckets (as equally as possible)
+    /// and returns the 1-indexed bucket number of each row.
+    /// ```sql
+    /// SELECT NTILE(4) OVER (ORDER BY col1) FROM df;
+    /// SELECT NTILE(2) OVER (PARTITION BY col1 ORDER BY col2) FROM df;
+    /// ```
+    Ntile,
+    /// SQL 'nth_value' window function.
+    /// Returns the value at the `n`-th row (1-indexed) of the window frame, or NULL if
+    /// the frame does not (yet) contain `n` rows.
+    /// ```sql
+    /// SELECT NTH_VALUE(col1, 2) OVER (PARTITION BY category ORDER BY id) FROM df;
+    /// ```
+    NthValue,
 
     // ----
     // Column selection
@@ -783,6 +926,12 @@ pub(crate) enum PolarsSQLFunctions {
     // User-defined
     // ----
     Udf(String),
+    /// SQL-defined function (macro), registered with `CREATE FUNCTION` or `CREATE MACRO`.
+    /// ```sql
+    /// CREATE MACRO add_tax(amount, rate := 0.2) AS amount * (1 + rate);
+    /// SELECT add_tax(price) FROM df;
+    /// ```
+    Macro(String),
 }
 
 impl PolarsSQLFunctions {
@@ -831,8 +980,12 @@ impl PolarsSQLFunctions {
             "covar",
             "covar_pop",
             "covar_samp",
+            "cume_dist",
             "date",
+            "date_add",
+            "date_diff",
             "date_part",
+            "date_trunc",
             "degrees",
             "dense_rank",
             "ends_with",
@@ -844,6 +997,14 @@ impl PolarsSQLFunctions {
             "if",
             "ifnull",
             "initcap",
+            #[cfg(feature = "json")]
+            "json_arrayagg",
+            #[cfg(feature = "json")]
+            "json_extract",
+            #[cfg(feature = "json")]
+            "json_extract_path_text",
+            #[cfg(feature = "json")]
+            "json_object",
             "lag",
             "last",
             "last_value",
@@ -851,6 +1012,7 @@ impl PolarsSQLFunctions {
             "least",
             "left",
             "length",
+            "listagg",
             "ln",
             "log",
             "log10",
@@ -864,8 +1026,11 @@ impl PolarsSQLFunctions {
             "quantile_disc",
             "min",
             "mod",
+            "nth_value",
+            "ntile",
             "nullif",
             "octet_length",
+            "percent_rank",
             "pi",
             "pow",
             "power",
@@ -873,7 +1038,12 @@ impl PolarsSQLFunctions {
             "quantile_disc",
             "radians",
             "rank",
+            "regexp_count",
+            "regexp_extract",
             "regexp_like",
+            "regexp_replace",
+            "regexp_split_to_array",
+            "regexp_substr",
             "replace",
             "reverse",
             "right",
@@ -891,6 +1061,7 @@ impl PolarsSQLFunctions {
             "stdev",
             "stdev_samp",
             "strftime",
+            "string_agg",
             "strpos",
             "strptime",
             "substr",
@@ -907,7 +1078,38 @@ impl PolarsSQLFunctions {
 }
 
 impl PolarsSQLFunctions {
-    fn try_from_sql(function: &'_ SQLFunction, ctx: &'_ SQLContext) -> PolarsResult<Self> {
+    /// Functions that aggregate their input (and so can take a `FILTER` clause).
+    pub(crate) fn is_aggregate(&self) -> bool {
+        #[cfg(feature = "json")]
+        if matches!(self, Self::JsonArrayAgg) {
+            return true;
+        }
+        matches!(
+            self,
+            Self::ArrayAgg
+                | Self::Avg
+                | Self::Corr
+                | Self::Count
+                | Self::CovarPop
+                | Self::CovarSamp
+                | Self::First
+                | Self::Last
+                | Self::Max
+                | Self::Median
+                | Self::Min
+                | Self::QuantileCont
+                | Self::QuantileDisc
+                | Self::StdDev
+                | Self::StringAgg
+                | Self::Sum
+                | Self::Variance
+        )
+    }
+
+    pub(crate) fn try_from_sql(
+        function: &'_ SQLFunction,
+        ctx: &'_ SQLContext,
+    ) -> PolarsResult<Self> {
         let function_name = function.name.0[0].as_ident().unwrap().value.to_lowercase();
         Ok(match function_name.as_str() {
             // ----
@@ -978,7 +1180,12 @@ impl PolarsSQLFunctions {
             // Temporal functions
             // ----
             "date" => Self::Date,
+            "date_add" | "dateadd" => Self::DateAdd,
+            "date_diff" | "datediff" => Self::DateDiff,
             "date_part" => Self::DatePart,
+            "date_trunc" => Self::DateTrunc,
+            "last_day" => Self::LastDay,
+            "make_date" => Self::MakeDate,
             "strftime" => Self::Strftime,
             "timestamp" | "datetime" => Self::Timestamp,
 
@@ -998,7 +1205,12 @@ impl PolarsSQLFunctions {
             "ltrim" => Self::LeftTrim,
             "normalize" => Self::Normalize,
             "octet_length" => Self::OctetLength,
+            "regexp_count" => Self::RegexpCount,
+            "regexp_extract" => Self::RegexpExtract,
             "regexp_like" => Self::RegexpLike,
+            "regexp_replace" => Self::RegexpReplace,
+            "regexp_split_to_array" => Self::RegexpSplitToArray,
+            "regexp_substr" => Self::RegexpSubstr,
             "replace" => Self::Replace,
             "reverse" => Self::Reverse,
             "right" => Self::Right,
@@ -1029,6 +1241,7 @@ impl PolarsSQLFunctions {
             "quantile_cont" => Self::QuantileCont,
             "quantile_disc" => Self::QuantileDisc,
             "stdev" | "stddev" | "stdev_samp" | "stddev_samp" => Self::StdDev,
+            "string_agg" | "listagg" => Self::StringAgg,
             "sum" => Self::Sum,
             "var" | "variance" | "var_samp" => Self::Variance,
 
@@ -1048,15 +1261,33 @@ impl PolarsSQLFunctions {
             "array_upper" => Self::ArrayMax,
             "unnest" => Self::Explode,
 
+            // ----
+            // JSON functions
+            // ----
+            #[cfg(feature = "json")]
+            "json_arrayagg" => Self::JsonArrayAgg,
+            #[cfg(feature = "json")]
+            "json_extract" => Self::JsonExtract,
+            #[cfg(feature = "json")]
+            "json_extract_path_text" => Self::JsonExtractPathText,
+            #[cfg(feature = "json")]
+            "json_object" => Self::JsonObject,
+
             // ----
             // Window functions
             // ----
             #[cfg(feature = "rank")]
+            "cume_dist" => Self::CumeDist,
+            #[cfg(feature = "rank")]
             "dense_rank" => Self::DenseRank,
             "first_value" => Self::FirstValue,
             "last_value" => Self::LastValue,
             "lag" => Self::Lag,
             "lead" => Self::Lead,
+            "nth_value" => Self::NthValue,
+            "ntile" => Self::Ntile,
+            #[cfg(feature = "rank")]
+            "percent_rank" => Self::PercentRank,
             #[cfg(feature = "rank")]
             "rank" => Self::Rank,
             "row_number" => Self::RowNumber,
@@ -1067,7 +1298,9 @@ impl PolarsSQLFunctions {
             "columns" => Self::Columns,
 
             other => {
-                if ctx.function_registry.contains(other) {
+                if ctx.macro_map.read().unwrap().contains_key(other) {
+                    Self::Macro(other.to_string())
+                } else if ctx.function_registry.contains(other) {
                     Self::Udf(other.to_string())
                 } else {
                     polars_bail!(SQLInterface: "unsupported function '{}'", other);
@@ -1086,20 +1319,26 @@ impl SQLFunctionVisitor<'_> {
         let function = self.func;
 
         // TODO: implement the following functions where possible
-        if !function.within_group.is_empty() {
+        if !function.within_group.is_empty() && !matches!(function_name, StringAgg) {
             polars_bail!(SQLInterface: "'WITHIN GROUP' is not currently supported")
         }
-        if function.filter.is_some() {
-            polars_bail!(SQLInterface: "'FILTER' is not currently supported")
-        }
         if function.null_treatment.is_some() {
             polars_bail!(SQLInterface: "'IGNORE|RESPECT NULLS' is not currently supported")
         }
+        if let Some(filter) = &function.filter {
+            if !function_name.is_aggregate() {
+                polars_bail!(SQLSyntax: "FILTER is only valid for aggregate functions (found '{}')", function.name)
+            }
+            if function.over.is_some() {
+                polars_bail!(SQLInterface: "FILTER is not currently supported with OVER")
+            }
+            self.agg_filter = Some(parse_sql_expr(filter, self.ctx, self.active_schema)?);
+        }
 
         let log_with_base =
             |e: Expr, base: f64| e.log(LiteralValue::Dyn(DynLiteralValue::Float(base)).lit());
 
-        match function_name {
+        let expr = match function_name {
             // ----
             // Bitwise functions
             // ----
@@ -1230,6 +1469,78 @@ impl SQLFunctionVisitor<'_> {
             // ----
             // Date functions
             // ----
+            DateAdd => {
+                let args = extract_args(function)?;
+                // Offsets by `n` of the given unit, where `n` must be an integer.
+                let offset_by_units = |n: Expr, unit: &'static str| -> PolarsResult<Expr> {
+                    Ok(match n {
+                        Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Int(n))) => {
+                            lit(format!("{n}{unit}"))
+                        },
+                        Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Float(_))) => {
+                            polars_bail!(SQLSyntax: "DATE_ADD expects an integer number of '{}' units", unit)
+                        },
+                        n => concat_str(
+                            [
+                                n.strict_cast(DataType::Int64).cast(DataType::String),
+                                lit(unit),
+                            ],
+                            "",
+                            false,
+                        ),
+                    })
+                };
+                match args.as_slice() {
+                    [
+                        FunctionArgExpr::Expr(sql_expr),
+                        FunctionArgExpr::Expr(SQLExpr::Interval(interval)),
+                    ] => {
+                        let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                        let duration = interval_to_duration(interval, false)?;
+                        Ok(expr.dt().offset_by(lit(duration.to_string())))
+                    },
+                    // A bare number is taken as a number of days.
+                    [
+                        FunctionArgExpr::Expr(sql_expr),
+                        FunctionArgExpr::Expr(n_expr),
+                    ] => {
+                        let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                        let n = parse_sql_expr(n_expr, self.ctx, self.active_schema)?;
+                        Ok(expr.dt().offset_by(offset_by_units(n, "d")?))
+                    },
+                    [
+                        part,
+                        FunctionArgExpr::Expr(n_expr),
+                        FunctionArgExpr::Expr(sql_expr),
+                    ] => {
+                        let unit = parse_temporal_unit("DATE_ADD", part)?;
+                        let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                        let n = parse_sql_expr(n_expr, self.ctx, self.active_schema)?;
+                        Ok(expr.dt().offset_by(offset_by_units(n, unit)?))
+                    },
+                    _ => {
+                        polars_bail!(SQLSyntax: "DATE_ADD expects 2-3 arguments (found {})", args.len())
+                    },
+                }
+            },
+            DateDiff => {
+                let args = extract_args(function)?;
+                match args.as_slice() {
+                    [
+                        part,
+                        FunctionArgExpr::Expr(start),
+                        FunctionArgExpr::Expr(end),
+                    ] => {
+                        let unit = parse_temporal_unit("DATEDIFF", part)?;
+                        let start = parse_sql_expr(start, self.ctx, self.active_schema)?;
+                        let end = parse_sql_expr(end, self.ctx, self.active_schema)?;
+                        Ok(temporal_diff(start, end, unit))
+                    },
+                    _ => {
+                        polars_bail!(SQLSyntax: "DATEDIFF expects 3 arguments (found {})", args.len())
+                    },
+                }
+            },
             DatePart => self.try_visit_binary(|part, e| {
                 match part {
                     Expr::Literal(p) if p.extract_str().is_some() => {
@@ -1250,6 +1561,39 @@ impl SQLFunctionVisitor<'_> {
                     },
                 }
             }),
+            DateTrunc => {
+                let args = extract_args(function)?;
+                match args.as_slice() {
+                    [part, FunctionArgExpr::Expr(sql_expr)] => {
+                        let unit = parse_temporal_unit("DATE_TRUNC", part)?;
+                        let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                        Ok(expr.dt().truncate(lit(format!("1{unit}"))))
+                    },
+                    _ => {
+                        polars_bail!(SQLSyntax: "DATE_TRUNC expects 2 arguments (found {})", args.len())
+                    },
+                }
+            },
+            LastDay => {
+                let args = extract_args(function)?;
+                match args.len() {
+                    1 => self.visit_unary(|e| e.dt().month_end().cast(DataType::Date)),
+                    _ => {
+                        polars_bail!(SQLSyntax: "LAST_DAY expects 1 argument (found {})", args.len())
+                    },
+                }
+            },
+            MakeDate => {
+                let args = extract_args(function)?;
+                match args.len() {
+                    3 => self.try_visit_ternary(|y, m, d: Expr| {
+                        Ok(datetime(DatetimeArgs::new(y, m, d)).dt().date())
+                    }),
+                    _ => {
+                        polars_bail!(SQLSyntax: "MAKE_DATE expects 3 arguments (found {})", args.len())
+                    },
+                }
+            },
             Strftime => {
                 let args = extract_args(function)?;
                 match args.len() {
@@ -1429,6 +1773,106 @@ impl SQLFunctionVisitor<'_> {
                     _ => polars_bail!(SQLSyntax: "REGEXP_LIKE expects 2-3 arguments (found {})",args.len()),
                 }
             },
+            RegexpCount => {
+                let args = self.visit_args_as_exprs("REGEXP_COUNT", 2, 4)?;
+                let mut args = args.into_iter();
+                let (e, pat) = (args.next().unwrap(), args.next().unwrap());
+                let start = match args.next() {
+                    Some(start) => extract_int_literal("REGEXP_COUNT", "start", &start)?,
+                    None => 1,
+                };
+                let flags = match args.next() {
+                    Some(flags) => extract_str_literal("REGEXP_COUNT", "flags", &flags)?,
+                    None => String::new(),
+                };
+                if start < 1 {
+                    polars_bail!(SQLSyntax: "REGEXP_COUNT 'start' must be a positive integer (found {})", start)
+                }
+                let (pat, _) = apply_regex_flags("REGEXP_COUNT", pat, &flags, false)?;
+                Ok(slice_from_position(e, start)
+                    .str()
+                    .count_matches(pat, false))
+            },
+            RegexpExtract => {
+                let args = self.visit_args_as_exprs("REGEXP_EXTRACT", 2, 3)?;
+                let mut args = args.into_iter();
+                let (e, pat) = (args.next().unwrap(), args.next().unwrap());
+                let group = match args.next() {
+                    Some(group) => extract_int_literal("REGEXP_EXTRACT", "group", &group)?,
+                    None => 0,
+                };
+                if group < 0 {
+                    polars_bail!(SQLSyntax: "REGEXP_EXTRACT 'group' must be a non-negative integer (found {})", group)
+                }
+                Ok(e.str().extract(pat, group as usize))
+            },
+            RegexpReplace => {
+                let args = self.visit_args_as_exprs("REGEXP_REPLACE", 3, 4)?;
+                let mut args = args.into_iter();
+                let (e, pat) = (args.next().unwrap(), args.next().unwrap());
+                let replacement = match args.next().unwrap() {
+                    Expr::Literal(lv) if lv.extract_str().is_some() => {
+                        lit(to_rust_regex_replacement(lv.extract_str().unwrap()))
+                    },
+                    replacement => replacement,
+                };
+                let flags = match args.next() {
+                    Some(flags) => extract_str_literal("REGEXP_REPLACE", "flags", &flags)?,
+                    None => String::new(),
+                };
+                let (pat, global) = apply_regex_flags("REGEXP_REPLACE", pat, &flags, true)?;
+                Ok(if global {
+                    e.str().replace_all(pat, replacement, false)
+                } else {
+                    e.str().replace(pat, replacement, false)
+                })
+            },
+            RegexpSplitToArray => {
+                let args = self.visit_args_as_exprs("REGEXP_SPLIT_TO_ARRAY", 2, 3)?;
+                let mut args = args.into_iter();
+                let (e, pat) = (args.next().unwrap(), args.next().unwrap());
+                let flags = match args.next() {
+                    Some(flags) => extract_str_literal("REGEXP_SPLIT_TO_ARRAY", "flags", &flags)?,
+                    None => String::new(),
+                };
+                let (pat, _) = apply_regex_flags("REGEXP_SPLIT_TO_ARRAY", pat, &flags, false)?;
+                Ok(e.str().split_regex(pat, true))
+            },
+            RegexpSubstr => {
+                let args = self.visit_args_as_exprs("REGEXP_SUBSTR", 2, 6)?;
+                let mut args = args.into_iter();
+                let (e, pat) = (args.next().unwrap(), args.next().unwrap());
+                let mut int_param = |param: &str| match args.next() {
+                    Some(value) => extract_int_literal("REGEXP_SUBSTR", param, &value).map(Some),
+                    None => Ok(None),
+                };
+                let start = int_param("start")?.unwrap_or(1);
+                let occurrence = int_param("occurrence")?.unwrap_or(1);
+                let flags = match args.next() {
+                    Some(flags) => extract_str_literal("REGEXP_SUBSTR", "flags", &flags)?,
+                    None => String::new(),
+                };
+                let group = match args.next() {
+                    Some(group) => extract_int_literal("REGEXP_SUBSTR", "group", &group)?,
+                    None => 0,
+                };
+                if start < 1 || occurrence < 1 || group < 0 {
+                    polars_bail!(SQLSyntax: "REGEXP_SUBSTR 'start' and 'occurrence' must be positive, and 'group' non-negative")
+                }
+                let (pat, _) = apply_regex_flags("REGEXP_SUBSTR", pat, &flags, false)?;
+                let e = slice_from_position(e, start);
+                match (occurrence, group) {
+                    (1, _) => Ok(e.str().extract(pat, group as usize)),
+                    (_, 0) => Ok(e
+                        .str()
+                        .extract_all(pat)
+                        .list()
+                        .get(lit(occurrence - 1), true)),
+                    _ => {
+                        polars_bail!(SQLInterface: "REGEXP_SUBSTR does not currently support a capture 'group' with 'occurrence' > 1")
+                    },
+                }
+            },
             Replace => {
                 let args = extract_args(function)?;
                 match args.len() {
@@ -1585,10 +2029,19 @@ impl SQLFunctionVisitor<'_> {
             // Aggregate functions
             // ----
             Avg => self.visit_unary(Expr::mean),
-            Corr => self.visit_binary(polars_lazy::dsl::pearson_corr),
+            Corr => {
+                let filter = self.agg_input_filter();
+                self.visit_binary(|a, b| polars_lazy::dsl::pearson_corr(a, filter(b)))
+            },
             Count => self.visit_count(),
-            CovarPop => self.visit_binary(|a, b| polars_lazy::dsl::cov(a, b, 0)),
-            CovarSamp => self.visit_binary(|a, b| polars_lazy::dsl::cov(a, b, 1)),
+            CovarPop => {
+                let filter = self.agg_input_filter();
+                self.visit_binary(|a, b| polars_lazy::dsl::cov(a, filter(b), 0))
+            },
+            CovarSamp => {
+                let filter = self.agg_input_filter();
+                self.visit_binary(|a, b| polars_lazy::dsl::cov(a, filter(b), 1))
+            },
             First => self.visit_unary(Expr::first),
             Last => self.visit_unary(Expr::last),
             Max => self.visit_unary_with_opt_cumulative(Expr::max, Expr::cum_max),
@@ -1626,6 +2079,7 @@ impl SQLFunctionVisitor<'_> {
             },
             Min => self.visit_unary_with_opt_cumulative(Expr::min, Expr::cum_min),
             StdDev => self.visit_unary(|e| e.std(1)),
+            StringAgg => self.visit_string_agg(),
             Sum => self.visit_unary_with_opt_cumulative(Expr::sum, Expr::cum_sum),
             Variance => self.visit_unary(|e| e.var(1)),
 
@@ -1698,6 +2152,83 @@ impl SQLFunctionVisitor<'_> {
                 })
             },
 
+            // ----
+            // JSON functions
+            // ----
+            #[cfg(feature = "json")]
+            JsonArrayAgg => self.visit_json_arrayagg(),
+            #[cfg(feature = "json")]
+            JsonExtract => {
+                let args = extract_args(function)?;
+                match args.as_slice() {
+                    [FunctionArgExpr::Expr(sql_expr), FunctionArgExpr::Expr(path)] => {
+                        let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                        let path = String::from_sql_expr(path, self.ctx)?;
+                        if is_json_string(&expr, self.active_schema) {
+                            Ok(expr.str().json_path_match(lit(path)))
+                        } else {
+                            Ok(struct_path_access(expr, split_json_path(&path)?))
+                        }
+                    },
+                    _ => {
+                        polars_bail!(SQLSyntax: "JSON_EXTRACT expects 2 arguments (found {})", args.len())
+                    },
+                }
+            },
+            #[cfg(feature = "json")]
+            JsonExtractPathText => {
+                let args = extract_args(function)?;
+                match args.as_slice() {
+                    [FunctionArgExpr::Expr(sql_expr), keys @ ..] if !keys.is_empty() => {
+                        let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                        let mut path = Vec::with_capacity(keys.len());
+                        for key in keys {
+                            match key {
+                                FunctionArgExpr::Expr(SQLExpr::Value(ValueWithSpan {
+                                    value: SQLValue::SingleQuotedString(s) | SQLValue::Number(s, _),
+                                    ..
+                                })) => path.push(s.clone()),
+                                _ => {
+                                    polars_bail!(SQLSyntax: "invalid key for JSON_EXTRACT_PATH_TEXT ({})", key)
+                                },
+                            }
+                        }
+                        if is_json_string(&expr, self.active_schema) {
+                            let json_path =
+                                json_path_from_elems(path.iter().map(|s| s.as_str()), true);
+                            Ok(expr.str().json_path_match(lit(json_path)))
+                        } else {
+                            Ok(struct_path_access(expr, path).cast(DataType::String))
+                        }
+                    },
+                    _ => {
+                        polars_bail!(SQLSyntax: "JSON_EXTRACT_PATH_TEXT expects at least 2 arguments (found {})", args.len())
+                    },
+                }
+            },
+            #[cfg(feature = "json")]
+            JsonObject => {
+                let args = extract_args(function)?;
+                if args.len() % 2 != 0 {
+                    polars_bail!(SQLSyntax: "JSON_OBJECT expects an even number of arguments (found {})", args.len())
+                }
+                if args.is_empty() {
+                    return Ok(lit("{}"));
+                }
+                let mut fields = Vec::with_capacity(args.len() / 2);
+                for kv in args.chunks(2) {
+                    match kv {
+                        [FunctionArgExpr::Expr(key), FunctionArgExpr::Expr(value)] => {
+                            let key = String::from_sql_expr(key, self.ctx)?;
+                            let value = parse_sql_expr(value, self.ctx, self.active_schema)?;
+                            fields.push(value.alias(key));
+                        },
+                        _ => return self.not_supported_error(),
+                    }
+                }
+                Ok(as_struct(fields).struct_().json_encode())
+            },
+
             // ----
             // Window functions
             // ----
@@ -1757,6 +2288,117 @@ impl SQLFunctionVisitor<'_> {
                 };
                 self.apply_window_spec(rank_expr, &self.func.over)
             },
+            #[cfg(feature = "rank")]
+            PercentRank | CumeDist => {
+                let (func_name, rank_method) = match function_name {
+                    PercentRank => ("PERCENT_RANK", RankMethod::Min),
+                    CumeDist => ("CUME_DIST", RankMethod::Max),
+                    _ => unreachable!(),
+                };
+                let args = extract_args(function)?;
+                if !args.is_empty() {
+                    polars_bail!(SQLSyntax: "{} expects 0 arguments (found {})", func_name, args.len());
+                }
+                let window_spec = match &self.func.over {
+                    Some(window_type) => self.resolve_window_spec(window_type)?,
+                    None => {
+                        polars_bail!(SQLSyntax: "{} requires an OVER clause with ORDER BY", func_name)
+                    },
+                };
+                if window_spec.order_by.is_empty() {
+                    polars_bail!(SQLSyntax: "{} requires an OVER clause with ORDER BY", func_name)
+                }
+                let (order_exprs, all_desc) =
+                    self.parse_order_by_in_window(&window_spec.order_by)?;
+                let rank_options = RankOptions {
+                    method: rank_method,
+                    descending: all_desc,
+                };
+                let rank_expr = if order_exprs.len() == 1 {
+                    order_exprs[0].clone().rank(rank_options, None)
+                } else {
+                    as_struct(order_exprs).rank(rank_options, None)
+                };
+                let rank_expr = rank_expr.cast(DataType::Float64);
+                let n_rows = len().cast(DataType::Float64);
+                let dist_expr = if matches!(function_name, PercentRank) {
+                    // note: a single-row partition has a percent_rank of zero
+                    when(n_rows.clone().gt(lit(1.0)))
+                        .then((rank_expr - lit(1.0)) / (n_rows - lit(1.0)))
+                        .otherwise(lit(0.0))
+                } else {
+                    rank_expr / n_rows
+                };
+                self.apply_window_spec(dist_expr, &self.func.over)
+            },
+            Ntile => {
+                let args = extract_args(function)?;
+                let n_buckets = match args.as_slice() {
+                    [FunctionArgExpr::Expr(sql_expr)] => {
+                        match parse_sql_expr(sql_expr, self.ctx, self.active_schema)? {
+                            Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Int(n))) if n > 0 => {
+                                i64::try_from(n).map_err(|_| {
+                                    polars_err!(SQLSyntax: "NTILE bucket count is too large ({})", n)
+                                })?
+                            },
+                            _ => polars_bail!(SQLSyntax: "NTILE expects a positive integer bucket count (found {})", args[0]),
+                        }
+                    },
+                    _ => polars_bail!(SQLSyntax: "NTILE expects 1 argument (found {})", args.len()),
+                };
+                if self.func.over.is_none() {
+                    polars_bail!(SQLSyntax: "NTILE requires an OVER clause");
+                }
+                // Buckets are filled in order; when the rows do not divide evenly,
+                // the first `n_rows % n_buckets` buckets get one additional row
+                let idx = int_range(lit(0i64), len(), 1, DataType::Int64);
+                let n_rows = len().cast(DataType::Int64);
+                let bucket_size = n_rows.clone() / lit(n_buckets);
+                let n_larger = n_rows % lit(n_buckets);
+                let threshold = n_larger.clone() * (bucket_size.clone() + lit(1i64));
+                let ntile_expr = when(idx.clone().lt(threshold.clone()))
+                    .then(idx.clone() / (bucket_size.clone() + lit(1i64)))
+                    .otherwise((idx - threshold) / bucket_size.clip_min(lit(1i64)) + n_larger)
+                    + lit(1i64);
+
+                self.apply_window_spec(ntile_expr.cast(DataType::UInt32), &self.func.over)
+            },
+            NthValue => {
+                let args = extract_args(function)?;
+                let (expr, n) = match args.as_slice() {
+                    [
+                        FunctionArgExpr::Expr(sql_expr),
+                        FunctionArgExpr::Expr(n_expr),
+                    ] => {
+                        let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                        match parse_sql_expr(n_expr, self.ctx, self.active_schema)? {
+                            Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Int(n))) if n > 0 => {
+                                let n = i64::try_from(n).map_err(|_| {
+                                    polars_err!(SQLSyntax: "NTH_VALUE offset is too large ({})", n)
+                                })?;
+                                (expr, n)
+                            },
+                            _ => {
+                                polars_bail!(SQLSyntax: "NTH_VALUE expects a positive integer for 'n' (found {})", args[1])
+                            },
+                        }
+                    },
+                    _ => {
+                        polars_bail!(SQLSyntax: "NTH_VALUE expects 2 arguments (found {})", args.len())
+                    },
+                };
+                if self.func.over.is_none() {
+                    polars_bail!(SQLSyntax: "NTH_VALUE requires an OVER clause");
+                }
+                // With the default window frame (ROWS UNBOUNDED PRECEDING TO CURRENT ROW),
+                // rows before the n-th row of the partition do not yet have an n-th value
+                let idx = int_range(lit(0i64), len(), 1, DataType::Int64);
+                let nth_expr = when(idx.gt_eq(lit(n - 1)))
+                    .then(expr.get(lit(n - 1), true))
+                    .otherwise(lit(LiteralValue::untyped_null()));
+
+                self.apply_window_spec(nth_expr, &self.func.over)
+            },
             RowNumber => {
                 let args = extract_args(function)?;
                 if !args.is_empty() {
@@ -1771,7 +2413,10 @@ impl SQLFunctionVisitor<'_> {
             // User-defined
             // ----
             Udf(func_name) => self.visit_udf(&func_name),
-        }
+            Macro(func_name) => self.visit_macro(&func_name),
+        }?;
+
+        Ok(expr)
     }
 
     fn visit_window_offset_function(&mut self, offset_multiplier: i64) -> PolarsResult<Expr> {
@@ -1830,6 +2475,24 @@ impl SQLFunctionVisitor<'_> {
             .call(args))
     }
 
+    fn visit_macro(&mut self, func_name: &str) -> PolarsResult<Expr> {
+        let sql_macro = self.ctx.get_macro(func_name)?;
+        if self.func.over.is_some() {
+            polars_bail!(SQLSyntax: "OVER clause is not supported for macro '{}'", func_name)
+        }
+        let args = match &self.func.args {
+            FunctionArguments::List(FunctionArgumentList {
+                args,
+                duplicate_treatment: None,
+                clauses,
+            }) if clauses.is_empty() => args.as_slice(),
+            FunctionArguments::None => &[],
+            _ => polars_bail!(SQLSyntax: "invalid arguments for macro '{}'", self.func),
+        };
+        let expr = sql_macro.expand_expr(func_name, args)?;
+        parse_sql_expr(&expr, self.ctx, self.active_schema)
+    }
+
     /// Validate window frame specifications.
     ///
     /// Polars only supports ROWS frame semantics, and does
@@ -1957,6 +2620,23 @@ impl SQLFunctionVisitor<'_> {
         }
     }
 
+    /// Restrict the input of an aggregate to the rows matching its `FILTER` clause (if
+    /// any); scalar inputs (eg: `SUM(1)`) are broadcast to every row before filtering.
+    fn agg_input(&self, expr: Expr) -> Expr {
+        (self.agg_input_filter())(expr)
+    }
+
+    fn agg_input_filter(&self) -> impl Fn(Expr) -> Expr + use<> {
+        let cond = self.agg_filter.clone();
+        move |expr| match &cond {
+            Some(cond) => when(cond.clone())
+                .then(expr)
+                .otherwise(lit(LiteralValue::untyped_null()))
+                .filter(cond.clone()),
+            None => expr,
+        }
+    }
+
     fn visit_unary(&mut self, f: impl Fn(Expr) -> Expr) -> PolarsResult<Expr> {
         self.try_visit_unary(|e| Ok(f(e)))
     }
@@ -1965,13 +2645,13 @@ impl SQLFunctionVisitor<'_> {
         let args = extract_args(self.func)?;
         match args.as_slice() {
             [FunctionArgExpr::Expr(sql_expr)] => {
-                f(parse_sql_expr(sql_expr, self.ctx, self.active_schema)?)
+                f(self.agg_input(parse_sql_expr(sql_expr, self.ctx, self.active_schema)?))
             },
-            [FunctionArgExpr::Wildcard] => f(parse_sql_expr(
+            [FunctionArgExpr::Wildcard] => f(self.agg_input(parse_sql_expr(
                 &SQLExpr::Wildcard(AttachedToken::empty()),
                 self.ctx,
                 self.active_schema,
-            )?),
+            )?)),
             _ => self.not_supported_error(),
         }
         .and_then(|e| self.apply_window_spec(e, &self.func.over))
@@ -2029,7 +2709,8 @@ impl SQLFunctionVisitor<'_> {
                 FunctionArgExpr::Expr(sql_expr1),
                 FunctionArgExpr::Expr(sql_expr2),
             ] => {
-                let expr1 = parse_sql_expr(sql_expr1, self.ctx, self.active_schema)?;
+                let expr1 =
+                    self.agg_input(parse_sql_expr(sql_expr1, self.ctx, self.active_schema)?);
                 let expr2 = Arg::from_sql_expr(sql_expr2, self.ctx)?;
                 f(expr1, expr2)
             },
@@ -2089,7 +2770,8 @@ impl SQLFunctionVisitor<'_> {
         let (args, is_distinct, clauses) = extract_args_and_clauses(self.func)?;
         match args.as_slice() {
             [FunctionArgExpr::Expr(sql_expr)] => {
-                let mut base = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                let mut base =
+                    self.agg_input(parse_sql_expr(sql_expr, self.ctx, self.active_schema)?);
                 let mut order_by_clause = None;
                 let mut limit_clause = None;
                 for clause in &clauses {
@@ -2134,6 +2816,105 @@ impl SQLFunctionVisitor<'_> {
         }
     }
 
+    fn visit_string_agg(&mut self) -> PolarsResult<Expr> {
+        let (args, is_distinct, clauses) = extract_args_and_clauses(self.func)?;
+        let func = self.func;
+        let (sql_expr, separator) = match args.as_slice() {
+            [FunctionArgExpr::Expr(sql_expr), FunctionArgExpr::Expr(sep)] => {
+                (sql_expr, String::from_sql_expr(sep, self.ctx)?)
+            },
+            // note: LISTAGG separator is optional (defaulting to the empty string)
+            [FunctionArgExpr::Expr(sql_expr)]
+                if func.name.to_string().eq_ignore_ascii_case("listagg") =>
+            {
+                (sql_expr, String::new())
+            },
+            _ => {
+                polars_bail!(SQLSyntax: "{} expects 2 arguments (found {})", func.name.to_string().to_uppercase(), args.len())
+            },
+        };
+        let mut order_by_clause = clauses.iter().find_map(|clause| match clause {
+            FunctionArgumentClause::OrderBy(order_exprs) => Some(order_exprs.as_slice()),
+            _ => None,
+        });
+        if !func.within_group.is_empty() {
+            if order_by_clause.is_some() {
+                polars_bail!(SQLSyntax: "cannot use both ORDER BY and WITHIN GROUP in {}", func.name)
+            }
+            order_by_clause = Some(func.within_group.as_slice());
+        }
+        let base = self.agg_input(parse_sql_expr(sql_expr, self.ctx, self.active_schema)?);
+        let base = self.apply_agg_ordering(base, sql_expr, is_distinct, order_by_clause)?;
+        Ok(base.cast(DataType::String).str().join(&separator, true))
+    }
+
+    #[cfg(feature = "json")]
+    fn visit_json_arrayagg(&mut self) -> PolarsResult<Expr> {
+        let (args, is_distinct, clauses) = extract_args_and_clauses(self.func)?;
+        match args.as_slice() {
+            [FunctionArgExpr::Expr(sql_expr)] => {
+                let order_by_clause = clauses.iter().find_map(|clause| match clause {
+                    FunctionArgumentClause::OrderBy(order_exprs) => Some(order_exprs.as_slice()),
+                    _ => None,
+                });
+                let base = self.agg_input(parse_sql_expr(sql_expr, self.ctx, self.active_schema)?);
+                let base = self.apply_agg_ordering(base, sql_expr, is_distinct, order_by_clause)?;
+
+                // encode each value as JSON, then join them into an array
+                let values = json_encode(base);
+                Ok(concat_str(
+                    [lit("["), values.str().join(",", false), lit("]")],
+                    "",
+                    false,
+                ))
+            },
+            _ => {
+                polars_bail!(SQLSyntax: "JSON_ARRAYAGG expects 1 argument (found {})", args.len())
+            },
+        }
+    }
+
+    /// Apply DISTINCT and/or ORDER BY to the input of an aggregate function.
+    fn apply_agg_ordering(
+        &mut self,
+        base: Expr,
+        sql_expr: &SQLExpr,
+        is_distinct: bool,
+        order_by: Option<&[OrderByExpr]>,
+    ) -> PolarsResult<Expr> {
+        Ok(match (is_distinct, order_by) {
+            (false, Some(order_by)) => self.apply_order_by(base, order_by)?,
+            (false, None) => base,
+            (true, Some(order_by)) => {
+                self.apply_order_by_to_distinct_array(base.unique_stable(), order_by, sql_expr)?
+            },
+            (true, None) => base.unique_stable(),
+        })
+    }
+
+    /// Parse all function arguments as expressions, validating the argument count.
+    fn visit_args_as_exprs(
+        &mut self,
+        func_name: &str,
+        min_args: usize,
+        max_args: usize,
+    ) -> PolarsResult<Vec<Expr>> {
+        let args = extract_args(self.func)?;
+        if !(min_args..=max_args).contains(&args.len()) {
+            polars_bail!(SQLSyntax: "{} expects {}-{} arguments (found {})", func_name, min_args, max_args, args.len())
+        }
+        let mut exprs = Vec::with_capacity(args.len());
+        for arg in args {
+            match arg {
+                FunctionArgExpr::Expr(sql_expr) => {
+                    exprs.push(parse_sql_expr(sql_expr, self.ctx, self.active_schema)?)
+                },
+                _ => polars_bail!(SQLSyntax: "invalid argument for {} ({})", func_name, arg),
+            }
+        }
+        Ok(exprs)
+    }
+
     fn visit_arr_to_string(&mut self) -> PolarsResult<Expr> {
         let args = extract_args(self.func)?;
         match args.len() {
@@ -2219,19 +3000,27 @@ impl SQLFunctionVisitor<'_> {
                 }
             }
         }
+        // COUNT(*) has no input to filter; count the matching rows instead
+        let count_rows = match &self.agg_filter {
+            Some(cond) => cond.clone().filter(cond.clone()).len(),
+            None => len(),
+        };
         let count_expr = match (is_distinct, args.as_slice()) {
             // COUNT(*), COUNT()
-            (false, [FunctionArgExpr::Wildcard] | []) => len(),
+            (false, [FunctionArgExpr::Wildcard] | []) => count_rows,
             // COUNT(<non-null literal>) is equivalent to COUNT(*)
-            (false, [FunctionArgExpr::Expr(sql_expr)]) if is_non_null_literal(sql_expr) => len(),
+            (false, [FunctionArgExpr::Expr(sql_expr)]) if is_non_null_literal(sql_expr) => {
+                count_rows
+            },
             // COUNT(col)
             (false, [FunctionArgExpr::Expr(sql_expr)]) => {
                 let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
-                expr.count()
+                self.agg_input(expr).count()
             },
             // COUNT(DISTINCT col)
             (true, [FunctionArgExpr::Expr(sql_expr)]) => {
                 let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                let expr = self.agg_input(expr);
                 expr.clone().n_unique().sub(expr.null_count().gt(lit(0)))
             },
             _ => self.not_supported_error()?,
@@ -2248,7 +3037,7 @@ impl SQLFunctionVisitor<'_> {
             // Note: if not specified 'NULLS FIRST' is default for DESC, 'NULLS LAST' otherwise
             // https://www.postgresql.org/docs/current/queries-order.html
             let desc_order = !ob.options.asc.unwrap_or(true);
-            by.push(parse_sql_expr(&ob.expr, self.ctx, self.active_schema)?);
+            by.push(self.agg_input(parse_sql_expr(&ob.expr, self.ctx, self.active_schema)?));
             nulls_last.push(!ob.options.nulls_first.unwrap_or(desc_order));
             descending.push(desc_order);
         }
@@ -2364,6 +3153,197 @@ fn is_non_null_literal(expr: &SQLExpr) -> bool {
     )
 }
 
+fn extract_int_literal(func_name: &str, param: &str, expr: &Expr) -> PolarsResult<i64> {
+    match expr {
+        Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Int(n))) => i64::try_from(*n)
+            .map_err(|_| polars_err!(SQLSyntax: "invalid '{}' for {} ({})", param, func_name, n)),
+        _ => polars_bail!(SQLSyntax: "invalid '{}' for {} ({:?})", param, func_name, expr),
+    }
+}
+
+fn extract_str_literal(func_name: &str, param: &str, expr: &Expr) -> PolarsResult<String> {
+    match expr {
+        Expr::Literal(lv) if lv.extract_str().is_some() => {
+            Ok(lv.extract_str().unwrap().to_string())
+        },
+        _ => polars_bail!(SQLSyntax: "invalid '{}' for {} ({:?})", param, func_name, expr),
+    }
+}
+
+/// Apply PostgreSQL-style regex `flags` to the given pattern (as inline flags),
+/// returning the updated pattern and whether or not the 'g' (global) flag was set.
+fn apply_regex_flags(
+    func_name: &str,
+    pat: Expr,
+    flags: &str,
+    allow_global: bool,
+) -> PolarsResult<(Expr, bool)> {
+    let mut inline_flags = String::new();
+    let mut global = false;
+    for c in flags.chars() {
+        match c {
+            'g' if allow_global => global = true,
+            // 'c' is case-sensitive matching (the default)
+            'c' => {},
+            'i' | 'm' | 's' | 'x' => {
+                if !inline_flags.contains(c) {
+                    inline_flags.push(c)
+                }
+            },
+            _ => polars_bail!(SQLSyntax: "invalid flag '{}' for {}", c, func_name),
+        }
+    }
+    if inline_flags.is_empty() {
+        return Ok((pat, global));
+    }
+    match pat {
+        Expr::Literal(lv) if lv.extract_str().is_some() => {
+            let pat = lv.extract_str().unwrap();
+            Ok((lit(format!("(?{inline_flags}){pat}")), global))
+        },
+        _ => {
+            polars_bail!(SQLSyntax: "{} flags can only be applied to a literal pattern", func_name)
+        },
+    }
+}
+
+/// Convert a PostgreSQL-style regex replacement string (using `\1` and `\&` to refer
+/// to capture groups) into the equivalent Rust regex replacement string.
+fn to_rust_regex_replacement(replacement: &str) -> String {
+    let mut out = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('$', _) => out.push_str("$$"),
+            ('\\', Some(&d)) if d.is_ascii_digit() => {
+                out.push_str(&format!("${{{d}}}"));
+                chars.next();
+            },
+            ('\\', Some('&')) => {
+                out.push_str("${0}");
+                chars.next();
+            },
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            },
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Slice a string from the given (1-indexed) character position.
+fn slice_from_position(expr: Expr, position: i64) -> Expr {
+    if position > 1 {
+        expr.str()
+            .slice(lit(position - 1), lit(LiteralValue::untyped_null()))
+    } else {
+        expr
+    }
+}
+
+#[cfg(feature = "json")]
+fn is_json_string(expr: &Expr, schema: Option<&Schema>) -> bool {
+    matches!(resolve_expr_dtype(expr, schema), Some(DataType::String))
+}
+
+/// Split a simple JSONPath (eg: `$.a.b[0]`) into its component keys/indices.
+#[cfg(feature = "json")]
+fn split_json_path(path: &str) -> PolarsResult<Vec<String>> {
+    let Some(path) = path.trim().strip_prefix('$') else {
+        polars_bail!(SQLSyntax: "JSON path must start with '$' (found '{}')", path)
+    };
+    Ok(path
+        .replace('[', ".")
+        .replace(']', "")
+        .split('.')
+        .map(|elem| elem.trim().trim_matches(|c| c == '\'' || c == '"'))
+        .filter(|elem| !elem.is_empty())
+        .map(|elem| elem.to_string())
+        .collect())
+}
+
+/// Traverse struct fields (and list indices) using the given path elements.
+#[cfg(feature = "json")]
+fn struct_path_access(expr: Expr, path: Vec<String>) -> Expr {
+    path.iter()
+        .fold(expr, |expr, elem| match elem.parse::<i64>() {
+            Ok(idx) => expr.list().get(lit(idx), true),
+            Err(_) => expr.struct_().field_by_name(elem),
+        })
+}
+
+/// Resolve the 'part' argument of a temporal function to the equivalent duration unit;
+/// the part can be given as a string (eg: 'day') or as a bare keyword (eg: day).
+fn parse_temporal_unit(func_name: &str, part: &FunctionArgExpr) -> PolarsResult<&'static str> {
+    let value = match part {
+        FunctionArgExpr::Expr(SQLExpr::Identifier(Ident {
+            value,
+            quote_style: None,
+            ..
+        }))
+        | FunctionArgExpr::Expr(SQLExpr::Value(ValueWithSpan {
+            value: SQLValue::SingleQuotedString(value),
+            ..
+        })) => value.to_ascii_lowercase(),
+        _ => polars_bail!(SQLSyntax: "invalid 'part' for {} ({})", func_name, part),
+    };
+    Ok(match value.as_str() {
+        "year" | "years" | "y" => "y",
+        "quarter" | "quarters" => "q",
+        "month" | "months" | "mon" | "mons" => "mo",
+        "week" | "weeks" => "w",
+        "day" | "days" | "d" => "d",
+        "hour" | "hours" | "h" => "h",
+        "minute" | "minutes" | "mins" | "min" | "m" => "m",
+        "second" | "seconds" | "sec" | "secs" | "s" => "s",
+        "millisecond" | "milliseconds" | "ms" => "ms",
+        "microsecond" | "microseconds" | "us" => "us",
+        "nanosecond" | "nanoseconds" | "ns" => "ns",
+        _ => polars_bail!(SQLSyntax: "{} does not support '{}' part", func_name, value),
+    })
+}
+
+/// Count the number of `unit` boundaries crossed between `start` and `end`.
+fn temporal_diff(start: Expr, end: Expr, unit: &str) -> Expr {
+    let year = |e: Expr| e.dt().year().cast(DataType::Int64);
+    match unit {
+        "y" => year(end) - year(start),
+        "q" => {
+            let quarters =
+                |e: Expr| year(e.clone()) * lit(4i64) + e.dt().quarter().cast(DataType::Int64);
+            quarters(end) - quarters(start)
+        },
+        "mo" => {
+            let months =
+                |e: Expr| year(e.clone()) * lit(12i64) + e.dt().month().cast(DataType::Int64);
+            months(end) - months(start)
+        },
+        _ => {
+            // note: Date values cannot be truncated at sub-millisecond resolution
+            let delta = if matches!(unit, "us" | "ns") {
+                end - start
+            } else {
+                let every = lit(format!("1{unit}"));
+                end.dt().truncate(every.clone()) - start.dt().truncate(every)
+            };
+            let delta = delta.dt();
+            let diff = match unit {
+                "w" => delta.total_days(false) / lit(7i64),
+                "d" => delta.total_days(false),
+                "h" => delta.total_hours(false),
+                "m" => delta.total_minutes(false),
+                "s" => delta.total_seconds(false),
+                "ms" => delta.total_milliseconds(false),
+                "us" => delta.total_microseconds(false),
+                _ => delta.total_nanoseconds(false),
+            };
+            diff.cast(DataType::Int64)
+        },
+    }
+}
+
 fn extract_args(func: &SQLFunction) -> PolarsResult<Vec<&FunctionArgExpr>> {
     let (args, _, _) = _extract_func_args(func, false, false)?;
     Ok(args)
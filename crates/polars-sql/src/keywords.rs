@@ -34,6 +34,7 @@ pub fn all_keywords() -> Vec<&'static str> {
         keywords::FLOAT,
         keywords::FROM,
         keywords::FULL,
+        keywords::FUNCTION,
         keywords::GROUP,
         keywords::HAVING,
         keywords::IN,
@@ -44,6 +45,7 @@ pub fn all_keywords() -> Vec<&'static str> {
         keywords::JOIN,
         keywords::LEFT,
         keywords::LIMIT,
+        keywords::MACRO,
         keywords::NOT,
         keywords::NULL,
         keywords::OFFSET,
@@ -0,0 +1,472 @@
+//! Prepared SQL statements, with typed parameter placeholders.
+//!
+//! A statement is parsed (and its placeholders resolved) once by [`SQLContext::prepare`],
+//! and planned once per set of parameter dtypes; each execution binds a fresh set of
+//! [`AnyValue`]s to the placeholders of that plan, without any string interpolation of
+//! the values into the query text.
+
+use std::ops::ControlFlow;
+
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_plan::dsl::{DslPlan, SpecialEq};
+use sqlparser::ast::{
+    Expr as SQLExpr, Statement, Value as SQLValue, ValueWithSpan, Visit, Visitor as SQLVisitor,
+    visit_expressions_mut,
+};
+
+use crate::SQLContext;
+
+/// Key prefix of the parameters bound to a statement (also naming their planned markers).
+const PARAM_PREFIX: &str = "$__polars_param_";
+
+/// The placeholder syntax used by a prepared statement (styles cannot be mixed).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PlaceholderStyle {
+    /// Anonymous positional placeholders, e.g. `?`; bound in order of appearance.
+    Anonymous,
+    /// Numbered positional placeholders, e.g. `$1`, `?1`.
+    Numbered,
+    /// Named placeholders, e.g. `:name`, `$name`.
+    Named,
+}
+
+/// Classified placeholder token.
+enum Placeholder<'a> {
+    Anonymous,
+    Numbered(usize),
+    Named(&'a str),
+}
+
+impl<'a> Placeholder<'a> {
+    fn parse(token: &'a str) -> PolarsResult<Self> {
+        if token == "?" {
+            return Ok(Self::Anonymous);
+        }
+        let Some((prefix, body)) = token.split_at_checked(1) else {
+            polars_bail!(SQLSyntax: "invalid placeholder '{}'", token)
+        };
+        match (prefix, body.parse::<usize>()) {
+            ("$" | "?", Ok(n)) if n > 0 => Ok(Self::Numbered(n)),
+            ("$" | "?", Ok(_)) => {
+                polars_bail!(SQLSyntax: "numbered placeholders start at 1; found '{}'", token)
+            },
+            ("$" | ":" | "@", Err(_)) if !body.is_empty() => Ok(Self::Named(body)),
+            _ => polars_bail!(SQLSyntax: "unsupported placeholder '{}'", token),
+        }
+    }
+
+    fn style(&self) -> PlaceholderStyle {
+        match self {
+            Self::Anonymous => PlaceholderStyle::Anonymous,
+            Self::Numbered(_) => PlaceholderStyle::Numbered,
+            Self::Named(_) => PlaceholderStyle::Named,
+        }
+    }
+}
+
+/// Visitor that collects placeholder tokens (and their source location) in traversal order.
+#[derive(Default)]
+struct PlaceholderCollector {
+    placeholders: Vec<(String, (u64, u64))>,
+}
+
+impl SQLVisitor for PlaceholderCollector {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &SQLExpr) -> ControlFlow<Self::Break> {
+        if let SQLExpr::Value(ValueWithSpan {
+            value: SQLValue::Placeholder(p),
+            span,
+        }) = expr
+        {
+            self.placeholders
+                .push((p.clone(), (span.start.line, span.start.column)));
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// A parsed SQL statement with parameter placeholders, created by [`SQLContext::prepare`].
+///
+/// Placeholders are either positional (`?`, `$1`, `?1`) or named (`:name`, `$name`);
+/// bind values with [`PreparedStatement::execute`] or [`PreparedStatement::execute_named`]
+/// respectively. The statement is planned on its first execution (and again only if the
+/// parameter dtypes change); later executions substitute their values into that plan, so
+/// tables are resolved against the context it was prepared from when it is first planned.
+#[derive(Clone)]
+pub struct PreparedStatement {
+    ctx: SQLContext,
+    statement: Statement,
+    style: Option<PlaceholderStyle>,
+    // parameter index of each placeholder occurrence, in AST traversal order
+    slots: Vec<usize>,
+    // parameter names (named placeholders only), in order of first appearance
+    names: Vec<PlSmallStr>,
+    n_params: usize,
+    plan: Option<CachedPlan>,
+}
+
+/// The plan of a prepared statement for a given set of parameter dtypes.
+#[derive(Clone)]
+struct CachedPlan {
+    dtypes: Vec<DataType>,
+    // `None` if the parameters cannot be rebound, in which case each execution is planned
+    lf: Option<LazyFrame>,
+}
+
+impl PreparedStatement {
+    pub(crate) fn new(ctx: SQLContext, statement: Statement) -> PolarsResult<Self> {
+        let mut collector = PlaceholderCollector::default();
+        let _ = statement.visit(&mut collector);
+        let found = collector.placeholders;
+
+        // establish a single placeholder style for the statement
+        let mut style = None;
+        for (token, _) in &found {
+            let current = Placeholder::parse(token)?.style();
+            match style {
+                None => style = Some(current),
+                Some(s) if s != current => polars_bail!(
+                    SQLSyntax: "cannot mix anonymous, numbered and named placeholders in the same statement"
+                ),
+                _ => {},
+            }
+        }
+
+        // order placeholder occurrences by source location (ties keep traversal order)
+        let mut by_location: Vec<usize> = (0..found.len()).collect();
+        by_location.sort_by_key(|&i| found[i].1);
+
+        let mut slots = vec![0; found.len()];
+        let mut names: Vec<PlSmallStr> = Vec::new();
+        let mut n_params = 0;
+        for (position, &i) in by_location.iter().enumerate() {
+            slots[i] = match Placeholder::parse(&found[i].0)? {
+                Placeholder::Anonymous => {
+                    n_params = position + 1;
+                    position
+                },
+                Placeholder::Numbered(n) => {
+                    n_params = n_params.max(n);
+                    n - 1
+                },
+                Placeholder::Named(name) => match names.iter().position(|nm| nm == name) {
+                    Some(idx) => idx,
+                    None => {
+                        names.push(name.into());
+                        n_params = names.len();
+                        names.len() - 1
+                    },
+                },
+            };
+        }
+        Ok(Self {
+            ctx,
+            statement,
+            style,
+            slots,
+            names,
+            n_params,
+            plan: None,
+        })
+    }
+
+    /// The number of distinct parameters expected by the statement.
+    pub fn param_count(&self) -> usize {
+        self.n_params
+    }
+
+    /// The parameter names expected by the statement (empty unless using named placeholders).
+    pub fn param_names(&self) -> &[PlSmallStr] {
+        &self.names
+    }
+
+    /// Execute the statement, binding the given values to positional placeholders.
+    ///
+    /// Values are bound in order of appearance for `?`, or by number for `$1`/`?1`.
+    /// ```rust
+    /// # use polars_sql::SQLContext;
+    /// # use polars_core::prelude::*;
+    /// # use polars_lazy::prelude::*;
+    /// # fn main() {
+    ///
+    /// let ctx = SQLContext::new();
+    /// let df = df! {
+    ///    "a" =>  [1, 2, 3],
+    ///    "b" =>  ["x", "y", "z"],
+    /// }
+    /// .unwrap();
+    ///
+    /// ctx.register("df", df.lazy());
+    /// let mut stmt = ctx.prepare("SELECT b FROM df WHERE a >= ? AND b <> ?").unwrap();
+    /// let sql_df = stmt
+    ///     .execute(&[AnyValue::Int64(2), AnyValue::String("z")])
+    ///     .unwrap()
+    ///     .collect()
+    ///     .unwrap();
+    /// assert_eq!(sql_df.height(), 1);
+    /// # }
+    ///```
+    pub fn execute(&mut self, params: &[AnyValue<'_>]) -> PolarsResult<LazyFrame> {
+        polars_ensure!(
+            self.style != Some(PlaceholderStyle::Named),
+            SQLInterface: "statement uses named placeholders; bind parameters with `execute_named`"
+        );
+        polars_ensure!(
+            params.len() == self.n_params,
+            SQLInterface: "expected {} parameter value(s) for prepared statement; found {}", self.n_params, params.len()
+        );
+        let values = params.iter().map(|av| av.clone().into_static()).collect();
+        self.execute_with_values(values)
+    }
+
+    /// Execute the statement, binding the given values to named placeholders.
+    ///
+    /// Names are given without their prefix, e.g. `("min_a", value)` binds `:min_a`.
+    pub fn execute_named(&mut self, params: &[(&str, AnyValue<'_>)]) -> PolarsResult<LazyFrame> {
+        polars_ensure!(
+            matches!(self.style, Some(PlaceholderStyle::Named) | None),
+            SQLInterface: "statement uses positional placeholders; bind parameters with `execute`"
+        );
+        let mut values: Vec<Option<AnyValue<'static>>> = vec![None; self.n_params];
+        for (name, av) in params {
+            let Some(idx) = self.names.iter().position(|nm| nm == name) else {
+                polars_bail!(SQLInterface: "prepared statement has no parameter named '{}'", name)
+            };
+            polars_ensure!(
+                values[idx].is_none(),
+                SQLInterface: "parameter '{}' is bound more than once", name
+            );
+            values[idx] = Some(av.clone().into_static());
+        }
+        let values = values
+            .into_iter()
+            .zip(&self.names)
+            .map(|(av, name)| {
+                av.ok_or_else(
+                    || polars_err!(SQLInterface: "no value given for parameter '{}'", name),
+                )
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        self.execute_with_values(values)
+    }
+
+    fn execute_with_values(&mut self, values: Vec<AnyValue<'static>>) -> PolarsResult<LazyFrame> {
+        // plan once per set of parameter dtypes; executions rebind the values in that plan
+        let dtypes: Vec<DataType> = values.iter().map(|av| av.dtype()).collect();
+        if self.plan.as_ref().is_none_or(|plan| plan.dtypes != dtypes) {
+            let lf = self.plan_with_markers(&values).ok();
+            self.plan = Some(CachedPlan { dtypes, lf });
+        }
+        match &self.plan.as_ref().unwrap().lf {
+            Some(lf) => Ok(bind_params(lf, &values)),
+            // a parameter is used where the planner needs its value (eg: LIMIT)
+            None => self.plan_with_values(values),
+        }
+    }
+
+    /// Plan the statement with its placeholders as aliased literals (markers) that are
+    /// rebound to the parameter values of each execution.
+    fn plan_with_markers(&mut self, values: &[AnyValue<'static>]) -> PolarsResult<LazyFrame> {
+        let (statement, bound_params) = self.bind_statement(values, false);
+
+        self.ctx.bound_params = bound_params;
+        self.ctx.param_markers = true;
+        let res = self.ctx.execute_parsed(&statement);
+        self.ctx.param_markers = false;
+        self.ctx.bound_params.clear();
+
+        // markers must not determine output column names
+        let mut lf = res?;
+        let schema = lf.collect_schema()?;
+        polars_ensure!(
+            !schema.iter_names().any(|name| name.starts_with(PARAM_PREFIX)),
+            SQLInterface: "bound parameters cannot name output columns"
+        );
+        Ok(lf)
+    }
+
+    /// Plan the statement with the given parameter values.
+    fn plan_with_values(&mut self, values: Vec<AnyValue<'static>>) -> PolarsResult<LazyFrame> {
+        let (statement, bound_params) = self.bind_statement(&values, true);
+
+        self.ctx.bound_params = bound_params;
+        let res = self.ctx.execute_parsed(&statement);
+        self.ctx.bound_params.clear();
+        res
+    }
+
+    /// Substitute the statement placeholders with parameter keys bound to the given values.
+    ///
+    /// If `inline` is set, values with a lossless SQL literal equivalent are substituted as
+    /// literals instead (so they are valid anywhere a literal is, eg: LIMIT).
+    fn bind_statement(
+        &self,
+        values: &[AnyValue<'static>],
+        inline: bool,
+    ) -> (Statement, PlHashMap<String, AnyValue<'static>>) {
+        let mut statement = self.statement.clone();
+        let mut bound_params = PlHashMap::new();
+        let mut occurrence = 0;
+        let _ = visit_expressions_mut(&mut statement, |expr| {
+            if let SQLExpr::Value(ValueWithSpan {
+                value: value @ SQLValue::Placeholder(_),
+                ..
+            }) = expr
+            {
+                let idx = self.slots[occurrence];
+                occurrence += 1;
+                *value = match any_value_to_sql_value(&values[idx]).filter(|_| inline) {
+                    Some(v) => v,
+                    None => {
+                        let key = format!("{PARAM_PREFIX}{}", idx + 1);
+                        bound_params.insert(key.clone(), values[idx].clone());
+                        SQLValue::Placeholder(key)
+                    },
+                };
+            }
+            ControlFlow::<()>::Continue(())
+        });
+        (statement, bound_params)
+    }
+}
+
+/// Rebind the parameter markers of a planned statement to the given values.
+fn bind_params(lf: &LazyFrame, values: &[AnyValue<'static>]) -> LazyFrame {
+    LazyFrame::from(bind_plan(&lf.logical_plan, values))
+        .with_optimizations(lf.get_current_optimizations())
+}
+
+fn bind_plan(plan: &DslPlan, values: &[AnyValue<'static>]) -> DslPlan {
+    use DslPlan::*;
+
+    let input = |input: &Arc<DslPlan>| Arc::new(bind_plan(input, values));
+    let inputs = |inputs: &[DslPlan]| -> Vec<DslPlan> {
+        inputs.iter().map(|plan| bind_plan(plan, values)).collect()
+    };
+    let exprs = |exprs: &[Expr]| -> Vec<Expr> {
+        exprs.iter().map(|e| bind_expr(e.clone(), values)).collect()
+    };
+
+    let mut plan = match plan {
+        // the cached IR was converted with the marker values
+        IR { dsl, .. } => return bind_plan(dsl, values),
+        plan => plan.clone(),
+    };
+    match &mut plan {
+        Filter {
+            input: i,
+            predicate,
+        } => {
+            *i = input(i);
+            *predicate = bind_expr(predicate.clone(), values);
+        },
+        Select { expr, input: i, .. } => {
+            *i = input(i);
+            *expr = exprs(expr);
+        },
+        GroupBy {
+            input: i,
+            keys,
+            predicates,
+            aggs,
+            ..
+        } => {
+            *i = input(i);
+            *keys = exprs(keys);
+            *predicates = exprs(predicates);
+            *aggs = exprs(aggs);
+        },
+        Join {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            predicates,
+            ..
+        } => {
+            *input_left = input(input_left);
+            *input_right = input(input_right);
+            *left_on = exprs(left_on);
+            *right_on = exprs(right_on);
+            *predicates = exprs(predicates);
+        },
+        HStack {
+            input: i, exprs: e, ..
+        } => {
+            *i = input(i);
+            *e = exprs(e);
+        },
+        Sort {
+            input: i,
+            by_column,
+            ..
+        } => {
+            *i = input(i);
+            *by_column = exprs(by_column);
+        },
+        Pivot { input: i, agg, .. } => {
+            *i = input(i);
+            *agg = bind_expr(agg.clone(), values);
+        },
+        Cache { input: i, .. }
+        | MatchToSchema { input: i, .. }
+        | Distinct { input: i, .. }
+        | Slice { input: i, .. }
+        | MapFunction { input: i, .. }
+        | Sink { input: i, .. } => *i = input(i),
+        Union { inputs: i, .. } | HConcat { inputs: i, .. } | SinkMultiple { inputs: i } => {
+            *i = inputs(i)
+        },
+        PipeWithSchema { input: i, .. } => *i = inputs(i).into(),
+        ExtContext { input: i, contexts } => {
+            *i = input(i);
+            *contexts = inputs(contexts);
+        },
+        // sources (and nodes that are not planned from SQL)
+        _ => {},
+    }
+    plan
+}
+
+fn bind_expr(expr: Expr, values: &[AnyValue<'static>]) -> Expr {
+    expr.map_expr(|e| match e {
+        Expr::Alias(lit, name) if matches!(lit.as_ref(), Expr::Literal(_)) => {
+            match name
+                .strip_prefix(PARAM_PREFIX)
+                .and_then(|n| n.parse::<usize>().ok())
+            {
+                Some(n) => {
+                    let av = &values[n - 1];
+                    Expr::Literal(LiteralValue::Scalar(Scalar::new(av.dtype(), av.clone())))
+                        .alias(name)
+                },
+                None => Expr::Alias(lit, name),
+            }
+        },
+        Expr::SubPlan(plan, names) => {
+            Expr::SubPlan(SpecialEq::new(Arc::new(bind_plan(&plan, values))), names)
+        },
+        e => e,
+    })
+}
+
+/// Convert a value to an equivalent SQL literal, if one exists that round-trips exactly.
+fn any_value_to_sql_value(av: &AnyValue<'_>) -> Option<SQLValue> {
+    Some(match av {
+        AnyValue::Null => SQLValue::Null,
+        AnyValue::Boolean(b) => SQLValue::Boolean(*b),
+        AnyValue::Int8(_)
+        | AnyValue::Int16(_)
+        | AnyValue::Int32(_)
+        | AnyValue::Int64(_)
+        | AnyValue::UInt8(_)
+        | AnyValue::UInt16(_)
+        | AnyValue::UInt32(_) => SQLValue::Number(av.extract::<i64>()?.to_string(), false),
+        AnyValue::UInt64(v) if i64::try_from(*v).is_ok() => SQLValue::Number(v.to_string(), false),
+        AnyValue::String(s) => SQLValue::SingleQuotedString(s.to_string()),
+        AnyValue::StringOwned(s) => SQLValue::SingleQuotedString(s.to_string()),
+        _ => return None,
+    })
+}
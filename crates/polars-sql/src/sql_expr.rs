@@ -539,6 +539,16 @@ impl SQLExprVisitor<'_> {
         }
         .split(',');
 
+        // JSON strings are traversed with the equivalent JSONPath expression
+        #[cfg(feature = "json")]
+        if matches!(
+            resolve_expr_dtype(expr, self.active_schema),
+            Some(DataType::String)
+        ) {
+            let json_path = json_path_from_elems(path_elems, infer_index);
+            return Ok(expr.clone().str().json_path_match(lit(json_path)));
+        }
+
         let mut expr = expr.clone();
         for p in path_elems {
             let p = p.trim();
@@ -582,14 +592,21 @@ impl SQLExprVisitor<'_> {
                 return Ok(self
                     .visit_expr(left)?
                     .dt()
-                    .offset_by(lit(format!("-{duration}"))));
+                    .offset_by(lit((-duration).to_string())));
             },
             (_, SQLBinaryOperator::Plus, SQLExpr::Interval(v)) => {
                 let duration = interval_to_duration(v, false)?;
                 return Ok(self
                     .visit_expr(left)?
                     .dt()
-                    .offset_by(lit(format!("{duration}"))));
+                    .offset_by(lit(duration.to_string())));
+            },
+            (SQLExpr::Interval(v), SQLBinaryOperator::Plus, _) => {
+                let duration = interval_to_duration(v, false)?;
+                return Ok(self
+                    .visit_expr(right)?
+                    .dt()
+                    .offset_by(lit(duration.to_string())));
             },
             (SQLExpr::Interval(v1), _, SQLExpr::Interval(v2)) => {
                 // shortcut interval comparison evaluation (-> bool)
@@ -807,6 +824,7 @@ impl SQLExprVisitor<'_> {
             func: function,
             ctx: self.ctx,
             active_schema: self.active_schema,
+            agg_filter: None,
         };
         visitor.visit_function()
     }
@@ -916,6 +934,21 @@ impl SQLExprVisitor<'_> {
             return Ok(expr.str().json_decode(DataType::Struct(Vec::new())));
         }
         let polars_type = map_sql_dtype_to_polars(dtype)?;
+
+        // JSON strings are decoded to the target struct type (TRY_CAST and SAFE_CAST
+        // yield NULL for strings that cannot be decoded)
+        #[cfg(feature = "json")]
+        if matches!(polars_type, DataType::Struct(_))
+            && matches!(
+                resolve_expr_dtype(&expr, self.active_schema),
+                Some(DataType::String)
+            )
+        {
+            return Ok(match cast_kind {
+                CastKind::Cast | CastKind::DoubleColon => expr.str().json_decode(polars_type),
+                CastKind::TryCast | CastKind::SafeCast => try_json_decode(expr, polars_type),
+            });
+        }
         Ok(match cast_kind {
             CastKind::Cast | CastKind::DoubleColon => expr.strict_cast(polars_type),
             CastKind::TryCast | CastKind::SafeCast => expr.cast(polars_type),
@@ -957,6 +990,15 @@ impl SQLExprVisitor<'_> {
                 bitstring_to_bytes_literal(b)?
             },
             SQLValue::SingleQuotedString(s) => lit(s.clone()),
+            SQLValue::Placeholder(p) => {
+                let av = self.ctx.get_bound_param(p)?;
+                let expr = lit(Scalar::new(av.dtype(), av.clone()));
+                if self.ctx.param_markers {
+                    expr.alias(p.as_str())
+                } else {
+                    expr
+                }
+            },
             other => {
                 polars_bail!(SQLInterface: "value {:?} is not a supported literal type", other)
             },
@@ -1018,6 +1060,15 @@ impl SQLExprVisitor<'_> {
                 }
             },
             SQLValue::SingleQuotedString(s) => AnyValue::StringOwned(s.as_str().into()),
+            SQLValue::Placeholder(p) => match op {
+                _ if self.ctx.param_markers => {
+                    polars_bail!(SQLInterface: "bound parameter '{}' is used as a constant value", p)
+                },
+                Some(SQLUnaryOperator::Plus) | None => self.ctx.get_bound_param(p)?.clone(),
+                Some(op) => {
+                    polars_bail!(SQLInterface: "unary op {:?} not supported for bound parameter '{}'", op, p)
+                },
+            },
             other => polars_bail!(SQLInterface: "value {:?} is not currently supported", other),
         })
     }
@@ -1337,30 +1388,39 @@ pub fn sql_expr<S: AsRef<str>>(s: S) -> PolarsResult<Expr> {
 
 pub(crate) fn interval_to_duration(interval: &Interval, fixed: bool) -> PolarsResult<Duration> {
     if interval.last_field.is_some()
-        || interval.leading_field.is_some()
         || interval.leading_precision.is_some()
         || interval.fractional_seconds_precision.is_some()
     {
         polars_bail!(SQLSyntax: "unsupported interval syntax ('{}')", interval)
     }
-    let s = match &*interval.value {
-        SQLExpr::UnaryOp { .. } => {
+    let s = match (&*interval.value, &interval.leading_field) {
+        (SQLExpr::UnaryOp { .. }, _) => {
             polars_bail!(SQLSyntax: "unary ops are not valid on interval strings; found {}", interval.value)
         },
-        SQLExpr::Value(ValueWithSpan {
-            value: SQLValue::SingleQuotedString(s),
-            ..
-        }) => Some(s),
+        // eg: INTERVAL '1 day', INTERVAL '-2 hours'
+        (
+            SQLExpr::Value(ValueWithSpan {
+                value: SQLValue::SingleQuotedString(s),
+                ..
+            }),
+            None,
+        ) => Some(s.clone()),
+        // eg: INTERVAL '3' DAY, INTERVAL 3 DAY
+        (
+            SQLExpr::Value(ValueWithSpan {
+                value: SQLValue::SingleQuotedString(s) | SQLValue::Number(s, _),
+                ..
+            }),
+            Some(field),
+        ) => Some(format!("{} {}", s.trim(), field)),
         _ => None,
     };
     match s {
-        Some(s) if s.contains('-') => {
-            polars_bail!(SQLInterface: "minus signs are not yet supported in interval strings; found '{}'", s)
-        },
         Some(s) => {
+            let duration = Duration::try_parse_interval(&s)
+                .map_err(|err| polars_err!(SQLSyntax: "invalid interval '{}' ({})", s, err))?;
             // years, quarters, and months do not have a fixed duration; these
             // interval parts can only be used with respect to a reference point
-            let duration = Duration::parse_interval(s);
             if fixed && duration.months() != 0 {
                 polars_bail!(SQLSyntax: "fixed-duration interval cannot contain years, quarters, or months; found {}", s)
             };
@@ -1370,6 +1430,81 @@ pub(crate) fn interval_to_duration(interval: &Interval, fixed: bool) -> PolarsRe
     }
 }
 
+/// Resolve the dtype of the given expression against the (optional) schema.
+#[cfg(feature = "json")]
+pub(crate) fn resolve_expr_dtype(expr: &Expr, schema: Option<&Schema>) -> Option<DataType> {
+    schema
+        .and_then(|schema| expr.to_field(schema).ok())
+        .map(|fld| fld.dtype)
+}
+
+/// Build a JSONPath expression (eg: `$['a'][0]`) from the given path elements;
+/// if `infer_index` is set, integer elements are treated as array indices.
+#[cfg(feature = "json")]
+pub(crate) fn json_path_from_elems<'a>(
+    elems: impl IntoIterator<Item = &'a str>,
+    infer_index: bool,
+) -> String {
+    let mut json_path = String::from("$");
+    for elem in elems {
+        let elem = elem.trim();
+        match elem.parse::<i64>() {
+            Ok(idx) if infer_index => json_path.push_str(&format!("[{idx}]")),
+            _ => json_path.push_str(&format!("['{}']", elem.replace('\'', "\\'"))),
+        }
+    }
+    json_path
+}
+
+/// Decode JSON strings to the given dtype, yielding NULL for strings that cannot be decoded.
+#[cfg(feature = "json")]
+pub(crate) fn try_json_decode(expr: Expr, dtype: DataType) -> Expr {
+    use polars_ops::chunked_array::Utf8JsonPathImpl;
+
+    let output_dtype = dtype.clone();
+    expr.map_with_fmt_str(
+        move |c| {
+            let ca = c.str()?;
+            let decode = |ca: &StringChunked| ca.json_decode(Some(dtype.clone()), None);
+            let out = match decode(ca) {
+                Ok(s) => s,
+                // at least one string is invalid; decode them one at a time
+                Err(_) => {
+                    let mut out = Series::new_empty(PlSmallStr::EMPTY, &dtype);
+                    for opt_v in ca.iter() {
+                        let v = opt_v
+                            .and_then(|v| {
+                                decode(&StringChunked::from_slice(PlSmallStr::EMPTY, &[v])).ok()
+                            })
+                            .unwrap_or_else(|| Series::full_null(PlSmallStr::EMPTY, 1, &dtype));
+                        out.append(&v)?;
+                    }
+                    out
+                },
+            };
+            Ok(out.with_name(c.name().clone()).into_column())
+        },
+        move |_, fld| Ok(Field::new(fld.name().clone(), output_dtype.clone())),
+        "try_json_decode",
+    )
+}
+
+/// Encode each value as JSON text, eg: `1`, `"abc"`, `{"a":[1,2]}` or `null`.
+#[cfg(feature = "json")]
+pub(crate) fn json_encode(expr: Expr) -> Expr {
+    expr.map_with_fmt_str(
+        |c| {
+            let s = c.as_materialized_series();
+            let chunks = (0..s.n_chunks()).map(|i| {
+                polars_json::json::write::serialize_to_utf8(&*s.to_arrow(i, CompatLevel::newest()))
+            });
+            Ok(StringChunked::from_chunk_iter(s.name().clone(), chunks).into_column())
+        },
+        |_, fld| Ok(Field::new(fld.name().clone(), DataType::String)),
+        "json_encode",
+    )
+}
+
 pub(crate) fn parse_sql_expr(
     expr: &SQLExpr,
     ctx: &mut SQLContext,
@@ -9,6 +9,9 @@ use polars_core::prelude::*;
 use sqlparser::ast::{Expr as SQLExpr, ObjectName, Query, SetExpr, Visit, Visitor as SQLVisitor};
 use sqlparser::keywords::ALL_KEYWORDS;
 
+use crate::SQLContext;
+use crate::functions::PolarsSQLFunctions;
+
 // ---------------------------------------------------------------------------
 // FindTableIdentifier
 // ---------------------------------------------------------------------------
@@ -49,6 +52,32 @@ pub(crate) fn expr_refers_to_table(expr: &SQLExpr, table_name: &str) -> bool {
     table_finder.found
 }
 
+// ---------------------------------------------------------------------------
+// FirstExpression
+// ---------------------------------------------------------------------------
+
+/// Visitor that captures the first (outermost) expression found in an AST node.
+#[derive(Default)]
+struct FirstExpression {
+    expr: Option<SQLExpr>,
+}
+
+impl SQLVisitor for FirstExpression {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &SQLExpr) -> ControlFlow<Self::Break> {
+        self.expr = Some(expr.clone());
+        ControlFlow::Break(())
+    }
+}
+
+/// Get the first (outermost) expression contained in an AST node, if any.
+pub(crate) fn first_expr<V: Visit>(node: &V) -> Option<SQLExpr> {
+    let mut finder = FirstExpression::default();
+    let _ = node.visit(&mut finder);
+    finder.expr
+}
+
 // ---------------------------------------------------------------------------
 // QualifyExpression
 // ---------------------------------------------------------------------------
@@ -263,3 +292,35 @@ impl SQLVisitor for WindowFunctionFinder {
 pub(crate) fn expr_has_window_functions(expr: &SQLExpr) -> bool {
     expr.visit(&mut WindowFunctionFinder).is_break()
 }
+
+// ---------------------------------------------------------------------------
+// AggregateFunctionFinder
+// ---------------------------------------------------------------------------
+
+/// Visitor that checks if a SQL expression calls an aggregate function (that is
+/// not evaluated over a window).
+struct AggregateFunctionFinder<'a> {
+    ctx: &'a SQLContext,
+}
+
+impl SQLVisitor for AggregateFunctionFinder<'_> {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &SQLExpr) -> ControlFlow<()> {
+        match expr {
+            SQLExpr::Function(f)
+                if f.over.is_none()
+                    && PolarsSQLFunctions::try_from_sql(f, self.ctx)
+                        .is_ok_and(|func| func.is_aggregate()) =>
+            {
+                ControlFlow::Break(())
+            },
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+/// Check if a SQL expression contains aggregate function calls.
+pub(crate) fn expr_has_aggregates(expr: &SQLExpr, ctx: &SQLContext) -> bool {
+    expr.visit(&mut AggregateFunctionFinder { ctx }).is_break()
+}
@@ -20,6 +20,17 @@ use sqlparser::ast::{
 /// Table functions that are supported by Polars
 #[allow(clippy::enum_variant_names)]
 pub(crate) enum PolarsTableFunctions {
+    /// SQL 'generate_series' function (the end value is inclusive).
+    /// ```sql
+    /// SELECT * FROM generate_series(1, 10, 2)
+    /// SELECT * FROM generate_series(DATE '2024-01-01', DATE '2024-12-31', INTERVAL '1 day')
+    /// ```
+    GenerateSeries,
+    /// SQL 'range' function (the end value is exclusive).
+    /// ```sql
+    /// SELECT * FROM range(0, 10)
+    /// ```
+    Range,
     /// SQL 'read_csv' function.
     /// ```sql
     /// SELECT * FROM read_csv('path/to/file.csv')
@@ -52,6 +63,8 @@ impl FromStr for PolarsTableFunctions {
     #[allow(unreachable_code)]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s.to_lowercase().as_str() {
+            "generate_series" => PolarsTableFunctions::GenerateSeries,
+            "range" => PolarsTableFunctions::Range,
             #[cfg(feature = "csv")]
             "read_csv" => PolarsTableFunctions::ReadCsv,
             #[cfg(feature = "parquet")]
@@ -66,6 +79,8 @@ impl FromStr for PolarsTableFunctions {
 }
 
 impl PolarsTableFunctions {
+    /// Execute a file-reading table function; note that the series-generating
+    /// functions depend on expression parsing, so are handled by the `SQLContext`.
     #[allow(unused_variables, unreachable_patterns)]
     pub(crate) fn execute(&self, args: &[SQLFunctionArg]) -> PolarsResult<(PlRefPath, LazyFrame)> {
         match self {
@@ -144,6 +159,8 @@ impl PolarsTableFunctions {
     // list sql names of all table functions
     pub(crate) fn keywords() -> &'static [&'static str] {
         &[
+            "generate_series",
+            "range",
             #[cfg(feature = "csv")]
             "read_csv",
             #[cfg(feature = "parquet")]
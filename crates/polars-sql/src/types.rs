@@ -1,7 +1,7 @@
 //! This module supports mapping SQL datatypes to Polars datatypes.
 //!
 //! It also provides utility functions for working with SQL datatypes.
-use polars_core::datatypes::{DataType, TimeUnit};
+use polars_core::datatypes::{DataType, Field, TimeUnit};
 use polars_error::{PolarsResult, polars_bail};
 use polars_plan::dsl::Expr;
 use polars_plan::dsl::functions::lit;
@@ -193,6 +193,24 @@ pub(crate) fn map_sql_dtype_to_polars(dtype: &SQLDataType) -> PolarsResult<DataT
         | SQLDataType::Uuid
         | SQLDataType::Varchar(_) => DataType::String,
 
+        // ---------------------------------
+        // struct
+        // ---------------------------------
+        SQLDataType::Struct(fields, _) => DataType::Struct(
+            fields
+                .iter()
+                .map(|fld| match &fld.field_name {
+                    Some(Ident { value, .. }) => Ok(Field::new(
+                        value.as_str().into(),
+                        map_sql_dtype_to_polars(&fld.field_type)?,
+                    )),
+                    None => {
+                        polars_bail!(SQLSyntax: "STRUCT fields must be named; found {}", fld.field_type)
+                    },
+                })
+                .collect::<PolarsResult<Vec<_>>>()?,
+        ),
+
         // ---------------------------------
         // custom
         // ---------------------------------
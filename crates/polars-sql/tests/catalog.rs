@@ -0,0 +1,126 @@
+use std::sync::{Arc, Mutex};
+
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::catalog::CatalogProvider;
+use polars_sql::*;
+
+/// In-memory catalog that records the tables it was asked to resolve.
+#[derive(Default)]
+struct MemoryCatalog {
+    tables: PlHashMap<(String, String), DataFrame>,
+    requested: Mutex<Vec<String>>,
+}
+
+impl CatalogProvider for MemoryCatalog {
+    fn get_table(&self, schema_name: &str, table_name: &str) -> PolarsResult<Option<LazyFrame>> {
+        self.requested
+            .lock()
+            .unwrap()
+            .push(format!("{schema_name}.{table_name}"));
+        let key = (schema_name.to_string(), table_name.to_string());
+        Ok(self.tables.get(&key).map(|df| df.clone().lazy()))
+    }
+}
+
+fn create_catalog() -> Arc<MemoryCatalog> {
+    let mut catalog = MemoryCatalog::default();
+    catalog.tables.insert(
+        ("sales".into(), "orders".into()),
+        df! {
+            "id" => [1i64, 2, 3],
+            "customer_id" => [10i64, 20, 10],
+            "amount" => [5.0, 2.5, 10.0],
+        }
+        .unwrap(),
+    );
+    catalog.tables.insert(
+        ("crm".into(), "customers".into()),
+        df! {
+            "id" => [10i64, 20],
+            "name" => ["alice", "bob"],
+        }
+        .unwrap(),
+    );
+    Arc::new(catalog)
+}
+
+#[test]
+fn test_catalog_three_part_names() {
+    let catalog = create_catalog();
+    let mut ctx = SQLContext::new();
+    ctx.register_catalog("main", catalog.clone());
+    assert_eq!(ctx.get_catalogs(), ["main"]);
+
+    let df = ctx
+        .execute(
+            "
+            SELECT c.name, SUM(orders.amount) AS total
+            FROM main.sales.orders
+            JOIN main.crm.customers AS c ON orders.customer_id = c.id
+            GROUP BY c.name
+            ORDER BY c.name
+            ",
+        )
+        .unwrap()
+        .collect()
+        .unwrap();
+
+    let expected = df! {
+        "name" => ["alice", "bob"],
+        "total" => [15.0, 2.5],
+    }
+    .unwrap();
+    assert!(df.equals(&expected));
+
+    // tables are only resolved from the catalog when referenced
+    let mut requested = catalog.requested.lock().unwrap().clone();
+    requested.sort();
+    assert_eq!(requested, ["crm.customers", "sales.orders"]);
+}
+
+#[test]
+fn test_catalog_tables_are_not_registered() {
+    let mut ctx = SQLContext::new();
+    ctx.register_catalog("main", create_catalog());
+    ctx.register("orders", df! { "id" => [99i64] }.unwrap().lazy());
+
+    // the catalog table does not shadow (or replace) the registered table
+    let df = ctx
+        .execute(
+            "
+            SELECT o.id, orders.id AS local_id
+            FROM main.sales.orders AS o
+            CROSS JOIN orders
+            WHERE o.amount > 5
+            ",
+        )
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! { "id" => [3i64], "local_id" => [99i64] }.unwrap();
+    assert!(df.equals(&expected));
+    assert_eq!(ctx.get_tables(), ["orders"]);
+
+    let df = ctx
+        .execute("SELECT * FROM orders")
+        .unwrap()
+        .collect()
+        .unwrap();
+    assert!(df.equals(&df! { "id" => [99i64] }.unwrap()));
+}
+
+#[test]
+fn test_catalog_errors() {
+    let mut ctx = SQLContext::new();
+    ctx.register_catalog("main", create_catalog());
+
+    // unknown table in a registered catalog
+    let err = ctx.execute("SELECT * FROM main.sales.returns").unwrap_err();
+    assert!(err.to_string().contains("main.sales.returns"));
+
+    // unregistered catalog
+    ctx.unregister_catalog("main");
+    assert!(ctx.get_catalogs().is_empty());
+    assert!(ctx.execute("SELECT * FROM main.sales.orders").is_err());
+}
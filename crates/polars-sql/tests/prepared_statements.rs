@@ -0,0 +1,167 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df = df! {
+        "id" => [1i64, 2, 3, 4, 5],
+        "name" => ["a", "b", "c", "d", "e"],
+        "score" => [0.5, 1.5, 2.5, 3.5, 4.5],
+        "dt" => [0i32, 10, 20, 30, 40],
+    }
+    .unwrap()
+    .lazy()
+    .with_column(col("dt").cast(DataType::Date));
+
+    let ctx = SQLContext::new();
+    ctx.register("df", df);
+    ctx
+}
+
+fn ids(lf: LazyFrame) -> Vec<Option<i64>> {
+    let df = lf.collect().unwrap();
+    df.column("id")
+        .unwrap()
+        .i64()
+        .unwrap()
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn test_prepared_positional() {
+    let ctx = create_ctx();
+    let mut stmt = ctx
+        .prepare("SELECT id FROM df WHERE id > ? AND name <> ? ORDER BY id LIMIT ?")
+        .unwrap();
+    assert_eq!(stmt.param_count(), 3);
+
+    let res = stmt
+        .execute(&[
+            AnyValue::Int32(1),
+            AnyValue::String("c"),
+            AnyValue::Int64(2),
+        ])
+        .unwrap();
+    assert_eq!(ids(res), [Some(2), Some(4)]);
+
+    // re-execute the same statement with different values
+    let res = stmt
+        .execute(&[
+            AnyValue::Int32(3),
+            AnyValue::String("x"),
+            AnyValue::Int64(5),
+        ])
+        .unwrap();
+    assert_eq!(ids(res), [Some(4), Some(5)]);
+}
+
+#[test]
+fn test_prepared_plans_once() {
+    let ctx = create_ctx();
+    let mut stmt = ctx
+        .prepare("SELECT id, score * :factor AS scaled FROM df WHERE name <> :name ORDER BY id")
+        .unwrap();
+    let scaled = |lf: LazyFrame| -> Vec<Option<f64>> {
+        let df = lf.collect().unwrap();
+        df.column("scaled")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect()
+    };
+
+    let res = stmt
+        .execute_named(&[
+            ("factor", AnyValue::Float64(2.0)),
+            ("name", AnyValue::String("a")),
+        ])
+        .unwrap();
+    assert_eq!(scaled(res), [Some(3.0), Some(5.0), Some(7.0), Some(9.0)]);
+
+    // the table can no longer be resolved, so re-planning the statement would fail
+    ctx.unregister("df");
+    let res = stmt
+        .execute_named(&[
+            ("factor", AnyValue::Float64(-1.0)),
+            ("name", AnyValue::String("e")),
+        ])
+        .unwrap();
+    assert_eq!(
+        scaled(res),
+        [Some(-0.5), Some(-1.5), Some(-2.5), Some(-3.5)]
+    );
+
+    // parameters of other dtypes are planned anew
+    let res = stmt.execute_named(&[
+        ("factor", AnyValue::Int64(2)),
+        ("name", AnyValue::String("a")),
+    ]);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_prepared_numbered() {
+    let ctx = create_ctx();
+    let mut stmt = ctx
+        .prepare("SELECT id FROM df WHERE id BETWEEN $1 AND $2 OR id = -$1 + 6 ORDER BY id")
+        .unwrap();
+    assert_eq!(stmt.param_count(), 2);
+
+    let res = stmt
+        .execute(&[AnyValue::Int64(1), AnyValue::Int64(2)])
+        .unwrap();
+    assert_eq!(ids(res), [Some(1), Some(2), Some(5)]);
+}
+
+#[test]
+fn test_prepared_named_typed() {
+    let ctx = create_ctx();
+    let mut stmt = ctx
+        .prepare(
+            "SELECT id FROM df WHERE score >= :min_score AND dt < :max_dt AND id IN (:id, 5) ORDER BY id",
+        )
+        .unwrap();
+    let names: Vec<_> = stmt.param_names().iter().map(|s| s.as_str()).collect();
+    assert_eq!(names, ["min_score", "max_dt", "id"]);
+
+    let res = stmt
+        .execute_named(&[
+            ("max_dt", AnyValue::Date(35)),
+            ("min_score", AnyValue::Float64(1.0)),
+            ("id", AnyValue::Int8(4)),
+        ])
+        .unwrap();
+    assert_eq!(ids(res), [Some(4)]);
+}
+
+#[test]
+fn test_prepared_errors() {
+    let ctx = create_ctx();
+
+    // mixed placeholder styles
+    assert!(
+        ctx.prepare("SELECT * FROM df WHERE id = ? OR id = $2")
+            .is_err()
+    );
+
+    // wrong number of positional parameters
+    let mut stmt = ctx.prepare("SELECT * FROM df WHERE id = $1").unwrap();
+    assert!(stmt.execute(&[]).is_err());
+    assert!(
+        stmt.execute(&[AnyValue::Int64(1), AnyValue::Int64(2)])
+            .is_err()
+    );
+    assert!(stmt.execute_named(&[("id", AnyValue::Int64(1))]).is_err());
+
+    // missing/unknown named parameters
+    let mut stmt = ctx.prepare("SELECT * FROM df WHERE id = :id").unwrap();
+    assert!(stmt.execute(&[AnyValue::Int64(1)]).is_err());
+    assert!(stmt.execute_named(&[]).is_err());
+    assert!(stmt.execute_named(&[("idx", AnyValue::Int64(1))]).is_err());
+
+    // placeholders cannot be executed without binding values
+    let mut ctx = create_ctx();
+    assert!(ctx.execute("SELECT * FROM df WHERE score > $1").is_err());
+}
@@ -31,6 +31,8 @@ pub struct NodeMetrics {
     pub io_total_bytes_requested: u64,
     pub io_total_bytes_received: u64,
     pub io_total_bytes_sent: u64,
+    pub io_range_cache_hits: u64,
+    pub io_range_cache_misses: u64,
 
     pub state_update_in_progress: bool,
     pub num_running_tasks: u32,
@@ -53,6 +55,8 @@ impl NodeMetrics {
         self.io_total_bytes_requested += io_metrics.bytes_requested.load();
         self.io_total_bytes_received += io_metrics.bytes_received.load();
         self.io_total_bytes_sent += io_metrics.bytes_sent.load();
+        self.io_range_cache_hits += io_metrics.range_cache_hits.load();
+        self.io_range_cache_misses += io_metrics.range_cache_misses.load();
     }
 
     fn reset_io_metrics(&mut self) {
@@ -60,6 +64,8 @@ impl NodeMetrics {
         self.io_total_bytes_requested = 0;
         self.io_total_bytes_received = 0;
         self.io_total_bytes_sent = 0;
+        self.io_range_cache_hits = 0;
+        self.io_range_cache_misses = 0;
     }
 
     fn start_state_update(&mut self) {
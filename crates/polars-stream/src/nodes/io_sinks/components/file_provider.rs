@@ -3,6 +3,8 @@ use std::sync::Arc;
 use polars_error::{PolarsResult, polars_ensure};
 use polars_io::cloud::CloudOptions;
 use polars_io::metrics::IOMetrics;
+#[cfg(feature = "hf_upload")]
+use polars_io::path_utils::HfCommitBatch;
 use polars_io::pl_async;
 use polars_io::utils::file::Writeable;
 use polars_plan::dsl::file_provider::{FileProviderReturn, FileProviderType};
@@ -20,6 +22,9 @@ pub struct FileProvider {
     pub upload_max_concurrency: usize,
     pub io_metrics: Option<Arc<IOMetrics>>,
     pub sinked_path_info_list: Option<SinkedPathInfoList>,
+    /// Set when sinking to a Hugging Face repository, where all files are added in one commit.
+    #[cfg(feature = "hf_upload")]
+    pub hf_commit_batch: Option<Arc<HfCommitBatch>>,
 }
 
 impl FileProvider {
@@ -28,6 +33,7 @@ impl FileProvider {
             let provided_writeable = match &self.provider_type {
                 FileProviderType::Hive(p) => break 'provided_path p.get_path(args)?,
                 FileProviderType::Iceberg(p) => break 'provided_path p.get_path(args)?,
+                FileProviderType::Delta(p) => break 'provided_path p.get_path(args)?,
                 FileProviderType::Function(f) => {
                     let f = f.clone();
 
@@ -87,6 +93,11 @@ impl FileProvider {
                 .push(SinkedPathInfo { path: path.clone() });
         }
 
+        #[cfg(feature = "hf_upload")]
+        if let Some(batch) = &self.hf_commit_batch {
+            return Ok(Writeable::Dyn(Box::new(batch.open_file(&path)?)));
+        }
+
         Writeable::try_new(
             path,
             self.cloud_options.as_deref(),
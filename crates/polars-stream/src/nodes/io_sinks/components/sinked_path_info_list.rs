@@ -38,7 +38,7 @@ impl SinkedPathInfoList {
     pub fn non_path_error(&self) -> PolarsError {
         polars_err!(
             ComputeError:
-            "paths callback was set but encountered non-path sink target"
+            "paths callback or atomic commit was set but encountered non-path sink target"
         )
     }
 }
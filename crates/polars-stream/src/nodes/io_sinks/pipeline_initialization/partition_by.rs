@@ -3,7 +3,10 @@ use std::sync::Arc;
 
 use polars_error::PolarsResult;
 use polars_io::metrics::IOMetrics;
+#[cfg(feature = "hf_upload")]
+use polars_io::path_utils::HfCommitBatch;
 use polars_plan::dsl::UnifiedSinkArgs;
+use polars_utils::pl_path::CloudScheme;
 use polars_utils::pl_str::PlSmallStr;
 
 use crate::async_executor::{self, TaskPriority};
@@ -79,18 +82,32 @@ pub fn start_partition_sink_pipeline(
         write!(file_part_prefix, "{uuid}").unwrap();
     }
 
-    let sinked_path_info_list: Option<SinkedPathInfoList> = sinked_paths_callback
-        .is_some()
+    // With atomic commits, a manifest listing the committed files is written at the end. Files sunk
+    // to Hugging Face are already added in a single commit.
+    let write_commit_manifest = cloud_options
+        .as_ref()
+        .is_some_and(|o| o.atomic_commit.is_some())
+        && base_path.scheme() != Some(CloudScheme::Hf);
+
+    let sinked_path_info_list: Option<SinkedPathInfoList> = (sinked_paths_callback.is_some()
+        || write_commit_manifest)
         .then(SinkedPathInfoList::default);
 
+    #[cfg(feature = "hf_upload")]
+    let hf_commit_batch = (base_path.scheme() == Some(CloudScheme::Hf))
+        .then(|| HfCommitBatch::new(&base_path, cloud_options.as_deref()).map(Arc::new))
+        .transpose()?;
+
     let file_provider = Arc::new(FileProvider {
-        base_path,
-        cloud_options,
+        base_path: base_path.clone(),
+        cloud_options: cloud_options.clone(),
         provider_type: file_path_provider,
         upload_chunk_size,
         upload_max_concurrency: upload_max_concurrency.get(),
         io_metrics,
         sinked_path_info_list: sinked_path_info_list.clone(),
+        #[cfg(feature = "hf_upload")]
+        hf_commit_batch: hf_commit_batch.clone(),
     });
 
     let file_writer_starter: Arc<dyn FileWriterStarter> =
@@ -195,6 +212,37 @@ pub fn start_partition_sink_pipeline(
             partitioner_handle.await;
             partition_distributor_handle.await?;
 
+            #[cfg(feature = "hf_upload")]
+            if let Some(batch) = hf_commit_batch {
+                if verbose {
+                    eprintln!("{node_name}: Commit files to Hugging Face repository");
+                }
+
+                batch.commit(None).await?;
+            }
+
+            if write_commit_manifest {
+                let paths = sinked_path_info_list
+                    .as_ref()
+                    .unwrap()
+                    .path_info_list
+                    .lock()
+                    .iter()
+                    .map(|info| info.path.clone())
+                    .collect::<Vec<_>>();
+
+                let manifest_path = polars_io::cloud::cloud_writer::write_commit_manifest(
+                    &base_path,
+                    &paths,
+                    cloud_options.as_deref(),
+                )
+                .await?;
+
+                if verbose {
+                    eprintln!("{node_name}: Wrote commit manifest: {manifest_path}");
+                }
+            }
+
             if let Some(sinked_paths_callback) = sinked_paths_callback {
                 if verbose {
                     eprintln!("{node_name}: Call sinked path info callback");
@@ -10,6 +10,7 @@ use polars_io::RowIndex;
 use polars_io::predicates::ScanIOPredicate;
 use polars_plan::dsl::{CastColumnsPolicy, MissingColumnsPolicy, ScanSource};
 use polars_plan::plans::hive::HivePartitionsDf;
+use polars_utils::pl_str::PlSmallStr;
 use polars_utils::row_counter::RowCounter;
 use polars_utils::slice_enum::Slice;
 
@@ -17,7 +18,9 @@ use crate::nodes::io_sources::multi_scan::components::column_selector::ColumnSel
 use crate::nodes::io_sources::multi_scan::components::column_selector::builder::ColumnSelectorBuilder;
 use crate::nodes::io_sources::multi_scan::components::errors::missing_column_err;
 use crate::nodes::io_sources::multi_scan::components::projection::Projection;
-use crate::nodes::io_sources::multi_scan::components::row_deletions::ExternalFilterMask;
+use crate::nodes::io_sources::multi_scan::components::row_deletions::{
+    EqualityDeleteFilter, ExternalFilterMask, IcebergEqualityDeletes,
+};
 use crate::nodes::io_sources::multi_scan::pipeline::models::ExtraOperations;
 
 /// Apply extra operations onto morsels originating from a reader. This should be initialized
@@ -39,6 +42,8 @@ pub enum ApplyExtraOps {
         hive_parts: Option<Arc<HivePartitionsDf>>,
         /// E.g. Iceberg deletion files.
         external_filter_mask: Option<ExternalFilterMask>,
+        /// `(_, column_names)`, see [`IcebergEqualityDeletes::build_filter`].
+        equality_deletes: Option<(Arc<IcebergEqualityDeletes>, Vec<Option<PlSmallStr>>)>,
     },
 
     /// Note: These fields are ordered according to the order in which they are applied.
@@ -46,6 +51,7 @@ pub enum ApplyExtraOps {
         /// Physical - i.e. applied before `external_filter_mask`. This is calculated in `initialize()` if needed.
         physical_pre_slice: Option<Slice>,
         external_filter_mask: Option<ExternalFilterMask>,
+        equality_delete_filter: Option<EqualityDeleteFilter>,
         /// `(_, insertion_position)`
         row_index: Option<(RowIndex, usize)>,
         /// This will have include_file_paths, hive columns, missing columns.
@@ -94,6 +100,7 @@ impl ApplyExtraOps {
                 scan_source_idx,
                 hive_parts,
                 external_filter_mask,
+                equality_deletes,
             } => {
                 // Negative slice should have been resolved earlier.
                 if let Some(Slice::Negative { .. }) = pre_slice {
@@ -202,9 +209,16 @@ impl ApplyExtraOps {
                     Some(column_selectors)
                 };
 
+                let equality_delete_filter = equality_deletes
+                    .map(|(deletes, column_names)| {
+                        deletes.build_filter(incoming_schema, &column_names)
+                    })
+                    .transpose()?;
+
                 let out = Self::Initialized {
                     physical_pre_slice: pre_slice,
                     external_filter_mask,
+                    equality_delete_filter,
                     row_index: row_index.map(|ri| (ri, row_index_col_idx)),
                     column_selectors,
                     predicate,
@@ -216,6 +230,7 @@ impl ApplyExtraOps {
                     Initialized {
                         physical_pre_slice: None,
                         external_filter_mask: None,
+                        equality_delete_filter: None,
                         row_index: None,
                         column_selectors: None,
                         predicate: None,
@@ -242,6 +257,7 @@ impl ApplyExtraOps {
         let Self::Initialized {
             physical_pre_slice,
             external_filter_mask,
+            equality_delete_filter,
             row_index,
             column_selectors,
             predicate,
@@ -291,6 +307,12 @@ impl ApplyExtraOps {
             local_filter_mask.filter_df(df)?;
         };
 
+        // The rows deleted by equality deletes are only known at this point, so the slice and row
+        // index are never pushed to scans with equality deletes.
+        if let Some(equality_delete_filter) = equality_delete_filter {
+            equality_delete_filter.filter_df(df)?;
+        }
+
         if let Some(column_selectors) = column_selectors.as_deref() {
             let new_cols = column_selectors
                 .iter()
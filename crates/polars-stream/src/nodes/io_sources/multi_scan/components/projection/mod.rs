@@ -195,6 +195,46 @@ impl Projection {
         }
     }
 
+    /// Appends columns that are read from the file as-is, i.e. without renaming or casting.
+    pub fn with_extra_file_columns(self, columns: Schema) -> Projection {
+        if columns.is_empty() {
+            return self;
+        }
+
+        match self {
+            Projection::Plain(projected_schema) => {
+                let mut projected_schema = Arc::unwrap_or_clone(projected_schema);
+                projected_schema.extend(columns);
+                Projection::Plain(Arc::new(projected_schema))
+            },
+
+            Projection::Mapped {
+                projected_schema,
+                mapping,
+                missing_columns_mask,
+                missing_column_defaults,
+            } => {
+                let n_extra = columns.len();
+                let mut projected_schema = Arc::unwrap_or_clone(projected_schema);
+                projected_schema.extend(columns);
+
+                // The mapping is keyed by index, appended columns do not shift existing entries.
+                let missing_columns_mask = missing_columns_mask.map(|mask| {
+                    mask.iter()
+                        .chain(std::iter::repeat_n(false, n_extra))
+                        .collect::<Bitmap>()
+                });
+
+                Projection::Mapped {
+                    projected_schema: Arc::new(projected_schema),
+                    mapping,
+                    missing_columns_mask,
+                    missing_column_defaults,
+                }
+            },
+        }
+    }
+
     /// Removes all column mapping information from this projection.
     pub fn clear_projection_transforms(&mut self) {
         match self {
@@ -7,15 +7,24 @@ use arrow::bitmap::bitmask::BitMask;
 use arrow::bitmap::{Bitmap, MutableBitmap};
 use polars_buffer::Buffer;
 use polars_core::frame::DataFrame;
-use polars_core::prelude::{BooleanChunked, ChunkAgg, DataType, NamedFrom, PlIndexMap};
+use polars_core::prelude::row_encode::encode_rows_unordered;
+use polars_core::prelude::{BooleanChunked, ChunkAgg, Column, DataType, NamedFrom, PlHashSet};
+#[cfg(feature = "parquet")]
+use polars_core::schema::iceberg::IcebergSchema;
 use polars_core::schema::{Schema, SchemaRef};
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+#[cfg(feature = "parquet")]
+use polars_core::utils::accumulate_dataframes_vertical_unchecked_optional;
 use polars_error::{PolarsResult, feature_gated, polars_bail, polars_err};
 use polars_io::cloud::CloudOptions;
 use polars_io::pl_async;
 use polars_plan::dsl::deletion::DeletionFilesList;
 #[cfg(feature = "python")]
 use polars_plan::dsl::deletion::DeltaDeletionVectorProvider;
+#[cfg(feature = "delta")]
+use polars_plan::dsl::deletion::{DeltaDeletionVectorDescriptor, DeltaDeletionVectors};
+#[cfg(feature = "parquet")]
+use polars_plan::dsl::deletion::{IcebergDeletionFiles, IcebergEqualityDeleteFile};
 use polars_plan::dsl::{CastColumnsPolicy, ScanSource, ScanSources};
 use polars_utils::format_pl_smallstr;
 use polars_utils::pl_path::PlRefPath;
@@ -25,6 +34,8 @@ use polars_utils::slice_enum::Slice;
 
 use crate::async_executor::{self, AbortOnDropHandle, TaskPriority};
 use crate::metrics::IOMetrics;
+#[cfg(feature = "parquet")]
+use crate::nodes::io_sources::multi_scan::reader_interface::FileReader;
 use crate::nodes::io_sources::multi_scan::reader_interface::builder::FileReaderBuilder;
 use crate::nodes::io_sources::multi_scan::reader_interface::{BeginReadArgs, FileReaderCallbacks};
 #[cfg(feature = "parquet")]
@@ -35,12 +46,14 @@ pub enum DeletionFilesProvider {
     None,
 
     #[cfg(feature = "parquet")]
-    IcebergPositionDelete {
-        paths: Arc<PlIndexMap<usize, Arc<[String]>>>,
+    IcebergDelete {
+        files: Arc<IcebergDeletionFiles>,
         // Amortized allocations
         reader_builder: ParquetReaderBuilder,
         projected_schema: SchemaRef,
     },
+    #[cfg(feature = "delta")]
+    DeltaDeletionVectors(Arc<DeltaDeletionVectors>),
     #[cfg(feature = "python")]
     DeltaDeletionVector {
         provider: DeltaDeletionVectorProvider,
@@ -57,36 +70,16 @@ impl DeletionFilesProvider {
         io_metrics: Option<Arc<IOMetrics>>,
     ) -> PolarsResult<Self> {
         match deletion_files {
-            Some(DeletionFilesList::IcebergPositionDelete(paths)) => feature_gated!("parquet", {
-                let reader_builder = ParquetReaderBuilder {
-                    first_metadata: None,
-                    options: Arc::new(polars_io::prelude::ParquetOptions {
-                        schema: Some(Arc::new(Schema::from_iter([
-                            (PlSmallStr::from_static("file_path"), DataType::String),
-                            (PlSmallStr::from_static("pos"), DataType::Int64),
-                        ]))),
-
-                        parallel: polars_io::prelude::ParallelStrategy::Auto,
-                        low_memory: false,
-                        use_statistics: false,
-                    }),
-                    prefetch_limit: RelaxedCell::new_usize(0),
-                    prefetch_semaphore: std::sync::OnceLock::new(),
-                    shared_prefetch_wait_group_slot: Default::default(),
-                    io_metrics: io_metrics.map(OnceLock::from).unwrap_or_default(),
-                };
-
-                reader_builder.set_execution_state(execution_state);
-
-                Ok(Self::IcebergPositionDelete {
-                    paths,
-                    reader_builder,
-                    projected_schema: Arc::new(Schema::from_iter([
-                        (PlSmallStr::from_static("file_path"), DataType::String),
-                        (PlSmallStr::from_static("pos"), DataType::Int64),
-                    ])),
+            Some(DeletionFilesList::IcebergDelete(files)) => feature_gated!("parquet", {
+                Ok(Self::IcebergDelete {
+                    files,
+                    reader_builder: iceberg_reader_builder(execution_state, io_metrics),
+                    projected_schema: position_delete_schema(),
                 })
             }),
+            Some(DeletionFilesList::DeltaDeletionVectors(dvs)) => {
+                feature_gated!("delta", Ok(Self::DeltaDeletionVectors(dvs)))
+            },
             #[cfg(feature = "python")]
             Some(DeletionFilesList::Delta(provider)) => {
                 let ScanSources::Paths(selected_paths) = selected_sources else {
@@ -103,6 +96,43 @@ impl DeletionFilesProvider {
         }
     }
 
+    /// Spawns a task to load the equality delete files of a data file. The deleted rows are
+    /// filtered while the data file is read, see [`IcebergEqualityDeletes`].
+    pub fn spawn_equality_deletes_init(
+        &self,
+        scan_source_idx: usize,
+        cloud_options: Option<Arc<CloudOptions>>,
+        num_pipelines: usize,
+        verbose: bool,
+    ) -> Option<EqualityDeletesInit> {
+        match self {
+            #[cfg(feature = "parquet")]
+            Self::IcebergDelete {
+                files,
+                reader_builder,
+                projected_schema: _,
+            } => {
+                let delete_files = files.equality_deletes.get(&scan_source_idx)?;
+
+                let equality_deletes = load_iceberg_equality_deletes(
+                    delete_files.clone(),
+                    reader_builder,
+                    scan_source_idx,
+                    cloud_options,
+                    num_pipelines,
+                    verbose,
+                );
+
+                Some(AbortOnDropHandle::new(async_executor::spawn(
+                    TaskPriority::Low,
+                    async move { equality_deletes.await.map(Arc::new) },
+                )))
+            },
+
+            _ => None,
+        }
+    }
+
     pub fn spawn_row_deletions_init(
         &self,
         scan_source_idx: usize,
@@ -114,53 +144,22 @@ impl DeletionFilesProvider {
             Self::None => None,
 
             #[cfg(feature = "parquet")]
-            Self::IcebergPositionDelete {
-                paths,
+            Self::IcebergDelete {
+                files,
                 reader_builder,
                 projected_schema,
             } => {
-                let paths = paths.get(&scan_source_idx)?;
-
-                if verbose {
-                    let s = if paths.len() == 1 { "" } else { "s" };
-                    eprintln!(
-                        "[DeletionFilesProvider[Iceberg]]: scan_source_idx: {}, {} file{s}",
-                        scan_source_idx,
-                        paths.len(),
-                    )
-                }
-
-                // We create the readers and immediately spawn off tasks to initialize all of them.
-                let file_readers = paths
-                    .iter()
-                    .enumerate()
-                    .map(|(deletion_file_idx, path)| {
-                        let source = ScanSource::Path(PlRefPath::new(path));
-                        let mut reader = reader_builder.build_file_reader(
-                            source,
-                            cloud_options.clone(),
-                            deletion_file_idx,
-                        );
+                let paths = files.position_deletes.get(&scan_source_idx)?;
 
-                        if verbose {
-                            eprintln!(
-                                "[DeletionFilesProvider[Iceberg]]: scan_source_idx: {scan_source_idx}, \
-                                deletion_file_idx: {deletion_file_idx}, \
-                                deletion_file_path: {path}"
-                            )
-                        }
-
-                        AbortOnDropHandle::new(async_executor::spawn(
-                            TaskPriority::Low,
-                            async move {
-                                reader.initialize().await?;
-                                PolarsResult::Ok(reader)
-                            },
-                        ))
-                    })
-                    .collect::<Vec<_>>();
-
-                let projected_schema = projected_schema.clone();
+                let position_deletes = load_iceberg_position_deletes(
+                    paths.clone(),
+                    reader_builder,
+                    projected_schema.clone(),
+                    scan_source_idx,
+                    cloud_options,
+                    num_pipelines,
+                    verbose,
+                );
 
                 // We choose to load deletion files immediately during the initialization phase -
                 // the main driver loop of the multi file may need to serially `.await` on this
@@ -168,116 +167,47 @@ impl DeletionFilesProvider {
                 //
                 // This does mean deletion file loads are tied to `NUM_READERS_PRE_INIT`, but this
                 // should be fine as the size of the data should not be too big.
-                let handle = AbortOnDropHandle::new(async_executor::spawn(
-                    TaskPriority::Low,
-                    async move {
-                        let handles = file_readers
-                            .into_iter()
-                            .map(|init_fut| {
-                                use crate::nodes::io_sources::multi_scan::components::projection::Projection;
-
-                                let begin_read_args = BeginReadArgs {
-                                    projection: Projection::Plain(projected_schema.clone()),
-                                    row_index: None,
-                                    pre_slice: None,
-                                    predicate: None,
-                                    cast_columns_policy: CastColumnsPolicy::ERROR_ON_MISMATCH,
-                                    num_pipelines,
-                                    disable_morsel_split: false,
-                                    callbacks: FileReaderCallbacks {
-                                        file_schema_tx: None,
-                                        n_rows_in_file_tx: None,
-                                        row_position_on_end_tx: None,
-                                    },
-                                };
-
-                                AbortOnDropHandle::new(async_executor::spawn(
-                                    TaskPriority::Low,
-                                    async move {
-                                        let mut reader = init_fut.await?;
-
-                                        let (mut rx, handle) =
-                                            reader.begin_read(begin_read_args)?;
-
-                                        let mut dfs = vec![];
-
-                                        while let Ok(morsel) = rx.recv().await {
-                                            dfs.push(morsel.into_df());
-                                        }
-
-                                        handle.await?;
-
-                                        let df = accumulate_dataframes_vertical_unchecked(dfs);
-
-                                        // Some quick testing on AWS Athena showed that it doesn't
-                                        // write deletion files that reference multiple distinct
-                                        // file paths, so we don't handle that for now.
-                                        assert!(
-                                            df.column("file_path")?.n_unique()? <= 1,
-                                            "assertion failed: iceberg position delete file: \
-                                            n_unique(data_file_paths) <= 1. \
-                                            This is a bug, please open an issue"
-                                        );
-
-                                        let positions_col = df.column("pos")?.clone();
-                                        let max_idx = usize::try_from(
-                                            positions_col
-                                                .as_materialized_series_maintain_scalar()
-                                                .i64()
-                                                .unwrap()
-                                                .max()
-                                                .unwrap_or(0),
-                                        )
-                                        .unwrap();
-
-                                        PolarsResult::Ok((positions_col, max_idx))
-                                    },
-                                ))
-                            })
-                            .collect::<Vec<_>>();
-
-                        let mut position_columns = Vec::with_capacity(handles.len());
-                        let mut filter_mask_len: usize = 0;
-
-                        for handle in handles {
-                            let (positions_col, max_idx) = handle.await?;
-                            filter_mask_len = filter_mask_len.max(max_idx.saturating_add(1));
-                            position_columns.push(positions_col);
-                        }
-
-                        let mut filter_mask = MutableBitmap::from_len_set(filter_mask_len);
+                let handle =
+                    AbortOnDropHandle::new(async_executor::spawn(TaskPriority::Low, async move {
+                        let filter_mask = position_deletes.await?;
+                        Ok(iceberg_filter_mask(filter_mask, scan_source_idx, verbose))
+                    }));
 
-                        for c in position_columns {
-                            for idx in c.as_materialized_series_maintain_scalar().i64().unwrap() {
-                                let idx = usize::try_from(idx.unwrap()).unwrap();
-                                filter_mask.set(idx, false);
-                            }
-                        }
+                Some(RowDeletionsInit::Initializing(handle))
+            },
 
-                        let bitmap = filter_mask.freeze();
+            #[cfg(feature = "delta")]
+            Self::DeltaDeletionVectors(dvs) => {
+                let descriptor = dvs.descriptors.get(&scan_source_idx)?.clone();
+                let table_uri = dvs.table_uri.clone();
 
-                        // Also trigger the bitcount to reduce blocking later down.
-                        bitmap.unset_bits();
-                        debug_assert!(bitmap.lazy_unset_bits().is_some());
+                let handle =
+                    AbortOnDropHandle::new(async_executor::spawn(TaskPriority::Low, async move {
+                        let positions = pl_async::get_runtime()
+                            .spawn(async move {
+                                load_delta_deletion_vector(
+                                    &descriptor,
+                                    &table_uri,
+                                    cloud_options.as_deref(),
+                                )
+                                .await
+                            })
+                            .await
+                            .unwrap()?;
 
-                        let mask = BooleanChunked::from_bitmap(PlSmallStr::EMPTY, bitmap);
-                        let mask = ExternalFilterMask::IcebergPositionDelete { mask };
+                        let mask = delta_deletion_vector_mask(&positions)?;
 
                         if verbose {
-                            let num_deleted_rows = mask.num_deleted_rows();
-                            let max_index = mask.len().checked_sub(1);
-
                             eprintln!(
-                                "[DeletionFilesProvider[Iceberg]]: \
+                                "[DeletionFilesProvider[DeltaDeletionVectors]]: \
                                 scan_source_idx: {scan_source_idx}, \
-                                num_deleted_rows: {num_deleted_rows}, \
-                                max_index: {max_index:?}",
-                            )
+                                num_deleted_rows: {}",
+                                positions.len()
+                            );
                         }
 
-                        Ok(mask)
-                    },
-                ));
+                        Ok(ExternalFilterMask::DeltaDeletionVector { mask })
+                    }));
 
                 Some(RowDeletionsInit::Initializing(handle))
             },
@@ -337,6 +267,510 @@ impl DeletionFilesProvider {
     }
 }
 
+#[cfg(feature = "delta")]
+async fn load_delta_deletion_vector(
+    descriptor: &DeltaDeletionVectorDescriptor,
+    table_uri: &str,
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<Vec<u64>> {
+    let DeltaDeletionVectorDescriptor {
+        storage_type,
+        path_or_inline_dv,
+        offset,
+        size_in_bytes,
+        cardinality,
+    } = descriptor.clone();
+
+    polars_io::delta::actions::DeletionVectorDescriptor {
+        storage_type,
+        path_or_inline_dv,
+        offset,
+        size_in_bytes,
+        cardinality,
+    }
+    .load(table_uri, cloud_options)
+    .await
+}
+
+/// Builds a selection mask from the deleted row positions. The mask ends at the highest deleted
+/// row, rows past the end of the mask are kept.
+#[cfg(feature = "delta")]
+fn delta_deletion_vector_mask(positions: &[u64]) -> PolarsResult<BooleanChunked> {
+    let len = match positions.iter().max() {
+        Some(max) => usize::try_from(*max)
+            .ok()
+            .and_then(|v| v.checked_add(1))
+            .ok_or_else(|| polars_err!(ComputeError: "deletion vector position out of range"))?,
+        None => 0,
+    };
+    let mut mask = MutableBitmap::from_len_set(len);
+
+    for position in positions {
+        mask.set(*position as usize, false);
+    }
+
+    Ok(BooleanChunked::from_bitmap(
+        PlSmallStr::EMPTY,
+        mask.freeze(),
+    ))
+}
+
+#[cfg(feature = "parquet")]
+fn position_delete_schema() -> SchemaRef {
+    Arc::new(Schema::from_iter([
+        (PlSmallStr::from_static("file_path"), DataType::String),
+        (PlSmallStr::from_static("pos"), DataType::Int64),
+    ]))
+}
+
+#[cfg(feature = "parquet")]
+fn iceberg_reader_builder(
+    execution_state: &crate::execute::StreamingExecutionState,
+    io_metrics: Option<Arc<IOMetrics>>,
+) -> ParquetReaderBuilder {
+    let reader_builder = ParquetReaderBuilder {
+        first_metadata: None,
+        options: Arc::new(polars_io::prelude::ParquetOptions {
+            schema: None,
+            parallel: polars_io::prelude::ParallelStrategy::Auto,
+            low_memory: false,
+            use_statistics: false,
+        }),
+        prefetch_limit: RelaxedCell::new_usize(0),
+        prefetch_semaphore: std::sync::OnceLock::new(),
+        prefetch_tuner: std::sync::OnceLock::new(),
+        shared_prefetch_wait_group_slot: Default::default(),
+        io_metrics: io_metrics.map(OnceLock::from).unwrap_or_default(),
+    };
+
+    reader_builder.set_execution_state(execution_state);
+
+    reader_builder
+}
+
+/// Reads all rows of the `projected_schema` columns from an initialized reader.
+#[cfg(feature = "parquet")]
+async fn read_to_df(
+    reader: &mut dyn FileReader,
+    projected_schema: SchemaRef,
+    num_pipelines: usize,
+) -> PolarsResult<DataFrame> {
+    use crate::nodes::io_sources::multi_scan::components::projection::Projection;
+
+    let begin_read_args = BeginReadArgs {
+        projection: Projection::Plain(projected_schema.clone()),
+        row_index: None,
+        pre_slice: None,
+        predicate: None,
+        cast_columns_policy: CastColumnsPolicy::ERROR_ON_MISMATCH,
+        num_pipelines,
+        disable_morsel_split: false,
+        callbacks: FileReaderCallbacks {
+            file_schema_tx: None,
+            n_rows_in_file_tx: None,
+            row_position_on_end_tx: None,
+        },
+    };
+
+    let (mut rx, handle) = reader.begin_read(begin_read_args)?;
+
+    let mut dfs = vec![];
+
+    while let Ok(morsel) = rx.recv().await {
+        dfs.push(morsel.into_df());
+    }
+
+    handle.await?;
+
+    Ok(accumulate_dataframes_vertical_unchecked_optional(dfs)
+        .unwrap_or_else(|| DataFrame::empty_with_arc_schema(projected_schema)))
+}
+
+/// Spawns tasks to load the position delete files of a data file, returning a future that
+/// resolves to the filter mask (true = keep).
+#[cfg(feature = "parquet")]
+fn load_iceberg_position_deletes(
+    paths: Arc<[String]>,
+    reader_builder: &ParquetReaderBuilder,
+    projected_schema: SchemaRef,
+    scan_source_idx: usize,
+    cloud_options: Option<Arc<CloudOptions>>,
+    num_pipelines: usize,
+    verbose: bool,
+) -> impl Future<Output = PolarsResult<MutableBitmap>> + Send + 'static {
+    if verbose {
+        let s = if paths.len() == 1 { "" } else { "s" };
+        eprintln!(
+            "[DeletionFilesProvider[Iceberg]]: scan_source_idx: {}, {} file{s}",
+            scan_source_idx,
+            paths.len(),
+        )
+    }
+
+    // We create the readers and immediately spawn off tasks to initialize and load all of them.
+    let handles = paths
+        .iter()
+        .enumerate()
+        .map(|(deletion_file_idx, path)| {
+            let source = ScanSource::Path(PlRefPath::new(path));
+            let mut reader =
+                reader_builder.build_file_reader(source, cloud_options.clone(), deletion_file_idx);
+
+            if verbose {
+                eprintln!(
+                    "[DeletionFilesProvider[Iceberg]]: scan_source_idx: {scan_source_idx}, \
+                    deletion_file_idx: {deletion_file_idx}, \
+                    deletion_file_path: {path}"
+                )
+            }
+
+            let projected_schema = projected_schema.clone();
+
+            AbortOnDropHandle::new(async_executor::spawn(TaskPriority::Low, async move {
+                reader.initialize().await?;
+                let df = read_to_df(reader.as_mut(), projected_schema, num_pipelines).await?;
+
+                // Some quick testing on AWS Athena showed that it doesn't write deletion files
+                // that reference multiple distinct file paths, so we don't handle that for now.
+                assert!(
+                    df.column("file_path")?.n_unique()? <= 1,
+                    "assertion failed: iceberg position delete file: \
+                    n_unique(data_file_paths) <= 1. \
+                    This is a bug, please open an issue"
+                );
+
+                let positions_col = df.column("pos")?.clone();
+                let max_idx = usize::try_from(
+                    positions_col
+                        .as_materialized_series_maintain_scalar()
+                        .i64()
+                        .unwrap()
+                        .max()
+                        .unwrap_or(0),
+                )
+                .unwrap();
+
+                PolarsResult::Ok((positions_col, max_idx))
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    async move {
+        let mut position_columns = Vec::with_capacity(handles.len());
+        let mut filter_mask_len: usize = 0;
+
+        for handle in handles {
+            let (positions_col, max_idx) = handle.await?;
+            filter_mask_len = filter_mask_len.max(max_idx.saturating_add(1));
+            position_columns.push(positions_col);
+        }
+
+        let mut filter_mask = MutableBitmap::from_len_set(filter_mask_len);
+
+        for c in position_columns {
+            for idx in c.as_materialized_series_maintain_scalar().i64().unwrap() {
+                let idx = usize::try_from(idx.unwrap()).unwrap();
+                filter_mask.set(idx, false);
+            }
+        }
+
+        Ok(filter_mask)
+    }
+}
+
+/// Spawns tasks to load the equality delete files of a data file, returning a future that
+/// resolves to the rows of all files.
+#[cfg(feature = "parquet")]
+fn load_iceberg_equality_deletes(
+    delete_files: Arc<[IcebergEqualityDeleteFile]>,
+    reader_builder: &ParquetReaderBuilder,
+    scan_source_idx: usize,
+    cloud_options: Option<Arc<CloudOptions>>,
+    num_pipelines: usize,
+    verbose: bool,
+) -> impl Future<Output = PolarsResult<IcebergEqualityDeletes>> + Send + 'static {
+    if verbose {
+        let s = if delete_files.len() == 1 { "" } else { "s" };
+        eprintln!(
+            "[DeletionFilesProvider[Iceberg]]: scan_source_idx: {}, {} equality delete file{s}",
+            scan_source_idx,
+            delete_files.len(),
+        )
+    }
+
+    let handles = delete_files
+        .iter()
+        .enumerate()
+        .map(|(deletion_file_idx, file)| {
+            let source = ScanSource::Path(PlRefPath::new(&file.path));
+            let mut reader =
+                reader_builder.build_file_reader(source, cloud_options.clone(), deletion_file_idx);
+
+            if verbose {
+                eprintln!(
+                    "[DeletionFilesProvider[Iceberg]]: scan_source_idx: {scan_source_idx}, \
+                    deletion_file_idx: {deletion_file_idx}, \
+                    deletion_file_path: {}, \
+                    equality_ids: {:?}",
+                    file.path, file.equality_ids,
+                )
+            }
+
+            let equality_ids = file.equality_ids.clone();
+
+            AbortOnDropHandle::new(async_executor::spawn(TaskPriority::Low, async move {
+                reader.initialize().await?;
+                let columns =
+                    read_iceberg_columns(reader.as_mut(), &equality_ids, num_pipelines).await?;
+
+                PolarsResult::Ok((equality_ids, columns))
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    let mut field_ids = delete_files
+        .iter()
+        .flat_map(|file| file.equality_ids.iter().copied())
+        .collect::<Vec<_>>();
+    field_ids.sort_unstable();
+    field_ids.dedup();
+
+    async move {
+        let mut files = Vec::with_capacity(handles.len());
+
+        for handle in handles {
+            files.push(handle.await?);
+        }
+
+        Ok(IcebergEqualityDeletes {
+            field_ids: field_ids.into(),
+            files,
+        })
+    }
+}
+
+/// Reads the columns with the given Iceberg field IDs, returning `None` for columns that are not
+/// in the file.
+#[cfg(feature = "parquet")]
+async fn read_iceberg_columns(
+    reader: &mut dyn FileReader,
+    field_ids: &[u32],
+    num_pipelines: usize,
+) -> PolarsResult<Vec<Option<Column>>> {
+    let Some(arrow_schema) = reader.file_arrow_schema().await? else {
+        polars_bail!(ComputeError: "iceberg deletes require a file with an arrow schema")
+    };
+    let iceberg_schema = IcebergSchema::from_arrow_schema(&arrow_schema)?;
+    let file_schema = reader.file_schema().await?;
+
+    let names = field_ids
+        .iter()
+        .map(|id| match iceberg_schema.get(id) {
+            Some(col) if col.type_.is_nested() => polars_bail!(
+                ComputeError:
+                "iceberg equality deletes on nested column '{}' are not supported",
+                col.name
+            ),
+            Some(col) => Ok(Some(col.name.clone())),
+            // The column was added to the table after the file was written.
+            None => Ok(None),
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    let projected_schema = names
+        .iter()
+        .flatten()
+        .map(|name| {
+            let dtype = file_schema.try_get(name)?.clone();
+            PolarsResult::Ok((name.clone(), dtype))
+        })
+        .collect::<PolarsResult<Schema>>()?;
+
+    let df = if projected_schema.is_empty() {
+        DataFrame::empty()
+    } else {
+        read_to_df(reader, Arc::new(projected_schema), num_pipelines).await?
+    };
+
+    names
+        .iter()
+        .map(|name| {
+            name.as_ref()
+                .map(|name| df.column(name).cloned())
+                .transpose()
+        })
+        .collect()
+}
+
+/// Handle to the task loading the equality delete files of a data file.
+pub type EqualityDeletesInit = AbortOnDropHandle<PolarsResult<Arc<IcebergEqualityDeletes>>>;
+
+/// The rows of the Iceberg equality delete files of a data file.
+///
+/// A row of the data file is deleted if its values in the equality columns of a delete file match
+/// a row of that file. Columns are matched by Iceberg field ID, and nulls compare equal. The
+/// equality columns are read along with the data file, see [`EqualityDeleteFilter`].
+#[derive(Debug)]
+pub struct IcebergEqualityDeletes {
+    /// Iceberg field IDs of the equality columns of all files, sorted.
+    pub field_ids: Arc<[u32]>,
+    /// `(equality_ids, columns)` of each delete file. Columns missing from a file are `None`.
+    files: Vec<(Arc<[u32]>, Vec<Option<Column>>)>,
+}
+
+impl IcebergEqualityDeletes {
+    /// Builds the filter for the morsels of a data file.
+    ///
+    /// `column_names` holds the name in the morsels of the column of each of `self.field_ids`, or
+    /// `None` if the data file does not have the column. Such columns are treated as all-null.
+    pub fn build_filter(
+        &self,
+        incoming_schema: &Schema,
+        column_names: &[Option<PlSmallStr>],
+    ) -> PolarsResult<EqualityDeleteFilter> {
+        assert_eq!(column_names.len(), self.field_ids.len());
+
+        let mut files = Vec::with_capacity(self.files.len());
+
+        for (equality_ids, delete_columns) in &self.files {
+            let Some(delete_height) = delete_columns.iter().flatten().map(|c| c.len()).next()
+            else {
+                // The delete file has none of the columns, so there is nothing to match against.
+                continue;
+            };
+
+            if delete_height == 0 {
+                continue;
+            }
+
+            let mut columns = Vec::with_capacity(equality_ids.len());
+            let mut dtypes = Vec::with_capacity(equality_ids.len());
+            let mut encode_columns = Vec::with_capacity(equality_ids.len());
+
+            for (i, (id, delete)) in equality_ids.iter().zip(delete_columns).enumerate() {
+                let name = format_pl_smallstr!("_{i}");
+                let data = column_names[self.field_ids.binary_search(id).unwrap()]
+                    .as_ref()
+                    .map(|name| {
+                        let (idx, _, dtype) = incoming_schema.try_get_full(name)?;
+                        PolarsResult::Ok((idx, dtype))
+                    })
+                    .transpose()?;
+
+                let delete = match (data, delete) {
+                    (Some((_, dtype)), Some(delete)) => delete.cast(dtype)?,
+                    (Some((_, dtype)), None) => Column::full_null(name, delete_height, dtype),
+                    (None, Some(delete)) => delete.clone(),
+                    (None, None) => Column::full_null(name, delete_height, &DataType::Null),
+                };
+
+                columns.push(data.map(|(idx, _)| idx));
+                dtypes.push(delete.dtype().clone());
+                encode_columns.push(delete);
+            }
+
+            let delete_rows = encode_rows_unordered(&encode_columns)?
+                .iter()
+                .flatten()
+                .map(Box::from)
+                .collect();
+
+            files.push(EqualityDeleteFileFilter {
+                columns,
+                dtypes,
+                delete_rows,
+            });
+        }
+
+        Ok(EqualityDeleteFilter { files })
+    }
+}
+
+/// Removes the rows matched by [`IcebergEqualityDeletes`] from the morsels of a data file.
+#[derive(Debug)]
+pub struct EqualityDeleteFilter {
+    files: Vec<EqualityDeleteFileFilter>,
+}
+
+#[derive(Debug)]
+struct EqualityDeleteFileFilter {
+    /// Position of each equality column in the morsels, `None` if the data file does not have it.
+    columns: Vec<Option<usize>>,
+    dtypes: Vec<DataType>,
+    /// Row-encoded values of the rows of the delete file.
+    delete_rows: PlHashSet<Box<[u8]>>,
+}
+
+impl EqualityDeleteFilter {
+    pub fn filter_df(&self, df: &mut DataFrame) -> PolarsResult<()> {
+        let height = df.height();
+
+        if height == 0 || self.files.is_empty() {
+            return Ok(());
+        }
+
+        let mut filter_mask = MutableBitmap::from_len_set(height);
+
+        for file in &self.files {
+            let data_columns = file
+                .columns
+                .iter()
+                .zip(&file.dtypes)
+                .enumerate()
+                .map(|(i, (idx, dtype))| match idx {
+                    Some(idx) => df.columns()[*idx].clone(),
+                    None => Column::full_null(format_pl_smallstr!("_{i}"), height, dtype),
+                })
+                .collect::<Vec<_>>();
+
+            let data_rows = encode_rows_unordered(&data_columns)?;
+
+            for (idx, row) in data_rows.iter().enumerate() {
+                if row.is_some_and(|row| file.delete_rows.contains(row)) {
+                    filter_mask.set(idx, false);
+                }
+            }
+        }
+
+        if filter_mask.unset_bits() > 0 {
+            let mask = BooleanChunked::from_bitmap(PlSmallStr::EMPTY, filter_mask.freeze());
+            *df = df.filter_seq(&mask)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn iceberg_filter_mask(
+    filter_mask: MutableBitmap,
+    scan_source_idx: usize,
+    verbose: bool,
+) -> ExternalFilterMask {
+    let bitmap = filter_mask.freeze();
+
+    // Also trigger the bitcount to reduce blocking later down.
+    bitmap.unset_bits();
+    debug_assert!(bitmap.lazy_unset_bits().is_some());
+
+    let mask = BooleanChunked::from_bitmap(PlSmallStr::EMPTY, bitmap);
+    let mask = ExternalFilterMask::IcebergPositionDelete { mask };
+
+    if verbose {
+        let num_deleted_rows = mask.num_deleted_rows();
+        let max_index = mask.len().checked_sub(1);
+
+        eprintln!(
+            "[DeletionFilesProvider[Iceberg]]: \
+            scan_source_idx: {scan_source_idx}, \
+            num_deleted_rows: {num_deleted_rows}, \
+            max_index: {max_index:?}",
+        )
+    }
+
+    mask
+}
+
 pub enum RowDeletionsInit {
     Initializing(AbortOnDropHandle<PolarsResult<ExternalFilterMask>>),
 
@@ -672,4 +1106,69 @@ mod tests {
             assert_eq!(slice, Slice::Positive { offset: 0, len: 53 });
         }
     }
+
+    #[test]
+    fn test_equality_delete_filter() {
+        use std::sync::Arc;
+
+        use polars_core::df;
+        use polars_core::prelude::*;
+
+        use super::IcebergEqualityDeletes;
+
+        let df = df!(
+            "a" => [1i64, 2, 3, 2],
+            "b" => [Some("x"), None, Some("y"), Some("z")],
+        )
+        .unwrap();
+
+        let filter = |files: Vec<(Arc<[u32]>, Vec<Option<Column>>)>,
+                      column_names: &[Option<PlSmallStr>]| {
+            let deletes = IcebergEqualityDeletes {
+                field_ids: Arc::from([1, 2, 3]),
+                files,
+            };
+            let mut df = df.clone();
+            deletes
+                .build_filter(df.schema(), column_names)
+                .unwrap()
+                .filter_df(&mut df)
+                .unwrap();
+            df.column("a").unwrap().i64().unwrap().to_vec()
+        };
+        let names: [Option<PlSmallStr>; 3] = [Some("a".into()), Some("b".into()), None];
+
+        // Nulls compare equal and delete values are cast to the data dtype.
+        let files = vec![(
+            Arc::from([1, 2]),
+            vec![
+                Some(Column::new("a".into(), &[2i32])),
+                Some(Column::new("b".into(), &[None::<&str>])),
+            ],
+        )];
+        assert_eq!(filter(files, &names), [Some(1), Some(3), Some(2)]);
+
+        // Columns missing from the delete file only match nulls.
+        let files = vec![(
+            Arc::from([1, 2]),
+            vec![Some(Column::new("a".into(), &[2i64, 3])), None],
+        )];
+        assert_eq!(filter(files, &names), [Some(1), Some(3), Some(2)]);
+
+        // Columns missing from the data file are all-null, and the files are applied in turn.
+        let files = vec![
+            (
+                Arc::from([3]),
+                vec![Some(Column::new("c".into(), &[None::<i64>]))],
+            ),
+            (Arc::from([1]), vec![Some(Column::new("a".into(), &[3i64]))]),
+        ];
+        assert!(filter(files, &names).is_empty());
+
+        let files = vec![
+            (Arc::from([3]), vec![Some(Column::new("c".into(), &[5i64]))]),
+            (Arc::from([1]), vec![Some(Column::new("a".into(), &[3i64]))]),
+        ];
+        assert_eq!(filter(files, &names), [Some(1), Some(2), Some(2)]);
+    }
 }
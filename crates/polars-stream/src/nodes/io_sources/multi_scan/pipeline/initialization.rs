@@ -187,6 +187,16 @@ async fn finish_initialize_multi_scan_pipeline(
         );
     }
 
+    // The rows deleted by equality deletes are only known after reading, so the slice and row
+    // index are applied after the scan.
+    if config
+        .deletion_files
+        .as_ref()
+        .is_some_and(|x| x.has_equality_deletes())
+    {
+        assert!(config.pre_slice.is_none() && config.row_index.is_none());
+    }
+
     let ResolvedSliceInfo {
         scan_source_idx,
         row_index,
@@ -352,6 +362,13 @@ async fn finish_initialize_multi_scan_pipeline(
                 let scan_source = sources.get(scan_source_idx).unwrap().into_owned();
 
                 AbortOnDropHandle::new(async_executor::spawn(TaskPriority::Low, async move {
+                    let equality_deletes = deletion_files_provider.spawn_equality_deletes_init(
+                        scan_source_idx,
+                        cloud_options.clone(),
+                        num_pipelines,
+                        verbose,
+                    );
+
                     let (scan_source, reader, n_rows_in_file) = async {
                         if verbose {
                             eprintln!("[MultiScan]: Initialize source {scan_source_idx}");
@@ -397,6 +414,7 @@ async fn finish_initialize_multi_scan_pipeline(
                         reader,
                         n_rows_in_file,
                         row_deletions,
+                        equality_deletes,
                     })
                 }))
             })
@@ -2,7 +2,7 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
-use components::row_deletions::ExternalFilterMask;
+use components::row_deletions::{EqualityDeletesInit, ExternalFilterMask};
 use polars_core::prelude::PlHashMap;
 use polars_core::schema::SchemaRef;
 use polars_error::PolarsResult;
@@ -95,6 +95,7 @@ pub(super) struct StartReaderArgsPerFile {
     pub(super) extra_ops_this_file: ExtraOperations,
     pub(super) callbacks: FileReaderCallbacks,
     pub(super) external_filter_mask: Option<ExternalFilterMask>,
+    pub(super) equality_deletes: Option<EqualityDeletesInit>,
 }
 
 /// State for a reader that has been started.
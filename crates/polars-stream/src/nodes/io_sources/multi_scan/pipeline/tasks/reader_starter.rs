@@ -1,17 +1,19 @@
 use std::sync::Arc;
 
 use components::bridge::BridgeRecvPort;
-use components::row_deletions::{ExternalFilterMask, RowDeletionsInit};
+use components::row_deletions::{EqualityDeletesInit, ExternalFilterMask, RowDeletionsInit};
 use futures::StreamExt;
 use futures::stream::BoxStream;
 use polars_core::config::verbose_print_sensitive;
 use polars_core::prelude::{AnyValue, DataType};
 use polars_core::scalar::Scalar;
+use polars_core::schema::Schema;
 use polars_core::schema::iceberg::IcebergSchema;
-use polars_error::{PolarsResult, polars_ensure};
+use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err};
 use polars_mem_engine::scan_predicate::skip_files_mask::SkipFilesMask;
 use polars_plan::dsl::{MissingColumnsPolicy, ScanSource};
 use polars_utils::IdxSize;
+use polars_utils::pl_str::PlSmallStr;
 use polars_utils::row_counter::RowCounter;
 use polars_utils::slice_enum::Slice;
 
@@ -55,6 +57,7 @@ pub struct InitializedReaderState {
     pub reader: Box<dyn FileReader>,
     pub n_rows_in_file: Option<RowCounter>,
     pub row_deletions: Option<RowDeletionsInit>,
+    pub equality_deletes: Option<EqualityDeletesInit>,
 }
 
 impl ReaderStarter {
@@ -117,6 +120,7 @@ impl ReaderStarter {
                 mut reader,
                 mut n_rows_in_file,
                 row_deletions,
+                equality_deletes,
             }) = readers_init_iter.next().await.transpose()?
             else {
                 if verbose {
@@ -335,6 +339,7 @@ impl ReaderStarter {
                 extra_ops_this_file,
                 callbacks,
                 external_filter_mask: external_filter_mask.clone(),
+                equality_deletes,
             };
 
             let reader_start_task_handle = AbortOnDropHandle::new(async_executor::spawn(
@@ -430,6 +435,7 @@ async fn start_reader_impl(
         extra_ops_this_file,
         mut callbacks,
         external_filter_mask,
+        equality_deletes,
     } = args_this_file;
 
     let file_iceberg_schema: Option<IcebergSchema> =
@@ -520,6 +526,58 @@ async fn start_reader_impl(
         !(callbacks.row_position_on_end_tx.is_some() && callbacks.n_rows_in_file_tx.is_some()),
     );
 
+    // The equality columns are read along with the projection, and dropped by the column
+    // selectors after the deleted rows are filtered out.
+    let equality_deletes = if let Some(equality_deletes) = equality_deletes {
+        let deletes = equality_deletes.await?;
+
+        let file_iceberg_schema = file_iceberg_schema.as_ref().ok_or_else(
+            || polars_err!(ComputeError: "iceberg equality deletes require an iceberg scan"),
+        )?;
+
+        let mut extra_columns = Schema::default();
+        let mut column_names: Vec<Option<PlSmallStr>> = Vec::with_capacity(deletes.field_ids.len());
+
+        for field_id in deletes.field_ids.iter() {
+            let Some(col) = file_iceberg_schema.get(field_id) else {
+                column_names.push(None);
+                continue;
+            };
+
+            if col.type_.is_nested() {
+                polars_bail!(
+                    ComputeError:
+                    "iceberg equality deletes on nested column '{}' are not supported",
+                    col.name
+                )
+            }
+
+            if let Some(projected) = projection_to_reader
+                .iter_non_missing_columns()
+                .find(|x| x.source_name == &col.name)
+            {
+                column_names.push(Some(projected.output_name.clone()));
+                continue;
+            }
+
+            polars_ensure!(
+                !projection_to_reader.projected_schema().contains(&col.name),
+                ComputeError:
+                "iceberg equality delete column '{}' conflicts with a projected column",
+                col.name
+            );
+
+            extra_columns.insert(col.name.clone(), col.type_.to_polars_dtype());
+            column_names.push(Some(col.name.clone()));
+        }
+
+        projection_to_reader = projection_to_reader.with_extra_file_columns(extra_columns);
+
+        Some((deletes, column_names))
+    } else {
+        None
+    };
+
     if let Some(predicate) = predicate.as_mut() {
         assert!(!projection_to_post.has_projection_transforms());
 
@@ -660,6 +718,7 @@ async fn start_reader_impl(
             scan_source_idx,
             hive_parts,
             external_filter_mask,
+            equality_deletes,
         }
         .initialize(first_morsel.df().schema())?
     } else {
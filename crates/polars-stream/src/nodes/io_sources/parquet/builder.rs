@@ -4,7 +4,7 @@ use std::sync::Arc;
 use polars_core::config;
 use polars_io::cloud::CloudOptions;
 use polars_io::prelude::{FileMetadata, ParallelStrategy, ParquetOptions};
-use polars_io::utils::byte_source::DynByteSourceBuilder;
+use polars_io::utils::byte_source::{DynByteSourceBuilder, PrefetchTuner};
 use polars_plan::dsl::ScanSource;
 use polars_utils::relaxed_cell::RelaxedCell;
 
@@ -20,6 +20,9 @@ pub struct ParquetReaderBuilder {
     pub options: Arc<ParquetOptions>,
     pub prefetch_limit: RelaxedCell<usize>,
     pub prefetch_semaphore: std::sync::OnceLock<Arc<tokio::sync::Semaphore>>,
+    /// Adapts the prefetch depth to the observed IO performance. `None` if the prefetch size was
+    /// configured explicitly.
+    pub prefetch_tuner: std::sync::OnceLock<Option<Arc<PrefetchTuner>>>,
     pub shared_prefetch_wait_group_slot: Arc<std::sync::Mutex<Option<WaitGroup>>>,
     pub io_metrics: std::sync::OnceLock<Arc<IOMetrics>>,
 }
@@ -58,14 +61,17 @@ impl FileReaderBuilder for ParquetReaderBuilder {
     }
 
     fn set_execution_state(&self, execution_state: &crate::execute::StreamingExecutionState) {
-        let prefetch_limit = std::env::var("POLARS_ROW_GROUP_PREFETCH_SIZE")
+        let env_prefetch_limit = std::env::var("POLARS_ROW_GROUP_PREFETCH_SIZE")
+            .ok()
             .map(|x| {
                 x.parse::<NonZeroUsize>()
                     .unwrap_or_else(|_| {
                         panic!("invalid value for POLARS_ROW_GROUP_PREFETCH_SIZE: {x}")
                     })
                     .get()
-            })
+            });
+
+        let prefetch_limit = env_prefetch_limit
             .unwrap_or(
                 execution_state
                     .num_pipelines
@@ -83,9 +89,19 @@ impl FileReaderBuilder for ParquetReaderBuilder {
             );
         }
 
-        self.prefetch_semaphore
-            .set(Arc::new(tokio::sync::Semaphore::new(prefetch_limit)))
-            .unwrap()
+        let prefetch_semaphore = Arc::new(tokio::sync::Semaphore::new(prefetch_limit));
+
+        // Starts at `prefetch_limit`, and may prefetch deeper for high-latency stores.
+        let prefetch_tuner = env_prefetch_limit.is_none().then(|| {
+            Arc::new(PrefetchTuner::new(
+                Arc::clone(&prefetch_semaphore),
+                prefetch_limit,
+                prefetch_limit.saturating_mul(4).clamp(prefetch_limit, 512),
+            ))
+        });
+
+        self.prefetch_semaphore.set(prefetch_semaphore).unwrap();
+        self.prefetch_tuner.set(prefetch_tuner).unwrap();
     }
 
     fn set_io_metrics(&self, io_metrics: Arc<IOMetrics>) {
@@ -113,6 +129,15 @@ impl FileReaderBuilder for ParquetReaderBuilder {
 
         assert!(self.prefetch_limit.load() > 0);
 
+        let prefetch_tuner = self.prefetch_tuner.get().unwrap().clone();
+
+        // The prefetch tuner adapts to the observed request latencies, so IO metrics are always
+        // recorded while it is enabled.
+        let io_metrics = match &prefetch_tuner {
+            Some(_) => Some(Arc::clone(self.io_metrics.get_or_init(Default::default))),
+            None => self.io_metrics.get().cloned(),
+        };
+
         let reader = ParquetFileReader {
             scan_source,
             cloud_options,
@@ -126,11 +151,12 @@ impl FileReaderBuilder for ParquetReaderBuilder {
             row_group_prefetch_sync: RowGroupPrefetchSync {
                 prefetch_limit: self.prefetch_limit.load(),
                 prefetch_semaphore: Arc::clone(self.prefetch_semaphore.get().unwrap()),
+                prefetch_tuner,
                 shared_prefetch_wait_group_slot: Arc::clone(&self.shared_prefetch_wait_group_slot),
                 prev_all_spawned: None,
                 current_all_spawned: None,
             },
-            io_metrics: OptIOMetrics(self.io_metrics.get().cloned()),
+            io_metrics: OptIOMetrics(io_metrics),
             verbose,
 
             init_data: None,
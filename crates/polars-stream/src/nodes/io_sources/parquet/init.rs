@@ -62,6 +62,7 @@ impl ParquetReadImpl {
         let row_index = self.row_index.clone();
 
         let rg_prefetch_semaphore = Arc::clone(&self.rg_prefetch_semaphore);
+        let rg_prefetch_tuner = self.rg_prefetch_tuner.clone();
         let rg_prefetch_prev_all_spawned = Option::take(&mut self.rg_prefetch_prev_all_spawned);
         let rg_prefetch_current_all_spawned =
             Option::take(&mut self.rg_prefetch_current_all_spawned);
@@ -152,6 +153,10 @@ impl ParquetReadImpl {
             loop {
                 let fetch_permit = rg_prefetch_semaphore.clone().acquire_owned().await.unwrap();
 
+                if let Some((tuner, io_metrics)) = &rg_prefetch_tuner {
+                    tuner.tune(io_metrics);
+                }
+
                 let Some(prefetch) = row_group_data_fetcher.next().await else {
                     break;
                 };
@@ -8,7 +8,9 @@ use polars_error::{PolarsResult, polars_err};
 use polars_io::cloud::CloudOptions;
 use polars_io::predicates::ScanIOPredicate;
 use polars_io::prelude::{FileMetadata, ParquetOptions};
-use polars_io::utils::byte_source::{BufferByteSource, DynByteSource, DynByteSourceBuilder};
+use polars_io::utils::byte_source::{
+    BufferByteSource, DynByteSource, DynByteSourceBuilder, PrefetchTuner,
+};
 use polars_io::{RowIndex, pl_async};
 use polars_parquet::read::schema::infer_schema_with_options;
 use polars_plan::dsl::ScanSource;
@@ -22,7 +24,7 @@ use super::multi_scan::reader_interface::{
 };
 use crate::async_executor::{self};
 use crate::async_primitives::wait_group::{WaitGroup, WaitToken};
-use crate::metrics::OptIOMetrics;
+use crate::metrics::{IOMetrics, OptIOMetrics};
 use crate::morsel::SourceToken;
 use crate::nodes::compute_node_prelude::*;
 use crate::nodes::io_sources::parquet::projection::{
@@ -57,6 +59,7 @@ pub struct ParquetFileReader {
 struct RowGroupPrefetchSync {
     prefetch_limit: usize,
     prefetch_semaphore: Arc<tokio::sync::Semaphore>,
+    prefetch_tuner: Option<Arc<PrefetchTuner>>,
     shared_prefetch_wait_group_slot: Arc<std::sync::Mutex<Option<WaitGroup>>>,
 
     /// Waits for the previous reader to finish spawning prefetches.
@@ -307,9 +310,13 @@ impl FileReader for ParquetFileReader {
         // Prepare parameters for dispatch
         let projected_arrow_fields = projected_arrow_fields()?.clone();
         let memory_prefetch_func = get_memory_prefetch_func(verbose);
+        // The prefetch tuner may have raised the number of prefetch permits above the limit.
         let row_group_prefetch_size = self
             .row_group_prefetch_sync
-            .prefetch_limit
+            .prefetch_tuner
+            .as_ref()
+            .map_or(0, |x| x.depth())
+            .max(self.row_group_prefetch_sync.prefetch_limit)
             .min(file_metadata.row_groups.len())
             .max(1);
 
@@ -342,6 +349,11 @@ impl FileReader for ParquetFileReader {
             memory_prefetch_func,
             row_index,
             rg_prefetch_semaphore: Arc::clone(&self.row_group_prefetch_sync.prefetch_semaphore),
+            rg_prefetch_tuner: self
+                .row_group_prefetch_sync
+                .prefetch_tuner
+                .clone()
+                .zip(self.io_metrics.0.clone()),
             rg_prefetch_prev_all_spawned: Option::take(
                 &mut self.row_group_prefetch_sync.prev_all_spawned,
             ),
@@ -435,6 +447,7 @@ struct ParquetReadImpl {
     row_index: Option<RowIndex>,
 
     rg_prefetch_semaphore: Arc<tokio::sync::Semaphore>,
+    rg_prefetch_tuner: Option<(Arc<PrefetchTuner>, Arc<IOMetrics>)>,
     rg_prefetch_prev_all_spawned: Option<WaitGroup>,
     rg_prefetch_current_all_spawned: Option<WaitToken>,
     disable_morsel_split: bool,
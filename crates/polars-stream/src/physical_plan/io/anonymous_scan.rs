@@ -0,0 +1,83 @@
+use std::sync::{Arc, Mutex};
+
+use polars_core::config;
+use polars_core::schema::SchemaRef;
+use polars_plan::plans::{AnonymousScan, AnonymousScanArgs, AnonymousScanBatchFn};
+use polars_utils::pl_str::PlSmallStr;
+
+use crate::execute::StreamingExecutionState;
+use crate::nodes::io_sources::batch::builder::BatchFnReaderBuilder;
+use crate::nodes::io_sources::batch::{BatchFnReader, GetBatchFn, GetBatchState};
+use crate::nodes::io_sources::multi_scan::reader_interface::builder::FileReaderBuilder;
+
+enum AnonymousScanState {
+    Init,
+    Batches(AnonymousScanBatchFn),
+    Finished,
+}
+
+/// Reads an [`AnonymousScan`] in batches if it provides an [`AnonymousScan::batch_fn`], otherwise
+/// the output of [`AnonymousScan::scan`] is read as a single batch.
+///
+/// The full file schema is requested from the scan, projections, slices and predicates are applied
+/// afterwards by the multi-scan.
+pub fn anonymous_scan_to_reader_builder(
+    function: Arc<dyn AnonymousScan>,
+    file_schema: SchemaRef,
+) -> Arc<dyn FileReaderBuilder> {
+    let name = PlSmallStr::from_static("anonymous_scan");
+
+    let output_schema = file_schema.clone();
+    let scan_args = move || AnonymousScanArgs {
+        n_rows: None,
+        with_columns: None,
+        schema: file_schema.clone(),
+        output_schema: None,
+        predicate: None,
+    };
+
+    let state = Mutex::new(AnonymousScanState::Init);
+
+    let get_batch_fn = Box::new(move |_state: &StreamingExecutionState| {
+        let mut state = state.lock().unwrap();
+
+        // Only start the scan once the first batch is requested.
+        if let AnonymousScanState::Init = &*state {
+            match function.batch_fn(scan_args())? {
+                Some(batch_fn) => *state = AnonymousScanState::Batches(batch_fn),
+                None => {
+                    *state = AnonymousScanState::Finished;
+                    return function.scan(scan_args()).map(Some);
+                },
+            }
+        }
+
+        match &mut *state {
+            AnonymousScanState::Init => unreachable!(),
+            AnonymousScanState::Batches(batch_fn) => {
+                let out = batch_fn()?;
+
+                if out.is_none() {
+                    *state = AnonymousScanState::Finished;
+                }
+
+                Ok(out)
+            },
+            AnonymousScanState::Finished => Ok(None),
+        }
+    }) as GetBatchFn;
+
+    let reader = BatchFnReader {
+        name: name.clone(),
+        output_schema: Some(output_schema),
+        get_batch_state: Some(GetBatchState::from(get_batch_fn)),
+        execution_state: None,
+        verbose: config::verbose(),
+    };
+
+    Arc::new(BatchFnReaderBuilder {
+        name,
+        reader: std::sync::Mutex::new(Some(reader)),
+        execution_state: Default::default(),
+    }) as Arc<dyn FileReaderBuilder>
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use arrow::array::{MutableBinaryViewArray, Utf8ViewArray};
 use arrow::datatypes::ArrowDataType;
 use parking_lot::Mutex;
+use polars_buffer::Buffer;
 use polars_core::frame::{DataFrame, UniqueKeepStrategy};
 use polars_core::prelude::{DataType, IntoColumn, PlHashMap, PlHashSet};
 use polars_core::scalar::Scalar;
@@ -22,6 +23,7 @@ use polars_plan::plans::{AExpr, FunctionIR, IR, IRAggExpr, LiteralValue, write_i
 use polars_plan::prelude::*;
 use polars_utils::arena::{Arena, Node};
 use polars_utils::itertools::Itertools;
+use polars_utils::pl_path::PlRefPath;
 use polars_utils::pl_str::PlSmallStr;
 #[cfg(any(feature = "parquet", feature = "csv", feature = "json"))]
 use polars_utils::relaxed_cell::RelaxedCell;
@@ -606,7 +608,7 @@ pub fn lower_ir(
 
         v @ IR::Scan { .. } => {
             let IR::Scan {
-                sources: scan_sources,
+                sources: mut scan_sources,
                 file_info,
                 mut hive_parts,
                 output_schema: _,
@@ -725,6 +727,7 @@ pub fn lower_ir(
                             first_metadata: first_metadata.clone(),
                             prefetch_limit: RelaxedCell::new_usize(0),
                             prefetch_semaphore: std::sync::OnceLock::new(),
+                            prefetch_tuner: std::sync::OnceLock::new(),
                             shared_prefetch_wait_group_slot: Default::default(),
                             io_metrics: std::sync::OnceLock::new(),
                         },
@@ -792,7 +795,17 @@ pub fn lower_ir(
 
                     FileScanIR::ExpandedPaths { name: _ } => unreachable!(),
 
-                    FileScanIR::Anonymous { .. } => todo!("unimplemented: AnonymousScan"),
+                    FileScanIR::Anonymous { function, .. } => {
+                        // Give multiscan a single scan source. (It doesn't actually read from this).
+                        scan_sources = ScanSources::Paths(Buffer::from_iter([PlRefPath::new(
+                            "anonymous-scan-0",
+                        )]));
+
+                        crate::physical_plan::io::anonymous_scan::anonymous_scan_to_reader_builder(
+                            function.clone(),
+                            file_info.schema.clone(),
+                        )
+                    },
                 };
 
                 {
@@ -844,7 +857,8 @@ pub fn lower_ir(
                         unified_scan_args.column_mapping.as_ref(),
                     );
 
-                    let pre_slice = unified_scan_args.pre_slice.clone();
+                    let mut pre_slice = unified_scan_args.pre_slice.clone();
+                    let mut predicate = predicate;
                     let disable_morsel_split = disable_morsel_split.unwrap_or(true);
 
                     // Set to None if empty for performance.
@@ -852,6 +866,19 @@ pub fn lower_ir(
                         .deletion_files
                         .and_then(|files| DeletionFilesList::filter_empty(Some(files)));
 
+                    // The rows deleted by equality deletes are only known after reading, so the
+                    // slice is applied after the scan, along with the row index and predicate
+                    // that come after it.
+                    let (slice_post, predicate_post) = if deletion_files
+                        .as_ref()
+                        .is_some_and(|x| x.has_equality_deletes())
+                        && (pre_slice.is_some() || unified_scan_args.row_index.is_some())
+                    {
+                        (pre_slice.take(), predicate.take())
+                    } else {
+                        (None, None)
+                    };
+
                     let mut multi_scan_node = PhysNodeKind::MultiScan {
                         scan_sources,
                         file_reader_builder,
@@ -1015,6 +1042,17 @@ pub fn lower_ir(
                         }
                     }
 
+                    if let Some(slice) = slice_post {
+                        let (offset, len) = slice.to_signed_offset_len();
+                        stream = build_slice_stream(stream, offset, len as usize, phys_sm);
+                    }
+
+                    if let Some(predicate) = predicate_post {
+                        stream = build_filter_stream(
+                            stream, predicate, expr_arena, phys_sm, expr_cache, ctx,
+                        )?;
+                    }
+
                     return Ok(stream);
                 }
             }
@@ -198,6 +198,8 @@ impl StreamingQuery {
                 let io_total_bytes_requested = node_metrics.io_total_bytes_requested;
                 let io_total_bytes_received = node_metrics.io_total_bytes_received;
                 let io_total_bytes_sent = node_metrics.io_total_bytes_sent;
+                let io_range_cache_hits = node_metrics.io_range_cache_hits;
+                let io_range_cache_misses = node_metrics.io_range_cache_misses;
 
                 lines.push(
                     (total_time, format!(
@@ -210,7 +212,8 @@ impl StreamingQuery {
                                     total_active_time={io_total_active_time:.2?}, \
                                     total_bytes_requested={io_total_bytes_requested}, \
                                     total_bytes_received={io_total_bytes_received}, \
-                                    total_bytes_sent={io_total_bytes_sent})"))
+                                    total_bytes_sent={io_total_bytes_sent}, \
+                                    range_cache(hits={io_range_cache_hits}, misses={io_range_cache_misses}))"))
                 );
 
                 total_query_ns += total_ns;
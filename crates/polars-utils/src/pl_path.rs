@@ -3,8 +3,9 @@ use std::ffi::OsStr;
 use std::fmt::Display;
 use std::ops::{Deref, Range};
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
-use polars_error::{PolarsResult, polars_err};
+use polars_error::{PolarsResult, polars_ensure, polars_err};
 
 use crate::format_pl_refstr;
 use crate::pl_str::PlRefStr;
@@ -355,6 +356,8 @@ macro_rules! impl_cloud_scheme {
         #[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
         pub enum CloudScheme {
             $($t,)+
+            /// Scheme registered with [`register_cloud_scheme`].
+            Custom(CustomCloudScheme),
         }
 
         impl CloudScheme {
@@ -364,13 +367,19 @@ macro_rules! impl_cloud_scheme {
             fn from_scheme_str(s: &str) -> Option<Self> {
                 Some(match s {
                     $($n => Self::$t,)+
-                    _ => return None,
+                    _ => return CustomCloudScheme::get(s).map(Self::Custom),
                 })
             }
 
+            #[expect(unreachable_patterns)]
+            fn is_builtin_scheme_str(s: &str) -> bool {
+                matches!(s, $($n)|+)
+            }
+
             pub const fn as_str(&self) -> &'static str {
                 match self {
                     $(Self::$t => $n,)+
+                    Self::Custom(s) => s.as_str(),
                 }
             }
         }
@@ -418,6 +427,94 @@ impl CloudScheme {
     }
 }
 
+/// Schemes registered with [`register_cloud_scheme`]. Registered names are leaked, so that
+/// [`CloudScheme`] stays `Copy`.
+static CUSTOM_CLOUD_SCHEMES: RwLock<Vec<&'static str>> = RwLock::new(Vec::new());
+
+/// URI scheme (e.g. `myblob`) registered at runtime, for which paths are treated as cloud paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CustomCloudScheme(&'static str);
+
+impl CustomCloudScheme {
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Returns the registered scheme with the given name.
+    pub fn get(name: &str) -> Option<Self> {
+        CUSTOM_CLOUD_SCHEMES
+            .read()
+            .unwrap()
+            .iter()
+            .find(|x| **x == name)
+            .map(|&x| Self(x))
+    }
+}
+
+/// Registers a custom URI scheme. Paths starting with `{name}://` are then recognized as cloud
+/// paths with [`CloudScheme::Custom`]. Registering an already registered name returns the existing
+/// scheme.
+pub fn register_cloud_scheme(name: &str) -> PolarsResult<CustomCloudScheme> {
+    polars_ensure!(
+        !name.is_empty()
+            && name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')),
+        InvalidOperation: "invalid URI scheme: '{}'", name
+    );
+
+    polars_ensure!(
+        !CloudScheme::is_builtin_scheme_str(name),
+        InvalidOperation: "cannot register built-in URI scheme: '{}'", name
+    );
+
+    let mut schemes = CUSTOM_CLOUD_SCHEMES.write().unwrap();
+
+    if let Some(&existing) = schemes.iter().find(|x| **x == name) {
+        return Ok(CustomCloudScheme(existing));
+    }
+
+    let name: &'static str = Box::leak(name.into());
+    schemes.push(name);
+
+    Ok(CustomCloudScheme(name))
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CustomCloudScheme {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CustomCloudScheme {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        Self::get(&name).ok_or_else(|| {
+            serde::de::Error::custom(format!("URI scheme '{name}' is not registered"))
+        })
+    }
+}
+
+#[cfg(feature = "dsl-schema")]
+impl schemars::JsonSchema for CustomCloudScheme {
+    fn inline_schema() -> bool {
+        str::inline_schema()
+    }
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        str::schema_name()
+    }
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        str::schema_id()
+    }
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        str::json_schema(generator)
+    }
+}
+
 impl Display for CloudScheme {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Display::fmt(self.as_str(), f)
@@ -610,4 +707,19 @@ mod tests {
         assert_eq!(PlRefPath::new("s3://").file_name(), None);
         assert_eq!(PlRefPath::new("").file_name(), None);
     }
+
+    #[test]
+    fn test_custom_cloud_scheme() {
+        assert_eq!(PlRefPath::new("test-scheme://bucket/a").scheme(), None);
+
+        let scheme = register_cloud_scheme("test-scheme").unwrap();
+        assert_eq!(register_cloud_scheme("test-scheme").unwrap(), scheme);
+
+        let p = PlRefPath::new("test-scheme://bucket/a");
+        assert_eq!(p.scheme(), Some(CloudScheme::Custom(scheme)));
+        assert_eq!(p.strip_scheme(), "bucket/a");
+
+        assert!(register_cloud_scheme("s3").is_err());
+        assert!(register_cloud_scheme("a/b").is_err());
+    }
 }
@@ -246,6 +246,88 @@ def test_group_by_errors() -> None:
         df.sql("SELECT a, COUNT(a) AS n FROM self HAVING n > 1")
 
 
+def test_group_by_aggregate_filter() -> None:
+    df = pl.DataFrame(
+        {
+            "grp": ["a", "a", "a", "b", "b", "c", "c"],
+            "status": ["paid", "open", "paid", "open", "paid", "paid", "open"],
+            "amount": [10, 20, 30, 40, 50, 60, None],
+        }
+    )
+    query = """
+        SELECT
+            grp,
+            COUNT(*) AS n,
+            COUNT(*) FILTER (WHERE status = 'paid') AS n_paid,
+            COUNT(amount) FILTER (WHERE status = 'open') AS n_open,
+            SUM(amount) FILTER (WHERE status = 'paid') AS paid,
+            MAX(amount) FILTER (WHERE amount < 40) AS max_small
+        FROM self
+        GROUP BY grp
+        ORDER BY grp
+    """
+    assert_sql_matches(
+        df,
+        query=query,
+        compare_with="sqlite",
+        expected={
+            "grp": ["a", "b", "c"],
+            "n": [3, 2, 2],
+            "n_paid": [2, 1, 1],
+            "n_open": [1, 1, 0],
+            "paid": [40, 50, 60],
+            "max_small": [30, None, None],
+        },
+    )
+
+    # the filter applies to the whole aggregate input (including literals)
+    assert_sql_matches(
+        df,
+        query="""
+            SELECT
+              grp,
+              SUM(1) FILTER (WHERE status = 'paid') AS n_paid,
+              SUM(amount + 1) FILTER (WHERE status = 'paid') AS paid_plus_one
+            FROM self
+            GROUP BY grp
+            ORDER BY grp
+        """,
+        compare_with="sqlite",
+        expected={
+            "grp": ["a", "b", "c"],
+            "n_paid": [2, 1, 1],
+            "paid_plus_one": [42, 51, 61],
+        },
+    )
+    res = df.sql(
+        """
+        SELECT STRING_AGG(status, ',' ORDER BY amount DESC)
+          FILTER (WHERE grp = 'a') AS statuses
+        FROM self
+        """
+    )
+    assert res.to_dict(as_series=False) == {"statuses": ["paid,open,paid"]}
+
+    # without GROUP BY
+    assert_sql_matches(
+        df,
+        query="""
+            SELECT
+              AVG(amount) FILTER (WHERE grp <> 'c') AS avg_amount,
+              COUNT(*) FILTER (WHERE amount IS NULL) AS n_null
+            FROM self
+        """,
+        compare_with="sqlite",
+        expected={"avg_amount": [30.0], "n_null": [1]},
+    )
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match="FILTER is only valid for aggregate functions",
+    ):
+        df.sql("SELECT UPPER(status) FILTER (WHERE amount > 10) FROM self")
+
+
 def test_group_by_having_aggregate_not_in_select() -> None:
     """Test HAVING with aggregate functions not present in SELECT."""
     df = pl.DataFrame(
@@ -667,3 +749,44 @@ def test_group_by_empty_or_scalar_key_exprs_23397() -> None:
         q.collect(),
         pl.DataFrame({"len": pl.Series([5], dtype=pl.get_index_type())}),
     )
+
+
+def test_group_by_string_agg() -> None:
+    df = pl.DataFrame(
+        {
+            "grp": ["a", "b", "a", "b", "a", "c"],
+            "val": ["x", "y", None, "z", "w", "v"],
+            "idx": [3, 1, 2, 0, 1, 5],
+        }
+    )
+    res = df.sql(
+        """
+        SELECT
+          grp,
+          STRING_AGG(val, ',' ORDER BY idx) AS s1,
+          STRING_AGG(val, '|' ORDER BY idx DESC) AS s2,
+          LISTAGG(val, '-') WITHIN GROUP (ORDER BY val) AS s3,
+          STRING_AGG(idx, ';' ORDER BY idx) AS s4,
+        FROM self
+        GROUP BY grp
+        ORDER BY grp
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "grp": ["a", "b", "c"],
+        "s1": ["w,x", "z,y", "v"],
+        "s2": ["x|w", "y|z", "v"],
+        "s3": ["w-x", "y-z", "v"],
+        "s4": ["1;2;3", "0;1", "5"],
+    }
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match="cannot use both ORDER BY and WITHIN GROUP in LISTAGG",
+    ):
+        df.sql(
+            """
+            SELECT LISTAGG(val, ',' ORDER BY idx) WITHIN GROUP (ORDER BY val)
+            FROM self
+            """
+        )
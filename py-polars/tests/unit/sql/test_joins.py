@@ -1371,3 +1371,154 @@ def test_qualified_wildcard_combinations(query: str, expected: dict[str, Any]) -
         right=pl.sql(query).collect(),
         check_row_order=False,
     )
+
+
+def test_lateral_join_top_n() -> None:
+    customers = pl.DataFrame({"id": [1, 2, 3], "name": ["ann", "bob", "cat"]})
+    orders = pl.DataFrame(
+        {
+            "customer_id": [1, 1, 1, 2, 2, 1],
+            "amount": [10, 40, 30, 5, 15, 20],
+        }
+    )
+    with pl.SQLContext(customers=customers, orders=orders) as ctx:
+        res = ctx.execute(
+            """
+            SELECT c.name, o.amount
+            FROM customers c
+            CROSS JOIN LATERAL (
+              SELECT amount FROM orders
+              WHERE orders.customer_id = c.id AND amount > 5
+              ORDER BY amount DESC
+              LIMIT 2
+            ) AS o
+            ORDER BY c.name, o.amount DESC
+            """,
+            eager=True,
+        )
+        assert res.to_dict(as_series=False) == {
+            "name": ["ann", "ann", "bob"],
+            "amount": [40, 30, 15],
+        }
+
+        # LEFT JOIN LATERAL retains outer rows that have no match
+        res = ctx.execute(
+            """
+            SELECT c.name, o.amount
+            FROM customers c
+            LEFT JOIN LATERAL (
+              SELECT amount FROM orders
+              WHERE c.id = orders.customer_id
+              ORDER BY amount
+              LIMIT 1 OFFSET 1
+            ) AS o ON TRUE
+            ORDER BY c.name
+            """,
+            eager=True,
+        )
+        assert res.to_dict(as_series=False) == {
+            "name": ["ann", "bob", "cat"],
+            "amount": [20, 15, None],
+        }
+
+
+def test_lateral_join_aggregate() -> None:
+    a = pl.DataFrame({"k": [1, 2, 3, None], "name": ["x", "y", "z", "w"]})
+    b = pl.DataFrame({"k": [1, 1, 2, None], "v": [10, 20, 30, 40]})
+    with pl.SQLContext(a=a, b=b) as ctx:
+        # without a GROUP BY clause the aggregate is evaluated per outer row,
+        # and outer rows without any matching inner rows are retained
+        res = ctx.execute(
+            """
+            SELECT a.name, x.n, x.total
+            FROM a
+            CROSS JOIN LATERAL (
+              SELECT COUNT(*) AS n, SUM(v) AS total FROM b WHERE b.k = a.k
+            ) AS x
+            ORDER BY a.name
+            """,
+            eager=True,
+        )
+        assert res.to_dict(as_series=False) == {
+            "name": ["w", "x", "y", "z"],
+            "n": [0, 2, 1, 0],
+            "total": [0, 30, 30, 0],
+        }
+
+        res = ctx.execute(
+            """
+            SELECT a.name, x.n
+            FROM a
+            LEFT JOIN LATERAL (
+              SELECT count(*) AS n FROM b WHERE b.k = a.k AND b.v > 10
+            ) AS x ON TRUE
+            ORDER BY a.name
+            """,
+            eager=True,
+        )
+        assert res.to_dict(as_series=False) == {
+            "name": ["w", "x", "y", "z"],
+            "n": [0, 1, 1, 0],
+        }
+
+
+def test_lateral_join_values() -> None:
+    a = pl.DataFrame({"k": [1, 2]})
+    b = pl.DataFrame({"k": [1, 2], "v": [10, 20]})
+    res = pl.sql(
+        """
+        SELECT a.k, x.v, x.w
+        FROM a
+        CROSS JOIN LATERAL (
+          SELECT b.v, t.w
+          FROM b, (VALUES (1 + 1), (CAST('3' AS INT))) AS t(w)
+          WHERE b.k = a.k
+        ) AS x
+        ORDER BY a.k, x.w
+        """,
+        eager=True,
+    )
+    assert res.to_dict(as_series=False) == {
+        "k": [1, 1, 2, 2],
+        "v": [10, 10, 20, 20],
+        "w": [2, 3, 2, 3],
+    }
+
+
+def test_lateral_join_errors() -> None:
+    df1 = pl.DataFrame({"id": [1, 2]})
+    df2 = pl.DataFrame({"id": [1, 2], "v": [3, 4]})
+    with pytest.raises(
+        SQLInterfaceError,
+        match="LATERAL subquery correlation only supports equality predicates",
+    ):
+        pl.sql(
+            """
+            SELECT * FROM df1
+            CROSS JOIN LATERAL (SELECT v FROM df2 WHERE df2.id > df1.id) AS x
+            """
+        ).collect()
+
+    with pytest.raises(
+        SQLInterfaceError,
+        match="LATERAL subqueries support CROSS JOIN, or INNER/LEFT JOIN",
+    ):
+        pl.sql(
+            """
+            SELECT * FROM df1
+            RIGHT JOIN LATERAL (SELECT v FROM df2 WHERE df2.id = df1.id) AS x ON TRUE
+            """
+        ).collect()
+
+    with pytest.raises(
+        SQLInterfaceError,
+        match=r"PIVOT \.\.\. IN \(ANY\) is not supported in LATERAL subqueries",
+    ):
+        pl.sql(
+            """
+            SELECT * FROM df1
+            CROSS JOIN LATERAL (
+              SELECT * FROM df2 PIVOT (SUM(v) FOR id IN (ANY)) AS p
+            ) AS x
+            """
+        ).collect()
@@ -0,0 +1,173 @@
+from __future__ import annotations
+
+import pytest
+
+import polars as pl
+from polars.exceptions import ComputeError, SQLSyntaxError
+
+
+@pytest.fixture
+def df_json() -> pl.DataFrame:
+    return pl.DataFrame(
+        {
+            "id": [1, 2, 3],
+            "payload": [
+                '{"user": {"name": "alice", "tags": ["a", "b"]}, "n": 10}',
+                '{"user": {"name": "bob", "tags": []}, "n": 20}',
+                None,
+            ],
+        }
+    )
+
+
+def test_json_extract(df_json: pl.DataFrame) -> None:
+    res = df_json.sql(
+        """
+        SELECT
+          JSON_EXTRACT(payload, '$.user.name') AS name,
+          JSON_EXTRACT(payload, '$.n') AS n,
+          JSON_EXTRACT_PATH_TEXT(payload, 'user', 'tags', '0') AS tag0,
+          payload -> 'user' ->> 'name' AS name_op,
+          payload #>> '{user,tags,1}' AS tag1,
+        FROM self
+        ORDER BY id
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "name": ["alice", "bob", None],
+        "n": ["10", "20", None],
+        "tag0": ["a", None, None],
+        "name_op": ["alice", "bob", None],
+        "tag1": ["b", None, None],
+    }
+
+
+def test_json_extract_struct() -> None:
+    df = pl.DataFrame(
+        {
+            "s": [
+                {"user": {"name": "alice", "tags": ["a", "b"]}, "n": 10},
+                {"user": {"name": "bob", "tags": []}, "n": 20},
+            ]
+        }
+    )
+    res = df.sql(
+        """
+        SELECT
+          JSON_EXTRACT(s, '$.user.name') AS name,
+          JSON_EXTRACT(s, '$.user.tags[1]') AS tag1,
+          JSON_EXTRACT_PATH_TEXT(s, 'n') AS n,
+        FROM self
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "name": ["alice", "bob"],
+        "tag1": ["b", None],
+        "n": ["10", "20"],
+    }
+
+
+def test_json_cast_to_struct(df_json: pl.DataFrame) -> None:
+    res = df_json.sql(
+        """
+        SELECT
+          TRY_CAST(payload AS STRUCT<n BIGINT>) AS s1,
+          CAST(payload AS STRUCT<n VARCHAR>) AS s2,
+        FROM self
+        ORDER BY id
+        """
+    )
+    assert res.schema == {
+        "s1": pl.Struct({"n": pl.Int64}),
+        "s2": pl.Struct({"n": pl.String}),
+    }
+    assert res["s1"].to_list() == [{"n": 10}, {"n": 20}, None]
+
+
+def test_json_try_cast_invalid() -> None:
+    df = pl.DataFrame({"payload": ['{"n": 1}', "not json", '{"n": 3}', None]})
+    for cast in ("TRY_CAST", "SAFE_CAST"):
+        res = df.sql(f"SELECT {cast}(payload AS STRUCT<n BIGINT>) AS s FROM self")
+        assert res.schema == {"s": pl.Struct({"n": pl.Int64})}
+        assert res["s"].to_list() == [{"n": 1}, None, {"n": 3}, None]
+
+    with pytest.raises(ComputeError, match="error deserializing JSON"):
+        df.sql("SELECT CAST(payload AS STRUCT<n BIGINT>) AS s FROM self")
+
+
+def test_json_object(df_json: pl.DataFrame) -> None:
+    res = df_json.sql(
+        """
+        SELECT
+          JSON_OBJECT('id', id, 'name', JSON_EXTRACT(payload, '$.user.name')) AS obj,
+          JSON_OBJECT() AS empty,
+        FROM self
+        ORDER BY id
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "obj": [
+            '{"id":1,"name":"alice"}',
+            '{"id":2,"name":"bob"}',
+            '{"id":3,"name":null}',
+        ],
+        "empty": ["{}", "{}", "{}"],
+    }
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match=r"JSON_OBJECT expects an even number of arguments \(found 3\)",
+    ):
+        df_json.sql("SELECT JSON_OBJECT('a', 1, 'b') FROM self")
+
+
+def test_json_arrayagg() -> None:
+    df = pl.DataFrame(
+        {
+            "grp": ["a", "b", "a", "b", "a"],
+            "val": ["x", "y", None, "z", "w"],
+            "idx": [3, 1, 2, 0, 1],
+        }
+    )
+    res = df.sql(
+        """
+        SELECT
+          grp,
+          JSON_ARRAYAGG(val ORDER BY idx) AS vals,
+          JSON_ARRAYAGG(idx ORDER BY idx DESC) AS idxs,
+        FROM self
+        GROUP BY grp
+        ORDER BY grp
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "grp": ["a", "b"],
+        "vals": ['["w",null,"x"]', '["z","y"]'],
+        "idxs": ["[3,2,1]", "[1,0]"],
+    }
+
+
+def test_json_arrayagg_nested() -> None:
+    df = pl.DataFrame(
+        {
+            "grp": [1, 1, 2],
+            "val": ['a}"b', "{}", None],
+            "pt": [{"x": 1, "y": [1, 2]}, {"x": 2, "y": []}, None],
+        }
+    )
+    res = df.sql(
+        """
+        SELECT
+          grp,
+          JSON_ARRAYAGG(val) AS vals,
+          JSON_ARRAYAGG(pt) AS pts,
+        FROM self
+        GROUP BY grp
+        ORDER BY grp
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "grp": [1, 2],
+        "vals": ['["a}\\"b","{}"]', "[null]"],
+        "pts": ['[{"x":1,"y":[1,2]},{"x":2,"y":[]}]', "[null]"],
+    }
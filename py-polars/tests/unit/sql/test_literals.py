@@ -145,12 +145,27 @@ def test_fixed_intervals() -> None:
 
         assert_frame_equal(expected, out)
 
-        # TODO: negative intervals
-        with pytest.raises(
-            SQLInterfaceError,
-            match="minus signs are not yet supported in interval strings; found '-7d'",
-        ):
-            ctx.execute("SELECT INTERVAL '-7d' AS one_week_ago FROM df")
+        # negative intervals, and intervals with a trailing unit
+        out = ctx.execute(
+            """
+            SELECT
+              INTERVAL '-7d' AS i1,
+              INTERVAL '-1 hour' AS i2,
+              INTERVAL '3' DAY AS i3,
+              INTERVAL 90 MINUTE AS i4
+            FROM df
+            """
+        )
+        expected = pl.DataFrame(
+            {
+                "i1": [timedelta(days=-7)],
+                "i2": [timedelta(hours=-1)],
+                "i3": [timedelta(days=3)],
+                "i4": [timedelta(minutes=90)],
+            },
+        ).cast(pl.Duration("ns"))
+
+        assert_frame_equal(expected, out)
 
         with pytest.raises(
             SQLSyntaxError,
@@ -186,7 +201,9 @@ def test_interval_offsets() -> None:
         SELECT
             dtm + INTERVAL '2 months, 30 minutes' AS dtm_plus_2mo30m,
             dt + INTERVAL '100 years' AS dt_plus_100y,
-            dt - INTERVAL '1 quarter' AS dt_minus_1q
+            dt - INTERVAL '1 quarter' AS dt_minus_1q,
+            dt - INTERVAL '-1 month' AS dt_plus_1mo,
+            INTERVAL '2 days' + dt AS dt_plus_2d
         FROM self
         ORDER BY 1
         """
@@ -207,6 +224,16 @@ def test_interval_offsets() -> None:
             date(2047, 10, 20),
             date(2026, 5, 5),
         ],
+        "dt_plus_1mo": [
+            date(1950, 5, 10),
+            date(2048, 2, 20),
+            date(2026, 9, 5),
+        ],
+        "dt_plus_2d": [
+            date(1950, 4, 12),
+            date(2048, 1, 22),
+            date(2026, 8, 7),
+        ],
     }
 
 
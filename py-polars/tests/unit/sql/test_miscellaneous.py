@@ -1,6 +1,6 @@
 from __future__ import annotations
 
-from datetime import date
+from datetime import date, datetime
 from pathlib import Path
 from typing import TYPE_CHECKING, Any
 
@@ -634,3 +634,75 @@ def test_unsupported_select_clauses(query: str) -> None:
         ),
     ):
         ctx.execute(query)
+
+
+def test_generate_series() -> None:
+    res = pl.sql(
+        """
+        SELECT * FROM generate_series(1, 10, 3)
+        """,
+        eager=True,
+    )
+    assert res.to_dict(as_series=False) == {"generate_series": [1, 4, 7, 10]}
+
+    res = pl.sql("SELECT * FROM range(5, 0, -2) AS r(n)", eager=True)
+    assert res.to_dict(as_series=False) == {"n": [5, 3, 1]}
+
+    res = pl.sql(
+        """
+        SELECT dt FROM generate_series(
+          DATE '2024-02-27', DATE '2024-03-01', INTERVAL '1 day'
+        ) AS calendar(dt)
+        """,
+        eager=True,
+    )
+    assert res.to_dict(as_series=False) == {
+        "dt": [date(2024, 2, 27), date(2024, 2, 28), date(2024, 2, 29), date(2024, 3, 1)]
+    }
+
+    res = pl.sql(
+        """
+        SELECT * FROM range(
+          TIMESTAMP '2024-01-01 00:00:00',
+          TIMESTAMP '2024-01-01 01:00:00',
+          INTERVAL '20 minutes'
+        )
+        """,
+        eager=True,
+    )
+    assert res.to_series().to_list() == [
+        datetime(2024, 1, 1, 0, 0),
+        datetime(2024, 1, 1, 0, 20),
+        datetime(2024, 1, 1, 0, 40),
+    ]
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match="generate_series step must be a non-zero integer or an INTERVAL",
+    ):
+        pl.sql("SELECT * FROM generate_series(1, 10, 0)")
+
+
+def test_values_type_inference() -> None:
+    res = pl.sql(
+        """
+        SELECT * FROM (
+          VALUES
+            (1, NULL, DATE '2024-01-01', 'a'),
+            (2.5, 10, CAST('2024-12-31' AS DATE), NULL),
+            (-3, 20, NULL, 'c')
+        ) AS tbl(x, y, dt, s)
+        """,
+        eager=True,
+    )
+    assert res.schema == {
+        "x": pl.Float64,
+        "y": pl.Int64,
+        "dt": pl.Date,
+        "s": pl.String,
+    }
+    assert res.rows() == [
+        (1.0, None, date(2024, 1, 1), "a"),
+        (2.5, 10, date(2024, 12, 31), None),
+        (-3.0, 20, None, "c"),
+    ]
@@ -0,0 +1,147 @@
+from __future__ import annotations
+
+from typing import TYPE_CHECKING
+
+import pytest
+
+import polars as pl
+from polars.exceptions import SQLInterfaceError
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+
+@pytest.fixture
+def df_sales() -> pl.DataFrame:
+    return pl.DataFrame(
+        {
+            "region": ["east", "east", "east", "west", "west", "west"],
+            "quarter": ["Q1", "Q2", "Q1", "Q1", "Q3", "Q3"],
+            "amount": [10, 20, 5, 7, 1, 2],
+        }
+    )
+
+
+def test_pivot(df_sales: pl.DataFrame) -> None:
+    res = df_sales.sql(
+        """
+        SELECT * FROM self
+          PIVOT (SUM(amount) FOR quarter IN ('Q1' AS q1, 'Q2', 'Q3' AS q3))
+        ORDER BY region
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "region": ["east", "west"],
+        "q1": [15, 7],
+        "Q2": [20, None],
+        "q3": [None, 3],
+    }
+
+    res = df_sales.sql(
+        """
+        SELECT region, Q1, Q3 FROM self
+          PIVOT (MAX(amount) FOR quarter IN (ANY) DEFAULT ON NULL (0))
+        ORDER BY region
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "region": ["east", "west"],
+        "Q1": [10, 7],
+        "Q3": [0, 2],
+    }
+
+
+def test_pivot_values_resolved_eagerly(
+    df_sales: pl.DataFrame, tmp_path: Path
+) -> None:
+    path = tmp_path / "sales.csv"
+    df_sales.write_csv(path)
+
+    with pl.SQLContext(sales=pl.scan_csv(path)) as ctx:
+        frames = [
+            ctx.execute(
+                f"""
+                SELECT * FROM sales
+                  PIVOT (SUM(amount) FOR quarter IN ({values}))
+                """
+            )
+            for values in ("ANY", "SELECT quarter FROM sales")
+        ]
+
+    # the pivot values were collected when the query was translated, so a
+    # new value in the underlying data does not add a pivot column
+    new_row = pl.DataFrame({"region": ["east"], "quarter": ["Q4"], "amount": [1]})
+    pl.concat([df_sales, new_row]).write_csv(path)
+
+    for lf in frames:
+        assert lf.collect_schema().names() == ["region", "Q1", "Q2", "Q3"]
+        assert lf.collect().columns == ["region", "Q1", "Q2", "Q3"]
+
+
+def test_pivot_alias_scope(df_sales: pl.DataFrame) -> None:
+    with pl.SQLContext(sales=df_sales) as ctx:
+        res = ctx.execute(
+            """
+            SELECT p.region, p.q1 FROM sales
+              PIVOT (SUM(amount) FOR quarter IN ('Q1' AS q1)) AS p
+            ORDER BY p.region
+            """,
+            eager=True,
+        )
+        assert res.to_dict(as_series=False) == {
+            "region": ["east", "west"],
+            "q1": [15, 7],
+        }
+
+        # the alias is not registered as a table in the context
+        assert ctx.tables() == ["sales"]
+        with pytest.raises(SQLInterfaceError, match="relation 'p' was not found"):
+            ctx.execute("SELECT * FROM p")
+
+
+def test_pivot_errors(df_sales: pl.DataFrame) -> None:
+    with pytest.raises(
+        SQLInterfaceError,
+        match="PIVOT currently supports a single aggregate function",
+    ):
+        df_sales.sql(
+            """
+            SELECT * FROM self
+              PIVOT (SUM(amount) AS s, MAX(amount) AS m FOR quarter IN ('Q1'))
+            """
+        )
+
+
+def test_unpivot() -> None:
+    df = pl.DataFrame(
+        {
+            "region": ["east", "west"],
+            "q1": [15, None],
+            "q2": [20, 3],
+        }
+    )
+    res = df.sql(
+        """
+        SELECT * FROM self
+          UNPIVOT (amount FOR quarter IN (q1, q2 AS second))
+        ORDER BY region, quarter
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "region": ["east", "east", "west"],
+        "quarter": ["q1", "second", "second"],
+        "amount": [15, 20, 3],
+    }
+
+    res = df.sql(
+        """
+        SELECT * FROM self
+          UNPIVOT INCLUDE NULLS (amount FOR quarter IN (q1, q2))
+        ORDER BY region, quarter
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "region": ["east", "east", "west", "west"],
+        "quarter": ["q1", "q2", "q1", "q2"],
+        "amount": [15, 20, None, 3],
+    }
@@ -138,3 +138,80 @@ def test_rank_funcs_require_order_by(df_test: pl.DataFrame) -> None:
         match="DENSE_RANK requires an OVER clause with ORDER BY",
     ):
         df_test.sql(query_dense)
+
+
+def test_rank_funcs_distribution(df_test: pl.DataFrame) -> None:
+    query = """
+        SELECT
+            category,
+            value,
+            PERCENT_RANK() OVER (PARTITION BY category ORDER BY value) AS pct_rank,
+            CUME_DIST() OVER (PARTITION BY category ORDER BY value) AS cume_dist
+        FROM self
+        ORDER BY category, value
+    """
+    assert_sql_matches(
+        df_test,
+        query=query,
+        compare_with="sqlite",
+        expected={
+            "category": ["A", "A", "A", "B", "B", "B", "C"],
+            "value": [10, 20, 25, 10, 25, 40, 35],
+            "pct_rank": [0.0, 0.5, 1.0, 0.0, 0.5, 1.0, 0.0],
+            "cume_dist": [1 / 3, 2 / 3, 1.0, 1 / 3, 2 / 3, 1.0, 1.0],
+        },
+    )
+
+    # ties share the same percent_rank/cume_dist
+    df = pl.DataFrame({"id": [1, 2, 3, 4, 5], "score": [85, 90, 90, 75, 80]})
+    assert_sql_matches(
+        df,
+        query="""
+            SELECT
+                id,
+                PERCENT_RANK() OVER (ORDER BY score) AS pct_rank,
+                CUME_DIST() OVER (ORDER BY score DESC) AS cume_dist
+            FROM self
+            ORDER BY id
+        """,
+        compare_with="sqlite",
+        expected={
+            "id": [1, 2, 3, 4, 5],
+            "pct_rank": [0.5, 0.75, 0.75, 0.0, 0.25],
+            "cume_dist": [0.6, 0.4, 0.4, 1.0, 0.8],
+        },
+    )
+
+
+@pytest.mark.parametrize("n_buckets", [1, 2, 3, 4, 10])
+def test_rank_funcs_ntile(df_test: pl.DataFrame, n_buckets: int) -> None:
+    query = f"""
+        SELECT
+            id,
+            NTILE({n_buckets}) OVER (ORDER BY value, id) AS bucket,
+            NTILE({n_buckets}) OVER (PARTITION BY category ORDER BY value) AS cat_bucket
+        FROM self
+        ORDER BY id
+    """
+    assert_sql_matches(df_test, query=query, compare_with="sqlite")
+
+
+def test_rank_funcs_ntile_errors(df_test: pl.DataFrame) -> None:
+    for bucket_count in ("0", "-1", "'x'", "value"):
+        with pytest.raises(
+            pl.exceptions.SQLSyntaxError,
+            match="NTILE expects a positive integer bucket count",
+        ):
+            df_test.sql(f"SELECT NTILE({bucket_count}) OVER (ORDER BY id) FROM self")
+
+    with pytest.raises(
+        pl.exceptions.SQLSyntaxError,
+        match="NTILE requires an OVER clause",
+    ):
+        df_test.sql("SELECT NTILE(2) FROM self")
+
+    with pytest.raises(
+        pl.exceptions.SQLSyntaxError,
+        match="CUME_DIST requires an OVER clause with ORDER BY",
+    ):
+        df_test.sql("SELECT CUME_DIST() OVER (PARTITION BY category) FROM self")
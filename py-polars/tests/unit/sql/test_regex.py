@@ -142,3 +142,63 @@ def test_regexp_like_errors() -> None:
             match=r"REGEXP_LIKE expects 2-3 arguments \(found 1\)",
         ):
             ctx.execute("SELECT * FROM df WHERE REGEXP_LIKE(scol)")
+
+
+def test_regexp_functions() -> None:
+    df = pl.DataFrame({"txt": ["abc-123 def-456", "XYZ-9", None, "no digits"]})
+    res = df.sql(
+        r"""
+        SELECT
+          REGEXP_REPLACE(txt, '([a-z]+)-([0-9]+)', '\2:\1', 'i') AS replace_one,
+          REGEXP_REPLACE(txt, '([a-z]+)-([0-9]+)', '\2:\1', 'gi') AS replace_all,
+          REGEXP_REPLACE(txt, 'x', '$', 'gi') AS replace_dollar,
+          REGEXP_COUNT(txt, '[0-9]') AS count_digits,
+          REGEXP_COUNT(txt, '[a-z]', 1, 'i') AS count_letters,
+          REGEXP_SUBSTR(txt, '[0-9]+') AS substr_first,
+          REGEXP_SUBSTR(txt, '[0-9]+', 1, 2) AS substr_second,
+          REGEXP_SUBSTR(txt, '([a-z]+)-([0-9]+)', 1, 1, 'i', 1) AS substr_group,
+          REGEXP_SUBSTR(txt, '[a-z]+', 5) AS substr_start,
+          REGEXP_EXTRACT(txt, '([A-Za-z]+)-([0-9]+)', 2) AS extract_group,
+          REGEXP_SPLIT_TO_ARRAY(txt, '[ -]+') AS split_array,
+        FROM self
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "replace_one": ["123:abc def-456", "9:XYZ", None, "no digits"],
+        "replace_all": ["123:abc 456:def", "9:XYZ", None, "no digits"],
+        "replace_dollar": ["abc-123 def-456", "$YZ-9", None, "no digits"],
+        "count_digits": [6, 1, None, 0],
+        "count_letters": [6, 3, None, 8],
+        "substr_first": ["123", "9", None, None],
+        "substr_second": ["456", None, None, None],
+        "substr_group": ["abc", "XYZ", None, None],
+        "substr_start": ["def", None, None, "igits"],
+        "extract_group": ["123", "9", None, None],
+        "split_array": [
+            ["abc", "123", "def", "456"],
+            ["XYZ", "9"],
+            None,
+            ["no", "digits"],
+        ],
+    }
+
+
+def test_regexp_function_errors() -> None:
+    df = pl.DataFrame({"txt": ["xyz"]})
+    with pytest.raises(
+        SQLSyntaxError,
+        match=r"REGEXP_REPLACE expects 3-4 arguments \(found 2\)",
+    ):
+        df.sql("SELECT REGEXP_REPLACE(txt, 'x') FROM self")
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match="invalid flag 'g' for REGEXP_SUBSTR",
+    ):
+        df.sql("SELECT REGEXP_SUBSTR(txt, 'x', 1, 1, 'g') FROM self")
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match="REGEXP_COUNT flags can only be applied to a literal pattern",
+    ):
+        df.sql("SELECT REGEXP_COUNT(txt, txt, 1, 'i') FROM self")
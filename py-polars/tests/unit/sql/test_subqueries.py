@@ -1,7 +1,11 @@
+from __future__ import annotations
+
+from typing import Any
+
 import pytest
 
 import polars as pl
-from polars.exceptions import SQLInterfaceError, SQLSyntaxError
+from polars.exceptions import ComputeError, SQLInterfaceError, SQLSyntaxError
 from polars.testing import assert_frame_equal
 
 
@@ -285,3 +289,179 @@ def test_derived_table_alias_errors() -> None:
             query="SELECT a FROM (SELECT a, b FROM df) ORDER BY sq.a",
             eager=True,
         )
+
+
+@pytest.fixture
+def customers_orders() -> pl.SQLContext[Any]:
+    customers = pl.DataFrame(
+        {
+            "id": [1, 2, 3, None],
+            "name": ["a", "b", "c", "d"],
+            "fav_amount": [30, 5, 1, 1],
+        }
+    )
+    orders = pl.DataFrame(
+        {
+            "customer_id": [1, 1, 2, 2, 2, None],
+            "amount": [10, 30, 5, 15, 25, 100],
+            "status": ["paid", "open", "paid", "paid", "open", "paid"],
+        }
+    )
+    return pl.SQLContext(customers=customers, orders=orders, eager=True)
+
+
+def test_exists_subquery(customers_orders: pl.SQLContext[Any]) -> None:
+    res = customers_orders.execute(
+        """
+        SELECT name FROM customers c
+        WHERE EXISTS (
+          SELECT 1 FROM orders o WHERE o.customer_id = c.id AND o.status = 'open'
+        )
+        ORDER BY name
+        """
+    )
+    assert res["name"].to_list() == ["a", "b"]
+
+    res = customers_orders.execute(
+        """
+        SELECT name FROM customers
+        WHERE NOT EXISTS (
+          SELECT 1 FROM orders WHERE customers.id = orders.customer_id
+        )
+        ORDER BY name
+        """
+    )
+    assert res["name"].to_list() == ["c", "d"]
+
+    # uncorrelated EXISTS, combined with other predicates
+    res = customers_orders.execute(
+        """
+        SELECT name FROM customers
+        WHERE id > 1 AND EXISTS (SELECT 1 FROM orders WHERE amount > 50)
+        ORDER BY name
+        """
+    )
+    assert res["name"].to_list() == ["b", "c"]
+
+
+def test_correlated_in_subquery(customers_orders: pl.SQLContext[Any]) -> None:
+    res = customers_orders.execute(
+        """
+        SELECT name FROM customers c
+        WHERE fav_amount IN (SELECT amount FROM orders o WHERE o.customer_id = c.id)
+          OR c.id = 3
+        ORDER BY name
+        """
+    )
+    assert res["name"].to_list() == ["a", "b", "c"]
+
+
+def test_correlated_not_in_subquery_nulls() -> None:
+    tbl = pl.DataFrame(
+        {
+            "id": ["a", "b", "c", "d", "e", "f", "g"],
+            "grp": [1, 1, 1, 2, 2, 3, 3],
+            "x": [1, None, 3, 1, 2, 1, None],
+        }
+    )
+    sub = pl.DataFrame({"grp": [1, 1, 2, 2], "y": [1, 2, 1, None]})
+    with pl.SQLContext(tbl=tbl, sub=sub, eager=True) as ctx:
+        for op, expected in (
+            ("IN", ["a", "d"]),
+            # only rows whose correlated group is empty, or that neither contain
+            # a NULL value nor have a NULL 'x', can be NOT IN the subquery
+            ("NOT IN", ["c", "f", "g"]),
+        ):
+            res = ctx.execute(
+                f"""
+                SELECT id FROM tbl t
+                WHERE x {op} (SELECT y FROM sub s WHERE s.grp = t.grp)
+                ORDER BY id
+                """
+            )
+            assert res["id"].to_list() == expected
+
+        res = ctx.execute(
+            """
+            SELECT id FROM tbl t
+            WHERE (x IN (SELECT y FROM sub s WHERE s.grp = t.grp)) IS NULL
+            ORDER BY id
+            """
+        )
+        assert res["id"].to_list() == ["b", "e"]
+
+
+def test_correlated_scalar_subquery(customers_orders: pl.SQLContext[Any]) -> None:
+    res = customers_orders.execute(
+        """
+        SELECT customer_id, amount FROM orders o1
+        WHERE amount > (
+          SELECT AVG(amount) FROM orders o2 WHERE o2.customer_id = o1.customer_id
+        )
+        ORDER BY customer_id, amount
+        """
+    )
+    assert res.rows() == [(1, 30), (2, 25)]
+
+    res = customers_orders.execute(
+        """
+        SELECT name FROM customers c
+        WHERE (SELECT COUNT(*) FROM orders o WHERE o.customer_id = c.id) < 2
+        ORDER BY name
+        """
+    )
+    assert res["name"].to_list() == ["c", "d"]
+
+    with pytest.raises(
+        SQLInterfaceError,
+        match="subquery correlation only supports equality predicates",
+    ):
+        customers_orders.execute(
+            """
+            SELECT name FROM customers c
+            WHERE EXISTS (SELECT 1 FROM orders o WHERE o.customer_id > c.id)
+            """
+        )
+
+
+def test_correlated_scalar_subquery_no_matching_rows(
+    customers_orders: pl.SQLContext[Any],
+) -> None:
+    # outer rows without matching inner rows evaluate the aggregate over an empty input
+    for expr, op, expected in (
+        ("COUNT(*) + 1", "= 1", ["c", "d"]),
+        ("COALESCE(MAX(amount), 0)", "< 26", ["b", "c", "d"]),
+        ("CAST(COUNT(*) AS VARCHAR)", "= '0'", ["c", "d"]),
+        ("MAX(amount)", "IS NULL", ["c", "d"]),
+    ):
+        res = customers_orders.execute(
+            f"""
+            SELECT name FROM customers c
+            WHERE (SELECT {expr} FROM orders o WHERE o.customer_id = c.id) {op}
+            ORDER BY name
+            """
+        )
+        assert res["name"].to_list() == expected
+
+
+def test_correlated_scalar_subquery_single_row(
+    customers_orders: pl.SQLContext[Any],
+) -> None:
+    res = customers_orders.execute(
+        """
+        SELECT name FROM customers c
+        WHERE fav_amount = (
+          SELECT amount FROM orders o WHERE o.customer_id = c.id AND o.status = 'open'
+        )
+        """
+    )
+    assert res["name"].to_list() == ["a"]
+
+    # a scalar subquery must not return more than one row per outer row
+    with pytest.raises(ComputeError, match="m:1 validation"):
+        customers_orders.execute(
+            """
+            SELECT name FROM customers c
+            WHERE fav_amount = (SELECT amount FROM orders o WHERE o.customer_id = c.id)
+            """
+        )
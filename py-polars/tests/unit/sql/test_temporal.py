@@ -41,6 +41,156 @@ def test_date_func() -> None:
         df.sql("SELECT DATE('2077-07-07','not_a_valid_strftime_format') FROM self")
 
 
+def test_date_add() -> None:
+    df = pl.DataFrame(
+        {
+            "dt": [date(2024, 2, 29), date(1999, 12, 31)],
+            "n": [1, 2],
+        }
+    )
+    res = df.sql(
+        """
+        SELECT
+          DATE_ADD(dt, INTERVAL '1 month') AS d1,
+          DATEADD(day, 3, dt) AS d2,
+          DATEADD('year', -1, dt) AS d3,
+          DATE_ADD('month', n, dt) AS d4,
+          DATE_ADD(dt, INTERVAL '-2 weeks') AS d5,
+        FROM self
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "d1": [date(2024, 3, 29), date(2000, 1, 31)],
+        "d2": [date(2024, 3, 3), date(2000, 1, 3)],
+        "d3": [date(2023, 2, 28), date(1998, 12, 31)],
+        "d4": [date(2024, 3, 29), date(2000, 2, 29)],
+        "d5": [date(2024, 2, 15), date(1999, 12, 17)],
+    }
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match=r"DATE_ADD expects 2-3 arguments \(found 1\)",
+    ):
+        df.sql("SELECT DATE_ADD(dt) FROM self")
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match="DATE_ADD does not support 'fortnight' part",
+    ):
+        df.sql("SELECT DATEADD('fortnight', 1, dt) FROM self")
+
+    # a bare number is taken as a number of days
+    res = df.sql("SELECT DATE_ADD(dt, 3) AS d1, DATEADD(dt, -n) AS d2 FROM self")
+    assert res.to_dict(as_series=False) == {
+        "d1": [date(2024, 3, 3), date(2000, 1, 3)],
+        "d2": [date(2024, 2, 28), date(1999, 12, 29)],
+    }
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match="DATE_ADD expects an integer number of 'd' units",
+    ):
+        df.sql("SELECT DATE_ADD(dt, 1.5) FROM self")
+
+
+def test_date_diff() -> None:
+    df = pl.DataFrame(
+        {
+            "d1": [date(2024, 2, 29), date(1999, 12, 31)],
+            "d2": [date(2025, 1, 1), date(2000, 1, 1)],
+            "dtm1": [datetime(2024, 2, 29, 13, 45, 30), datetime(2020, 1, 1)],
+            "dtm2": [datetime(2024, 2, 29, 15, 5), datetime(2019, 12, 31, 23)],
+        }
+    )
+    res = df.sql(
+        """
+        SELECT
+          DATEDIFF('year', d1, d2) AS years,
+          DATEDIFF('quarter', d1, d2) AS quarters,
+          DATE_DIFF('month', d1, d2) AS months,
+          DATEDIFF(week, d1, d2) AS weeks,
+          DATEDIFF('day', d1, d2) AS days,
+          DATEDIFF('hour', dtm1, dtm2) AS hours,
+        FROM self
+        """
+    )
+    assert res.schema == dict.fromkeys(
+        ["years", "quarters", "months", "weeks", "days", "hours"], pl.Int64
+    )
+    assert res.to_dict(as_series=False) == {
+        "years": [1, 1],
+        "quarters": [4, 1],
+        "months": [11, 1],
+        "weeks": [44, 0],
+        "days": [307, 1],
+        "hours": [2, -1],
+    }
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match=r"DATEDIFF expects 3 arguments \(found 2\)",
+    ):
+        df.sql("SELECT DATEDIFF(d1, d2) FROM self")
+
+
+def test_date_trunc() -> None:
+    df = pl.DataFrame(
+        {
+            "dtm": [
+                datetime(2024, 2, 29, 13, 45, 30),
+                datetime(1999, 12, 31, 23, 59, 59),
+            ],
+            "dt": [date(2024, 2, 29), date(1999, 12, 31)],
+        }
+    )
+    res = df.sql(
+        """
+        SELECT
+          DATE_TRUNC('month', dtm) AS dtm_month,
+          DATE_TRUNC('hour', dtm) AS dtm_hour,
+          DATE_TRUNC('year', dt) AS dt_year,
+          DATE_TRUNC('week', dt) AS dt_week,
+        FROM self
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "dtm_month": [datetime(2024, 2, 1), datetime(1999, 12, 1)],
+        "dtm_hour": [datetime(2024, 2, 29, 13), datetime(1999, 12, 31, 23)],
+        "dt_year": [date(2024, 1, 1), date(1999, 1, 1)],
+        "dt_week": [date(2024, 2, 26), date(1999, 12, 27)],
+    }
+
+    with pytest.raises(
+        SQLSyntaxError,
+        match="DATE_TRUNC does not support 'millennium' part",
+    ):
+        df.sql("SELECT DATE_TRUNC('millennium', dt) FROM self")
+
+
+def test_last_day_make_date() -> None:
+    df = pl.DataFrame(
+        {
+            "y": [2024, 2023, 1999],
+            "m": [2, 2, 12],
+            "d": [29, 28, 1],
+        }
+    )
+    res = df.sql(
+        """
+        SELECT
+          MAKE_DATE(y, m, d) AS dt,
+          LAST_DAY(MAKE_DATE(y, m, d)) AS dt_last,
+          LAST_DAY(MAKE_DATE(y, m, d)::timestamp) AS dtm_last,
+        FROM self
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "dt": [date(2024, 2, 29), date(2023, 2, 28), date(1999, 12, 1)],
+        "dt_last": [date(2024, 2, 29), date(2023, 2, 28), date(1999, 12, 31)],
+        "dtm_last": [date(2024, 2, 29), date(2023, 2, 28), date(1999, 12, 31)],
+    }
+
+
 @pytest.mark.parametrize("time_unit", ["ms", "us", "ns"])
 def test_datetime_to_time(time_unit: Literal["ns", "us", "ms"]) -> None:
     s = pl.Series(
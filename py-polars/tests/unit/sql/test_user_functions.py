@@ -0,0 +1,112 @@
+from __future__ import annotations
+
+import pytest
+
+import polars as pl
+from polars.exceptions import SQLInterfaceError, SQLSyntaxError
+
+
+@pytest.fixture
+def ctx() -> pl.SQLContext[pl.DataFrame]:
+    df = pl.DataFrame(
+        {
+            "id": [1, 2, 3, 4],
+            "name": ["a", "b", "c", "d"],
+            "amount": [10.0, 25.0, 5.0, 40.0],
+        }
+    )
+    return pl.SQLContext(sales=df, eager=True)
+
+
+def test_create_function(ctx: pl.SQLContext[pl.DataFrame]) -> None:
+    res = ctx.execute(
+        """
+        CREATE FUNCTION add_tax(amount DOUBLE, rate DOUBLE DEFAULT 0.5)
+          RETURNS DOUBLE AS 'amount * (1 + rate)'
+        """
+    )
+    assert res.to_dict(as_series=False) == {"Response": ["CREATE FUNCTION add_tax"]}
+
+    ctx.execute(
+        "CREATE FUNCTION label(s VARCHAR, n INT) RETURN s || ':' || CAST(n AS VARCHAR)"
+    )
+    res = ctx.execute(
+        """
+        SELECT
+          ADD_TAX(amount) AS t1,
+          add_tax(amount, 0.0) AS t2,
+          add_tax(rate => 1, amount => id) AS t3,
+          label(name, id) AS lbl,
+        FROM sales
+        WHERE add_tax(amount) > 10
+        ORDER BY id
+        """
+    )
+    assert res.to_dict(as_series=False) == {
+        "t1": [15.0, 37.5, 60.0],
+        "t2": [10.0, 25.0, 40.0],
+        "t3": [2.0, 4.0, 8.0],
+        "lbl": ["a:1", "b:2", "d:4"],
+    }
+
+    # nested function calls; replacement
+    ctx.execute("CREATE FUNCTION double_tax(x DOUBLE) RETURN add_tax(add_tax(x))")
+    ctx.execute("CREATE OR REPLACE FUNCTION add_tax(amount DOUBLE) RETURN amount + 1")
+    res = ctx.execute("SELECT double_tax(amount) AS dt FROM sales ORDER BY id")
+    assert res["dt"].to_list() == [12.0, 27.0, 7.0, 42.0]
+
+
+def test_create_macro(ctx: pl.SQLContext[pl.DataFrame]) -> None:
+    ctx.execute(
+        "CREATE MACRO clamp(x, lo := 0, hi := 20) AS GREATEST(lo, LEAST(hi, x))"
+    )
+    ctx.execute(
+        """
+        CREATE MACRO top_n(n, min_amount := 0) AS TABLE
+          SELECT id, amount FROM sales
+          WHERE amount > min_amount
+          ORDER BY amount DESC
+          LIMIT n
+        """
+    )
+    res = ctx.execute("SELECT clamp(amount, hi := 30) AS c FROM sales ORDER BY id")
+    assert res["c"].to_list() == [10.0, 25.0, 5.0, 30.0]
+
+    res = ctx.execute("SELECT * FROM top_n(2)")
+    assert res.to_dict(as_series=False) == {"id": [4, 2], "amount": [40.0, 25.0]}
+
+    res = ctx.execute(
+        """
+        SELECT t.id, s.name
+        FROM top_n(3, min_amount := 5) AS t
+        JOIN sales s ON s.id = t.id
+        ORDER BY t.id
+        """
+    )
+    assert res.to_dict(as_series=False) == {"id": [1, 2, 4], "name": ["a", "b", "d"]}
+
+
+def test_user_function_errors(ctx: pl.SQLContext[pl.DataFrame]) -> None:
+    ctx.execute("CREATE MACRO f(x) AS x + 1")
+    ctx.execute("CREATE MACRO t() AS TABLE SELECT * FROM sales")
+
+    with pytest.raises(SQLInterfaceError, match="function 'f' already exists"):
+        ctx.execute("CREATE MACRO f(y) AS y")
+
+    with pytest.raises(SQLInterfaceError, match="cannot redefine built-in function"):
+        ctx.execute("CREATE MACRO upper(x) AS x")
+
+    with pytest.raises(SQLInterfaceError, match="recursive function definitions"):
+        ctx.execute("CREATE OR REPLACE MACRO f(x) AS f(x) + 1")
+
+    with pytest.raises(SQLSyntaxError, match="expects at most 1 argument"):
+        ctx.execute("SELECT f(id, 2) FROM sales")
+
+    with pytest.raises(SQLSyntaxError, match="missing argument for parameter 'x'"):
+        ctx.execute("SELECT f() FROM sales")
+
+    with pytest.raises(SQLSyntaxError, match="can only be used in a FROM clause"):
+        ctx.execute("SELECT t() FROM sales")
+
+    with pytest.raises(SQLSyntaxError, match="cannot be used as a table"):
+        ctx.execute("SELECT * FROM f(1)")
@@ -399,6 +399,34 @@ def test_window_function_first_last() -> None:
         assert_sql_matches(df, query=query, compare_with="duckdb", expected=expected)
 
 
+def test_window_function_nth_value(df_test: pl.DataFrame) -> None:
+    query = """
+        SELECT
+            id,
+            category,
+            NTH_VALUE(value, 2) OVER (PARTITION BY category ORDER BY id) AS nth_2,
+            NTH_VALUE(value, 3) OVER (ORDER BY value) AS nth_3
+        FROM self
+        ORDER BY id
+    """
+    assert_sql_matches(
+        df_test,
+        query=query,
+        compare_with="sqlite",
+        expected={
+            "id": [1, 2, 3, 4, 5, 6, 7],
+            "category": ["A", "A", "A", "B", "B", "B", "C"],
+            "nth_2": [None, 10, 10, None, 40, 40, None],
+            "nth_3": [20, None, 20, None, 20, 20, 20],
+        },
+    )
+    with pytest.raises(
+        pl.exceptions.SQLSyntaxError,
+        match="NTH_VALUE expects a positive integer for 'n'",
+    ):
+        df_test.sql("SELECT NTH_VALUE(value, 0) OVER (ORDER BY id) FROM self")
+
+
 def test_window_function_over_clause_misc() -> None:
     df = pl.DataFrame(
         {